@@ -80,6 +80,61 @@ impl BenchmarkDurations {
             / self.durations.len() as u32;
         var
     }
+
+    /// Returns the durations without the outliers, which are the durations more than 1.5 times
+    /// the interquartile range below the first quartile or above the third quartile.
+    ///
+    /// Runs with fewer than 4 durations are returned as is.
+    pub fn reject_outliers(&self) -> Self {
+        if self.durations.len() < 4 {
+            return self.clone();
+        }
+
+        let mut sorted = self.durations.clone();
+        sorted.sort();
+        let q1 = sorted[sorted.len() / 4].as_secs_f64();
+        let q3 = sorted[sorted.len() * 3 / 4].as_secs_f64();
+        let fence = 1.5 * (q3 - q1);
+        let (low, high) = (q1 - fence, q3 + fence);
+
+        let durations = self
+            .durations
+            .iter()
+            .filter(|duration| (low..=high).contains(&duration.as_secs_f64()))
+            .copied()
+            .collect();
+
+        Self {
+            timing_method: self.timing_method,
+            durations,
+        }
+    }
+
+    /// Returns the mean and the unbiased sample variance of the durations in seconds.
+    fn sample_mean_variance(&self) -> (f64, f64) {
+        if self.durations.is_empty() {
+            return (0., 0.);
+        }
+
+        let n = self.durations.len() as f64;
+        let mean = self
+            .durations
+            .iter()
+            .map(Duration::as_secs_f64)
+            .sum::<f64>()
+            / n;
+        let variance = self
+            .durations
+            .iter()
+            .map(|duration| {
+                let tmp = duration.as_secs_f64() - mean;
+                tmp * tmp
+            })
+            .sum::<f64>()
+            / (n - 1.).max(1.);
+
+        (mean, variance)
+    }
 }
 
 impl Display for BenchmarkDurations {
@@ -153,8 +208,8 @@ pub trait Benchmark {
     ///
     /// # Notes
     ///
-    /// This should not include warmup, the benchmark will be run at least one time without
-    /// measuring the execution time.
+    /// This should not include warmup, the benchmark will be run
+    /// [num_warmup](Benchmark::num_warmup) times without measuring the execution time.
     fn prepare(&self) -> Self::Args;
     /// Execute the benchmark and returns the time it took to complete.
     fn execute(&self, args: Self::Args);
@@ -162,6 +217,19 @@ pub trait Benchmark {
     fn num_samples(&self) -> usize {
         10
     }
+    /// Number of executions before the samples are measured.
+    fn num_warmup(&self) -> usize {
+        1
+    }
+    /// Number of bytes read and written by one execution, used to compute the throughput.
+    fn num_bytes(&self) -> Option<u64> {
+        None
+    }
+    /// Number of floating point operations done by one execution, used to compute the
+    /// throughput.
+    fn num_flops(&self) -> Option<u64> {
+        None
+    }
     /// Name of the benchmark, should be short and it should match the name
     /// defined in the crate Cargo.toml
     fn name(&self) -> String;
@@ -192,7 +260,9 @@ pub trait Benchmark {
             // Warmup
             let args = self.prepare();
 
-            self.execute(args.clone());
+            for _ in 0..self.num_warmup() {
+                self.execute(args.clone());
+            }
 
             match timing_method {
                 TimingMethod::Full => self.sync(),
@@ -253,14 +323,20 @@ pub trait Benchmark {
 }
 
 /// Result of a benchmark run, with metadata
-#[derive(Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct BenchmarkResult {
     /// Individual raw results of the run
     pub raw: BenchmarkDurations,
-    /// Computed values for the run
+    /// Computed values for the run, without the outliers
     pub computed: BenchmarkComputations,
-    /// Git commit hash of the commit in which the run occurred
-    pub git_hash: String,
+    /// Number of durations rejected as outliers
+    pub outliers: usize,
+    /// Bytes read and written per second, based on the median duration
+    pub bytes_per_second: Option<f64>,
+    /// Floating point operations per second, based on the median duration
+    pub flops_per_second: Option<f64>,
+    /// Git commit hash of the commit in which the run occurred, if it ran in a git checkout
+    pub git_hash: Option<String>,
     /// Name of the benchmark
     pub name: String,
     /// Options passed to the benchmark
@@ -271,6 +347,65 @@ pub struct BenchmarkResult {
     pub timestamp: u128,
 }
 
+impl BenchmarkResult {
+    /// Header of the [CSV records](BenchmarkResult::to_csv_record).
+    pub const CSV_HEADER: &'static str = "name,options,shapes,timing_method,samples,outliers,\
+        mean_us,median_us,min_us,max_us,bytes_per_second,flops_per_second,git_hash,timestamp";
+
+    /// Format the result as a CSV record, with the durations in microseconds.
+    pub fn to_csv_record(&self) -> String {
+        let micros = |duration: Duration| duration.as_secs_f64() * 1_000_000.;
+        let computed = &self.computed;
+
+        let fields = [
+            csv_field(&self.name),
+            csv_field(self.options.as_deref().unwrap_or_default()),
+            csv_field(&format!("{:?}", self.shapes)),
+            format!("{}", self.raw.timing_method),
+            format!("{}", self.raw.durations.len()),
+            format!("{}", self.outliers),
+            format!("{:.3}", micros(computed.mean)),
+            format!("{:.3}", micros(computed.median)),
+            format!("{:.3}", micros(computed.min)),
+            format!("{:.3}", micros(computed.max)),
+            optional_field(self.bytes_per_second),
+            optional_field(self.flops_per_second),
+            self.git_hash.clone().unwrap_or_default(),
+            format!("{}", self.timestamp),
+        ];
+
+        fields.join(",")
+    }
+
+    fn same_benchmark(&self, other: &Self) -> bool {
+        self.name == other.name && self.options == other.options && self.shapes == other.shapes
+    }
+}
+
+/// Format the results as CSV, starting with the [header](BenchmarkResult::CSV_HEADER).
+pub fn results_to_csv(results: &[BenchmarkResult]) -> String {
+    let mut csv = String::from(BenchmarkResult::CSV_HEADER);
+
+    for result in results {
+        csv.push('\n');
+        csv.push_str(&result.to_csv_record());
+    }
+
+    csv
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.into()
+    }
+}
+
+fn optional_field(value: Option<f64>) -> String {
+    value.map(|value| format!("{value:.0}")).unwrap_or_default()
+}
+
 impl Display for BenchmarkResult {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_str(
@@ -280,16 +415,40 @@ impl Display for BenchmarkResult {
         Git Hash: {}
         Benchmarking - {}{}
         ",
-                self.timestamp, self.git_hash, self.name, self.raw
+                self.timestamp,
+                self.git_hash.as_deref().unwrap_or("unknown"),
+                self.name,
+                self.raw
             )
             .as_str(),
-        )
+        )?;
+
+        if self.outliers > 0 {
+            writeln!(f, "  Outliers    {}", self.outliers)?;
+        }
+        if let Some(bytes_per_second) = self.bytes_per_second {
+            writeln!(f, "  Bandwidth   {:.3} GB/s", bytes_per_second / 1e9)?;
+        }
+        if let Some(flops_per_second) = self.flops_per_second {
+            writeln!(f, "  Compute     {:.3} TFLOP/s", flops_per_second / 1e12)?;
+        }
+
+        Ok(())
     }
 }
 
 #[cfg(feature = "std")]
 /// Runs the given benchmark on the device and prints result and information.
 pub fn run_benchmark<BM>(benchmark: BM) -> BenchmarkResult
+where
+    BM: Benchmark,
+{
+    run_benchmark_with(benchmark, TimingMethod::Full)
+}
+
+#[cfg(feature = "std")]
+/// Runs the given benchmark on the device with the given timing method.
+pub fn run_benchmark_with<BM>(benchmark: BM, timing_method: TimingMethod) -> BenchmarkResult
 where
     BM: Benchmark,
 {
@@ -297,16 +456,25 @@ where
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
         .as_millis();
-    let output = std::process::Command::new("git")
+    let git_hash = std::process::Command::new("git")
         .args(["rev-parse", "HEAD"])
         .output()
-        .unwrap();
-    let git_hash = String::from_utf8(output.stdout).unwrap().trim().to_string();
-    let durations = benchmark.run(TimingMethod::Full);
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_string());
+    let durations = benchmark.run(timing_method);
+    let kept = durations.reject_outliers();
+    let computed = BenchmarkComputations::new(&kept);
+    let per_second =
+        |amount: Option<u64>| amount.map(|amount| amount as f64 / computed.median.as_secs_f64());
 
     BenchmarkResult {
-        raw: durations.clone(),
-        computed: BenchmarkComputations::new(&durations),
+        outliers: durations.durations.len() - kept.durations.len(),
+        bytes_per_second: per_second(benchmark.num_bytes()),
+        flops_per_second: per_second(benchmark.num_flops()),
+        raw: durations,
+        computed,
         git_hash,
         name: benchmark.name(),
         options: benchmark.options(),
@@ -315,6 +483,246 @@ where
     }
 }
 
+/// Whether a benchmark got slower or faster between two runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ComparisonVerdict {
+    /// The benchmark got significantly slower.
+    Regression,
+    /// The benchmark got significantly faster.
+    Improvement,
+    /// The change isn't significant or is within the threshold.
+    Unchanged,
+}
+
+impl Display for ComparisonVerdict {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ComparisonVerdict::Regression => f.write_str("regression"),
+            ComparisonVerdict::Improvement => f.write_str("improvement"),
+            ComparisonVerdict::Unchanged => f.write_str("unchanged"),
+        }
+    }
+}
+
+/// Comparison of the same benchmark between a baseline and a current run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkComparison {
+    /// Name of the benchmark
+    pub name: String,
+    /// Options passed to the benchmark
+    pub options: Option<String>,
+    /// Shape dimensions
+    pub shapes: Vec<Vec<usize>>,
+    /// Mean duration of the baseline run, without the outliers.
+    pub baseline: Duration,
+    /// Mean duration of the current run, without the outliers.
+    pub current: Duration,
+    /// Change of the mean duration relative to the baseline, positive when the current run is
+    /// slower.
+    pub change: f64,
+    /// Whether the means differ according to a two-sided Welch's t-test at the 5% level.
+    pub significant: bool,
+    /// Whether the benchmark regressed or improved.
+    pub verdict: ComparisonVerdict,
+}
+
+impl BenchmarkComparison {
+    /// Compare two runs of the same benchmark, where only significant changes larger than the
+    /// threshold relative to the baseline are regressions or improvements.
+    pub fn new(baseline: &BenchmarkResult, current: &BenchmarkResult, threshold: f64) -> Self {
+        let baseline_durations = baseline.raw.reject_outliers();
+        let current_durations = current.raw.reject_outliers();
+        let (baseline_mean, _) = baseline_durations.sample_mean_variance();
+        let (current_mean, _) = current_durations.sample_mean_variance();
+
+        // A zero baseline can't be scaled, so any slowdown from it is an unbounded change.
+        let change = if baseline_mean > 0. {
+            (current_mean - baseline_mean) / baseline_mean
+        } else if current_mean > 0. {
+            f64::INFINITY
+        } else {
+            0.
+        };
+        let significant = welch_t_test(&baseline_durations, &current_durations);
+        let verdict = match significant {
+            true if change > threshold => ComparisonVerdict::Regression,
+            true if change < -threshold => ComparisonVerdict::Improvement,
+            _ => ComparisonVerdict::Unchanged,
+        };
+
+        Self {
+            name: current.name.clone(),
+            options: current.options.clone(),
+            shapes: current.shapes.clone(),
+            baseline: Duration::from_secs_f64(baseline_mean),
+            current: Duration::from_secs_f64(current_mean),
+            change,
+            significant,
+            verdict,
+        }
+    }
+
+    /// Whether the current run is a regression.
+    pub fn is_regression(&self) -> bool {
+        self.verdict == ComparisonVerdict::Regression
+    }
+}
+
+impl Display for BenchmarkComparison {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}: {:.3?} -> {:.3?} ({:+.2}%) {}",
+            self.name,
+            self.baseline,
+            self.current,
+            self.change * 100.,
+            self.verdict
+        )
+    }
+}
+
+/// Compare the current results with the baseline results of the same benchmarks, matched by name,
+/// options and shapes. Benchmarks without a baseline are skipped.
+pub fn compare_results(
+    baseline: &[BenchmarkResult],
+    current: &[BenchmarkResult],
+    threshold: f64,
+) -> Vec<BenchmarkComparison> {
+    current
+        .iter()
+        .filter_map(|current| {
+            baseline
+                .iter()
+                .find(|baseline| baseline.same_benchmark(current))
+                .map(|baseline| BenchmarkComparison::new(baseline, current, threshold))
+        })
+        .collect()
+}
+
+/// Two-sided Welch's t-test at the 5% level, returning whether the means of the durations differ.
+fn welch_t_test(lhs: &BenchmarkDurations, rhs: &BenchmarkDurations) -> bool {
+    let (n_lhs, n_rhs) = (lhs.durations.len() as f64, rhs.durations.len() as f64);
+    if n_lhs < 2. || n_rhs < 2. {
+        return false;
+    }
+
+    let (mean_lhs, var_lhs) = lhs.sample_mean_variance();
+    let (mean_rhs, var_rhs) = rhs.sample_mean_variance();
+    let (se_lhs, se_rhs) = (var_lhs / n_lhs, var_rhs / n_rhs);
+    let se = se_lhs + se_rhs;
+    let diff = (mean_rhs - mean_lhs) * (mean_rhs - mean_lhs);
+
+    if se == 0. {
+        return diff > 0.;
+    }
+
+    // Welch–Satterthwaite degrees of freedom, compared with the squared t statistic to avoid
+    // needing a square root without std.
+    let df = se * se / (se_lhs * se_lhs / (n_lhs - 1.) + se_rhs * se_rhs / (n_rhs - 1.));
+    let critical = t_critical_value(df);
+
+    diff / se > critical * critical
+}
+
+/// Critical value of the Student's t-distribution for a two-sided test at the 5% level.
+fn t_critical_value(df: f64) -> f64 {
+    const TABLE: [f64; 30] = [
+        12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+        2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+        2.052, 2.048, 2.045, 2.042,
+    ];
+
+    // Rounding down the degrees of freedom gives a larger, more conservative, critical value.
+    match df as usize {
+        0 => TABLE[0],
+        df if df <= TABLE.len() => TABLE[df - 1],
+        df if df <= 60 => 2.000,
+        df if df <= 120 => 1.980,
+        _ => 1.960,
+    }
+}
+
+#[cfg(feature = "std")]
+/// Save the results to a file, as CSV if its extension is `csv` and as JSON otherwise.
+pub fn save_results(
+    path: impl AsRef<std::path::Path>,
+    results: &[BenchmarkResult],
+) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let content = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("csv") => results_to_csv(results),
+        _ => serde_json::to_string_pretty(results).map_err(std::io::Error::other)?,
+    };
+
+    std::fs::write(path, content)
+}
+
+#[cfg(feature = "std")]
+/// Load results saved as JSON with [save_results].
+pub fn load_results(path: impl AsRef<std::path::Path>) -> std::io::Result<Vec<BenchmarkResult>> {
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content).map_err(std::io::Error::other)
+}
+
+#[cfg(feature = "std")]
+/// Add the results to the ones already saved at the path, replacing the previous runs of the
+/// same benchmarks.
+///
+/// Results are always merged as JSON: when the extension of the path is `csv`, they are kept in
+/// the JSON file of the same name, and the CSV file is exported from it.
+pub fn merge_results(
+    path: impl AsRef<std::path::Path>,
+    results: &[BenchmarkResult],
+) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let json_path = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("csv") => path.with_extension("json"),
+        _ => path.to_path_buf(),
+    };
+
+    let mut saved = match json_path.exists() {
+        true => load_results(&json_path)?,
+        false => Vec::new(),
+    };
+    saved.retain(|saved| !results.iter().any(|result| result.same_benchmark(saved)));
+    saved.extend(results.iter().cloned());
+
+    save_results(&json_path, &saved)?;
+    if json_path != path {
+        save_results(path, &saved)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+/// Report the results of benchmarks to continuous integration, using environment variables.
+///
+/// - `CUBECL_BENCH_OUTPUT`: File where the results are [merged](merge_results), so it can be
+///   shared by multiple benchmark binaries. A `csv` file is exported from a JSON file of the
+///   same name.
+/// - `CUBECL_BENCH_BASELINE`: JSON file with the results to compare with.
+/// - `CUBECL_BENCH_THRESHOLD`: Relative change above which a significant change is a
+///   regression or an improvement, `0.05` by default.
+///
+/// Returns the comparisons with the baseline, if any.
+pub fn report_results(results: &[BenchmarkResult]) -> std::io::Result<Vec<BenchmarkComparison>> {
+    if let Ok(path) = std::env::var("CUBECL_BENCH_OUTPUT") {
+        merge_results(path, results)?;
+    }
+
+    let Ok(path) = std::env::var("CUBECL_BENCH_BASELINE") else {
+        return Ok(Vec::new());
+    };
+    let threshold = match std::env::var("CUBECL_BENCH_THRESHOLD") {
+        Ok(threshold) => threshold.parse().map_err(std::io::Error::other)?,
+        Err(_) => 0.05,
+    };
+
+    Ok(compare_results(&load_results(path)?, results, threshold))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -386,4 +794,108 @@ mod tests {
         let variance = durations.variance_duration(mean);
         assert_eq!(variance, Duration::from_secs(200));
     }
+
+    fn result(name: &str, durations: &[u64]) -> BenchmarkResult {
+        let raw = BenchmarkDurations {
+            timing_method: TimingMethod::Full,
+            durations: durations
+                .iter()
+                .map(|d| Duration::from_micros(*d))
+                .collect(),
+        };
+
+        BenchmarkResult {
+            computed: BenchmarkComputations::new(&raw),
+            raw,
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_reject_outliers() {
+        let durations = result("a", &[10, 11, 10, 12, 11, 10, 100]).raw;
+
+        let kept = durations.reject_outliers();
+
+        assert_eq!(kept.durations.len(), 6);
+        assert!(!kept.durations.contains(&Duration::from_micros(100)));
+    }
+
+    #[test]
+    fn test_compare_results_flags_regressions() {
+        let baseline = [
+            result("a", &[100, 101, 99, 100, 102, 98, 100, 101]),
+            result("b", &[100, 101, 99, 100, 102, 98, 100, 101]),
+        ];
+        let current = [
+            result("a", &[120, 121, 119, 120, 122, 118, 120, 121]),
+            result("b", &[101, 100, 99, 101, 100, 99, 102, 100]),
+            result("c", &[10, 10, 10, 10]),
+        ];
+
+        let comparisons = compare_results(&baseline, &current, 0.05);
+
+        assert_eq!(comparisons.len(), 2);
+        assert_eq!(comparisons[0].verdict, ComparisonVerdict::Regression);
+        assert!(comparisons[0].change > 0.19 && comparisons[0].change < 0.21);
+        assert_eq!(comparisons[1].verdict, ComparisonVerdict::Unchanged);
+    }
+
+    #[test]
+    fn test_compare_results_with_zero_baseline() {
+        let baseline = [result("a", &[0, 0, 0, 0]), result("b", &[0, 0, 0, 0])];
+        let current = [result("a", &[0, 0, 0, 0]), result("b", &[10, 11, 10, 11])];
+
+        let comparisons = compare_results(&baseline, &current, 0.05);
+
+        assert_eq!(comparisons[0].change, 0.);
+        assert_eq!(comparisons[0].verdict, ComparisonVerdict::Unchanged);
+        assert_eq!(comparisons[1].change, f64::INFINITY);
+        assert_eq!(comparisons[1].verdict, ComparisonVerdict::Regression);
+    }
+
+    #[test]
+    fn test_result_json_round_trip() {
+        let mut result = result("a", &[10, 20, 30]);
+        result.options = Some("lhs, rhs".into());
+        result.timestamp = 1_700_000_000_000;
+
+        let json = serde_json::to_string(&[result]).unwrap();
+        let loaded: Vec<BenchmarkResult> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            loaded[0].raw.durations,
+            vec![
+                Duration::from_micros(10),
+                Duration::from_micros(20),
+                Duration::from_micros(30),
+            ]
+        );
+        assert_eq!(loaded[0].timestamp, 1_700_000_000_000);
+        assert_eq!(
+            results_to_csv(&loaded).lines().nth(1).unwrap(),
+            "a,\"lhs, rhs\",[],full,3,0,20.000,20.000,10.000,30.000,,,,1700000000000"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_merge_results_to_csv() {
+        let dir = std::env::temp_dir().join(format!("cubecl-bench-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("results.csv");
+
+        merge_results(&path, &[result("a", &[10]), result("b", &[10])]).unwrap();
+        merge_results(&path, &[result("b", &[20]), result("c", &[30])]).unwrap();
+
+        let saved = load_results(dir.join("results.json")).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<&str> = saved.iter().map(|result| result.name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "c"]);
+        assert_eq!(saved[1].raw.durations, vec![Duration::from_micros(20)]);
+        assert_eq!(csv, results_to_csv(&saved));
+    }
 }
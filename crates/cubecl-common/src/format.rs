@@ -0,0 +1,47 @@
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// A segment of a print format string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatSegment<'a> {
+    /// Text printed verbatim, with escaped braces already resolved.
+    Text(Cow<'a, str>),
+    /// A placeholder for the next argument.
+    Arg,
+}
+
+/// Split a print format string into text and argument placeholders.
+///
+/// Only `{}` placeholders are supported, and braces are escaped by doubling them like in Rust.
+pub fn parse_format_string(format_string: &str) -> Result<Vec<FormatSegment<'_>>, String> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut chars = format_string.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                chars.next();
+                text.push(c);
+            }
+            ('{', Some('}')) => {
+                chars.next();
+                if !text.is_empty() {
+                    segments.push(FormatSegment::Text(core::mem::take(&mut text).into()));
+                }
+                segments.push(FormatSegment::Arg);
+            }
+            ('{', _) => return Err("Only `{}` placeholders are supported".to_string()),
+            ('}', _) => return Err("Unmatched `}` in format string".to_string()),
+            _ => text.push(c),
+        }
+    }
+    if !text.is_empty() {
+        segments.push(FormatSegment::Text(text.into()));
+    }
+
+    Ok(segments)
+}
@@ -1,6 +1,14 @@
-use std::{fmt::Display, marker::PhantomData};
+use std::{
+    fmt::Display,
+    hash::{DefaultHasher, Hash, Hasher},
+    marker::PhantomData,
+};
 
-use crate::{codegen::CompilerRepresentation, ir::CubeDim, Compiler, Kernel, KernelId};
+use crate::{
+    codegen::CompilerRepresentation,
+    ir::{CubeDim, KernelDefinition},
+    Compiler, Kernel, KernelId,
+};
 use alloc::sync::Arc;
 use cubecl_runtime::ExecutionMode;
 
@@ -176,15 +184,30 @@ pub trait CubeTask<C: Compiler>: Send + Sync {
     fn name(&self) -> &'static str {
         core::any::type_name::<Self>()
     }
+    /// The definition of the kernel, if it has one, used to record the kernel.
+    fn definition(&self) -> Option<KernelDefinition> {
+        None
+    }
+    /// Clone the task, to execute it again, like the kernels captured by a graph.
+    fn clone_box(&self) -> Box<dyn CubeTask<C>>;
 }
 
 /// Wraps a [kernel](Kernel) to create a [cube task](CubeTask).
-#[derive(new)]
 pub struct KernelTask<C: Compiler, K: Kernel> {
-    kernel_definition: K,
+    kernel_definition: Arc<K>,
     _compiler: PhantomData<C>,
 }
 
+impl<C: Compiler, K: Kernel> KernelTask<C, K> {
+    /// Create a cube task from a kernel.
+    pub fn new(kernel_definition: K) -> Self {
+        Self {
+            kernel_definition: Arc::new(kernel_definition),
+            _compiler: PhantomData,
+        }
+    }
+}
+
 impl<C: Compiler, K: Kernel> CubeTask<C> for KernelTask<C, K> {
     fn compile(&self, mode: ExecutionMode) -> CompiledKernel<C> {
         let gpu_ir = self.kernel_definition.define();
@@ -209,6 +232,60 @@ impl<C: Compiler, K: Kernel> CubeTask<C> for KernelTask<C, K> {
     fn name(&self) -> &'static str {
         core::any::type_name::<K>()
     }
+
+    fn definition(&self) -> Option<KernelDefinition> {
+        Some(self.kernel_definition.define())
+    }
+
+    fn clone_box(&self) -> Box<dyn CubeTask<C>> {
+        Box::new(Self {
+            kernel_definition: self.kernel_definition.clone(),
+            _compiler: PhantomData,
+        })
+    }
+}
+
+/// A [kernel](Kernel) created from its [definition](KernelDefinition), like the kernels of a
+/// replayed [trace](cubecl_runtime::channel::Trace).
+pub struct DefinitionKernel {
+    definition: KernelDefinition,
+    id: KernelId,
+}
+
+impl DefinitionKernel {
+    /// Create a kernel from its definition, identified by the whole definition.
+    pub fn new(definition: KernelDefinition) -> Self {
+        let id = KernelId::new::<Self>().info(DefinitionKey(format!("{definition:?}")));
+        Self { definition, id }
+    }
+
+    /// Create a [cube task](CubeTask) from a recorded kernel definition.
+    pub fn replay<C: Compiler>(definition: KernelDefinition) -> Box<dyn CubeTask<C>> {
+        Box::new(KernelTask::<C, _>::new(Self::new(definition)))
+    }
+}
+
+/// The debug representation of a definition, compared in full so different definitions never
+/// share an id, but only shown as its hash to keep kernel ids readable.
+#[derive(PartialEq, Eq, Hash)]
+struct DefinitionKey(String);
+
+impl core::fmt::Debug for DefinitionKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut hasher = DefaultHasher::new();
+        self.0.hash(&mut hasher);
+        write!(f, "{:016x}", hasher.finish())
+    }
+}
+
+impl Kernel for DefinitionKernel {
+    fn define(&self) -> KernelDefinition {
+        self.definition.clone()
+    }
+
+    fn id(&self) -> KernelId {
+        self.id.clone()
+    }
 }
 
 impl<C: Compiler> CubeTask<C> for Arc<dyn CubeTask<C>> {
@@ -222,6 +299,14 @@ impl<C: Compiler> CubeTask<C> for Arc<dyn CubeTask<C>> {
     fn name(&self) -> &'static str {
         self.as_ref().name()
     }
+
+    fn definition(&self) -> Option<KernelDefinition> {
+        self.as_ref().definition()
+    }
+
+    fn clone_box(&self) -> Box<dyn CubeTask<C>> {
+        Box::new(self.clone())
+    }
 }
 
 impl<C: Compiler> CubeTask<C> for Box<dyn CubeTask<C>> {
@@ -236,4 +321,18 @@ impl<C: Compiler> CubeTask<C> for Box<dyn CubeTask<C>> {
     fn name(&self) -> &'static str {
         self.as_ref().name()
     }
+
+    fn definition(&self) -> Option<KernelDefinition> {
+        self.as_ref().definition()
+    }
+
+    fn clone_box(&self) -> Box<dyn CubeTask<C>> {
+        self.as_ref().clone_box()
+    }
+}
+
+impl<C: Compiler> Clone for Box<dyn CubeTask<C>> {
+    fn clone(&self) -> Self {
+        self.as_ref().clone_box()
+    }
 }
@@ -10,6 +10,7 @@ use crate::{
 use crate::{Kernel, Runtime};
 use bytemuck::NoUninit;
 use cubecl_runtime::client::ComputeClient;
+use cubecl_runtime::graph::ScalarBinding;
 use cubecl_runtime::server::{Binding, CubeCount};
 
 /// Prepare a kernel for [launch](KernelLauncher::launch).
@@ -28,6 +29,8 @@ pub struct KernelLauncher<R: Runtime> {
     scalar_i16: ScalarState<i16>,
     scalar_i8: ScalarState<i8>,
     scalar_order: Vec<Elem>,
+    /// The type of each scalar, in the order they are registered.
+    scalar_args: Vec<Elem>,
     pub settings: KernelSettings,
     runtime: PhantomData<R>,
 }
@@ -122,11 +125,11 @@ impl<R: Runtime> KernelLauncher<R> {
         kernel: K,
         client: &ComputeClient<R::Server, R::Channel>,
     ) {
-        let bindings = self.into_bindings(client);
+        let (bindings, scalars) = self.into_bindings(client);
 
         let kernel = Box::new(KernelTask::<R::Compiler, K>::new(kernel));
 
-        client.execute(kernel, cube_count, bindings);
+        client.execute_with_scalars(kernel, cube_count, bindings, scalars);
     }
 
     /// Launch the kernel without check bounds.
@@ -140,11 +143,11 @@ impl<R: Runtime> KernelLauncher<R> {
         kernel: K,
         client: &ComputeClient<R::Server, R::Channel>,
     ) {
-        let bindings = self.into_bindings(client);
+        let (bindings, scalars) = self.into_bindings(client);
 
         let kernel = Box::new(KernelTask::<R::Compiler, K>::new(kernel));
 
-        client.execute_unchecked(kernel, cube_count, bindings);
+        client.execute_unchecked_with_scalars(kernel, cube_count, bindings, scalars);
     }
 
     /// We need to create the bindings in the same order they are defined in the compilation step.
@@ -153,12 +156,20 @@ impl<R: Runtime> KernelLauncher<R> {
     /// by the output tensors. Then the tensor metadata, and the scalars at the end. The scalars
     /// are registered in the same order they are added. This is why we store the scalar data type
     /// in the `scalar_order` vector, so that we can register them in the same order.
-    fn into_bindings(mut self, client: &ComputeClient<R::Server, R::Channel>) -> Vec<Binding> {
+    ///
+    /// The location of each scalar in those bindings is returned as well, so that the graphs
+    /// capturing the kernel can update it.
+    fn into_bindings(
+        mut self,
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) -> (Vec<Binding>, Vec<ScalarBinding>) {
         let mut bindings = Vec::new();
 
         self.tensors.register(client, &mut bindings);
 
+        let mut scalar_bindings = Vec::with_capacity(self.scalar_order.len());
         for elem in self.scalar_order.drain(..) {
+            scalar_bindings.push((elem, bindings.len()));
             match elem {
                 Elem::Float(kind) => match kind {
                     FloatKind::F16 => self.scalar_f16.register::<R>(client, &mut bindings),
@@ -196,16 +207,61 @@ impl<R: Runtime> KernelLauncher<R> {
             }
         }
 
-        bindings
+        let scalars = self
+            .scalar_args
+            .iter()
+            .enumerate()
+            .map(|(arg, elem)| {
+                let (_, binding) = scalar_bindings
+                    .iter()
+                    .find(|(registered, _)| registered == elem)
+                    .unwrap();
+                let index = self.scalar_args[..arg]
+                    .iter()
+                    .filter(|previous| *previous == elem)
+                    .count();
+                scalar_binding(*elem, *binding, index)
+            })
+            .collect();
+
+        (bindings, scalars)
     }
 
     fn register_scalar(&mut self, elem: Elem) {
+        self.scalar_args.push(elem);
         if !self.scalar_order.contains(&elem) {
             self.scalar_order.push(elem);
         }
     }
 }
 
+/// The location of a scalar of the given type, at the given index of its binding.
+fn scalar_binding(elem: Elem, binding: usize, index: usize) -> ScalarBinding {
+    match elem {
+        Elem::Float(kind) => match kind {
+            FloatKind::F16 => ScalarBinding::new::<half::f16>(binding, index),
+            FloatKind::BF16 => ScalarBinding::new::<half::bf16>(binding, index),
+            FloatKind::TF32 | FloatKind::Flex32 | FloatKind::F32 => {
+                ScalarBinding::new::<f32>(binding, index)
+            }
+            FloatKind::F64 => ScalarBinding::new::<f64>(binding, index),
+        },
+        Elem::Int(kind) | Elem::AtomicInt(kind) => match kind {
+            IntKind::I8 => ScalarBinding::new::<i8>(binding, index),
+            IntKind::I16 => ScalarBinding::new::<i16>(binding, index),
+            IntKind::I32 => ScalarBinding::new::<i32>(binding, index),
+            IntKind::I64 => ScalarBinding::new::<i64>(binding, index),
+        },
+        Elem::UInt(kind) | Elem::AtomicUInt(kind) => match kind {
+            UIntKind::U8 => ScalarBinding::new::<u8>(binding, index),
+            UIntKind::U16 => ScalarBinding::new::<u16>(binding, index),
+            UIntKind::U32 => ScalarBinding::new::<u32>(binding, index),
+            UIntKind::U64 => ScalarBinding::new::<u64>(binding, index),
+        },
+        Elem::Bool => panic!("Bool can't be passed as bindings."),
+    }
+}
+
 /// Handles the tensor state.
 pub enum TensorState<R: Runtime> {
     /// No tensor is registered yet.
@@ -360,6 +416,7 @@ impl<R: Runtime> Default for KernelLauncher<R> {
             scalar_i16: ScalarState::Empty,
             scalar_i8: ScalarState::Empty,
             scalar_order: Vec::new(),
+            scalar_args: Vec::new(),
             settings: Default::default(),
             runtime: PhantomData,
         }
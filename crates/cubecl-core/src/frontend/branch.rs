@@ -1,12 +1,16 @@
+use core::cell::RefCell;
+
 use num_traits::NumCast;
 
-use crate::ir::{Branch, If, IfElse, Item, Loop, RangeLoop};
+use crate::ir::{Branch, Elem, If, IfElse, Item, Loop, RangeLoop};
 use crate::{
     frontend::{CubeContext, ExpandElement},
     ir::Switch,
 };
 
-use super::{assign, CubePrimitive, CubeType, ExpandElementTyped, Int, Numeric};
+use crate::unexpanded;
+
+use super::{assign, Assign, CubePrimitive, CubeType, ExpandElementTyped, Int, Numeric};
 
 /// Something that can be iterated on by a for loop. Currently only includes `Range`, `StepBy` and
 /// `Sequence`.
@@ -529,3 +533,107 @@ pub fn loop_expand(context: &mut CubeContext, mut block: impl FnMut(&mut CubeCon
         scope: inside_loop.into_scope(),
     })));
 }
+
+/// Expansion state of a cube function with early returns.
+///
+/// The body of the function is expanded in a loop that every return exits, after assigning the
+/// returned value to a result variable. Returns nested in other loops also set a flag, checked
+/// after each of those loops to exit them in turn.
+pub struct ReturnExpand<T> {
+    value: RefCell<Option<T>>,
+    returned: Option<ExpandElementTyped<bool>>,
+}
+
+impl<T: Assign + Clone> ReturnExpand<T> {
+    /// Assign `value` to the result of the function and exit it.
+    pub fn expand_return(&self, context: &mut CubeContext, value: T) {
+        let output = self
+            .value
+            .borrow_mut()
+            .get_or_insert_with(|| value.expand_declare(context))
+            .clone();
+        value.expand_assign(context, output);
+
+        if let Some(returned) = &self.returned {
+            assign::expand(
+                context,
+                ExpandElementTyped::from_lit(true),
+                returned.clone(),
+            );
+        }
+
+        break_expand(context);
+    }
+
+    /// Exit the enclosing loop if the function returned in the loop that just ended.
+    pub fn expand_propagate(&self, context: &mut CubeContext) {
+        if let Some(returned) = &self.returned {
+            if_expand(context, returned.clone().into(), break_expand);
+        }
+    }
+}
+
+/// Expand the body of a function with early returns, see [ReturnExpand].
+///
+/// `nested` must be set when some of the returns are nested in loops.
+pub fn return_scope_expand<T: Assign + Clone>(
+    context: &mut CubeContext,
+    nested: bool,
+    body: impl FnOnce(&mut CubeContext, &ReturnExpand<T>),
+) -> T {
+    let returned = nested.then(|| {
+        let returned: ExpandElementTyped<bool> =
+            context.create_local_variable(Item::new(Elem::Bool)).into();
+        assign::expand(
+            context,
+            ExpandElementTyped::from_lit(false),
+            returned.clone(),
+        );
+        returned
+    });
+    let state = ReturnExpand {
+        value: RefCell::new(None),
+        returned,
+    };
+
+    let mut inside_loop = context.child();
+    body(&mut inside_loop, &state);
+    context.register(Branch::Loop(Box::new(Loop {
+        scope: inside_loop.into_scope(),
+    })));
+
+    state
+        .value
+        .into_inner()
+        .expect("A function with early returns should return a value")
+}
+
+/// Trait to be implemented by [expand types](CubeType::ExpandType) that support the `?` operator
+/// in cube functions. The function must return the same type as the operand.
+pub trait CubeTry: Sized {
+    /// The value produced by `?` when the function doesn't return early.
+    type Output;
+
+    /// Returns whether the function should return `self` early, and the value produced otherwise.
+    fn expand_branch(&self, context: &mut CubeContext) -> (ExpandElementTyped<bool>, Self::Output);
+}
+
+/// Output of the `?` operator on types whose [expand type](CubeType::ExpandType) implements
+/// [CubeTry], used to type check the unexpanded function.
+pub trait CubeTryOutput {
+    /// The value produced by `?`.
+    type Output;
+}
+
+impl<T> CubeTryOutput for Option<T> {
+    type Output = T;
+}
+
+impl<T, E> CubeTryOutput for Result<T, E> {
+    type Output = T;
+}
+
+/// Replaces the `?` operator in the unexpanded function.
+pub fn try_unexpanded<T: CubeTryOutput>(_value: T) -> T::Output {
+    unexpanded!()
+}
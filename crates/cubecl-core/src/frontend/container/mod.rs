@@ -5,6 +5,7 @@ mod registry;
 mod sequence;
 mod shared_memory;
 mod slice;
+mod struct_array;
 mod tensor;
 
 pub use array::*;
@@ -14,4 +15,5 @@ pub use registry::*;
 pub use sequence::*;
 pub use shared_memory::*;
 pub use slice::*;
+pub use struct_array::*;
 pub use tensor::*;
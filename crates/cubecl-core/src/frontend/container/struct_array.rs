@@ -0,0 +1,172 @@
+use std::marker::PhantomData;
+
+use crate::{
+    frontend::{
+        binary_expand, indexation::Index, CubeContext, CubeIndex, CubeIndexMut, CubeType,
+        ExpandElement, ExpandElementTyped, IndexAssignExpand, IndexExpand, Init,
+    },
+    ir::{BinaryOperator, Instruction, Item, Operator},
+    unexpanded,
+};
+
+/// A struct that can be stored in a [StructArray], with one array per field.
+///
+/// Implemented by `#[derive(CubeType)]` on structs marked with `#[expand(array)]`, whose fields
+/// must all be primitives.
+pub trait ArrayElement: CubeType {
+    /// The item of each field, in declaration order.
+    fn field_items() -> Vec<Item>;
+
+    /// Split a value into its fields, in declaration order.
+    fn __expand_into_fields(value: Self::ExpandType) -> Vec<ExpandElement>;
+
+    /// Create a value from its fields, in declaration order.
+    fn __expand_from_fields(fields: Vec<ExpandElement>) -> Self::ExpandType;
+}
+
+/// A local or shared array of structs, stored as one array per field (struct-of-arrays).
+///
+/// Elements are read and written as a whole: `array[i].field = value` doesn't write to the array.
+#[derive(Clone, Copy)]
+pub struct StructArray<T: ArrayElement> {
+    _val: PhantomData<T>,
+}
+
+/// Expand type of [StructArray].
+pub struct StructArrayExpand<T: ArrayElement> {
+    fields: Vec<ExpandElement>,
+    length: u32,
+    _val: PhantomData<T>,
+}
+
+impl<T: ArrayElement> Clone for StructArrayExpand<T> {
+    fn clone(&self) -> Self {
+        Self {
+            fields: self.fields.clone(),
+            length: self.length,
+            _val: PhantomData,
+        }
+    }
+}
+
+impl<T: ArrayElement> CubeType for StructArray<T> {
+    type ExpandType = StructArrayExpand<T>;
+}
+
+impl<T: ArrayElement> Init for StructArrayExpand<T> {
+    fn init(self, _context: &mut CubeContext) -> Self {
+        self
+    }
+}
+
+impl<T: ArrayElement, I: Index> CubeIndex<I> for StructArray<T> {
+    type Output = T;
+}
+
+impl<T: ArrayElement, I: Index> CubeIndexMut<I> for StructArray<T> {}
+
+impl<T: ArrayElement> StructArray<T> {
+    /// Create a new local array of the given length.
+    pub fn new<L: Index>(_length: L) -> Self {
+        StructArray { _val: PhantomData }
+    }
+
+    /// Create a new array of the given length in shared memory.
+    pub fn new_shared<L: Index>(_length: L) -> Self {
+        StructArray { _val: PhantomData }
+    }
+
+    /// Get the length of the array.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u32 {
+        unexpanded!()
+    }
+
+    /// Expand function of [new](StructArray::new).
+    pub fn __expand_new(
+        context: &mut CubeContext,
+        length: ExpandElementTyped<u32>,
+    ) -> StructArrayExpand<T> {
+        let length = length
+            .constant()
+            .expect("Array need constant initialization value")
+            .as_u32();
+        StructArrayExpand::new(length, |item| context.create_local_array(item, length))
+    }
+
+    /// Expand function of [new_shared](StructArray::new_shared).
+    pub fn __expand_new_shared(
+        context: &mut CubeContext,
+        length: ExpandElementTyped<u32>,
+    ) -> StructArrayExpand<T> {
+        let length = length
+            .constant()
+            .expect("Shared memory need constant initialization value")
+            .as_u32();
+        StructArrayExpand::new(length, |item| context.create_shared(item, length))
+    }
+
+    /// Expand function of [len](StructArray::len).
+    pub fn __expand_len(
+        context: &mut CubeContext,
+        this: StructArrayExpand<T>,
+    ) -> ExpandElementTyped<u32> {
+        this.__expand_len_method(context)
+    }
+}
+
+impl<T: ArrayElement> StructArrayExpand<T> {
+    fn new(length: u32, mut create: impl FnMut(Item) -> ExpandElement) -> Self {
+        StructArrayExpand {
+            fields: T::field_items().into_iter().map(&mut create).collect(),
+            length,
+            _val: PhantomData,
+        }
+    }
+
+    /// Expand method of [len](StructArray::len).
+    pub fn __expand_len_method(&self, _context: &mut CubeContext) -> ExpandElementTyped<u32> {
+        ExpandElementTyped::from_lit(self.length)
+    }
+}
+
+impl<T: ArrayElement> IndexExpand for StructArrayExpand<T> {
+    type Output = T::ExpandType;
+
+    fn expand_index(
+        self,
+        context: &mut CubeContext,
+        index: ExpandElementTyped<u32>,
+    ) -> T::ExpandType {
+        let index: ExpandElement = index.into();
+        let fields = self
+            .fields
+            .into_iter()
+            .map(|field| binary_expand(context, field, index.clone(), Operator::Index))
+            .collect();
+        T::__expand_from_fields(fields)
+    }
+}
+
+impl<T: ArrayElement> IndexAssignExpand for StructArrayExpand<T> {
+    type Value = T::ExpandType;
+
+    fn expand_index_assign(
+        self,
+        context: &mut CubeContext,
+        index: ExpandElementTyped<u32>,
+        value: T::ExpandType,
+    ) {
+        let index: ExpandElement = index.into();
+        let values = T::__expand_into_fields(value);
+        for (field, value) in self.fields.into_iter().zip(values) {
+            context.register(Instruction::new(
+                Operator::IndexAssign(BinaryOperator {
+                    lhs: *index,
+                    rhs: *value,
+                }),
+                *field,
+            ));
+        }
+    }
+}
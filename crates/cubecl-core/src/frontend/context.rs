@@ -1,4 +1,6 @@
-use crate::ir::{self, Elem, Instruction, Item, ReusingAllocator, Scope, Variable, VariableKind};
+use crate::ir::{
+    self, Elem, Instruction, Item, ReusingAllocator, Scope, SourceLoc, Variable, VariableKind,
+};
 use crate::{frontend::ExpandElement, ir::LocalAllocator};
 use alloc::rc::Rc;
 use core::cell::RefCell;
@@ -7,6 +9,8 @@ pub struct CubeContext {
     pub root: Rc<RefCell<Scope>>,
     pub scope: Rc<RefCell<Scope>>,
     pub local_allocator: Rc<dyn LocalAllocator>,
+    /// Whether instructions record the source location of the `#[cube]` code registering them.
+    pub debug_symbols: bool,
 }
 
 impl Default for CubeContext {
@@ -28,6 +32,7 @@ impl CubeContext {
             local_allocator: Rc::new(allocator),
             scope,
             root,
+            debug_symbols: cubecl_runtime::debug::debug_symbols_enabled(),
         }
     }
 
@@ -35,6 +40,16 @@ impl CubeContext {
         self.scope.borrow_mut().register(op)
     }
 
+    /// Set the source location given to the instructions registered from now on.
+    pub fn set_source_loc(&mut self, source_loc: Option<SourceLoc>) {
+        self.scope.borrow_mut().source_loc = source_loc;
+    }
+
+    /// The source location given to the instructions registered from now on.
+    pub fn source_loc(&self) -> Option<SourceLoc> {
+        self.scope.borrow().source_loc.clone()
+    }
+
     pub fn child(&mut self) -> CubeContext {
         let scope = self.scope.borrow_mut().child();
 
@@ -42,15 +57,19 @@ impl CubeContext {
             scope: Rc::new(RefCell::new(scope)),
             root: self.root.clone(),
             local_allocator: self.local_allocator.clone(),
+            debug_symbols: self.debug_symbols,
         }
     }
 
     pub fn into_scope(self) -> Scope {
         core::mem::drop(self.root);
 
-        Rc::into_inner(self.scope)
+        let mut scope = Rc::into_inner(self.scope)
             .expect("Only one reference")
-            .into_inner()
+            .into_inner();
+        // Only used while expanding, the instructions keep their own location.
+        scope.source_loc = None;
+        scope
     }
 
     /// Create a new mutable local variable
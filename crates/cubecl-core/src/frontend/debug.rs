@@ -0,0 +1,32 @@
+use crate::ir::{NonSemantic, SourceLoc};
+
+use super::{CubeContext, ExpandElement};
+
+/// Set the source location of the instructions registered next, if debug symbols are enabled.
+/// Called by `#[cube]` before each statement.
+pub fn source_loc_expand(context: &mut CubeContext, file: &'static str, line: u32, column: u32) {
+    if context.debug_symbols {
+        context.set_source_loc(Some(SourceLoc::new(file, line, column)));
+    }
+}
+
+/// Print a format string with runtime arguments. Called by `debug_print!`.
+pub fn print_expand(
+    context: &mut CubeContext,
+    format_string: impl Into<String>,
+    args: Vec<ExpandElement>,
+) {
+    let args = args.iter().map(|arg| **arg).collect();
+    context.register(NonSemantic::Print {
+        format_string: format_string.into(),
+        args,
+    });
+}
+
+/// Record an error with the given code if the condition is false. Called by `cube_assert!`.
+pub fn assert_expand(context: &mut CubeContext, condition: ExpandElement, code: u32) {
+    context.register(NonSemantic::Assert {
+        condition: *condition,
+        code,
+    });
+}
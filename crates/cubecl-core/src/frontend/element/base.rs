@@ -1,7 +1,7 @@
 use super::{flex32, CubePrimitive, Numeric, Vectorized};
 use crate::tf32;
 use crate::{
-    ir::{ConstantScalarValue, Elem, Item, Operation, Variable, VariableKind},
+    ir::{ConstantScalarValue, Elem, Instruction, Item, Operation, Variable, VariableKind},
     prelude::{
         cast as ir_cast, init_expand, CubeContext, CubeIndex, KernelBuilder, KernelLauncher,
     },
@@ -52,6 +52,15 @@ pub trait Init: Sized {
     fn init(self, context: &mut CubeContext) -> Self;
 }
 
+/// Trait to be implemented by [expand types](CubeType::ExpandType) that can be the target of a
+/// runtime assignment.
+pub trait Assign: Sized {
+    /// Copy the runtime value of `self` into `output`.
+    fn expand_assign(self, context: &mut CubeContext, output: Self);
+    /// Declare a new mutable variable with the same type as `self`, without initializing it.
+    fn expand_declare(&self, context: &mut CubeContext) -> Self;
+}
+
 /// Defines how a [launch argument](LaunchArg) can be expanded.
 ///
 /// Normally this type should be implemented two times for an argument.
@@ -250,6 +259,19 @@ impl<T: ExpandElementBaseInit> Init for ExpandElementTyped<T> {
     }
 }
 
+impl<T: CubeType> Assign for ExpandElementTyped<T> {
+    fn expand_assign(self, context: &mut CubeContext, output: Self) {
+        context.register(Instruction::new(
+            Operation::Copy(*self.expand),
+            *output.expand,
+        ));
+    }
+
+    fn expand_declare(&self, context: &mut CubeContext) -> Self {
+        context.create_local_variable(self.expand.item).into()
+    }
+}
+
 impl<T: CubeType> Vectorized for ExpandElementTyped<T> {
     fn vectorization_factor(&self) -> u32 {
         self.expand.vectorization_factor()
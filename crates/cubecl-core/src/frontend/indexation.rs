@@ -1,4 +1,4 @@
-use super::{CubeType, ExpandElement, ExpandElementTyped};
+use super::{CubeContext, CubeType, ExpandElement, ExpandElementTyped};
 use crate::{
     ir::{IntKind, UIntKind, Variable},
     unexpanded,
@@ -20,6 +20,30 @@ pub trait CubeIndexMut<T: Index>: CubeIndex<T> {
     }
 }
 
+/// Expand types that can be indexed at runtime, used by the expansion of `array[index]`.
+pub trait IndexExpand {
+    type Output;
+
+    fn expand_index(
+        self,
+        context: &mut CubeContext,
+        index: ExpandElementTyped<u32>,
+    ) -> Self::Output;
+}
+
+/// Expand types whose elements can be assigned at runtime, used by the expansion of
+/// `array[index] = value`.
+pub trait IndexAssignExpand {
+    type Value;
+
+    fn expand_index_assign(
+        self,
+        context: &mut CubeContext,
+        index: ExpandElementTyped<u32>,
+        value: Self::Value,
+    );
+}
+
 pub trait Index {
     fn value(self) -> Variable;
 }
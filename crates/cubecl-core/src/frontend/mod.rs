@@ -1,5 +1,6 @@
 pub mod branch;
 pub mod cmma;
+pub mod debug;
 pub mod synchronization;
 
 mod base;
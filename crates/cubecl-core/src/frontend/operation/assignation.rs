@@ -2,7 +2,7 @@ use half::{bf16, f16};
 
 use crate::{
     frontend::{Array, CubeContext, ExpandElement, SharedMemory, Tensor},
-    prelude::{CubeIndex, CubeIndexMut, CubeType},
+    prelude::{CubeIndex, CubeIndexMut, CubeType, IndexAssignExpand, IndexExpand},
 };
 use crate::{ir, prelude::Index};
 
@@ -30,21 +30,12 @@ pub mod cast {
 }
 
 pub mod assign {
-    use ir::{Instruction, Operation};
-
-    use crate::prelude::ExpandElementTyped;
+    use crate::prelude::Assign;
 
     use super::*;
 
-    pub fn expand<C: CubeType>(
-        context: &mut CubeContext,
-        input: ExpandElementTyped<C>,
-        output: ExpandElementTyped<C>,
-    ) {
-        context.register(Instruction::new(
-            Operation::Copy(*input.expand),
-            *output.expand,
-        ));
+    pub fn expand<A: Assign>(context: &mut CubeContext, input: A, output: A) {
+        input.expand_assign(context, output);
     }
 }
 
@@ -105,6 +96,22 @@ pub mod index_assign {
     impl_index_vec!(i64, i32, i16, i8, f16, bf16, flex32, tf32, f32, f64, u64, u32, u16, u8);
 
     impl<E: CubeType, I: Index> CubeIndexMut<I> for SliceMut<E> {}
+
+    impl<A: CubeType + CubeIndex<u32>> IndexAssignExpand for ExpandElementTyped<A>
+    where
+        A::Output: CubeType + Sized,
+    {
+        type Value = ExpandElementTyped<A::Output>;
+
+        fn expand_index_assign(
+            self,
+            context: &mut CubeContext,
+            index: ExpandElementTyped<u32>,
+            value: Self::Value,
+        ) {
+            expand(context, self, index, value)
+        }
+    }
 }
 
 pub mod index {
@@ -181,6 +188,21 @@ pub mod index {
     impl<E: CubeType, I: Index> CubeIndex<I> for SliceMut<E> {
         type Output = E;
     }
+
+    impl<A: CubeType + CubeIndex<ExpandElementTyped<u32>>> IndexExpand for ExpandElementTyped<A>
+    where
+        A::Output: CubeType + Sized,
+    {
+        type Output = ExpandElementTyped<A::Output>;
+
+        fn expand_index(
+            self,
+            context: &mut CubeContext,
+            index: ExpandElementTyped<u32>,
+        ) -> Self::Output {
+            expand(context, self, index)
+        }
+    }
 }
 
 pub mod add_assign_array_op {
@@ -3,6 +3,7 @@ mod cmma;
 mod kernel;
 mod local_allocator;
 mod macros;
+mod non_semantic;
 mod operation;
 mod plane;
 mod processing;
@@ -15,6 +16,7 @@ pub use branch::*;
 pub use cmma::*;
 pub use kernel::*;
 pub use local_allocator::*;
+pub use non_semantic::*;
 pub use operation::*;
 pub use plane::*;
 pub use scope::*;
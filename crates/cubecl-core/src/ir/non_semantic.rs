@@ -0,0 +1,39 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use super::Variable;
+
+pub use cubecl_common::format::{parse_format_string, FormatSegment};
+
+/// Operations that don't change the result of a kernel, used for debugging.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[allow(missing_docs)]
+pub enum NonSemantic {
+    /// Print a format string, where each `{}` is replaced by the next argument.
+    Print {
+        format_string: String,
+        args: Vec<Variable>,
+    },
+    /// Record an error with the given code if the condition is false. Removed in
+    /// [unchecked](cubecl_runtime::ExecutionMode::Unchecked) mode.
+    Assert { condition: Variable, code: u32 },
+}
+
+impl Display for NonSemantic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NonSemantic::Print {
+                format_string,
+                args,
+            } => {
+                write!(f, "print({format_string:?}")?;
+                for arg in args {
+                    write!(f, ", {arg}")?;
+                }
+                write!(f, ")")
+            }
+            NonSemantic::Assert { condition, code } => write!(f, "assert({condition}, {code})"),
+        }
+    }
+}
@@ -1,8 +1,11 @@
-use std::fmt::Display;
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Display},
+};
 
 use crate::prelude::AtomicOp;
 
-use super::{Branch, CoopMma, Item, Plane, Select, Synchronization, Variable};
+use super::{Branch, CoopMma, Item, NonSemantic, Plane, Select, Synchronization, Variable};
 use serde::{Deserialize, Serialize};
 
 /// All operations that can be used in a GPU compute shader.
@@ -23,13 +26,57 @@ pub enum Operation {
     Synchronization(Synchronization),
     Plane(Plane),
     CoopMma(CoopMma),
+    NonSemantic(NonSemantic),
 }
 
 /// An instruction that contains a right hand side [`Operation`] and an optional out variable.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Serialize, Deserialize, PartialEq)]
 pub struct Instruction {
     pub out: Option<Variable>,
     pub operation: Operation,
+    /// The location of the `#[cube]` code that registered this instruction, if known.
+    #[serde(default)]
+    pub source_loc: Option<SourceLoc>,
+}
+
+/// A location in the Rust source of a `#[cube]` function.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+pub struct SourceLoc {
+    pub file: Cow<'static, str>,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl SourceLoc {
+    /// Create a new source location, usually from `file!()`, `line!()` and `column!()`.
+    pub fn new(file: &'static str, line: u32, column: u32) -> Self {
+        Self {
+            file: Cow::Borrowed(file),
+            line,
+            column,
+        }
+    }
+}
+
+impl Display for SourceLoc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+impl Debug for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("Instruction");
+        debug
+            .field("out", &self.out)
+            .field("operation", &self.operation);
+        // Only shown when recorded, since debug symbols are usually disabled.
+        if let Some(source_loc) = &self.source_loc {
+            debug.field("source_loc", source_loc);
+        }
+        debug.finish()
+    }
 }
 
 impl Instruction {
@@ -37,6 +84,7 @@ impl Instruction {
         Instruction {
             out: Some(out),
             operation: operation.into(),
+            source_loc: None,
         }
     }
 
@@ -103,6 +151,7 @@ impl Display for Operation {
             Operation::Plane(plane) => write!(f, "{plane}"),
             Operation::CoopMma(coop_mma) => write!(f, "{coop_mma}"),
             Operation::Copy(variable) => write!(f, "{variable}"),
+            Operation::NonSemantic(non_semantic) => write!(f, "{non_semantic}"),
         }
     }
 }
@@ -375,6 +424,23 @@ impl From<Branch> for Instruction {
         Instruction {
             out: None,
             operation: value.into(),
+            source_loc: None,
+        }
+    }
+}
+
+impl From<NonSemantic> for Operation {
+    fn from(value: NonSemantic) -> Self {
+        Self::NonSemantic(value)
+    }
+}
+
+impl From<NonSemantic> for Instruction {
+    fn from(value: NonSemantic) -> Self {
+        Instruction {
+            out: None,
+            operation: value.into(),
+            source_loc: None,
         }
     }
 }
@@ -390,6 +456,7 @@ impl From<Synchronization> for Instruction {
         Instruction {
             out: None,
             operation: value.into(),
+            source_loc: None,
         }
     }
 }
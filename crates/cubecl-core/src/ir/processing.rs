@@ -298,6 +298,9 @@ impl ScopeProcessing {
                 Operation::Plane(_) => {
                     // Nothing to do since no constant is possible.
                 }
+                Operation::NonSemantic(_) => {
+                    // Nothing to do.
+                }
                 Operation::CoopMma(op) => match op {
                     CoopMma::Fill { value } => {
                         sanitize_constant_scalar_ref_var(value, &inst.out.unwrap());
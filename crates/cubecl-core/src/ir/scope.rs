@@ -1,8 +1,8 @@
 use crate::{ir::ConstantScalarValue, prelude::CubePrimitive};
 
 use super::{
-    cpa, processing::ScopeProcessing, Elem, Instruction, Item, Matrix, Operation, Variable,
-    VariableKind,
+    cpa, processing::ScopeProcessing, Elem, Instruction, Item, Matrix, Operation, SourceLoc,
+    Variable, VariableKind,
 };
 use serde::{Deserialize, Serialize};
 
@@ -30,6 +30,8 @@ pub struct Scope {
     reads_scalar: Vec<(Variable, Variable)>,
     pub layout_ref: Option<Variable>,
     pub undeclared: u16,
+    /// The source location given to registered instructions that don't have one.
+    pub source_loc: Option<SourceLoc>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Hash, Eq)]
@@ -62,6 +64,7 @@ impl Scope {
             reads_scalar: Vec::new(),
             layout_ref: None,
             undeclared: 0,
+            source_loc: None,
         }
     }
 
@@ -252,7 +255,11 @@ impl Scope {
 
     /// Register an [operation](Operation) into the scope.
     pub fn register<T: Into<Instruction>>(&mut self, operation: T) {
-        self.operations.push(operation.into())
+        let mut instruction = operation.into();
+        if instruction.source_loc.is_none() {
+            instruction.source_loc = self.source_loc.clone();
+        }
+        self.operations.push(instruction)
     }
 
     /// Create an empty child scope.
@@ -272,6 +279,7 @@ impl Scope {
             reads_scalar: Vec::new(),
             layout_ref: self.layout_ref,
             undeclared: 0,
+            source_loc: self.source_loc.clone(),
         }
     }
 
@@ -0,0 +1,57 @@
+use crate::{self as cubecl, as_bytes};
+
+use cubecl::prelude::*;
+
+#[cube(launch)]
+pub fn kernel_debug_print<F: Float>(input: &Array<F>, output: &mut Array<F>) {
+    if UNIT_POS < input.len() {
+        let value = input[UNIT_POS] * F::new(2.0);
+        debug_print!(
+            "output[{}] = {} (positive: {})",
+            UNIT_POS,
+            value,
+            value > F::new(0.0)
+        );
+        output[UNIT_POS] = value;
+    }
+}
+
+pub fn test_debug_print<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.create(as_bytes![F: 1.0, -2.0, 3.0]);
+    let output = client.create(as_bytes![F: 0.0, 0.0, 0.0]);
+
+    // Launch twice, to make sure printing doesn't break the following launches.
+    for _ in 0..2 {
+        kernel_debug_print::launch::<F, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::default(),
+            unsafe { ArrayArg::from_raw_parts::<F>(&input, 3, 1) },
+            unsafe { ArrayArg::from_raw_parts::<F>(&output, 3, 1) },
+        );
+        cubecl::future::block_on(client.sync());
+    }
+
+    let actual = client.read_one(output.binding());
+    let actual = F::from_bytes(&actual);
+
+    assert_eq!(actual, &[F::new(2.0), F::new(-4.0), F::new(6.0)]);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_debug_print {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_debug_print() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::debug_print::test_debug_print::<TestRuntime, FloatType>(
+                client,
+            );
+        }
+    };
+}
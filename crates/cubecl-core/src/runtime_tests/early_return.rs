@@ -0,0 +1,162 @@
+use crate::{self as cubecl, as_bytes};
+
+use cubecl::prelude::*;
+
+#[derive(CubeType)]
+#[expand(runtime)]
+pub enum Lookup {
+    #[expand(try_output)]
+    Found(u32),
+    Missing,
+}
+
+#[cube]
+fn find<F: Float>(input: &Array<F>, target: F) -> Lookup {
+    for i in 0..input.len() {
+        if input[i] == target {
+            return Lookup::Found(i);
+        }
+    }
+
+    Lookup::Missing.runtime()
+}
+
+#[cube]
+fn find_distance<F: Float>(input: &Array<F>, start: F, end: F) -> Lookup {
+    let start = find::<F>(input, start)?;
+    let end = find::<F>(input, end)?;
+
+    if end < start {
+        return Lookup::Found(start - end);
+    }
+    Lookup::Found(end - start)
+}
+
+#[cube(launch)]
+pub fn kernel_early_return<F: Float>(input: &Array<F>, output: &mut Array<F>, start: F, end: F) {
+    if UNIT_POS == 0 {
+        match find_distance::<F>(input, start, end) {
+            Lookup::Found(distance) => {
+                output[0] = F::cast_from(distance);
+            }
+            Lookup::Missing => {
+                output[0] = F::new(-1.0);
+            }
+        }
+    }
+}
+
+#[cube]
+fn lookup_or_first<F: Float>(input: &Array<F>, target: F) -> u32 {
+    match find::<F>(input, target) {
+        Lookup::Found(index) => {
+            return index;
+        }
+        Lookup::Missing => {}
+    }
+    0
+}
+
+#[cube]
+fn offset_of(mode: u32, input_len: u32) -> u32 {
+    match mode {
+        0 => {
+            return 0;
+        }
+        1 => {
+            return input_len / 2;
+        }
+        _ => {}
+    }
+    input_len - 1
+}
+
+#[cube(launch)]
+pub fn kernel_match_return<F: Float>(
+    input: &Array<F>,
+    output: &mut Array<F>,
+    target: F,
+    mode: u32,
+) {
+    if UNIT_POS == 0 {
+        output[0] = F::cast_from(lookup_or_first::<F>(input, target));
+        output[1] = F::cast_from(offset_of(mode, input.len()));
+    }
+}
+
+pub fn test_early_return<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.create(as_bytes![F: 1.0, 3.0, 5.0, 7.0]);
+    let run = |start: f32, end: f32| {
+        let output = client.create(as_bytes![F: 0.0]);
+
+        kernel_early_return::launch::<F, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::default(),
+            unsafe { ArrayArg::from_raw_parts::<F>(&input, 4, 1) },
+            unsafe { ArrayArg::from_raw_parts::<F>(&output, 1, 1) },
+            ScalarArg::new(F::new(start)),
+            ScalarArg::new(F::new(end)),
+        );
+
+        let actual = client.read_one(output.binding());
+        F::from_bytes(&actual)[0]
+    };
+
+    assert_eq!(run(7.0, 3.0), F::new(2.0));
+    assert_eq!(run(1.0, 5.0), F::new(2.0));
+    assert_eq!(run(4.0, 5.0), F::new(-1.0));
+    assert_eq!(run(1.0, 6.0), F::new(-1.0));
+}
+
+pub fn test_match_return<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.create(as_bytes![F: 1.0, 3.0, 5.0, 7.0]);
+    let run = |target: f32, mode: u32| {
+        let output = client.create(as_bytes![F: 0.0, 0.0]);
+
+        kernel_match_return::launch::<F, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::default(),
+            unsafe { ArrayArg::from_raw_parts::<F>(&input, 4, 1) },
+            unsafe { ArrayArg::from_raw_parts::<F>(&output, 2, 1) },
+            ScalarArg::new(F::new(target)),
+            ScalarArg::new(mode),
+        );
+
+        let actual = client.read_one(output.binding());
+        F::from_bytes(&actual).to_vec()
+    };
+
+    assert_eq!(run(5.0, 0), vec![F::new(2.0), F::new(0.0)]);
+    assert_eq!(run(7.0, 1), vec![F::new(3.0), F::new(2.0)]);
+    assert_eq!(run(4.0, 2), vec![F::new(0.0), F::new(3.0)]);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_early_return {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_early_return() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::early_return::test_early_return::<TestRuntime, FloatType>(
+                client,
+            );
+        }
+
+        #[test]
+        fn test_match_return() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::early_return::test_match_return::<TestRuntime, FloatType>(
+                client,
+            );
+        }
+    };
+}
@@ -0,0 +1,55 @@
+use crate::{self as cubecl};
+use cubecl::prelude::*;
+
+#[cube(launch)]
+pub fn kernel_scale(input: &Array<f32>, output: &mut Array<f32>, scale: f32, len: u32, shift: f32) {
+    if UNIT_POS < len {
+        output[UNIT_POS] = input[UNIT_POS] * scale + shift;
+    }
+}
+
+pub fn test_graph_replays_with_updated_scalars<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.create(f32::as_bytes(&[1.0, 2.0]));
+    let output = client.empty(2 * core::mem::size_of::<f32>());
+
+    let graph = client.capture(|| {
+        kernel_scale::launch::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(2, 1, 1),
+            unsafe { ArrayArg::from_raw_parts::<f32>(&input, 2, 1) },
+            unsafe { ArrayArg::from_raw_parts::<f32>(&output, 2, 1) },
+            ScalarArg::new(2.0),
+            ScalarArg::new(2),
+            ScalarArg::new(0.0),
+        );
+    });
+    let captured = client.read_one(output.clone().binding());
+
+    graph.update_scalar(0, 0, 3.0f32);
+    graph.update_scalar(0, 1, 1u32);
+    graph.update_scalar(0, 2, 0.5f32);
+    graph.replay();
+    let replayed = client.read_one(output.binding());
+
+    assert_eq!(f32::from_bytes(&captured), &[2.0, 4.0]);
+    assert_eq!(f32::from_bytes(&replayed), &[3.5, 4.0]);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_graph {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_graph_replays_with_updated_scalars() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::graph::test_graph_replays_with_updated_scalars::<
+                TestRuntime,
+            >(client);
+        }
+    };
+}
@@ -0,0 +1,137 @@
+use crate::{self as cubecl, as_bytes};
+
+use cubecl::prelude::*;
+use cubecl_runtime::server::KernelErrorKind;
+
+#[cube(launch, launch_unchecked)]
+pub fn kernel_cube_assert<F: Float>(input: &Array<F>, output: &mut Array<F>) {
+    if UNIT_POS < input.len() {
+        let value = input[UNIT_POS];
+        cube_assert!(value >= F::new(0.0), 7);
+        output[UNIT_POS] = value;
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_out_of_bounds<F: Float>(input: &Array<F>, output: &mut Array<F>) {
+    output[UNIT_POS] = input[UNIT_POS];
+}
+
+pub fn test_cube_assert<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.create(as_bytes![F: 1.0, -2.0, 3.0]);
+    let output = client.create(as_bytes![F: 0.0, 0.0, 0.0]);
+
+    kernel_cube_assert::launch::<F, R>(
+        &client,
+        CubeCount::Static(2, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts::<F>(&input, 3, 1) },
+        unsafe { ArrayArg::from_raw_parts::<F>(&output, 3, 1) },
+    );
+    let errors = cubecl::future::block_on(client.try_sync()).unwrap_err();
+    let [error] = errors.as_slice() else {
+        panic!("Expected a single error, got {errors:?}");
+    };
+
+    assert_eq!(error.kind, KernelErrorKind::Assertion { code: 7 });
+    assert_eq!(error.unit_pos, 1);
+    assert_eq!(error.count, 2, "Each cube should raise the error");
+
+    // Errors are only reported once, and the buffer is cleared for the next launches.
+    assert!(cubecl::future::block_on(client.try_sync()).is_ok());
+
+    let valid = client.create(as_bytes![F: 1.0, 2.0, 3.0]);
+    kernel_cube_assert::launch::<F, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts::<F>(&valid, 3, 1) },
+        unsafe { ArrayArg::from_raw_parts::<F>(&output, 3, 1) },
+    );
+    let actual = client.try_read(vec![output.clone().binding()]).unwrap();
+    assert_eq!(
+        F::from_bytes(&actual[0]),
+        &[F::new(1.0), F::new(2.0), F::new(3.0)]
+    );
+
+    // Assertions are removed from unchecked kernels.
+    unsafe {
+        kernel_cube_assert::launch_unchecked::<F, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::default(),
+            ArrayArg::from_raw_parts::<F>(&input, 3, 1),
+            ArrayArg::from_raw_parts::<F>(&output, 3, 1),
+        )
+    };
+
+    assert!(cubecl::future::block_on(client.try_sync()).is_ok());
+}
+
+pub fn test_strict_bounds<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.create(as_bytes![F: 1.0, 2.0, 3.0]);
+    let output = client.create(as_bytes![F: 0.0, 0.0, 0.0]);
+    let launch = |client: &ComputeClient<R::Server, R::Channel>| {
+        kernel_out_of_bounds::launch::<F, R>(
+            client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(4, 1, 1),
+            unsafe { ArrayArg::from_raw_parts::<F>(&input, 3, 1) },
+            unsafe { ArrayArg::from_raw_parts::<F>(&output, 3, 1) },
+        )
+    };
+
+    // Out-of-bound accesses are silently masked in checked mode.
+    launch(&client);
+    assert!(cubecl::future::block_on(client.try_sync()).is_ok());
+
+    let strict = client.clone().with_strict_bounds();
+    launch(&strict);
+    let errors = cubecl::future::block_on(strict.try_sync()).unwrap_err();
+    let [error] = errors.as_slice() else {
+        panic!("Expected a single error, got {errors:?}");
+    };
+
+    assert_eq!(
+        error.kind,
+        KernelErrorKind::OutOfBounds {
+            binding: Some(0),
+            index: 3,
+            length: 3
+        }
+    );
+    assert_eq!(error.unit_pos, 3);
+    assert_eq!(error.count, 2, "Both the read and the write should fail");
+
+    // The accesses are still masked.
+    let actual = strict.try_read(vec![output.binding()]).unwrap();
+    assert_eq!(
+        F::from_bytes(&actual[0]),
+        &[F::new(1.0), F::new(2.0), F::new(3.0)]
+    );
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_kernel_errors {
+    () => {
+        use super::*;
+
+        // Kernel errors are shared by all the clients of a device, so the tests raising them
+        // can't run concurrently.
+        #[test]
+        fn test_kernel_errors() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::kernel_errors::test_cube_assert::<TestRuntime, FloatType>(
+                client.clone(),
+            );
+            cubecl_core::runtime_tests::kernel_errors::test_strict_bounds::<TestRuntime, FloatType>(
+                client,
+            );
+        }
+    };
+}
@@ -0,0 +1,93 @@
+use crate as cubecl;
+
+use cubecl::prelude::*;
+
+pub fn test_write_unaligned<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let handle = client.create(&[9; 16]);
+
+    client.write(handle.clone().binding(), 2, &[1, 2, 3, 4, 5, 6]);
+    client.write(handle.clone().binding(), 13, &[7]);
+
+    let actual = client.read_one(handle.binding());
+    assert_eq!(actual, [9, 9, 1, 2, 3, 4, 5, 6, 9, 9, 9, 9, 9, 7, 9, 9]);
+}
+
+pub fn test_fill_unaligned<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let handle = client.create(&[9; 16]);
+
+    // Bytes 2..14 of the memory.
+    client.fill(
+        handle.clone().offset_start(2).offset_end(2).binding(),
+        &[1, 2],
+    );
+    let actual = client.read_one(handle.clone().binding());
+    assert_eq!(actual, [9, 9, 1, 2, 1, 2, 1, 2, 1, 2, 1, 2, 1, 2, 9, 9]);
+
+    client.fill(handle.clone().offset_start(5).offset_end(6).binding(), &[0]);
+    let actual = client.read_one(handle.binding());
+    assert_eq!(actual, [9, 9, 1, 2, 1, 0, 0, 0, 0, 0, 1, 2, 1, 2, 9, 9]);
+}
+
+pub fn test_copy_unaligned<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let src = client.create(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    let dst = client.create(&[9; 16]);
+
+    // Six bytes to the middle of the destination.
+    client.copy(
+        src.clone().offset_end(2).binding(),
+        dst.clone().offset_start(2).binding(),
+    );
+    let actual = client.read_one(dst.clone().binding());
+    assert_eq!(actual, [9, 9, 1, 2, 3, 4, 5, 6, 9, 9, 9, 9, 9, 9, 9, 9]);
+
+    // From an unaligned source.
+    client.copy(
+        src.offset_start(3).binding(),
+        dst.clone().offset_start(9).binding(),
+    );
+    let actual = client.read_one(dst.binding());
+    assert_eq!(actual, [9, 9, 1, 2, 3, 4, 5, 6, 9, 4, 5, 6, 7, 8, 9, 9]);
+}
+
+pub fn test_copy_aligned<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let src = client.create(u32::as_bytes(&[1, 2, 3]));
+    let dst = client.create(u32::as_bytes(&[0, 0, 0, 0]));
+
+    client.copy(src.binding(), dst.clone().offset_start(4).binding());
+    client.fill(dst.clone().offset_end(12).binding(), &[0xff; 4]);
+
+    let actual = client.read_one(dst.binding());
+    assert_eq!(u32::from_bytes(&actual), [u32::MAX, 1, 2, 3]);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_memory {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_memory_write_unaligned() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::memory::test_write_unaligned::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_memory_fill_unaligned() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::memory::test_fill_unaligned::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_memory_copy_unaligned() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::memory::test_copy_unaligned::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_memory_copy_aligned() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::memory::test_copy_aligned::<TestRuntime>(client);
+        }
+    };
+}
@@ -4,12 +4,22 @@ pub mod branch;
 pub mod cmma;
 pub mod const_match;
 pub mod constants;
+pub mod debug_print;
 pub mod different_rank;
+pub mod early_return;
+pub mod graph;
+pub mod kernel_errors;
 pub mod launch;
+pub mod memory;
 pub mod metadata;
 pub mod plane;
+pub mod profile;
+pub mod runtime_enum;
 pub mod sequence;
 pub mod slice;
+pub mod source_locs;
+pub mod stream;
+pub mod struct_array;
 pub mod topology;
 pub mod unary;
 
@@ -26,8 +36,16 @@ macro_rules! testgen_all {
         cubecl_core::testgen_assign!();
         cubecl_core::testgen_branch!();
         cubecl_core::testgen_const_match!();
+        cubecl_core::testgen_debug_print!();
         cubecl_core::testgen_different_rank!();
+        cubecl_core::testgen_early_return!();
+        cubecl_core::testgen_graph!();
+        cubecl_core::testgen_kernel_errors!();
         cubecl_core::testgen_launch!();
+        cubecl_core::testgen_profile!();
+        cubecl_core::testgen_runtime_enum!();
+        cubecl_core::testgen_stream!();
+        cubecl_core::testgen_struct_array!();
 
         $crate::testgen_untyped!();
     };
@@ -69,11 +87,19 @@ macro_rules! testgen_float {
         cubecl_core::testgen_binary!();
         cubecl_core::testgen_branch!();
         cubecl_core::testgen_const_match!();
+        cubecl_core::testgen_debug_print!();
         cubecl_core::testgen_different_rank!();
+        cubecl_core::testgen_early_return!();
+        cubecl_core::testgen_graph!();
+        cubecl_core::testgen_kernel_errors!();
         cubecl_core::testgen_launch!();
         cubecl_core::testgen_plane!();
+        cubecl_core::testgen_profile!();
+        cubecl_core::testgen_runtime_enum!();
         cubecl_core::testgen_sequence!();
         cubecl_core::testgen_slice!();
+        cubecl_core::testgen_stream!();
+        cubecl_core::testgen_struct_array!();
         cubecl_core::testgen_unary!();
     };
 }
@@ -97,6 +123,7 @@ macro_rules! testgen_uint {
 macro_rules! testgen_untyped {
     () => {
         cubecl_core::testgen_cmma!();
+        cubecl_core::testgen_memory!();
         cubecl_core::testgen_metadata!();
         cubecl_core::testgen_topology!();
 
@@ -0,0 +1,98 @@
+use crate::{self as cubecl, as_bytes};
+
+use cubecl::prelude::*;
+use cubecl_runtime::benchmark::TimestampsError;
+use cubecl_runtime::profile::KernelLaunch;
+
+#[cube(launch)]
+pub fn kernel_profile_double<F: Float>(output: &mut Array<F>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] *= F::new(2.0);
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_profile_increment<F: Float>(output: &mut Array<F>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] += F::new(1.0);
+    }
+}
+
+pub fn test_kernel_profile<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let output = client.create(as_bytes![F: 1.0, 2.0, 3.0, 4.0]);
+    let arg = || unsafe { ArrayArg::from_raw_parts::<F>(&output, 4, 1) };
+
+    client.start_kernel_profile();
+    kernel_profile_double::launch::<F, R>(
+        &client,
+        CubeCount::Static(2, 1, 1),
+        CubeDim::new(2, 1, 1),
+        arg(),
+    );
+    kernel_profile_increment::launch::<F, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(4, 1, 1),
+        arg(),
+    );
+    kernel_profile_double::launch::<F, R>(
+        &client,
+        CubeCount::Static(2, 1, 1),
+        CubeDim::new(2, 1, 1),
+        arg(),
+    );
+
+    let profile = match cubecl::future::block_on(client.end_kernel_profile()) {
+        Ok(profile) => profile,
+        // The device can't time kernels.
+        Err(TimestampsError::Unavailable) => return,
+        Err(err) => panic!("Profiling failed: {err:?}"),
+    };
+
+    // Other tests can launch kernels on the same device during the profile.
+    let launches: Vec<&KernelLaunch> = profile
+        .kernels
+        .iter()
+        .map(|kernel| &kernel.launch)
+        .filter(|launch| launch.name.contains("kernel_profile_"))
+        .collect();
+
+    assert_eq!(launches.len(), 3, "Each dispatch should be profiled");
+    for (launch, (name, cube_count, cube_dim)) in launches.iter().zip([
+        ("kernel_profile_double", (2, 1, 1), (2, 1, 1)),
+        ("kernel_profile_increment", (1, 1, 1), (4, 1, 1)),
+        ("kernel_profile_double", (2, 1, 1), (2, 1, 1)),
+    ]) {
+        assert!(
+            launch.name.contains(name),
+            "Expected {name}, got {}",
+            launch.name
+        );
+        assert_eq!(launch.cube_count, Some(cube_count));
+        assert_eq!(launch.cube_dim, cube_dim);
+    }
+
+    let actual = client.read_one(output.binding());
+    assert_eq!(
+        F::from_bytes(&actual),
+        &[F::new(6.0), F::new(10.0), F::new(14.0), F::new(18.0)]
+    );
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_profile {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_kernel_profile() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::profile::test_kernel_profile::<TestRuntime, FloatType>(
+                client,
+            );
+        }
+    };
+}
@@ -0,0 +1,105 @@
+use crate::{self as cubecl, as_bytes};
+
+use cubecl::prelude::*;
+
+#[derive(CubeType)]
+#[expand(runtime)]
+pub enum Search<F: Float> {
+    NotFound,
+    Found { index: u32, value: F },
+}
+
+#[cube(launch)]
+pub fn kernel_runtime_enum_search<F: Float>(input: &Array<F>, output: &mut Array<F>, target: F) {
+    if UNIT_POS == 0 {
+        let mut search = Search::<F>::NotFound.runtime();
+
+        for i in 0..input.len() {
+            if input[i] == target {
+                search = Search::<F>::Found {
+                    index: i,
+                    value: input[i],
+                };
+            }
+        }
+
+        match search {
+            Search::Found { index, value } => {
+                output[0] = F::cast_from(index);
+                output[1] = value;
+            }
+            Search::NotFound => {
+                output[0] = F::new(-1.0);
+                output[1] = F::new(-1.0);
+            }
+        }
+    }
+}
+
+pub fn test_runtime_enum_found<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.create(as_bytes![F: 1.0, 3.0, 5.0, 7.0]);
+    let output = client.create(as_bytes![F: 0.0, 0.0]);
+
+    kernel_runtime_enum_search::launch::<F, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts::<F>(&input, 4, 1) },
+        unsafe { ArrayArg::from_raw_parts::<F>(&output, 2, 1) },
+        ScalarArg::new(F::new(5.0)),
+    );
+
+    let actual = client.read_one(output.binding());
+    let actual = F::from_bytes(&actual);
+
+    assert_eq!(actual, &[F::new(2.0), F::new(5.0)]);
+}
+
+pub fn test_runtime_enum_not_found<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.create(as_bytes![F: 1.0, 3.0, 5.0, 7.0]);
+    let output = client.create(as_bytes![F: 0.0, 0.0]);
+
+    kernel_runtime_enum_search::launch::<F, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts::<F>(&input, 4, 1) },
+        unsafe { ArrayArg::from_raw_parts::<F>(&output, 2, 1) },
+        ScalarArg::new(F::new(4.0)),
+    );
+
+    let actual = client.read_one(output.binding());
+    let actual = F::from_bytes(&actual);
+
+    assert_eq!(actual, &[F::new(-1.0), F::new(-1.0)]);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_runtime_enum {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_runtime_enum_found() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::runtime_enum::test_runtime_enum_found::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
+
+        #[test]
+        fn test_runtime_enum_not_found() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::runtime_enum::test_runtime_enum_not_found::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
+    };
+}
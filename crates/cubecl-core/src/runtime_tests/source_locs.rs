@@ -0,0 +1,43 @@
+use crate::{
+    ir::{
+        BinaryOperator, ConstantScalarValue, Elem, FloatKind, Instruction, Item, KernelDefinition,
+        Operator, SourceLoc, UIntKind, Variable,
+    },
+    prelude::KernelBuilder,
+    KernelSettings,
+};
+
+/// A kernel with two instructions at `kernel.rs:3:5` followed by one at `kernel.rs:4:5`, to test
+/// how compilers emit source locations.
+pub fn kernel_with_source_locs() -> KernelDefinition {
+    let mut builder = KernelBuilder::default();
+    let item = Item::new(Elem::Float(FloatKind::F32));
+    let input = builder.scalar(item.elem);
+    let output = builder.output_array(item);
+    let context = &mut builder.context;
+    let squared = context.create_local_variable(item);
+    let sum = context.create_local_variable(item);
+
+    context.set_source_loc(Some(SourceLoc::new("kernel.rs", 3, 5)));
+    let mul = BinaryOperator {
+        lhs: *input,
+        rhs: *input,
+    };
+    context.register(Instruction::new(Operator::Mul(mul), *squared));
+    let add = BinaryOperator {
+        lhs: *squared,
+        rhs: *input,
+    };
+    context.register(Instruction::new(Operator::Add(add), *sum));
+    context.set_source_loc(Some(SourceLoc::new("kernel.rs", 4, 5)));
+    let assign = BinaryOperator {
+        lhs: Variable::constant(ConstantScalarValue::UInt(0, UIntKind::U32)),
+        rhs: *sum,
+    };
+    context.register(Instruction::new(
+        Operator::UncheckedIndexAssign(assign),
+        *output,
+    ));
+
+    builder.build(KernelSettings::default())
+}
@@ -0,0 +1,141 @@
+use crate::{self as cubecl};
+use cubecl::prelude::*;
+use cubecl_runtime::server::Handle;
+
+#[cube(launch)]
+pub fn kernel_stream_double(input: &Array<f32>, output: &mut Array<f32>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] * 2.0;
+    }
+}
+
+const NUM_ELEMS: usize = 4096;
+
+fn launch_double<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    count: CubeCount,
+    input: &Handle,
+    output: &Handle,
+) {
+    kernel_stream_double::launch::<R>(
+        client,
+        count,
+        CubeDim::new(256, 1, 1),
+        unsafe { ArrayArg::from_raw_parts::<f32>(input, NUM_ELEMS, 1) },
+        unsafe { ArrayArg::from_raw_parts::<f32>(output, NUM_ELEMS, 1) },
+    );
+}
+
+fn values(offset: f32) -> Vec<f32> {
+    (0..NUM_ELEMS).map(|i| i as f32 + offset).collect()
+}
+
+pub fn test_stream_ordered_with_events<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let stream = client.create_stream();
+    let side = client.clone().on_stream(stream);
+
+    let input = client.create(f32::as_bytes(&values(0.0)));
+    let output = client.empty(NUM_ELEMS * core::mem::size_of::<f32>());
+    side.wait_event(client.record_event());
+
+    launch_double::<R>(&side, CubeCount::Static(16, 1, 1), &input, &output);
+    side.write(output.clone().binding(), 0, f32::as_bytes(&[-1.0]));
+    client.wait_event(side.record_event());
+
+    let actual = client.read_one(output.binding());
+    let mut expected = values(0.0).iter().map(|x| x * 2.0).collect::<Vec<_>>();
+    expected[0] = -1.0;
+    assert_eq!(f32::from_bytes(&actual), expected);
+}
+
+pub fn test_stream_memory_not_reused_while_in_use<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let stream = client.create_stream();
+    let side = client.clone().on_stream(stream);
+
+    let input = client.create(f32::as_bytes(&values(0.0)));
+    let output = client.empty(NUM_ELEMS * core::mem::size_of::<f32>());
+    side.wait_event(client.record_event());
+
+    launch_double::<R>(&side, CubeCount::Static(16, 1, 1), &input, &output);
+    let event = side.record_event();
+    drop(input);
+
+    // Would overwrite the input of the kernel if its memory was reused.
+    let others = (0..4)
+        .map(|_| client.create(f32::as_bytes(&values(1000.0))))
+        .collect::<Vec<_>>();
+
+    client.wait_event(event);
+    let actual = client.read_one(output.binding());
+    let expected = values(0.0).iter().map(|x| x * 2.0).collect::<Vec<_>>();
+    assert_eq!(f32::from_bytes(&actual), expected);
+
+    for other in others {
+        let actual = client.read_one(other.binding());
+        assert_eq!(f32::from_bytes(&actual), values(1000.0));
+    }
+}
+
+pub fn test_stream_events_outlive_dynamic_dispatch<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let stream = client.create_stream();
+    let side = client.clone().on_stream(stream);
+
+    let input = client.create(f32::as_bytes(&values(0.0)));
+    let first = client.empty(NUM_ELEMS * core::mem::size_of::<f32>());
+    let second = client.empty(NUM_ELEMS * core::mem::size_of::<f32>());
+    side.wait_event(client.record_event());
+
+    launch_double::<R>(&side, CubeCount::Static(16, 1, 1), &input, &first);
+    let event = side.record_event();
+
+    let count = client.create(u32::as_bytes(&[16, 1, 1]));
+    launch_double::<R>(
+        &client,
+        CubeCount::Dynamic(count.binding()),
+        &input,
+        &second,
+    );
+
+    client.wait_event(event);
+    let expected = values(0.0).iter().map(|x| x * 2.0).collect::<Vec<_>>();
+    let actual = client.read_one(first.binding());
+    assert_eq!(f32::from_bytes(&actual), expected);
+    let actual = client.read_one(second.binding());
+    assert_eq!(f32::from_bytes(&actual), expected);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_stream {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_stream_ordered_with_events() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::stream::test_stream_ordered_with_events::<TestRuntime>(
+                client,
+            );
+        }
+
+        #[test]
+        fn test_stream_memory_not_reused_while_in_use() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::stream::test_stream_memory_not_reused_while_in_use::<
+                TestRuntime,
+            >(client);
+        }
+
+        #[test]
+        fn test_stream_events_outlive_dynamic_dispatch() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::stream::test_stream_events_outlive_dynamic_dispatch::<
+                TestRuntime,
+            >(client);
+        }
+    };
+}
@@ -0,0 +1,127 @@
+use crate::{self as cubecl, as_bytes};
+
+use cubecl::prelude::*;
+
+#[derive(CubeType, Clone, Copy)]
+#[expand(array)]
+pub struct Candidate<F: Float> {
+    value: F,
+    index: u32,
+}
+
+#[cube(launch)]
+pub fn kernel_struct_array_top_k<F: Float>(input: &Array<F>, output: &mut Array<u32>) {
+    if UNIT_POS == 0 {
+        let mut top = StructArray::<Candidate<F>>::new(2);
+        for i in 0..top.len() {
+            top[i] = Candidate::<F> {
+                value: F::new(-1.0e30),
+                index: 0,
+            };
+        }
+
+        for i in 0..input.len() {
+            let mut candidate = Candidate::<F> {
+                value: input[i],
+                index: i,
+            };
+            for k in 0..top.len() {
+                let current = top[k];
+                if candidate.value > current.value {
+                    top[k] = candidate;
+                    candidate = current;
+                }
+            }
+        }
+
+        for k in 0..top.len() {
+            output[k] = top[k].index;
+        }
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_struct_array_shared<F: Float>(input: &Array<F>, output: &mut Array<F>) {
+    let mut shared = StructArray::<Candidate<F>>::new_shared(4);
+    if UNIT_POS < 4 {
+        shared[UNIT_POS] = Candidate::<F> {
+            value: input[UNIT_POS],
+            index: UNIT_POS,
+        };
+    }
+    sync_units();
+
+    if UNIT_POS < 4 {
+        let neighbor = shared[(UNIT_POS + 1) % 4];
+        output[UNIT_POS] = neighbor.value * F::cast_from(neighbor.index);
+    }
+}
+
+pub fn test_struct_array_top_k<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.create(as_bytes![F: 3.0, 9.0, 1.0, 7.0, 5.0]);
+    let output = client.create(u32::as_bytes(&[0, 0]));
+
+    kernel_struct_array_top_k::launch::<F, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts::<F>(&input, 5, 1) },
+        unsafe { ArrayArg::from_raw_parts::<u32>(&output, 2, 1) },
+    );
+
+    let actual = client.read_one(output.binding());
+    let actual = u32::from_bytes(&actual);
+
+    assert_eq!(actual, &[1, 3]);
+}
+
+pub fn test_struct_array_shared<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.create(as_bytes![F: 1.0, 2.0, 3.0, 4.0]);
+    let output = client.create(as_bytes![F: 0.0, 0.0, 0.0, 0.0]);
+
+    kernel_struct_array_shared::launch::<F, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts::<F>(&input, 4, 1) },
+        unsafe { ArrayArg::from_raw_parts::<F>(&output, 4, 1) },
+    );
+
+    let actual = client.read_one(output.binding());
+    let actual = F::from_bytes(&actual);
+
+    assert_eq!(
+        actual,
+        &[F::new(2.0), F::new(6.0), F::new(12.0), F::new(0.0)]
+    );
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_struct_array {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_struct_array_top_k() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::struct_array::test_struct_array_top_k::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
+
+        #[test]
+        fn test_struct_array_shared() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::struct_array::test_struct_array_shared::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
+    };
+}
@@ -0,0 +1,16 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+fn closure_return(x: u32, y: u32) -> u32 {
+    let max = |x: u32, y: u32| {
+        if x > y {
+            return x;
+        }
+        y
+    };
+
+    max(x, y)
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+fn assert_missing_code(x: u32) {
+    cube_assert!(x > 0);
+}
+
+fn main() {}
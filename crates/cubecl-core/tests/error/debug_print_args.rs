@@ -0,0 +1,9 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+fn print_missing_arg(x: u32, y: u32) {
+    debug_print!("x = {}, y = {}", x);
+}
+
+fn main() {}
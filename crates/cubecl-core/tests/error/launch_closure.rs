@@ -0,0 +1,9 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube(launch)]
+fn launch_closure(output: &mut Array<u32>, f: impl Fn(u32) -> u32) {
+    let _ = f(output.len());
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+fn print_on_host(x: u32) {
+    println!("x = {}", x);
+}
+
+fn main() {}
@@ -0,0 +1,64 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[allow(dead_code)]
+mod test_compilation {
+    use super::*;
+
+    #[cube]
+    fn apply<F: Float>(value: F, f: impl Fn(F) -> F) -> F {
+        f(value)
+    }
+
+    #[cube]
+    fn apply_twice<F: Float>(value: F, f: impl Fn(F) -> F) -> F {
+        let once = apply::<F>(value, &f);
+        f(once)
+    }
+
+    #[cube]
+    fn fold<N: Numeric>(input: &Array<N>, init: N, op: impl Fn(N, N) -> N) -> N {
+        let mut acc = init;
+        for i in 0..input.len() {
+            acc = op(acc, input[i]);
+        }
+        acc
+    }
+
+    #[cube]
+    fn for_each(input: &Array<f32>, output: &mut Array<f32>, mut f: impl FnMut(f32) -> f32) {
+        for i in 0..input.len() {
+            output[i] = f(input[i]);
+        }
+    }
+
+    #[cube]
+    fn scale<F: Float>(value: F, factor: F) -> F {
+        apply::<F>(value, |x| x * factor)
+    }
+
+    #[cube]
+    fn add_one_twice(value: f32) -> f32 {
+        apply_twice::<f32>(value, |x: f32| x + 1.0)
+    }
+
+    #[cube]
+    fn max<N: Numeric>(input: &Array<N>) -> N {
+        fold::<N>(input, input[0], |a, b| N::max(a, b))
+    }
+
+    #[cube]
+    fn sum_squares(input: &Array<u32>) -> u32 {
+        fold::<u32>(input, 0, |acc, value| acc + value * value)
+    }
+
+    #[cube]
+    fn relu(input: &Array<f32>, output: &mut Array<f32>) {
+        for_each(input, output, |x| f32::max(x, 0.0));
+    }
+
+    #[cube(launch)]
+    fn kernel_sum_squares(input: &Array<u32>, output: &mut Array<u32>) {
+        output[0] = sum_squares(input);
+    }
+}
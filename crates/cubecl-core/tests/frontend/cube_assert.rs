@@ -0,0 +1,53 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+pub fn cube_assert_kernel<F: Float>(input: &Array<F>) {
+    let value = input[UNIT_POS];
+    cube_assert!(value > F::new(0.0), 1);
+    cube_assert!(true, 2u32 + 1);
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::{
+        cpa,
+        ir::{Builtin, Elem, Item, NonSemantic, Variable},
+    };
+
+    type ElemType = f32;
+
+    #[test]
+    fn cube_support_cube_assert() {
+        let mut context = CubeContext::default();
+        let input = context.input(0, Item::new(ElemType::as_elem()));
+
+        cube_assert_kernel::expand::<ElemType>(&mut context, input.into());
+        assert_eq!(
+            format!("{:?}", context.into_scope().operations),
+            inline_macro_ref()
+        );
+    }
+
+    fn inline_macro_ref() -> String {
+        let mut context = CubeContext::default();
+        let item = Item::new(ElemType::as_elem());
+        let input = context.input(0, item);
+
+        let mut scope = context.into_scope();
+        let input: Variable = input.into();
+        let value = scope.create_local(item);
+        let condition = scope.create_local(Item::new(Elem::Bool));
+
+        let unit_pos = Variable::builtin(Builtin::UnitPos);
+        cpa!(&mut scope, value = input[unit_pos]);
+        cpa!(&mut scope, condition = value > 0.0f32);
+        scope.register(NonSemantic::Assert { condition, code: 1 });
+        scope.register(NonSemantic::Assert {
+            condition: true.into(),
+            code: 3,
+        });
+
+        format!("{:?}", scope.operations)
+    }
+}
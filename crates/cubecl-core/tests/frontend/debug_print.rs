@@ -0,0 +1,54 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+pub fn debug_print_kernel<F: Float>(input: &Array<F>) {
+    let value = input[UNIT_POS];
+    debug_print!("input[{}] = {}", UNIT_POS, value);
+    debug_print!("{{constant}} {}", 2u32);
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::{
+        cpa,
+        ir::{Builtin, Item, NonSemantic, Variable},
+    };
+
+    type ElemType = f32;
+
+    #[test]
+    fn cube_support_debug_print() {
+        let mut context = CubeContext::default();
+        let input = context.input(0, Item::new(ElemType::as_elem()));
+
+        debug_print_kernel::expand::<ElemType>(&mut context, input.into());
+        assert_eq!(
+            format!("{:?}", context.into_scope().operations),
+            inline_macro_ref()
+        );
+    }
+
+    fn inline_macro_ref() -> String {
+        let mut context = CubeContext::default();
+        let item = Item::new(ElemType::as_elem());
+        let input = context.input(0, item);
+
+        let mut scope = context.into_scope();
+        let input: Variable = input.into();
+        let value = scope.create_local(item);
+
+        let unit_pos = Variable::builtin(Builtin::UnitPos);
+        cpa!(&mut scope, value = input[unit_pos]);
+        scope.register(NonSemantic::Print {
+            format_string: "input[{}] = {}".to_string(),
+            args: vec![unit_pos, value],
+        });
+        scope.register(NonSemantic::Print {
+            format_string: "{{constant}} {}".to_string(),
+            args: vec![2u32.into()],
+        });
+
+        format!("{:?}", scope.operations)
+    }
+}
@@ -0,0 +1,114 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[allow(dead_code)]
+mod test_compilation {
+    use super::*;
+
+    #[derive(CubeType)]
+    #[expand(runtime)]
+    enum Checked {
+        #[expand(try_output)]
+        Valid(u32),
+        OutOfBounds,
+    }
+
+    #[cube]
+    fn clamp_index(index: u32, len: u32) -> u32 {
+        if index >= len {
+            return len - 1;
+        }
+
+        index
+    }
+
+    #[cube]
+    fn sign(value: f32) -> f32 {
+        if value < 0.0 {
+            return -1.0;
+        } else if value > 0.0 {
+            return 1.0;
+        }
+        0.0
+    }
+
+    #[cube]
+    fn select<F: Float>(lhs: Line<F>, rhs: Line<F>, cond: bool) -> Line<F> {
+        if cond {
+            return lhs;
+        }
+        rhs
+    }
+
+    #[cube]
+    fn first_index(input: &Array<f32>, target: f32) -> u32 {
+        for i in 0..input.len() {
+            if input[i] == target {
+                return i;
+            }
+        }
+
+        input.len()
+    }
+
+    #[cube]
+    fn first_index_2d(input: &Array<f32>, width: u32, target: f32) -> u32 {
+        let height = input.len() / width;
+        let mut y = 0;
+        loop {
+            for x in 0..width {
+                if input[y * width + x] == target {
+                    return y;
+                }
+            }
+            y += 1;
+            if y == height {
+                break;
+            }
+        }
+
+        height
+    }
+
+    #[cube]
+    fn checked_index(index: u32, len: u32) -> Checked {
+        if index >= len {
+            return Checked::OutOfBounds.runtime();
+        }
+
+        Checked::Valid(index)
+    }
+
+    #[cube]
+    fn checked_sum(input: &Array<u32>, lhs: u32, rhs: u32) -> Checked {
+        let lhs = checked_index(lhs, input.len())?;
+        let rhs = checked_index(rhs, input.len())?;
+
+        Checked::Valid(input[lhs] + input[rhs])
+    }
+
+    #[cube]
+    fn checked_or_zero(input: &Array<u32>, index: u32) -> u32 {
+        match checked_index(index, input.len()) {
+            Checked::Valid(index) => {
+                return input[index];
+            }
+            Checked::OutOfBounds => {}
+        }
+        0
+    }
+
+    #[cube]
+    fn step(mode: u32) -> u32 {
+        match mode {
+            0 => {
+                return 1;
+            }
+            1 => {
+                return 2;
+            }
+            _ => {}
+        }
+        mode
+    }
+}
@@ -29,3 +29,84 @@ mod test_compilation {
         Mul { lhs: u32, rhs: u32 },
     }
 }
+
+#[allow(dead_code)]
+mod test_runtime {
+    use super::*;
+
+    #[derive(CubeType)]
+    #[expand(runtime)]
+    enum Found {
+        None,
+        Some(u32),
+        At { index: u32, value: f32 },
+    }
+
+    #[derive(CubeType)]
+    #[expand(runtime)]
+    enum Accumulated<F: Float> {
+        Empty,
+        Value(F),
+    }
+
+    #[cube]
+    fn find(input: &Array<f32>, target: f32) -> u32 {
+        let mut found = Found::None.runtime();
+
+        for i in 0..input.len() {
+            if input[i] == target {
+                found = Found::Some(i);
+            }
+        }
+
+        match found {
+            Found::Some(index) => index,
+            Found::At { index, .. } => index,
+            Found::None => input.len(),
+        }
+    }
+
+    #[cube]
+    fn find_at(input: &Array<f32>, target: f32) -> f32 {
+        let mut found = Found::None.runtime();
+
+        for i in 0..input.len() {
+            if input[i] == target {
+                found = Found::At {
+                    value: input[i],
+                    index: i,
+                };
+            }
+        }
+
+        let mut out = 0.0;
+        match found {
+            Found::At { value, .. } => {
+                out = value;
+            }
+            Found::None | Found::Some(_) => {}
+        }
+        out
+    }
+
+    #[cube]
+    fn accumulate<F: Float>(input: &Array<F>) -> F {
+        let mut acc = Accumulated::<F>::Empty.runtime();
+
+        for i in 0..input.len() {
+            match acc {
+                Accumulated::Value(value) => {
+                    acc = Accumulated::<F>::Value(value + input[i]);
+                }
+                Accumulated::Empty => {
+                    acc = Accumulated::<F>::Value(input[i]);
+                }
+            }
+        }
+
+        match acc {
+            Accumulated::Value(value) => value,
+            Accumulated::Empty => F::new(0.0),
+        }
+    }
+}
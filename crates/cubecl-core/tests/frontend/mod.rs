@@ -2,10 +2,14 @@ mod array;
 mod assign;
 mod cast_elem;
 mod cast_kind;
+mod closure;
 mod comptime;
 mod constants;
+mod cube_assert;
 mod cube_impl;
 mod cube_trait;
+mod debug_print;
+mod early_return;
 mod enum_type;
 mod for_loop;
 mod function_call;
@@ -21,6 +25,7 @@ mod redeclare;
 mod reuse;
 mod shared_memory;
 mod r#struct;
+mod struct_array;
 mod tensor;
 mod topology;
 mod r#trait;
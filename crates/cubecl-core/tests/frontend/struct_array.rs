@@ -0,0 +1,56 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[allow(dead_code)]
+mod test_compilation {
+    use super::*;
+
+    #[derive(CubeType, Clone, Copy)]
+    #[expand(array)]
+    struct Candidate<F: Float> {
+        value: F,
+        index: u32,
+    }
+
+    #[cube]
+    fn insert_top_k<F: Float>(top: &mut StructArray<Candidate<F>>, value: F, index: u32) {
+        let mut candidate = Candidate::<F> { value, index };
+        for i in 0..top.len() {
+            let current = top[i];
+            if candidate.value > current.value {
+                top[i] = candidate;
+                candidate = current;
+            }
+        }
+    }
+
+    #[cube]
+    fn top_k<F: Float>(input: &Array<F>) -> u32 {
+        let mut top = StructArray::<Candidate<F>>::new(4);
+        for i in 0..4 {
+            top[i] = Candidate::<F> {
+                value: F::new(-1.0e30),
+                index: 0,
+            };
+        }
+
+        for i in 0..input.len() {
+            insert_top_k::<F>(&mut top, input[i], i);
+        }
+
+        top[0].index
+    }
+
+    #[cube]
+    fn shared_candidates<F: Float>(input: &Array<F>) -> F {
+        let mut shared = StructArray::<Candidate<F>>::new_shared(32);
+        shared[UNIT_POS] = Candidate::<F> {
+            value: input[UNIT_POS],
+            index: UNIT_POS,
+        };
+        sync_units();
+
+        let neighbor = shared[(UNIT_POS + 1) % 32];
+        neighbor.value
+    }
+}
@@ -7,7 +7,7 @@ use cubecl_core::{
     prelude::CubePrimitive,
     Compiler, Feature,
 };
-use cubecl_runtime::{DeviceProperties, ExecutionMode};
+use cubecl_runtime::{server::KernelErrorKind, DeviceProperties, ExecutionMode};
 
 use super::{
     AtomicKind, BinaryInstruction, Binding, Body, ComputeKernel, ConstArray, Elem, Fragment,
@@ -53,6 +53,9 @@ pub struct CppCompiler<D: Dialect> {
     items: HashSet<Item<D>>,
     strategy: ExecutionMode,
     settings: VariableSettings,
+    debug_symbols: bool,
+    source_loc: Option<gpu::SourceLoc>,
+    errors: bool,
 }
 
 impl<D: Dialect> Compiler for CppCompiler<D> {
@@ -64,6 +67,7 @@ impl<D: Dialect> Compiler for CppCompiler<D> {
     ) -> Self::Representation {
         let compiler = Self {
             strategy,
+            debug_symbols: cubecl_runtime::debug::debug_symbols_enabled(),
             ..Self::default()
         };
         let ir = compiler.compile_ir(kernel);
@@ -99,11 +103,21 @@ impl<D: Dialect> CppCompiler<D> {
             .into_iter()
             .map(|b| self.compile_binding(b))
             .collect();
-        let named = value
+        let mut named = value
             .named
             .into_iter()
             .map(|(name, binding)| (name, self.compile_binding(binding)))
-            .collect();
+            .collect::<Vec<_>>();
+        // Bound by the server after all other bindings.
+        if self.errors {
+            named.push((
+                "errors".to_string(),
+                Binding {
+                    item: Item::scalar(Elem::U32),
+                    size: None,
+                },
+            ));
+        }
 
         let body = Body {
             instructions,
@@ -183,14 +197,32 @@ impl<D: Dialect> CppCompiler<D> {
             });
         }
 
-        processing
-            .operations
-            .into_iter()
-            .for_each(|op| self.compile_operation(&mut instructions, op, scope));
+        processing.operations.into_iter().for_each(|op| {
+            self.compile_source_loc(&mut instructions, op.source_loc.clone());
+            self.compile_operation(&mut instructions, op, scope)
+        });
 
         instructions
     }
 
+    /// Point the next instructions to their source location when it changes.
+    fn compile_source_loc(
+        &mut self,
+        instructions: &mut Vec<Instruction<D>>,
+        source_loc: Option<gpu::SourceLoc>,
+    ) {
+        match source_loc {
+            Some(loc) if self.debug_symbols && self.source_loc.as_ref() != Some(&loc) => {
+                instructions.push(Instruction::Line {
+                    file: loc.file.to_string(),
+                    line: loc.line,
+                });
+                self.source_loc = Some(loc);
+            }
+            _ => {}
+        }
+    }
+
     fn compile_operation(
         &mut self,
         instructions: &mut Vec<Instruction<D>>,
@@ -266,6 +298,49 @@ impl<D: Dialect> CppCompiler<D> {
                 }
             }
             gpu::Operation::CoopMma(cmma) => instructions.push(self.compile_cmma(cmma, out)),
+            gpu::Operation::NonSemantic(gpu::NonSemantic::Print {
+                format_string,
+                args,
+            }) => instructions.push(Instruction::Print {
+                format_string,
+                args: args
+                    .into_iter()
+                    .map(|arg| self.compile_variable(arg))
+                    .collect(),
+            }),
+            gpu::Operation::NonSemantic(gpu::NonSemantic::Assert { condition, code }) => {
+                if let ExecutionMode::Checked | ExecutionMode::Strict = self.strategy {
+                    self.errors = true;
+                    instructions.push(Instruction::Assert {
+                        condition: self.compile_variable(condition),
+                        code,
+                        unit_pos: self
+                            .compile_variable(gpu::Variable::builtin(gpu::Builtin::UnitPos)),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Record an out-of-bounds error if `index` isn't smaller than the length of `array`.
+    fn compile_bounds_check(
+        &mut self,
+        array: gpu::Variable,
+        index: gpu::Variable,
+        len: gpu::Variable,
+    ) -> Instruction<D> {
+        self.errors = true;
+        let binding = match array.kind {
+            gpu::VariableKind::GlobalInputArray(id) => id as u32,
+            gpu::VariableKind::GlobalOutputArray(id) => self.num_inputs as u32 + id as u32,
+            _ => KernelErrorKind::NO_BINDING,
+        };
+
+        Instruction::CheckBounds {
+            index: self.compile_variable(index),
+            len: self.compile_variable(len),
+            binding,
+            unit_pos: self.compile_variable(gpu::Variable::builtin(gpu::Builtin::UnitPos)),
         }
     }
 
@@ -505,7 +580,11 @@ impl<D: Dialect> CppCompiler<D> {
                 out: self.compile_variable(out),
             }),
             gpu::Operator::Index(op) => {
-                if matches!(self.strategy, ExecutionMode::Checked) && has_length(&op.lhs) {
+                if matches!(
+                    self.strategy,
+                    ExecutionMode::Checked | ExecutionMode::Strict
+                ) && has_length(&op.lhs)
+                {
                     let lhs = op.lhs;
                     let rhs = op.rhs;
                     let array_len = scope.create_local(gpu::Item::new(u32::as_elem()));
@@ -518,6 +597,9 @@ impl<D: Dialect> CppCompiler<D> {
                     };
 
                     instructions.push(self.compile_metadata(length, Some(array_len)));
+                    if let ExecutionMode::Strict = self.strategy {
+                        instructions.push(self.compile_bounds_check(lhs, rhs, array_len));
+                    }
                     instructions.push(Instruction::CheckedIndex {
                         len: self.compile_variable(array_len),
                         lhs: self.compile_variable(lhs),
@@ -532,8 +614,19 @@ impl<D: Dialect> CppCompiler<D> {
                 instructions.push(Instruction::Index(self.compile_binary(op, out)))
             }
             gpu::Operator::IndexAssign(op) => {
-                if let ExecutionMode::Checked = self.strategy {
+                if let ExecutionMode::Checked | ExecutionMode::Strict = self.strategy {
                     if has_length(&out) {
+                        if let ExecutionMode::Strict = self.strategy {
+                            let array_len = scope.create_local(gpu::Item::new(u32::as_elem()));
+                            instructions.extend(self.compile_scope(scope));
+
+                            let length = match has_buffer_length(&out) {
+                                true => gpu::Metadata::BufferLength { var: out },
+                                false => gpu::Metadata::Length { var: out },
+                            };
+                            instructions.push(self.compile_metadata(length, Some(array_len)));
+                            instructions.push(self.compile_bounds_check(out, op.lhs, array_len));
+                        }
                         CheckedIndexAssign {
                             lhs: op.lhs,
                             rhs: op.rhs,
@@ -1025,3 +1118,32 @@ pub fn register_supported_types(props: &mut DeviceProperties<Feature>) {
         props.register_feature(Feature::Type(ty));
     }
 }
+
+#[cfg(all(test, feature = "cuda"))]
+mod tests {
+    use super::*;
+    use crate::CudaCompiler;
+    use cubecl_core::runtime_tests::source_locs::kernel_with_source_locs;
+
+    #[test]
+    fn source_locs_are_emitted_as_line_directives_when_they_change() {
+        let compiler = CudaCompiler {
+            debug_symbols: true,
+            ..Default::default()
+        };
+        let kernel = compiler.compile_ir(kernel_with_source_locs()).to_string();
+
+        assert_eq!(kernel.matches("#line 3 \"kernel.rs\"\n").count(), 1);
+        assert_eq!(kernel.matches("#line 4 \"kernel.rs\"\n").count(), 1);
+        assert!(kernel.find("#line 3").unwrap() < kernel.find("#line 4").unwrap());
+    }
+
+    #[test]
+    fn source_locs_are_skipped_without_debug_symbols() {
+        let kernel = CudaCompiler::default()
+            .compile_ir(kernel_with_source_locs())
+            .to_string();
+
+        assert!(!kernel.contains("#line"));
+    }
+}
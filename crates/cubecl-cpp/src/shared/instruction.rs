@@ -1,8 +1,11 @@
 use crate::shared::FmtLeft;
 
 use super::{
-    binary::*, unary::*, Component, Dialect, Elem, Item, Variable, WarpInstruction, WmmaInstruction,
+    binary::*, unary::*, AtomicKind, Component, Dialect, Elem, Item, Variable, WarpInstruction,
+    WmmaInstruction,
 };
+use cubecl_core::ir::{parse_format_string, FormatSegment};
+use cubecl_runtime::server::KernelErrorKind;
 use std::{fmt::Display, marker::PhantomData};
 
 #[derive(Debug, Clone)]
@@ -36,6 +39,25 @@ pub enum Instruction<D: Dialect> {
     DeclareVariable {
         var: Variable<D>,
     },
+    Line {
+        file: String,
+        line: u32,
+    },
+    Print {
+        format_string: String,
+        args: Vec<Variable<D>>,
+    },
+    Assert {
+        condition: Variable<D>,
+        code: u32,
+        unit_pos: Variable<D>,
+    },
+    CheckBounds {
+        index: Variable<D>,
+        len: Variable<D>,
+        binding: u32,
+        unit_pos: Variable<D>,
+    },
     Modulo(BinaryInstruction<D>),
     Remainder(BinaryInstruction<D>),
     Add(BinaryInstruction<D>),
@@ -187,6 +209,45 @@ impl<D: Dialect> Display for Instruction<D> {
                     writeln!(f, "{item} {var};")
                 }
             },
+            // Start on a new line, since some instructions aren't terminated by one.
+            Instruction::Line { file, line } => write!(f, "\n#line {line} {file:?}\n"),
+            Instruction::Print {
+                format_string,
+                args,
+            } => format_print(f, format_string, args),
+            Instruction::Assert {
+                condition,
+                code,
+                unit_pos,
+            } => {
+                writeln!(f, "if (!({condition})) {{")?;
+                format_error(
+                    f,
+                    KernelErrorKind::ASSERTION,
+                    unit_pos,
+                    [format!("{code}u")],
+                )?;
+                writeln!(f, "}}")
+            }
+            Instruction::CheckBounds {
+                index,
+                len,
+                binding,
+                unit_pos,
+            } => {
+                writeln!(f, "if (!(uint({index}) < {len})) {{")?;
+                format_error(
+                    f,
+                    KernelErrorKind::OUT_OF_BOUNDS,
+                    unit_pos,
+                    [
+                        format!("{binding}u"),
+                        format!("uint({index})"),
+                        len.to_string(),
+                    ],
+                )?;
+                writeln!(f, "}}")
+            }
             Instruction::Add(it) => Add::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Slice {
                 input,
@@ -786,3 +847,78 @@ impl<'a, V: Display, D: Dialect> Display for EnsureBoolArg<'a, V, D> {
         }
     }
 }
+
+/// Record an error in the error buffer, if it's the first one raised by the launch.
+fn format_error<D: Dialect, const N: usize>(
+    f: &mut std::fmt::Formatter<'_>,
+    tag: u32,
+    unit_pos: &Variable<D>,
+    args: [String; N],
+) -> std::fmt::Result {
+    writeln!(f, "if (atomicAdd(&errors[0], 1u) == 0u) {{")?;
+    writeln!(f, "errors[1] = {tag}u;")?;
+    writeln!(f, "errors[2] = {unit_pos};")?;
+    writeln!(f, "errors[3] = blockIdx.x;")?;
+    writeln!(f, "errors[4] = blockIdx.y;")?;
+    writeln!(f, "errors[5] = blockIdx.z;")?;
+    for (i, arg) in args.iter().enumerate() {
+        writeln!(f, "errors[{}] = {arg};", 6 + i)?;
+    }
+    writeln!(f, "}}")
+}
+
+fn format_print<D: Dialect>(
+    f: &mut std::fmt::Formatter<'_>,
+    format_string: &str,
+    args: &[Variable<D>],
+) -> std::fmt::Result {
+    let segments = parse_format_string(format_string)
+        .unwrap_or_else(|err| panic!("Invalid print format string {format_string:?}: {err}"));
+    let mut format = String::new();
+    let mut values = Vec::new();
+    let mut args = args.iter();
+
+    for segment in segments {
+        match segment {
+            FormatSegment::Text(text) => format.push_str(&text.replace('%', "%%")),
+            FormatSegment::Arg => {
+                let arg = args.next().expect("Missing print argument");
+                let item = arg.item();
+                let (spec, cast) = match item.elem {
+                    Elem::F16 | Elem::F162 | Elem::BF16 | Elem::BF162 | Elem::TF32 => {
+                        ("%f", Some("(float)"))
+                    }
+                    Elem::F32 | Elem::F64 => ("%f", None),
+                    Elem::I8 | Elem::I16 | Elem::Bool => ("%d", Some("(int)")),
+                    Elem::I32 | Elem::Atomic(AtomicKind::I32) => ("%d", None),
+                    Elem::I64 => ("%lld", None),
+                    Elem::U8 | Elem::U16 => ("%u", Some("(unsigned int)")),
+                    Elem::U32 | Elem::Atomic(AtomicKind::U32) => ("%u", None),
+                    Elem::U64 => ("%llu", None),
+                    Elem::_Dialect(_) => unreachable!(),
+                };
+                let value = |value: String| match cast {
+                    Some(cast) => format!("{cast}({value})"),
+                    None => value,
+                };
+
+                if item.vectorization > 1 {
+                    let specs = vec![spec; item.vectorization];
+                    format.push_str(&format!("[{}]", specs.join(", ")));
+                    values.extend((0..item.vectorization).map(|i| value(arg.index(i).to_string())));
+                } else {
+                    format.push_str(spec);
+                    values.push(value(arg.to_string()));
+                }
+            }
+        }
+    }
+
+    // Each print is on its own line, like the prints logged on the host.
+    format.push('\n');
+    write!(f, "printf({format:?}")?;
+    for value in values {
+        write!(f, ", {value}")?;
+    }
+    f.write_str(");\n")
+}
@@ -61,6 +61,14 @@ pub struct ComputeKernel<D: Dialect> {
     pub items: HashSet<super::Item<D>>,
 }
 
+impl<D: Dialect> ComputeKernel<D> {
+    /// Whether the kernel can raise errors, in which case the server must bind a zeroed error
+    /// buffer after all other bindings.
+    pub fn raises_errors(&self) -> bool {
+        self.named.iter().any(|(name, _)| name == "errors")
+    }
+}
+
 impl<D: Dialect> CompilerRepresentation for ComputeKernel<D> {
     fn shared_memory_size(&self) -> usize {
         let mut current = 0usize;
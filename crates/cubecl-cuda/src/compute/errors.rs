@@ -0,0 +1,113 @@
+use cubecl_core::KernelId;
+use cubecl_runtime::{
+    errors::{KernelErrorBuffers, PendingErrors},
+    memory_management::MemoryManagement,
+    server::{self, KernelError, StreamId},
+};
+use cudarc::driver::sys::CUstream;
+use std::collections::HashMap;
+
+use super::{storage::CudaStorage, CudaResource};
+
+type Buffers = KernelErrorBuffers<(StreamId, KernelId), server::Handle>;
+
+/// Errors raised by kernels, like failed `cube_assert!`.
+///
+/// Each stream has its own buffer per kernel, so kernels running concurrently on different
+/// streams don't race on the same buffer. The buffers of the kernels launched since the last read
+/// are copied and cleared on sync and read, and the errors are collected once the copies are done.
+#[derive(Debug, Default)]
+pub(crate) struct KernelErrors {
+    /// The names of the kernels that can raise errors.
+    kernels: HashMap<KernelId, String>,
+    buffers: Buffers,
+}
+
+/// Copies of error buffers that are collected once the stream reaches them.
+pub(crate) struct CopiedErrors {
+    pending: PendingErrors,
+    words: Vec<Vec<u32>>,
+}
+
+impl KernelErrors {
+    /// Register a kernel that can raise errors. Its buffers are created on first launch.
+    pub fn register(&mut self, kernel_id: KernelId, name: &str) {
+        self.kernels.insert(kernel_id, name.to_string());
+    }
+
+    /// The buffer to bind when launching the kernel on the stream, if it can raise errors.
+    pub fn binding(
+        &mut self,
+        stream_id: StreamId,
+        kernel_id: &KernelId,
+        memory_management: &mut MemoryManagement<CudaStorage>,
+        stream: CUstream,
+    ) -> Option<CudaResource> {
+        let name = self.kernels.get(kernel_id)?;
+        let key = (stream_id, kernel_id.clone());
+
+        if !self.buffers.contains(&key) {
+            let size = Buffers::BUFFER_SIZE;
+            let memory = memory_management.reserve(size as u64, None);
+            let handle = server::Handle::new(memory, None, None, size as u64);
+            let resource = buffer_resource(&handle, memory_management);
+            unsafe {
+                cudarc::driver::result::memset_d8_async(resource.ptr, 0, size, stream).unwrap();
+            }
+            self.buffers.register(key.clone(), name.as_str(), handle);
+        }
+
+        let handle = self.buffers.launch(&key)?;
+        Some(buffer_resource(handle, memory_management))
+    }
+
+    /// Copy and clear the buffers of the kernels launched since the last read.
+    pub fn read(
+        &mut self,
+        memory_management: &mut MemoryManagement<CudaStorage>,
+        stream: CUstream,
+    ) -> CopiedErrors {
+        let (handles, pending) = self.buffers.read();
+        let words = handles
+            .into_iter()
+            .map(|handle| {
+                let resource = buffer_resource(handle, memory_management);
+                let mut words = vec![0u32; KernelError::BUFFER_WORDS];
+                unsafe {
+                    cudarc::driver::result::memcpy_dtoh_async(&mut words, resource.ptr, stream)
+                        .unwrap();
+                    cudarc::driver::result::memset_d8_async(
+                        resource.ptr,
+                        0,
+                        Buffers::BUFFER_SIZE,
+                        stream,
+                    )
+                    .unwrap();
+                }
+                words
+            })
+            .collect();
+
+        CopiedErrors { pending, words }
+    }
+
+    /// Take the errors collected so far.
+    pub fn take(&mut self) -> Vec<KernelError> {
+        self.buffers.take()
+    }
+}
+
+impl CopiedErrors {
+    /// Collect the errors of the copied buffers. The stream must have completed the copies.
+    pub fn collect(self) {
+        self.pending.collect(self.words);
+    }
+}
+
+fn buffer_resource(
+    handle: &server::Handle,
+    memory_management: &mut MemoryManagement<CudaStorage>,
+) -> CudaResource {
+    let binding = handle.clone().binding();
+    memory_management.get_resource(binding.memory, binding.offset_start, binding.offset_end)
+}
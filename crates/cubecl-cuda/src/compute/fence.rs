@@ -1,10 +1,11 @@
-use cudarc::driver::sys::{CUevent_flags, CUevent_st, CUevent_wait_flags, CUstream_st};
+use cudarc::driver::sys::{CUevent_flags, CUevent_st, CUevent_wait_flags, CUresult, CUstream_st};
 
 /// A fence is simply an [event](CUevent_st) created on a [stream](CUevent_st) that you can wait
 /// until completion.
 ///
 /// This is useful for doing synchronization outside of the compute server, which is normally
 /// locked by a mutex or a channel. This allows the server to continue accepting other tasks.
+#[derive(Debug)]
 pub struct Fence {
     stream: *mut CUstream_st,
     event: *mut CUevent_st,
@@ -51,6 +52,38 @@ impl Fence {
             cudarc::driver::result::event::destroy(self.event).unwrap();
         }
     }
+
+    /// Make the tasks enqueued to the given [stream](CUstream_st) after this call wait until the
+    /// [Fence] is reached, without blocking the host.
+    pub fn wait_on(&self, stream: *mut CUstream_st) {
+        unsafe {
+            cudarc::driver::result::stream::wait_event(
+                stream,
+                self.event,
+                CUevent_wait_flags::CU_EVENT_WAIT_DEFAULT,
+            )
+            .unwrap();
+        }
+    }
+
+    /// Whether all tasks enqueued to the [stream](CUstream_st) before the [Fence] are completed,
+    /// without blocking the host.
+    pub fn is_reached(&self) -> bool {
+        match unsafe { cudarc::driver::sys::lib().cuEventQuery(self.event) } {
+            CUresult::CUDA_SUCCESS => true,
+            CUresult::CUDA_ERROR_NOT_READY => false,
+            status => panic!("Can't query the fence: {status:?}"),
+        }
+    }
+
+    /// Destroy the [Fence] without waiting for it.
+    ///
+    /// Streams already waiting on the [Fence] are unaffected.
+    pub fn destroy(self) {
+        unsafe {
+            cudarc::driver::result::event::destroy(self.event).unwrap();
+        }
+    }
 }
 
 /// A stream synchronization point that blocks until all previously enqueued work in the stream
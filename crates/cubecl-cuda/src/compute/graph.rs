@@ -0,0 +1,92 @@
+use cubecl_core::{prelude::CubeCount, KernelId};
+use cubecl_runtime::server::Binding;
+use cudarc::driver::sys::{CUgraphExec, CUstream, CUstreamCaptureMode};
+
+/// A kernel executed while capturing a graph, with the bindings it keeps alive.
+#[derive(Debug, Clone)]
+pub struct CapturedLaunch {
+    pub kernel_id: KernelId,
+    pub count: CubeCount,
+    pub bindings: Vec<Binding>,
+}
+
+/// The kernels of a graph, launched all at once from a CUDA graph when they could be recorded in
+/// one, or one by one otherwise.
+#[derive(Debug)]
+pub struct CudaGraph {
+    pub launches: Vec<CapturedLaunch>,
+    exec: Option<CUgraphExec>,
+}
+
+impl CudaGraph {
+    /// Start recording the kernels launched on the [stream](CUstream), instead of executing them.
+    pub fn begin(stream: CUstream) {
+        unsafe {
+            cudarc::driver::sys::lib()
+                .cuStreamBeginCapture_v2(
+                    stream,
+                    CUstreamCaptureMode::CU_STREAM_CAPTURE_MODE_THREAD_LOCAL,
+                )
+                .result()
+                .unwrap();
+        }
+    }
+
+    /// Stop recording the kernels launched on the [stream](CUstream) since [begin](Self::begin),
+    /// and instantiate the CUDA graph launching them.
+    pub fn end(stream: CUstream, launches: Vec<CapturedLaunch>) -> Self {
+        unsafe {
+            let lib = cudarc::driver::sys::lib();
+            let mut graph = std::ptr::null_mut();
+            lib.cuStreamEndCapture(stream, &mut graph).result().unwrap();
+
+            let mut exec = std::ptr::null_mut();
+            lib.cuGraphInstantiateWithFlags(&mut exec, graph, 0)
+                .result()
+                .unwrap();
+            lib.cuGraphDestroy(graph).result().unwrap();
+
+            Self {
+                launches,
+                exec: Some(exec),
+            }
+        }
+    }
+
+    /// A graph whose kernels are launched one by one.
+    pub fn unrecorded(launches: Vec<CapturedLaunch>) -> Self {
+        Self {
+            launches,
+            exec: None,
+        }
+    }
+
+    /// Launch the CUDA graph on the [stream](CUstream), returning `false` if the kernels
+    /// couldn't be recorded in one and must be launched one by one.
+    pub fn launch(&self, stream: CUstream) -> bool {
+        let Some(exec) = self.exec else {
+            return false;
+        };
+
+        unsafe {
+            cudarc::driver::sys::lib()
+                .cuGraphLaunch(exec, stream)
+                .result()
+                .unwrap();
+        }
+        true
+    }
+}
+
+impl Drop for CudaGraph {
+    fn drop(&mut self) {
+        if let Some(exec) = self.exec.take() {
+            unsafe {
+                cudarc::driver::sys::lib()
+                    .cuGraphExecDestroy(exec)
+                    .result()
+                    .unwrap();
+            }
+        }
+    }
+}
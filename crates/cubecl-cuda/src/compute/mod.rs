@@ -1,3 +1,6 @@
+mod errors;
+mod graph;
+mod profile;
 mod server;
 mod storage;
 
@@ -0,0 +1,77 @@
+use cubecl_runtime::profile::{KernelLaunch, KernelProfile, ProfiledKernel};
+use cudarc::driver::sys::{CUevent, CUevent_flags, CUstream};
+use std::time::Duration;
+
+/// Times the kernels launched on a [stream](CUstream) with a pair of events recorded around each
+/// launch.
+#[derive(Debug, Default)]
+pub struct EventProfile {
+    kernels: Vec<(KernelLaunch, CUevent, CUevent)>,
+}
+
+impl EventProfile {
+    /// Record the start event of the given launch, which must be followed by a call to
+    /// [end](EventProfile::end) once the kernel is enqueued.
+    pub fn start(&mut self, launch: KernelLaunch, stream: CUstream) {
+        let start = record(stream);
+        self.kernels.push((launch, start, std::ptr::null_mut()));
+    }
+
+    /// Record the end event of the last started launch.
+    pub fn end(&mut self, stream: CUstream) {
+        let kernel = self.kernels.last_mut().expect("A launch should be started");
+        kernel.2 = record(stream);
+    }
+
+    /// Compute the profile from the recorded events, then destroy them.
+    ///
+    /// # Notes
+    ///
+    /// The [stream](CUstream) must be synchronized first.
+    pub fn finish(mut self) -> KernelProfile {
+        let recorded = core::mem::take(&mut self.kernels);
+        let first = recorded.first().map(|kernel| kernel.1);
+
+        let kernels = recorded
+            .into_iter()
+            .map(|(launch, start, end)| unsafe {
+                let offset = cudarc::driver::result::event::elapsed(first.unwrap(), start).unwrap();
+                let duration = cudarc::driver::result::event::elapsed(start, end).unwrap();
+                destroy(start, end);
+
+                ProfiledKernel {
+                    launch,
+                    start: Duration::from_secs_f32(offset / 1000.),
+                    duration: Duration::from_secs_f32(duration / 1000.),
+                }
+            })
+            .collect();
+
+        KernelProfile { kernels }
+    }
+}
+
+impl Drop for EventProfile {
+    fn drop(&mut self) {
+        for (_, start, end) in self.kernels.drain(..) {
+            destroy(start, end);
+        }
+    }
+}
+
+fn record(stream: CUstream) -> CUevent {
+    unsafe {
+        let event = cudarc::driver::result::event::create(CUevent_flags::CU_EVENT_DEFAULT).unwrap();
+        cudarc::driver::result::event::record(event, stream).unwrap();
+        event
+    }
+}
+
+fn destroy(start: CUevent, end: CUevent) {
+    unsafe {
+        cudarc::driver::result::event::destroy(start).unwrap();
+        if !end.is_null() {
+            cudarc::driver::result::event::destroy(end).unwrap();
+        }
+    }
+}
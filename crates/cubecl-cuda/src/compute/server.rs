@@ -1,19 +1,24 @@
 use cubecl_cpp::cuda::arch::CudaArchitecture;
 use cubecl_cpp::{formatter::format_cpp, CudaCompiler};
 
+use super::errors::KernelErrors;
 use super::fence::{Fence, SyncStream};
+use super::graph::{CapturedLaunch, CudaGraph};
+use super::profile::EventProfile;
 use super::storage::CudaStorage;
 use super::{uninit_vec, CudaResource};
-use cubecl_core::compute::DebugInformation;
+use cubecl_core::compute::{DebugInformation, DefinitionKernel};
 use cubecl_core::ir::CubeDim;
+use cubecl_core::ir::KernelDefinition;
 use cubecl_core::Feature;
 use cubecl_core::{prelude::*, KernelId};
 use cubecl_runtime::debug::{DebugLogger, ProfileLevel};
-use cubecl_runtime::memory_management::MemoryUsage;
+use cubecl_runtime::memory_management::{MemoryUsage, StreamMemory};
+use cubecl_runtime::profile::{KernelLaunch, ProfileResult};
 use cubecl_runtime::storage::BindingResource;
 use cubecl_runtime::{
     memory_management::MemoryManagement,
-    server::{self, ComputeServer},
+    server::{self, ComputeServer, EventId, GraphId, KernelError, RecordableServer, StreamId},
 };
 use cubecl_runtime::{ExecutionMode, TimestampsError, TimestampsResult};
 use cudarc::driver::sys::CUctx_st;
@@ -34,10 +39,26 @@ pub struct CudaServer {
 #[derive(Debug)]
 pub(crate) struct CudaContext {
     context: *mut CUctx_st,
+    /// The stream tasks are currently enqueued to, which is the default stream unless a task
+    /// targets another one.
     stream: cudarc::driver::sys::CUstream,
+    /// The id of the stream tasks are currently enqueued to.
+    stream_id: StreamId,
+    /// Every stream of the context, the default stream being the first.
+    streams: Vec<cudarc::driver::sys::CUstream>,
+    events: HashMap<EventId, Fence>,
+    next_event: u64,
+    /// The kernels executed since the capture started, if kernels are being captured.
+    capture: Option<Vec<CapturedLaunch>>,
+    graphs: HashMap<GraphId, CudaGraph>,
+    next_graph: u64,
     memory_management: MemoryManagement<CudaStorage>,
+    /// The memory used by the tasks of the streams other than the default one.
+    stream_memory: StreamMemory<Fence>,
     module_names: HashMap<KernelId, CompiledKernel>,
     timestamps: KernelTimestamps,
+    profile: Option<EventProfile>,
+    errors: KernelErrors,
     pub(crate) arch: CudaArchitecture,
 }
 
@@ -115,26 +136,73 @@ impl CudaServer {
             result.push(data);
         }
 
+        let errors = ctx.errors.read(&mut ctx.memory_management, ctx.stream);
         let fence = ctx.fence();
 
         async move {
             fence.wait();
+            errors.collect();
             result
         }
     }
 
     fn sync_stream_async(&mut self) -> impl Future<Output = ()> + 'static + Send {
         let ctx = self.get_context();
+        ctx.join_streams();
+        ctx.release_stream_memory();
         // We can't use a fence here because no action has been recorded on the context.
         // We need at least one action to be recorded after the context is initialized
         // with `cudarc::driver::result::ctx::set_current(self.ctx.context)` for the fence
         // to have any effect. Otherwise, it seems to be ignored.
+        let errors = ctx.errors.read(&mut ctx.memory_management, ctx.stream);
         let sync = ctx.lazy_sync_stream();
 
         async move {
             sync.wait();
+            errors.collect();
         }
     }
+
+    fn cube_count(&mut self, count: CubeCount) -> (u32, u32, u32) {
+        match count {
+            CubeCount::Static(x, y, z) => (x, y, z),
+            // TODO: CUDA doesn't have an exact equivalen of dynamic dispatch. Instead, kernels are free to launch other kernels.
+            // One option is to create a dummy kernel with 1 thread that launches the real kernel with the dynamic dispatch settings.
+            // For now, just read the dispatch settings from the buffer.
+            CubeCount::Dynamic(binding) => {
+                let data = self.read_sync(binding);
+                let data = bytemuck::cast_slice(&data);
+                assert!(
+                    data.len() == 3,
+                    "Dynamic cube count should contain 3 values"
+                );
+                (data[0], data[1], data[2])
+            }
+        }
+    }
+
+    /// Run the function with the tasks it enqueues targeting the given stream.
+    ///
+    /// The memory of the bindings isn't reused until those tasks are completed.
+    fn on_stream<R>(
+        &mut self,
+        stream: StreamId,
+        bindings: Vec<server::Binding>,
+        func: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.ctx.stream = self.ctx.get_stream(stream);
+        self.ctx.stream_id = stream;
+        let result = func(self);
+
+        if stream != StreamId::default() {
+            let fence = self.ctx.fence();
+            self.ctx.stream_memory.register(stream, fence, bindings);
+        }
+        self.ctx.stream = self.ctx.streams[0];
+        self.ctx.stream_id = StreamId::default();
+
+        result
+    }
 }
 
 impl ComputeServer for CudaServer {
@@ -169,10 +237,81 @@ impl ComputeServer for CudaServer {
 
     fn empty(&mut self, size: usize) -> server::Handle {
         let ctx = self.get_context();
+        ctx.release_stream_memory();
         let handle = ctx.memory_management.reserve(size as u64, None);
         server::Handle::new(handle, None, None, size as u64)
     }
 
+    fn copy(&mut self, src: server::Binding, dst: server::Binding) {
+        let ctx = self.get_context();
+        let src = ctx
+            .memory_management
+            .get_resource(src.memory, src.offset_start, src.offset_end);
+        let dst = ctx
+            .memory_management
+            .get_resource(dst.memory, dst.offset_start, dst.offset_end);
+        assert!(
+            src.size() <= dst.size(),
+            "Can't copy {} bytes to a binding of {} bytes",
+            src.size(),
+            dst.size()
+        );
+
+        unsafe {
+            cudarc::driver::result::memcpy_dtod_async(
+                dst.ptr,
+                src.ptr,
+                src.size() as usize,
+                ctx.stream,
+            )
+            .unwrap();
+        }
+    }
+
+    fn fill(&mut self, binding: server::Binding, pattern: &[u8]) {
+        let ctx = self.get_context();
+        let resource = ctx.memory_management.get_resource(
+            binding.memory,
+            binding.offset_start,
+            binding.offset_end,
+        );
+        let data = server::fill_pattern(pattern, resource.size() as usize);
+
+        unsafe {
+            if pattern.iter().all(|byte| *byte == pattern[0]) {
+                cudarc::driver::result::memset_d8_async(
+                    resource.ptr,
+                    pattern[0],
+                    data.len(),
+                    ctx.stream,
+                )
+                .unwrap();
+            } else {
+                cudarc::driver::result::memcpy_htod_async(resource.ptr, &data, ctx.stream).unwrap();
+            }
+        }
+    }
+
+    fn write(&mut self, binding: server::Binding, offset: u64, data: &[u8]) {
+        let ctx = self.get_context();
+        let resource = ctx.memory_management.get_resource(
+            binding.memory,
+            binding.offset_start,
+            binding.offset_end,
+        );
+        assert!(
+            offset + data.len() as u64 <= resource.size(),
+            "Can't write {} bytes at offset {offset} of a binding of {} bytes",
+            data.len(),
+            resource.size()
+        );
+
+        unsafe {
+            cudarc::driver::result::memcpy_htod_async(resource.ptr + offset, data, ctx.stream)
+                .unwrap();
+        }
+    }
+
     unsafe fn execute(
         &mut self,
         kernel: Self::Kernel,
@@ -190,21 +329,21 @@ impl ComputeServer for CudaServer {
             None
         };
 
-        let count = match count {
-            CubeCount::Static(x, y, z) => (x, y, z),
-            // TODO: CUDA doesn't have an exact equivalen of dynamic dispatch. Instead, kernels are free to launch other kernels.
-            // One option is to create a dummy kernel with 1 thread that launches the real kernel with the dynamic dispatch settings.
-            // For now, just read the dispatch settings from the buffer.
-            CubeCount::Dynamic(binding) => {
-                let data = self.read_sync(binding);
-                let data = bytemuck::cast_slice(&data);
-                assert!(
-                    data.len() == 3,
-                    "Dynamic cube count should contain 3 values"
-                );
-                (data[0], data[1], data[2])
-            }
-        };
+        let profiled = self
+            .ctx
+            .profile
+            .is_some()
+            .then(|| (kernel.name(), matches!(count, CubeCount::Static(..))));
+
+        if let Some(capture) = &mut self.ctx.capture {
+            capture.push(CapturedLaunch {
+                kernel_id: kernel_id.clone(),
+                count: count.clone(),
+                bindings: bindings.clone(),
+            });
+        }
+
+        let count = self.cube_count(count);
 
         let (ctx, logger) = self.get_context_with_logger();
 
@@ -212,21 +351,21 @@ impl ComputeServer for CudaServer {
             ctx.compile_kernel(&kernel_id, kernel, logger, mode);
         }
 
-        let resources = bindings
-            .into_iter()
-            .map(|binding| {
-                ctx.memory_management.get_resource(
-                    binding.memory,
-                    binding.offset_start,
-                    binding.offset_end,
-                )
-            })
-            .collect::<Vec<_>>();
+        let resources = ctx.resources(&kernel_id, bindings);
+
+        let launch = profiled.map(|(name, is_static)| {
+            let cube_dim = ctx.module_names[&kernel_id].cube_dim;
+            KernelLaunch {
+                name: name.to_string(),
+                cube_count: is_static.then_some(count),
+                cube_dim: (cube_dim.x, cube_dim.y, cube_dim.z),
+            }
+        });
 
         if let Some(level) = profile_level {
             ctx.sync();
             let start = std::time::SystemTime::now();
-            ctx.execute_task(kernel_id, count, resources);
+            ctx.execute_task(kernel_id, count, resources, launch);
             ctx.sync();
 
             let (name, kernel_id) = profile_info.unwrap();
@@ -246,10 +385,147 @@ impl ComputeServer for CudaServer {
             self.logger
                 .register_profiled(info, start.elapsed().unwrap());
         } else {
-            ctx.execute_task(kernel_id, count, resources);
+            ctx.execute_task(kernel_id, count, resources, launch);
+        }
+    }
+
+    fn create_stream(&mut self) -> StreamId {
+        let ctx = self.get_context();
+        let stream = cudarc::driver::result::stream::create(
+            cudarc::driver::result::stream::StreamKind::NonBlocking,
+        )
+        .unwrap();
+        ctx.streams.push(stream);
+
+        StreamId {
+            value: ctx.streams.len() as u32 - 1,
         }
     }
 
+    fn copy_on(&mut self, stream: StreamId, src: server::Binding, dst: server::Binding) {
+        self.on_stream(stream, vec![src.clone(), dst.clone()], |server| {
+            server.copy(src, dst)
+        })
+    }
+
+    fn fill_on(&mut self, stream: StreamId, binding: server::Binding, pattern: &[u8]) {
+        self.on_stream(stream, vec![binding.clone()], |server| {
+            server.fill(binding, pattern)
+        })
+    }
+
+    fn write_on(&mut self, stream: StreamId, binding: server::Binding, offset: u64, data: &[u8]) {
+        self.on_stream(stream, vec![binding.clone()], |server| {
+            server.write(binding, offset, data)
+        })
+    }
+
+    unsafe fn execute_on(
+        &mut self,
+        stream: StreamId,
+        kernel: Self::Kernel,
+        count: CubeCount,
+        bindings: Vec<server::Binding>,
+        mode: ExecutionMode,
+    ) {
+        let mut used = bindings.clone();
+        if let CubeCount::Dynamic(binding) = &count {
+            used.push(binding.clone());
+        }
+
+        self.on_stream(stream, used, |server| {
+            server.execute(kernel, count, bindings, mode)
+        })
+    }
+
+    fn record_event(&mut self, stream: StreamId) -> EventId {
+        let ctx = self.get_context();
+        let event = EventId {
+            value: ctx.next_event,
+        };
+        ctx.next_event += 1;
+        ctx.events.insert(event, Fence::new(ctx.get_stream(stream)));
+
+        event
+    }
+
+    fn wait_event(&mut self, stream: StreamId, event: EventId) {
+        let ctx = self.get_context();
+        let stream = ctx.get_stream(stream);
+        // Events are freed on sync, once they are reached, so there is nothing to wait on.
+        if let Some(fence) = ctx.events.get(&event) {
+            fence.wait_on(stream);
+        }
+    }
+
+    fn start_capture(&mut self) {
+        self.ctx.capture = Some(Vec::new());
+    }
+
+    fn end_capture(&mut self) -> Option<GraphId> {
+        let ctx = self.get_context();
+        let launches = ctx.capture.take().expect("Kernels should be captured");
+
+        // Dynamic cube counts are read on the host before each launch, so kernels using them
+        // can't be recorded in a CUDA graph.
+        let graph = if launches
+            .iter()
+            .all(|launch| matches!(launch.count, CubeCount::Static(..)))
+        {
+            CudaGraph::begin(ctx.stream);
+            for launch in launches.iter() {
+                let CubeCount::Static(x, y, z) = launch.count else {
+                    unreachable!()
+                };
+                let resources = ctx.resources(&launch.kernel_id, launch.bindings.clone());
+                ctx.execute_task(launch.kernel_id.clone(), (x, y, z), resources, None);
+            }
+            CudaGraph::end(ctx.stream, launches)
+        } else {
+            CudaGraph::unrecorded(launches)
+        };
+
+        let id = GraphId {
+            value: ctx.next_graph,
+        };
+        ctx.next_graph += 1;
+        ctx.graphs.insert(id, graph);
+
+        Some(id)
+    }
+
+    fn replay_graph(&mut self, graph: GraphId) {
+        let ctx = self.get_context();
+        let graph = match ctx.graphs.get(&graph) {
+            Some(graph) => graph,
+            None => panic!("Unknown graph {graph:?}"),
+        };
+
+        if graph.launch(ctx.stream) {
+            // Mark the error buffers of the kernels as launched, so they are read on sync.
+            for launch in graph.launches.iter() {
+                ctx.errors.binding(
+                    ctx.stream_id,
+                    &launch.kernel_id,
+                    &mut ctx.memory_management,
+                    ctx.stream,
+                );
+            }
+            return;
+        }
+
+        for launch in graph.launches.clone() {
+            let count = self.cube_count(launch.count);
+            let ctx = self.get_context();
+            let resources = ctx.resources(&launch.kernel_id, launch.bindings);
+            ctx.execute_task(launch.kernel_id, count, resources, None);
+        }
+    }
+
+    fn free_graph(&mut self, graph: GraphId) {
+        self.ctx.graphs.remove(&graph);
+    }
+
     fn flush(&mut self) {}
 
     fn sync(&mut self) -> impl Future<Output = ()> + 'static {
@@ -300,6 +576,47 @@ impl ComputeServer for CudaServer {
             self.ctx.timestamps.disable();
         }
     }
+
+    fn start_profile(&mut self) {
+        self.ctx.profile = Some(EventProfile::default());
+    }
+
+    fn end_profile(&mut self) -> impl Future<Output = ProfileResult> + Send + 'static {
+        let ctx = self.get_context();
+        ctx.sync();
+
+        let profile = match ctx.profile.take() {
+            Some(profile) => Ok(profile.finish()),
+            None => Err(TimestampsError::Disabled),
+        };
+
+        async move { profile }
+    }
+
+    fn errors(&mut self) -> Vec<KernelError> {
+        let errors = self.ctx.errors.take();
+        errors
+            .into_iter()
+            .map(|error| self.logger.debug(error))
+            .collect()
+    }
+}
+
+impl RecordableServer for CudaServer {
+    type KernelRecord = KernelDefinition;
+    type KernelKey = KernelId;
+
+    fn kernel_key(kernel: &Self::Kernel) -> KernelId {
+        kernel.id()
+    }
+
+    fn record_kernel(kernel: &Self::Kernel) -> Option<KernelDefinition> {
+        kernel.definition()
+    }
+
+    fn replay_kernel(definition: KernelDefinition) -> Self::Kernel {
+        DefinitionKernel::replay(definition)
+    }
 }
 
 impl CudaContext {
@@ -312,10 +629,20 @@ impl CudaContext {
         Self {
             context,
             memory_management,
+            stream_memory: StreamMemory::default(),
             module_names: HashMap::new(),
             stream,
+            stream_id: StreamId::default(),
+            streams: vec![stream],
+            events: HashMap::new(),
+            next_event: 0,
+            capture: None,
+            graphs: HashMap::new(),
+            next_graph: 0,
             arch,
             timestamps: KernelTimestamps::Disabled,
+            profile: None,
+            errors: KernelErrors::default(),
         }
     }
 
@@ -329,8 +656,44 @@ impl CudaContext {
 
     fn sync(&mut self) {
         unsafe {
-            cudarc::driver::result::stream::synchronize(self.stream).unwrap();
+            for stream in self.streams.iter() {
+                cudarc::driver::result::stream::synchronize(*stream).unwrap();
+            }
         };
+        for fence in self.stream_memory.release_all() {
+            fence.destroy();
+        }
+    }
+
+    fn get_stream(&self, stream: StreamId) -> cudarc::driver::sys::CUstream {
+        match self.streams.get(stream.value as usize) {
+            Some(stream) => *stream,
+            None => panic!("Unknown stream {stream:?}"),
+        }
+    }
+
+    /// Make the default stream wait on the tasks already enqueued to every other stream, so that
+    /// synchronizing it synchronizes the whole context, then free the recorded events.
+    fn join_streams(&mut self) {
+        for stream in self.streams.iter().skip(1) {
+            let fence = Fence::new(*stream);
+            fence.wait_on(self.streams[0]);
+            fence.destroy();
+        }
+        self.free_events();
+    }
+
+    /// Let the memory pools reuse the memory of the completed tasks of the streams.
+    fn release_stream_memory(&mut self) {
+        for fence in self.stream_memory.release(Fence::is_reached) {
+            fence.destroy();
+        }
+    }
+
+    fn free_events(&mut self) {
+        for (_, fence) in self.events.drain() {
+            fence.destroy();
+        }
     }
 
     fn compile_kernel(
@@ -352,6 +715,11 @@ impl CudaContext {
 
         let shared_mem_bytes = kernel_compiled.shared_mem_bytes;
         let cube_dim = kernel_compiled.cube_dim;
+        if let Some(repr) = &kernel_compiled.repr {
+            if repr.raises_errors() {
+                self.errors.register(kernel_id.clone(), kernel.name());
+            }
+        }
         let arch = format!("--gpu-architecture=sm_{}", self.arch);
 
         let include_path = include_path();
@@ -395,11 +763,38 @@ impl CudaContext {
         );
     }
 
+    /// The resources of the bindings of a kernel, followed by its error buffer if it has one.
+    fn resources(
+        &mut self,
+        kernel_id: &KernelId,
+        bindings: Vec<server::Binding>,
+    ) -> Vec<CudaResource> {
+        let mut resources = bindings
+            .into_iter()
+            .map(|binding| {
+                self.memory_management.get_resource(
+                    binding.memory,
+                    binding.offset_start,
+                    binding.offset_end,
+                )
+            })
+            .collect::<Vec<_>>();
+        resources.extend(self.errors.binding(
+            self.stream_id,
+            kernel_id,
+            &mut self.memory_management,
+            self.stream,
+        ));
+
+        resources
+    }
+
     fn execute_task(
         &mut self,
         kernel_id: KernelId,
         dispatch_count: (u32, u32, u32),
         resources: Vec<CudaResource>,
+        launch: Option<KernelLaunch>,
     ) {
         let mut bindings = resources
             .iter()
@@ -408,6 +803,11 @@ impl CudaContext {
 
         let kernel = self.module_names.get(&kernel_id).unwrap();
         let cube_dim = kernel.cube_dim;
+
+        if let (Some(profile), Some(launch)) = (&mut self.profile, launch.clone()) {
+            profile.start(launch, self.stream);
+        }
+
         unsafe {
             cudarc::driver::result::launch_kernel(
                 kernel.func,
@@ -419,6 +819,10 @@ impl CudaContext {
             )
             .unwrap();
         };
+
+        if let (Some(profile), Some(_)) = (&mut self.profile, launch) {
+            profile.end(self.stream);
+        }
     }
 
     fn memory_usage(&self) -> MemoryUsage {
@@ -20,5 +20,9 @@ mod tests {
     cubecl_linalg::testgen_plane_mma!([f16, bf16, f32], f32);
     cubecl_linalg::testgen_tiling2d!([f16, bf16, f32]);
     cubecl_linalg::testgen_cmma_old!([f16, bf16, f32 /*, f64*/]);
+    cubecl_linalg::testgen_conv2d!([f16, f32]);
+    cubecl_linalg::testgen_permute!([f16, f32]);
+    cubecl_linalg::testgen_attention!([f16, bf16, f32]);
     cubecl_std::testgen_reduce!();
+    cubecl_std::testgen_functional!();
 }
@@ -0,0 +1,120 @@
+use cubecl_core::KernelId;
+use cubecl_hip_sys::HIP_SUCCESS;
+use cubecl_runtime::{
+    errors::KernelErrorBuffers,
+    memory_management::MemoryManagement,
+    server::{self, KernelError, StreamId},
+};
+use std::collections::HashMap;
+
+use super::{storage::HipStorage, HipResource};
+
+type Buffers = KernelErrorBuffers<(StreamId, KernelId), server::Handle>;
+
+/// Errors raised by kernels, like failed `cube_assert!`.
+///
+/// Each stream has its own buffer per kernel, so kernels running concurrently on different
+/// streams don't race on the same buffer. The buffers of the kernels launched since the last read
+/// are read and cleared on sync and read.
+#[derive(Debug, Default)]
+pub(crate) struct KernelErrors {
+    /// The names of the kernels that can raise errors.
+    kernels: HashMap<KernelId, String>,
+    buffers: Buffers,
+}
+
+impl KernelErrors {
+    /// Register a kernel that can raise errors. Its buffers are created on first launch.
+    pub fn register(&mut self, kernel_id: KernelId, name: &str) {
+        self.kernels.insert(kernel_id, name.to_string());
+    }
+
+    /// The buffer to bind when launching the kernel on the stream, if it can raise errors.
+    pub fn binding(
+        &mut self,
+        stream_id: StreamId,
+        kernel_id: &KernelId,
+        memory_management: &mut MemoryManagement<HipStorage>,
+        stream: cubecl_hip_sys::hipStream_t,
+    ) -> Option<HipResource> {
+        let name = self.kernels.get(kernel_id)?;
+        let key = (stream_id, kernel_id.clone());
+
+        if !self.buffers.contains(&key) {
+            let size = Buffers::BUFFER_SIZE as u64;
+            let memory = memory_management.reserve(size, None);
+            let handle = server::Handle::new(memory, None, None, size);
+            clear(&buffer_resource(&handle, memory_management), stream);
+            self.buffers.register(key.clone(), name.as_str(), handle);
+        }
+
+        let handle = self.buffers.launch(&key)?;
+        Some(buffer_resource(handle, memory_management))
+    }
+
+    /// Read and clear the buffers of the kernels launched since the last read. The stream must be
+    /// synchronized.
+    pub fn read(
+        &mut self,
+        memory_management: &mut MemoryManagement<HipStorage>,
+        stream: cubecl_hip_sys::hipStream_t,
+    ) {
+        let (handles, pending) = self.buffers.read();
+        let words = handles
+            .into_iter()
+            .map(|handle| {
+                let resource = buffer_resource(handle, memory_management);
+                let mut words = vec![0u32; KernelError::BUFFER_WORDS];
+                unsafe {
+                    let status = cubecl_hip_sys::hipMemcpyDtoHAsync(
+                        words.as_mut_ptr() as *mut _,
+                        resource.ptr,
+                        Buffers::BUFFER_SIZE,
+                        stream,
+                    );
+                    assert_eq!(status, HIP_SUCCESS, "Should copy data from device to host");
+                    let status = cubecl_hip_sys::hipStreamSynchronize(stream);
+                    assert_eq!(
+                        status, HIP_SUCCESS,
+                        "Should successfully synchronize stream"
+                    );
+                }
+                clear(&resource, stream);
+                words
+            })
+            .collect::<Vec<_>>();
+        pending.collect(words);
+    }
+
+    /// Take the errors read so far.
+    pub fn take(&mut self) -> Vec<KernelError> {
+        self.buffers.take()
+    }
+}
+
+fn clear(resource: &HipResource, stream: cubecl_hip_sys::hipStream_t) {
+    let zeros = [0u32; KernelError::BUFFER_WORDS];
+    unsafe {
+        let status = cubecl_hip_sys::hipMemcpyHtoDAsync(
+            resource.ptr,
+            zeros.as_ptr() as *const _ as *mut _,
+            Buffers::BUFFER_SIZE,
+            stream,
+        );
+        assert_eq!(status, HIP_SUCCESS, "Should send data to device");
+        // The zeros are on the stack, so the copy must complete before they go out of scope.
+        let status = cubecl_hip_sys::hipStreamSynchronize(stream);
+        assert_eq!(
+            status, HIP_SUCCESS,
+            "Should successfully synchronize stream"
+        );
+    }
+}
+
+fn buffer_resource(
+    handle: &server::Handle,
+    memory_management: &mut MemoryManagement<HipStorage>,
+) -> HipResource {
+    let binding = handle.clone().binding();
+    memory_management.get_resource(binding.memory, binding.offset_start, binding.offset_end)
+}
@@ -1,3 +1,5 @@
+mod errors;
+mod profile;
 mod server;
 mod storage;
 
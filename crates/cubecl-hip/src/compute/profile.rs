@@ -0,0 +1,89 @@
+use cubecl_hip_sys::{hipEvent_t, hipStream_t, HIP_SUCCESS};
+use cubecl_runtime::profile::{KernelLaunch, KernelProfile, ProfiledKernel};
+use std::time::Duration;
+
+/// Times the kernels launched on a [stream](hipStream_t) with a pair of events recorded around
+/// each launch.
+#[derive(Debug, Default)]
+pub struct EventProfile {
+    kernels: Vec<(KernelLaunch, hipEvent_t, hipEvent_t)>,
+}
+
+impl EventProfile {
+    /// Record the start event of the given launch, which must be followed by a call to
+    /// [end](EventProfile::end) once the kernel is enqueued.
+    pub fn start(&mut self, launch: KernelLaunch, stream: hipStream_t) {
+        let start = record(stream);
+        self.kernels.push((launch, start, std::ptr::null_mut()));
+    }
+
+    /// Record the end event of the last started launch.
+    pub fn end(&mut self, stream: hipStream_t) {
+        let kernel = self.kernels.last_mut().expect("A launch should be started");
+        kernel.2 = record(stream);
+    }
+
+    /// Compute the profile from the recorded events, then destroy them.
+    ///
+    /// # Notes
+    ///
+    /// The [stream](hipStream_t) must be synchronized first.
+    pub fn finish(mut self) -> KernelProfile {
+        let recorded = core::mem::take(&mut self.kernels);
+        let first = recorded.first().map(|kernel| kernel.1);
+
+        let kernels = recorded
+            .into_iter()
+            .map(|(launch, start, end)| {
+                let offset = elapsed(first.unwrap(), start);
+                let duration = elapsed(start, end);
+                destroy(start, end);
+
+                ProfiledKernel {
+                    launch,
+                    start: Duration::from_secs_f32(offset / 1000.),
+                    duration: Duration::from_secs_f32(duration / 1000.),
+                }
+            })
+            .collect();
+
+        KernelProfile { kernels }
+    }
+}
+
+impl Drop for EventProfile {
+    fn drop(&mut self) {
+        for (_, start, end) in self.kernels.drain(..) {
+            destroy(start, end);
+        }
+    }
+}
+
+fn record(stream: hipStream_t) -> hipEvent_t {
+    unsafe {
+        let mut event: hipEvent_t = std::ptr::null_mut();
+        let status = cubecl_hip_sys::hipEventCreate(&mut event);
+        assert_eq!(status, HIP_SUCCESS, "Should create an event");
+        let status = cubecl_hip_sys::hipEventRecord(event, stream);
+        assert_eq!(status, HIP_SUCCESS, "Should record the event");
+        event
+    }
+}
+
+fn elapsed(start: hipEvent_t, end: hipEvent_t) -> f32 {
+    let mut ms = 0.0;
+    unsafe {
+        let status = cubecl_hip_sys::hipEventElapsedTime(&mut ms, start, end);
+        assert_eq!(status, HIP_SUCCESS, "Should compute the elapsed time");
+    }
+    ms
+}
+
+fn destroy(start: hipEvent_t, end: hipEvent_t) {
+    unsafe {
+        cubecl_hip_sys::hipEventDestroy(start);
+        if !end.is_null() {
+            cubecl_hip_sys::hipEventDestroy(end);
+        }
+    }
+}
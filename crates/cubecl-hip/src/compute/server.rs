@@ -2,19 +2,23 @@ use cubecl_cpp::formatter::format_cpp;
 
 use crate::runtime::HipCompiler;
 
+use super::errors::KernelErrors;
+use super::profile::EventProfile;
 use super::storage::HipStorage;
 use super::HipResource;
-use cubecl_core::compute::DebugInformation;
+use cubecl_core::compute::{DebugInformation, DefinitionKernel};
 use cubecl_core::ir::CubeDim;
+use cubecl_core::ir::KernelDefinition;
 use cubecl_core::Feature;
 use cubecl_core::{prelude::*, KernelId};
 use cubecl_hip_sys::{hiprtcResult_HIPRTC_SUCCESS, HIP_SUCCESS};
 use cubecl_runtime::debug::{DebugLogger, ProfileLevel};
-use cubecl_runtime::memory_management::MemoryUsage;
+use cubecl_runtime::memory_management::{MemoryUsage, StreamMemory};
+use cubecl_runtime::profile::{KernelLaunch, ProfileResult};
 use cubecl_runtime::storage::BindingResource;
 use cubecl_runtime::{
     memory_management::MemoryManagement,
-    server::{self, ComputeServer},
+    server::{self, ComputeServer, EventId, GraphId, KernelError, RecordableServer, StreamId},
 };
 use cubecl_runtime::{ExecutionMode, TimestampsError, TimestampsResult};
 use std::collections::HashMap;
@@ -33,10 +37,34 @@ pub struct HipServer {
 #[derive(Debug)]
 pub(crate) struct HipContext {
     context: cubecl_hip_sys::hipCtx_t,
+    /// The stream tasks are currently enqueued to, which is the default stream unless a task
+    /// targets another one.
     stream: cubecl_hip_sys::hipStream_t,
+    /// The id of the stream tasks are currently enqueued to.
+    stream_id: StreamId,
+    /// Every stream of the context, the default stream being the first.
+    streams: Vec<cubecl_hip_sys::hipStream_t>,
+    events: HashMap<EventId, cubecl_hip_sys::hipEvent_t>,
+    next_event: u64,
+    /// The kernels executed since the capture started, if kernels are being captured.
+    capture: Option<Vec<CapturedLaunch>>,
+    graphs: HashMap<GraphId, Vec<CapturedLaunch>>,
+    next_graph: u64,
     memory_management: MemoryManagement<HipStorage>,
+    /// The memory used by the tasks of the streams other than the default one.
+    stream_memory: StreamMemory<cubecl_hip_sys::hipEvent_t>,
     module_names: HashMap<KernelId, HipCompiledKernel>,
     timestamps: KernelTimestamps,
+    profile: Option<EventProfile>,
+    errors: KernelErrors,
+}
+
+/// A kernel executed while capturing a graph, launched again as is when the graph is replayed.
+#[derive(Debug, Clone)]
+struct CapturedLaunch {
+    kernel_id: KernelId,
+    count: CubeCount,
+    bindings: Vec<server::Binding>,
 }
 
 #[derive(Debug)]
@@ -91,9 +119,50 @@ impl HipServer {
             );
             assert_eq!(status, HIP_SUCCESS, "Should copy data from device to host");
         };
-        ctx.sync();
+        ctx.sync_streams();
         data
     }
+
+    fn cube_count(&mut self, count: CubeCount) -> (u32, u32, u32) {
+        match count {
+            CubeCount::Static(x, y, z) => (x, y, z),
+            // TODO: CUDA doesn't have an exact equivalen of dynamic dispatch. Instead, kernels are free to launch other kernels.
+            // One option is to create a dummy kernel with 1 thread that launches the real kernel with the dynamic dispatch settings.
+            // For now, just read the dispatch settings from the buffer.
+            CubeCount::Dynamic(binding) => {
+                let data = self.read_sync(binding);
+                let data = bytemuck::cast_slice(&data);
+                assert!(
+                    data.len() == 3,
+                    "Dynamic cube count should contain 3 values"
+                );
+                (data[0], data[1], data[2])
+            }
+        }
+    }
+
+    /// Run the function with the tasks it enqueues targeting the given stream.
+    ///
+    /// The memory of the bindings isn't reused until those tasks are completed.
+    fn on_stream<R>(
+        &mut self,
+        stream: StreamId,
+        bindings: Vec<server::Binding>,
+        func: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.ctx.stream = self.ctx.get_stream(stream);
+        self.ctx.stream_id = stream;
+        let result = func(self);
+
+        if stream != StreamId::default() {
+            let event = create_event(self.ctx.stream);
+            self.ctx.stream_memory.register(stream, event, bindings);
+        }
+        self.ctx.stream = self.ctx.streams[0];
+        self.ctx.stream_id = StreamId::default();
+
+        result
+    }
 }
 
 impl ComputeServer for HipServer {
@@ -109,6 +178,8 @@ impl ComputeServer for HipServer {
             .into_iter()
             .map(|binding| self.read_sync(binding))
             .collect();
+        let ctx = self.get_context();
+        ctx.errors.read(&mut ctx.memory_management, ctx.stream);
         async { value }
     }
 
@@ -141,10 +212,82 @@ impl ComputeServer for HipServer {
 
     fn empty(&mut self, size: usize) -> server::Handle {
         let ctx = self.get_context();
+        ctx.release_stream_memory();
         let handle = ctx.memory_management.reserve(size as u64, None);
         server::Handle::new(handle, None, None, size as u64)
     }
 
+    fn copy(&mut self, src: server::Binding, dst: server::Binding) {
+        let ctx = self.get_context();
+        let src = ctx
+            .memory_management
+            .get_resource(src.memory, src.offset_start, src.offset_end);
+        let dst = ctx
+            .memory_management
+            .get_resource(dst.memory, dst.offset_start, dst.offset_end);
+        assert!(
+            src.size <= dst.size,
+            "Can't copy {} bytes to a binding of {} bytes",
+            src.size,
+            dst.size
+        );
+
+        unsafe {
+            let status =
+                cubecl_hip_sys::hipMemcpyDtoDAsync(dst.ptr, src.ptr, src.size as usize, ctx.stream);
+            assert_eq!(status, HIP_SUCCESS, "Should copy data on the device");
+        }
+    }
+
+    fn fill(&mut self, binding: server::Binding, pattern: &[u8]) {
+        let ctx = self.get_context();
+        let resource = ctx.memory_management.get_resource(
+            binding.memory,
+            binding.offset_start,
+            binding.offset_end,
+        );
+        let data = server::fill_pattern(pattern, resource.size as usize);
+
+        unsafe {
+            let status = if pattern.iter().all(|byte| *byte == pattern[0]) {
+                cubecl_hip_sys::hipMemsetD8Async(resource.ptr, pattern[0], data.len(), ctx.stream)
+            } else {
+                cubecl_hip_sys::hipMemcpyHtoDAsync(
+                    resource.ptr,
+                    data.as_ptr() as *mut _,
+                    data.len(),
+                    ctx.stream,
+                )
+            };
+            assert_eq!(status, HIP_SUCCESS, "Should fill data on the device");
+        }
+    }
+
+    fn write(&mut self, binding: server::Binding, offset: u64, data: &[u8]) {
+        let ctx = self.get_context();
+        let resource = ctx.memory_management.get_resource(
+            binding.memory,
+            binding.offset_start,
+            binding.offset_end,
+        );
+        assert!(
+            offset + data.len() as u64 <= resource.size,
+            "Can't write {} bytes at offset {offset} of a binding of {} bytes",
+            data.len(),
+            resource.size
+        );
+
+        unsafe {
+            let status = cubecl_hip_sys::hipMemcpyHtoDAsync(
+                (resource.ptr as *mut u8).add(offset as usize) as *mut _,
+                data.as_ptr() as *mut _,
+                data.len(),
+                ctx.stream,
+            );
+            assert_eq!(status, HIP_SUCCESS, "Should send data to device");
+        }
+    }
+
     unsafe fn execute(
         &mut self,
         kernel: Self::Kernel,
@@ -162,21 +305,21 @@ impl ComputeServer for HipServer {
             None
         };
 
-        let count = match count {
-            CubeCount::Static(x, y, z) => (x, y, z),
-            // TODO: CUDA doesn't have an exact equivalen of dynamic dispatch. Instead, kernels are free to launch other kernels.
-            // One option is to create a dummy kernel with 1 thread that launches the real kernel with the dynamic dispatch settings.
-            // For now, just read the dispatch settings from the buffer.
-            CubeCount::Dynamic(binding) => {
-                let data = self.read_sync(binding);
-                let data = bytemuck::cast_slice(&data);
-                assert!(
-                    data.len() == 3,
-                    "Dynamic cube count should contain 3 values"
-                );
-                (data[0], data[1], data[2])
-            }
-        };
+        let profiled = self
+            .ctx
+            .profile
+            .is_some()
+            .then(|| (kernel.name(), matches!(count, CubeCount::Static(..))));
+
+        if let Some(capture) = &mut self.ctx.capture {
+            capture.push(CapturedLaunch {
+                kernel_id: kernel_id.clone(),
+                count: count.clone(),
+                bindings: bindings.clone(),
+            });
+        }
+
+        let count = self.cube_count(count);
 
         let (ctx, logger) = self.get_context_with_logger();
 
@@ -184,20 +327,20 @@ impl ComputeServer for HipServer {
             ctx.compile_kernel(&kernel_id, kernel, logger, mode);
         }
 
-        let resources = bindings
-            .into_iter()
-            .map(|binding| {
-                ctx.memory_management.get_resource(
-                    binding.memory,
-                    binding.offset_start,
-                    binding.offset_end,
-                )
-            })
-            .collect::<Vec<_>>();
+        let resources = ctx.resources(&kernel_id, bindings);
+
+        let launch = profiled.map(|(name, is_static)| {
+            let cube_dim = ctx.module_names[&kernel_id].cube_dim;
+            KernelLaunch {
+                name: name.to_string(),
+                cube_count: is_static.then_some(count),
+                cube_dim: (cube_dim.x, cube_dim.y, cube_dim.z),
+            }
+        });
 
         if let Some(level) = profile_level {
             let start = std::time::SystemTime::now();
-            ctx.execute_task(kernel_id, count, resources);
+            ctx.execute_task(kernel_id, count, resources, launch);
             ctx.sync();
 
             let (name, kernel_id) = profile_info.unwrap();
@@ -217,18 +360,130 @@ impl ComputeServer for HipServer {
             self.logger
                 .register_profiled(info, start.elapsed().unwrap());
         } else {
-            ctx.execute_task(kernel_id, count, resources);
+            ctx.execute_task(kernel_id, count, resources, launch);
+            ctx.sync();
+        }
+    }
+
+    fn create_stream(&mut self) -> StreamId {
+        let ctx = self.get_context();
+        let stream = unsafe {
+            let mut stream: cubecl_hip_sys::hipStream_t = std::ptr::null_mut();
+            let status = cubecl_hip_sys::hipStreamCreate(&mut stream);
+            assert_eq!(status, HIP_SUCCESS, "Should create a stream");
+            stream
+        };
+        ctx.streams.push(stream);
+
+        StreamId {
+            value: ctx.streams.len() as u32 - 1,
+        }
+    }
+
+    fn copy_on(&mut self, stream: StreamId, src: server::Binding, dst: server::Binding) {
+        self.on_stream(stream, vec![src.clone(), dst.clone()], |server| {
+            server.copy(src, dst)
+        })
+    }
+
+    fn fill_on(&mut self, stream: StreamId, binding: server::Binding, pattern: &[u8]) {
+        self.on_stream(stream, vec![binding.clone()], |server| {
+            server.fill(binding, pattern)
+        })
+    }
+
+    fn write_on(&mut self, stream: StreamId, binding: server::Binding, offset: u64, data: &[u8]) {
+        self.on_stream(stream, vec![binding.clone()], |server| {
+            server.write(binding, offset, data)
+        })
+    }
+
+    unsafe fn execute_on(
+        &mut self,
+        stream: StreamId,
+        kernel: Self::Kernel,
+        count: CubeCount,
+        bindings: Vec<server::Binding>,
+        mode: ExecutionMode,
+    ) {
+        let mut used = bindings.clone();
+        if let CubeCount::Dynamic(binding) = &count {
+            used.push(binding.clone());
+        }
+
+        self.on_stream(stream, used, |server| {
+            server.execute(kernel, count, bindings, mode)
+        })
+    }
+
+    fn record_event(&mut self, stream: StreamId) -> EventId {
+        let ctx = self.get_context();
+        let event = create_event(ctx.get_stream(stream));
+        let id = EventId {
+            value: ctx.next_event,
+        };
+        ctx.next_event += 1;
+        ctx.events.insert(id, event);
+
+        id
+    }
+
+    fn wait_event(&mut self, stream: StreamId, event: EventId) {
+        let ctx = self.get_context();
+        let stream = ctx.get_stream(stream);
+        // Events are freed on sync, once they are reached, so there is nothing to wait on.
+        let Some(event) = ctx.events.get(&event).copied() else {
+            return;
+        };
+
+        unsafe {
+            let status = cubecl_hip_sys::hipStreamWaitEvent(stream, event, 0);
+            assert_eq!(status, HIP_SUCCESS, "Should wait on the event");
+        }
+    }
+
+    fn start_capture(&mut self) {
+        self.ctx.capture = Some(Vec::new());
+    }
+
+    fn end_capture(&mut self) -> Option<GraphId> {
+        let launches = self.ctx.capture.take().expect("Kernels should be captured");
+        let graph = GraphId {
+            value: self.ctx.next_graph,
+        };
+        self.ctx.next_graph += 1;
+        self.ctx.graphs.insert(graph, launches);
+
+        Some(graph)
+    }
+
+    fn replay_graph(&mut self, graph: GraphId) {
+        let launches = match self.ctx.graphs.get(&graph) {
+            Some(launches) => launches.clone(),
+            None => panic!("Unknown graph {graph:?}"),
+        };
+
+        for launch in launches {
+            let count = self.cube_count(launch.count);
+            let ctx = self.get_context();
+            let resources = ctx.resources(&launch.kernel_id, launch.bindings);
+            ctx.execute_task(launch.kernel_id, count, resources, None);
             ctx.sync();
         }
     }
 
+    fn free_graph(&mut self, graph: GraphId) {
+        self.ctx.graphs.remove(&graph);
+    }
+
     fn flush(&mut self) {}
 
     fn sync(&mut self) -> impl Future<Output = ()> + 'static {
         self.logger.profile_summary();
 
         let ctx = self.get_context();
-        ctx.sync();
+        ctx.sync_streams();
+        ctx.errors.read(&mut ctx.memory_management, ctx.stream);
         async move {}
     }
 
@@ -236,7 +491,7 @@ impl ComputeServer for HipServer {
         self.logger.profile_summary();
 
         let ctx = self.get_context();
-        ctx.sync();
+        ctx.sync_streams();
 
         let duration = match &mut ctx.timestamps {
             KernelTimestamps::Inferred { start_time } => {
@@ -271,6 +526,47 @@ impl ComputeServer for HipServer {
             self.ctx.timestamps.disable();
         }
     }
+
+    fn start_profile(&mut self) {
+        self.ctx.profile = Some(EventProfile::default());
+    }
+
+    fn end_profile(&mut self) -> impl Future<Output = ProfileResult> + Send + 'static {
+        let ctx = self.get_context();
+        ctx.sync_streams();
+
+        let profile = match ctx.profile.take() {
+            Some(profile) => Ok(profile.finish()),
+            None => Err(TimestampsError::Disabled),
+        };
+
+        async move { profile }
+    }
+
+    fn errors(&mut self) -> Vec<KernelError> {
+        let errors = self.ctx.errors.take();
+        errors
+            .into_iter()
+            .map(|error| self.logger.debug(error))
+            .collect()
+    }
+}
+
+impl RecordableServer for HipServer {
+    type KernelRecord = KernelDefinition;
+    type KernelKey = KernelId;
+
+    fn kernel_key(kernel: &Self::Kernel) -> KernelId {
+        kernel.id()
+    }
+
+    fn record_kernel(kernel: &Self::Kernel) -> Option<KernelDefinition> {
+        kernel.definition()
+    }
+
+    fn replay_kernel(definition: KernelDefinition) -> Self::Kernel {
+        DefinitionKernel::replay(definition)
+    }
 }
 
 impl HipContext {
@@ -281,10 +577,20 @@ impl HipContext {
     ) -> Self {
         Self {
             memory_management,
+            stream_memory: StreamMemory::default(),
             module_names: HashMap::new(),
             stream,
+            stream_id: StreamId::default(),
+            streams: vec![stream],
+            events: HashMap::new(),
+            next_event: 0,
+            capture: None,
+            graphs: HashMap::new(),
+            next_graph: 0,
             context,
             timestamps: KernelTimestamps::Disabled,
+            profile: None,
+            errors: KernelErrors::default(),
         }
     }
 
@@ -299,6 +605,55 @@ impl HipContext {
         self.memory_management.storage().flush();
     }
 
+    /// Synchronize every stream of the context, then free the recorded events and the memory of
+    /// the tasks of the streams.
+    fn sync_streams(&mut self) {
+        for stream in self.streams.iter() {
+            unsafe {
+                let status = cubecl_hip_sys::hipStreamSynchronize(*stream);
+                assert_eq!(
+                    status, HIP_SUCCESS,
+                    "Should successfully synchronize stream"
+                );
+            };
+        }
+        self.memory_management.storage().flush();
+
+        for (_, event) in self.events.drain() {
+            unsafe {
+                cubecl_hip_sys::hipEventDestroy(event);
+            }
+        }
+        for event in self.stream_memory.release_all() {
+            unsafe {
+                cubecl_hip_sys::hipEventDestroy(event);
+            }
+        }
+    }
+
+    /// Let the memory pools reuse the memory of the completed tasks of the streams.
+    fn release_stream_memory(&mut self) {
+        let released = self.stream_memory.release(|event| unsafe {
+            match cubecl_hip_sys::hipEventQuery(*event) {
+                HIP_SUCCESS => true,
+                cubecl_hip_sys::hipError_t_hipErrorNotReady => false,
+                status => panic!("Can't query the event: {status:?}"),
+            }
+        });
+        for event in released {
+            unsafe {
+                cubecl_hip_sys::hipEventDestroy(event);
+            }
+        }
+    }
+
+    fn get_stream(&self, stream: StreamId) -> cubecl_hip_sys::hipStream_t {
+        match self.streams.get(stream.value as usize) {
+            Some(stream) => *stream,
+            None => panic!("Unknown stream {stream:?}"),
+        }
+    }
+
     fn memory_usage(&self) -> MemoryUsage {
         self.memory_management.memory_usage()
     }
@@ -324,6 +679,12 @@ impl HipContext {
         }
         let jitc_kernel = logger.debug(jitc_kernel);
 
+        if let Some(repr) = &jitc_kernel.repr {
+            if repr.raises_errors() {
+                self.errors.register(kernel_id.clone(), cube_kernel.name());
+            }
+        }
+
         // Create HIP Program
         let program = unsafe {
             let source = CString::new(jitc_kernel.source.clone()).unwrap();
@@ -431,11 +792,38 @@ impl HipContext {
         );
     }
 
+    /// The resources of the bindings of a kernel, followed by its error buffer if it has one.
+    fn resources(
+        &mut self,
+        kernel_id: &KernelId,
+        bindings: Vec<server::Binding>,
+    ) -> Vec<HipResource> {
+        let mut resources = bindings
+            .into_iter()
+            .map(|binding| {
+                self.memory_management.get_resource(
+                    binding.memory,
+                    binding.offset_start,
+                    binding.offset_end,
+                )
+            })
+            .collect::<Vec<_>>();
+        resources.extend(self.errors.binding(
+            self.stream_id,
+            kernel_id,
+            &mut self.memory_management,
+            self.stream,
+        ));
+
+        resources
+    }
+
     fn execute_task(
         &mut self,
         kernel_id: KernelId,
         dispatch_count: (u32, u32, u32),
         resources: Vec<HipResource>,
+        launch: Option<KernelLaunch>,
     ) {
         let mut bindings = resources
             .iter()
@@ -445,6 +833,10 @@ impl HipContext {
         let kernel = self.module_names.get(&kernel_id).unwrap();
         let cube_dim = kernel.cube_dim;
 
+        if let (Some(profile), Some(launch)) = (&mut self.profile, launch.clone()) {
+            profile.start(launch, self.stream);
+        }
+
         unsafe {
             let status = cubecl_hip_sys::hipModuleLaunchKernel(
                 kernel.func,
@@ -464,6 +856,10 @@ impl HipContext {
             }
             assert_eq!(status, HIP_SUCCESS, "Should launch the kernel");
         };
+
+        if let (Some(profile), Some(_)) = (&mut self.profile, launch) {
+            profile.end(self.stream);
+        }
     }
 }
 
@@ -518,3 +914,15 @@ fn hip_path() -> Option<PathBuf> {
     // Default path (only Linux is supported for now)
     Some(PathBuf::from("/opt/rocm"))
 }
+
+/// Create an event recorded on the stream after the tasks already enqueued to it.
+fn create_event(stream: cubecl_hip_sys::hipStream_t) -> cubecl_hip_sys::hipEvent_t {
+    unsafe {
+        let mut event: cubecl_hip_sys::hipEvent_t = std::ptr::null_mut();
+        let status = cubecl_hip_sys::hipEventCreate(&mut event);
+        assert_eq!(status, HIP_SUCCESS, "Should create an event");
+        let status = cubecl_hip_sys::hipEventRecord(event, stream);
+        assert_eq!(status, HIP_SUCCESS, "Should record the event");
+        event
+    }
+}
@@ -0,0 +1,275 @@
+use cubecl_core::prelude::*;
+use cubecl_core::tensor_line_size;
+use cubecl_core::Feature;
+
+use crate::matmul::components::tile::accelerated::Accelerated16x16x16;
+use crate::matmul::components::tile::plane::PlaneMma16x16x16;
+use crate::matmul::components::{tile, MatmulKernel, MatmulProblem, MatrixLayout};
+use crate::matmul::kernels::matmul::AdvancedConfig;
+use crate::tensor::broadcast_batches;
+
+use super::config::{AttentionConfig, AttentionInputsLaunch};
+use super::kernel;
+
+/// Number of planes in a cube, each computing the output of a tile of queries
+const NUM_PLANES: u32 = 4;
+const PLANE_DIM: u32 = 32;
+
+/// Options of the attention, on top of the softmax of the scaled scores.
+///
+/// The default options compute `softmax(query @ key^T / sqrt(head_dim)) @ value`.
+pub struct AttentionOptions<'a, R: Runtime> {
+    /// Scale of the dot products of queries and keys, `1 / sqrt(head_dim)` if not set
+    pub scale: Option<f32>,
+    /// Whether each query only attends to the keys at the same position or before,
+    /// the first query and the first key being aligned
+    pub causal: bool,
+    /// Number of valid keys of each batch, as a `u32` vector of length `batch`,
+    /// the following keys being masked
+    pub key_lengths: Option<TensorHandleRef<'a, R>>,
+    /// Tensor broadcastable to `[batch, heads, seq_q, seq_k]`, added to the scaled scores
+    pub bias: Option<TensorHandleRef<'a, R>>,
+    /// `f32` vector of length `heads`, where `-slope * |i - j|` is added to the score of
+    /// the query `i` with the key `j`
+    pub alibi_slopes: Option<TensorHandleRef<'a, R>>,
+}
+
+impl<R: Runtime> Default for AttentionOptions<'_, R> {
+    fn default() -> Self {
+        Self {
+            scale: None,
+            causal: false,
+            key_lengths: None,
+            bias: None,
+            alibi_slopes: None,
+        }
+    }
+}
+
+/// Returns whether the attention kernel can be launched with the given input element
+pub fn is_available<R: Runtime, EI: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+) -> bool {
+    let properties = client.properties();
+
+    properties.feature_enabled(Feature::Plane)
+        && properties.feature_enabled(Feature::Type(EI::as_elem()))
+        && properties.feature_enabled(Feature::Type(f32::as_elem()))
+}
+
+/// Launch a scaled dot-product attention kernel, writing
+/// `softmax(scale * query @ key^T + bias) @ value` to `out`.
+///
+/// The query and output have the shape `[batch, heads, seq_q, head_dim]`,
+/// and the key and value the shape `[batch, heads, seq_k, head_dim]`.
+/// The head dimension should be a multiple of 16.
+///
+/// The softmax is computed online over blocks of keys, so the scores are never
+/// written to global memory, and are always accumulated in `f32`.
+/// Queries whose keys are all masked have an output of zero.
+///
+/// Cmma will be used if available and enabled,
+/// otherwise it will fall back on a non-cmma implementation
+pub fn launch_ref<R: Runtime, EI: Float, EO: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    query: TensorHandleRef<'_, R>,
+    key: TensorHandleRef<'_, R>,
+    value: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    options: &AttentionOptions<'_, R>,
+    disable_cmma: bool,
+) {
+    if !disable_cmma && Accelerated16x16x16::<EI, f32>::check_availability::<R>(client).is_ok() {
+        launch::<R, EI, EO, EI, Accelerated16x16x16<EI, f32>>(
+            client, query, key, value, out, options,
+        );
+    } else {
+        launch::<R, EI, EO, f32, PlaneMma16x16x16<f32, f32>>(
+            client, query, key, value, out, options,
+        );
+    }
+}
+
+fn launch<R: Runtime, EI: Float, EO: Float, ES: Float, TMM: tile::Matmul<ES, f32>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    query: TensorHandleRef<'_, R>,
+    key: TensorHandleRef<'_, R>,
+    value: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    options: &AttentionOptions<'_, R>,
+) {
+    for (name, tensor) in [
+        ("query", &query),
+        ("key", &key),
+        ("value", &value),
+        ("output", &out),
+    ] {
+        assert_eq!(
+            tensor.shape.len(),
+            4,
+            "The {name} should have the shape [batch, heads, seq, head_dim]"
+        );
+    }
+    let [batch_size, heads, seq_q, head_dim] = [0, 1, 2, 3].map(|dim| query.shape[dim]);
+    let seq_k = key.shape[2];
+
+    assert_eq!(
+        key.shape,
+        &[batch_size, heads, seq_k, head_dim],
+        "The key should have the batch size, heads and head dimension of the query"
+    );
+    assert_eq!(
+        value.shape, key.shape,
+        "The value should have the same shape as the key"
+    );
+    assert_eq!(
+        out.shape, query.shape,
+        "The output should have the same shape as the query"
+    );
+    assert!(
+        TMM::M == TMM::N && TMM::M == TMM::K,
+        "The attention requires square tiles"
+    );
+    assert_eq!(
+        head_dim % TMM::K as usize,
+        0,
+        "The head dimension should be a multiple of {}",
+        TMM::K
+    );
+
+    let bias_layout = options.bias.as_ref().map(|bias| {
+        let (mut shape, mut strides) =
+            broadcast_batches(bias.shape, bias.strides, &[batch_size, heads]);
+
+        for (dim, size) in [(2, seq_q), (3, seq_k)] {
+            assert!(
+                shape[dim] == size || shape[dim] == 1,
+                "Can't broadcast a bias of shape {:?} to the scores",
+                bias.shape
+            );
+            if shape[dim] != size {
+                shape[dim] = size;
+                strides[dim] = 0;
+            }
+        }
+
+        (shape, strides)
+    });
+
+    let line_size = [&query, &key, &value]
+        .into_iter()
+        .map(|tensor| line_size::<R>(tensor))
+        .min()
+        .unwrap();
+
+    let cube_dim = CubeDim::new(PLANE_DIM, NUM_PLANES, 1);
+    let cube_count = CubeCount::Static(
+        seq_q.div_ceil(NUM_PLANES as usize * TMM::M as usize) as u32,
+        1,
+        (batch_size * heads) as u32,
+    );
+
+    // Tiles are staged in shared memory one value at a time, the keys being
+    // the columns of the transposed key tile.
+    let tile_config = |rhs_layout| {
+        let problem = MatmulProblem {
+            m: TMM::M as usize,
+            n: TMM::N as usize,
+            k: TMM::K as usize,
+            batches: vec![],
+            lhs_layout: MatrixLayout::RowMajor,
+            rhs_layout,
+            lhs_line_size: 1,
+            rhs_line_size: 1,
+            out_line_size: 1,
+        };
+        let config = TMM::make_config(&problem, &cube_dim, &cube_count, &AdvancedConfig::default());
+        TMM::check_config(config);
+        config
+    };
+    let score_config = tile_config(MatrixLayout::ColMajor);
+    let value_config = tile_config(MatrixLayout::RowMajor);
+
+    let config = AttentionConfig {
+        head_dim: head_dim as u32,
+        num_planes: NUM_PLANES,
+        line_size: line_size as u32,
+        causal: options.causal,
+        key_lengths: options.key_lengths.is_some(),
+        bias: options.bias.is_some(),
+        alibi: options.alibi_slopes.is_some(),
+    };
+
+    // Disabled inputs are never read, so they are replaced by placeholders.
+    let placeholder = client.empty(f32::as_elem().size());
+    let placeholder_arg =
+        || unsafe { TensorArg::<R>::from_raw_parts::<f32>(&placeholder, &[1], &[1], 1) };
+    let bias_arg = match (&options.bias, &bias_layout) {
+        (Some(bias), Some((shape, strides))) => unsafe {
+            TensorArg::<R>::from_raw_parts::<EI>(bias.handle, strides, shape, 1)
+        },
+        _ => placeholder_arg(),
+    };
+    let alibi_slopes_arg = match &options.alibi_slopes {
+        Some(slopes) => unsafe {
+            TensorArg::<R>::from_raw_parts::<f32>(slopes.handle, slopes.strides, slopes.shape, 1)
+        },
+        None => placeholder_arg(),
+    };
+    let key_lengths_arg = match &options.key_lengths {
+        Some(lengths) => unsafe {
+            TensorArg::<R>::from_raw_parts::<u32>(lengths.handle, lengths.strides, lengths.shape, 1)
+        },
+        None => placeholder_arg(),
+    };
+
+    let scale = options
+        .scale
+        .unwrap_or_else(|| 1. / (head_dim as f32).sqrt());
+
+    unsafe {
+        kernel::flash_attention::launch_unchecked::<EI, EO, ES, TMM, R>(
+            client,
+            cube_count,
+            cube_dim,
+            TensorArg::<R>::from_raw_parts::<EI>(
+                query.handle,
+                query.strides,
+                query.shape,
+                line_size,
+            ),
+            TensorArg::<R>::from_raw_parts::<EI>(key.handle, key.strides, key.shape, line_size),
+            TensorArg::<R>::from_raw_parts::<EI>(
+                value.handle,
+                value.strides,
+                value.shape,
+                line_size,
+            ),
+            TensorArg::<R>::from_raw_parts::<EO>(out.handle, out.strides, out.shape, 1),
+            AttentionInputsLaunch::new(
+                ScalarArg::new(scale),
+                bias_arg,
+                alibi_slopes_arg,
+                key_lengths_arg,
+            ),
+            config,
+            score_config,
+            value_config,
+        );
+    }
+}
+
+/// Largest line size along the head dimension dividing all strides of the tensor
+fn line_size<R: Runtime>(tensor: &TensorHandleRef<'_, R>) -> u8 {
+    let mut line_size =
+        tensor_line_size(R::supported_line_sizes(), tensor.shape, tensor.strides, 3);
+
+    while tensor.strides[..3]
+        .iter()
+        .any(|stride| stride % line_size as usize != 0)
+    {
+        line_size /= 2;
+    }
+
+    line_size
+}
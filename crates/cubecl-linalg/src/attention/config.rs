@@ -0,0 +1,36 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[derive(CubeType, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+/// Comptime parameters of the attention kernel
+pub struct AttentionConfig {
+    /// Size of the last dimension of the query, key and value
+    pub head_dim: u32,
+    /// Number of planes in a cube, each computing the output of a tile of queries
+    pub num_planes: u32,
+    /// Line size of the query, key and value along the head dimension
+    pub line_size: u32,
+    /// Whether each query only attends to the keys at the same position or before
+    pub causal: bool,
+    /// Whether the keys past the length of their batch are masked
+    pub key_lengths: bool,
+    /// Whether a bias is added to the scores
+    pub bias: bool,
+    /// Whether ALiBi biases are added to the scores
+    pub alibi: bool,
+}
+
+#[derive(CubeLaunch)]
+/// Inputs of the attention kernel alongside the query, key and value.
+///
+/// Tensors that are disabled in the [AttentionConfig] are never read.
+pub struct AttentionInputs<EI: Numeric> {
+    /// Scale of the dot products of queries and keys
+    pub scale: f32,
+    /// Bias of shape `[batch, heads, seq_q, seq_k]`, added to the scaled scores
+    pub bias: Tensor<Line<EI>>,
+    /// Slope of the ALiBi biases of each head
+    pub alibi_slopes: Tensor<Line<f32>>,
+    /// Number of valid keys of each batch
+    pub key_lengths: Tensor<Line<u32>>,
+}
@@ -0,0 +1,344 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+use crate::matmul::components::global::tensor_view::batch_offset;
+use crate::matmul::components::tile::{self, Config as _};
+
+use super::config::{AttentionConfig, AttentionInputs};
+
+#[cube(launch_unchecked)]
+/// Computes `softmax(scale * query @ key^T + bias) @ value` for the batch and head of
+/// the cube along z, without materializing the scores in global memory.
+///
+/// Each plane computes the output of a tile of queries, iterating over tiles of keys
+/// shared by all planes of the cube:
+/// - The scores of the tile are computed with the tile matmul, and read to shared memory.
+/// - The running maximum and sum of each row are updated with plane reductions,
+///   and the probabilities are written to shared memory.
+/// - The probabilities are multiplied by the values with the tile matmul,
+///   and added to the output accumulated by the units, rescaled by the change of maximum.
+///
+/// The query and key tiles must have the same size as the key and value tiles,
+/// so the tile matmul should be square.
+pub(crate) fn flash_attention<EI: Float, EO: Float, ES: Float, TMM: tile::Matmul<ES, f32>>(
+    query: &Tensor<Line<EI>>,
+    key: &Tensor<Line<EI>>,
+    value: &Tensor<Line<EI>>,
+    out: &mut Tensor<Line<EO>>,
+    inputs: &AttentionInputs<EI>,
+    #[comptime] config: AttentionConfig,
+    #[comptime] score_config: TMM::Config,
+    #[comptime] value_config: TMM::Config,
+) {
+    let plane_dim = score_config.plane_dim();
+    let tile_size = TMM::M;
+    let tile_elements = comptime!(TMM::M * TMM::M);
+    let num_chunks = comptime!(config.head_dim / TMM::M);
+    let elements_per_unit = comptime!(TMM::M * TMM::M / score_config.plane_dim());
+    let num_units = comptime!(config.num_planes * score_config.plane_dim());
+    let unit_id = UNIT_POS_Y * plane_dim + UNIT_POS_X;
+
+    let rank = query.rank();
+    let seq_q = query.shape(rank - 2);
+    let seq_k = key.shape(rank - 2);
+    let heads = query.shape(rank - 3);
+    let nth_batch = CUBE_POS_Z;
+    let cube_queries = comptime!(config.num_planes * TMM::M);
+    let first_query = CUBE_POS_X * cube_queries + UNIT_POS_Y * tile_size;
+
+    // Keys and values are loaded one after the other in the same shared memory,
+    // while each plane has its own scores and probabilities.
+    let mut kv_smem = SharedMemory::<ES>::new_lined(comptime!(config.head_dim * TMM::M), 1u32);
+    let mut score_smem =
+        SharedMemory::<f32>::new_lined(comptime!(config.num_planes * TMM::M * TMM::M), 1u32);
+    let mut prob_smem =
+        SharedMemory::<ES>::new_lined(comptime!(config.num_planes * TMM::M * TMM::M), 1u32);
+    let plane_start = UNIT_POS_Y * tile_elements;
+    let plane_end = plane_start + tile_elements;
+
+    // The query tiles are kept in the tile matmul inputs for all keys,
+    // and are staged in the shared memory of the probabilities.
+    let query_offset = batch_offset(query, nth_batch);
+    let mut query_tiles = Sequence::<TMM::Lhs>::new();
+
+    #[unroll]
+    for chunk in 0..num_chunks {
+        let mut query_tile = TMM::init_lhs(score_config);
+
+        load_rows::<EI, ES>(
+            query,
+            query_offset,
+            first_query,
+            seq_q,
+            chunk * tile_size,
+            &mut prob_smem.slice_mut(plane_start, plane_end),
+            UNIT_POS_X,
+            tile_size,
+            plane_dim,
+            config.line_size,
+            tile_size,
+        );
+        sync_units();
+
+        TMM::fill_lhs(
+            &prob_smem.slice(plane_start, plane_end),
+            &mut query_tile,
+            score_config,
+        );
+        sync_units();
+
+        query_tiles.push(query_tile);
+    }
+
+    // Keys past the length of the batch, or after the last query of the cube when causal,
+    // are never attended to.
+    let mut num_keys = seq_k;
+    if comptime!(config.key_lengths) {
+        num_keys = Min::min(num_keys, inputs.key_lengths[nth_batch / heads][0]);
+    }
+    if comptime!(config.causal) {
+        num_keys = Min::min(num_keys, (CUBE_POS_X + 1) * cube_queries);
+    }
+    let num_blocks = (num_keys + tile_size - 1) / tile_size;
+
+    let mut bias_offset = 0;
+    let mut bias_stride_q = 0;
+    let mut bias_stride_k = 0;
+    if comptime!(config.bias) {
+        bias_offset = batch_offset(&inputs.bias, nth_batch);
+        bias_stride_q = inputs.bias.stride(rank - 2);
+        bias_stride_k = inputs.bias.stride(rank - 1);
+    }
+    let mut slope = 0f32;
+    if comptime!(config.alibi) {
+        slope = inputs.alibi_slopes[nth_batch % heads][0];
+    }
+
+    // Running maximum and sum of each row, and the factor rescaling the previous
+    // accumulated outputs after the last block, all uniform within the plane.
+    let mut row_max = Array::<f32>::new(tile_size);
+    let mut row_sum = Array::<f32>::new(tile_size);
+    let mut row_scale = Array::<f32>::new(tile_size);
+    for row in 0..tile_size {
+        row_max[row] = f32::new(-1e30);
+        row_sum[row] = f32::new(0.);
+    }
+
+    // Outputs of the plane accumulated by the unit, in tiles of the head dimension.
+    let mut acc = Array::<f32>::new(comptime!(num_chunks * elements_per_unit));
+    for i in 0..comptime!(num_chunks * elements_per_unit) {
+        acc[i] = f32::new(0.);
+    }
+
+    let mut score_acc = TMM::init_accumulator(score_config);
+    let mut value_acc = TMM::init_accumulator(value_config);
+    let mut key_tile = TMM::init_rhs(score_config);
+    let mut prob_tile = TMM::init_lhs(value_config);
+    let mut value_tile = TMM::init_rhs(value_config);
+
+    let key_offset = batch_offset(key, nth_batch);
+    let value_offset = batch_offset(value, nth_batch);
+
+    for block in 0..num_blocks {
+        let first_key = block * tile_size;
+
+        load_rows::<EI, ES>(
+            key,
+            key_offset,
+            first_key,
+            seq_k,
+            0,
+            &mut kv_smem.to_slice_mut(),
+            unit_id,
+            config.head_dim,
+            num_units,
+            config.line_size,
+            tile_size,
+        );
+        sync_units();
+
+        TMM::zero_accumulator(&mut score_acc, score_config);
+
+        #[unroll]
+        for chunk in 0..num_chunks {
+            TMM::fill_rhs(
+                &kv_smem.slice(chunk * tile_elements, (chunk + 1) * tile_elements),
+                &mut key_tile,
+                score_config,
+            );
+            TMM::execute(
+                query_tiles.index(chunk),
+                &key_tile,
+                &mut score_acc,
+                score_config,
+            );
+        }
+
+        TMM::read_accumulator(
+            &score_acc,
+            &mut score_smem.slice_mut(plane_start, plane_end),
+            score_config,
+        );
+        sync_units();
+
+        // Online softmax, each unit of the plane handling a key of the row.
+        for row in 0..tile_size {
+            let query_pos = first_query + row;
+            let key_pos = first_key + UNIT_POS_X;
+
+            let mut valid = UNIT_POS_X < tile_size && key_pos < num_keys && query_pos < seq_q;
+            if comptime!(config.causal) {
+                valid = valid && key_pos <= query_pos;
+            }
+
+            let mut score = -1e30;
+            if valid {
+                score = score_smem[plane_start + row * tile_size + UNIT_POS_X][0] * inputs.scale;
+
+                if comptime!(config.bias) {
+                    score += f32::cast_from(
+                        inputs.bias
+                            [bias_offset + query_pos * bias_stride_q + key_pos * bias_stride_k][0],
+                    );
+                }
+
+                if comptime!(config.alibi) {
+                    score -= slope * Abs::abs(f32::cast_from(query_pos) - f32::cast_from(key_pos));
+                }
+            }
+
+            let max = Max::max(row_max[row], plane_max(score));
+            let prob = select(valid, Exp::exp(score - max), 0.);
+            let scale = Exp::exp(row_max[row] - max);
+
+            row_sum[row] = row_sum[row] * scale + plane_sum(prob);
+            row_max[row] = max;
+            row_scale[row] = scale;
+
+            if UNIT_POS_X < tile_size {
+                prob_smem[plane_start + row * tile_size + UNIT_POS_X] =
+                    Line::new(ES::cast_from(prob));
+            }
+        }
+        sync_units();
+
+        load_rows::<EI, ES>(
+            value,
+            value_offset,
+            first_key,
+            seq_k,
+            0,
+            &mut kv_smem.to_slice_mut(),
+            unit_id,
+            config.head_dim,
+            num_units,
+            config.line_size,
+            tile_size,
+        );
+        sync_units();
+
+        TMM::fill_lhs(
+            &prob_smem.slice(plane_start, plane_end),
+            &mut prob_tile,
+            value_config,
+        );
+
+        #[unroll]
+        for chunk in 0..num_chunks {
+            TMM::fill_rhs(
+                &kv_smem.slice(chunk * tile_elements, (chunk + 1) * tile_elements),
+                &mut value_tile,
+                value_config,
+            );
+            TMM::zero_accumulator(&mut value_acc, value_config);
+            TMM::execute(&prob_tile, &value_tile, &mut value_acc, value_config);
+
+            // The scores are not needed anymore, so their shared memory receives the outputs.
+            TMM::read_accumulator(
+                &value_acc,
+                &mut score_smem.slice_mut(plane_start, plane_end),
+                value_config,
+            );
+            sync_units();
+
+            #[unroll]
+            for i in 0..elements_per_unit {
+                let position = UNIT_POS_X + i * plane_dim;
+                let index = chunk * elements_per_unit + i;
+
+                acc[index] = acc[index] * row_scale[position / tile_size]
+                    + score_smem[plane_start + position][0];
+            }
+            sync_units();
+        }
+    }
+
+    let out_offset = batch_offset(out, nth_batch);
+    let out_stride_row = out.stride(rank - 2);
+    let out_stride_col = out.stride(rank - 1);
+
+    #[unroll]
+    for chunk in 0..num_chunks {
+        #[unroll]
+        for i in 0..elements_per_unit {
+            let position = UNIT_POS_X + i * plane_dim;
+            let row = position / tile_size;
+            let query_pos = first_query + row;
+
+            if query_pos < seq_q {
+                // Queries attending to no key have a sum of zero, and an output of zero.
+                let sum = row_sum[row];
+                let output = select(sum > 0., acc[chunk * elements_per_unit + i] / sum, 0.);
+                let col = chunk * tile_size + position % tile_size;
+
+                out[out_offset + query_pos * out_stride_row + col * out_stride_col] =
+                    Line::cast_from(output);
+            }
+        }
+    }
+}
+
+#[cube]
+#[allow(clippy::too_many_arguments)]
+/// Loads `tile_size` rows of `num_cols` values of the tensor to the slice, starting at row `first_row`
+/// and column `first_col`, as consecutive row major tiles of `tile_size` columns.
+///
+/// Rows past `num_rows` are filled with zeros.
+fn load_rows<EI: Numeric, ES: Numeric>(
+    tensor: &Tensor<Line<EI>>,
+    offset: u32,
+    first_row: u32,
+    num_rows: u32,
+    first_col: u32,
+    slice: &mut SliceMut<Line<ES>>,
+    unit: u32,
+    #[comptime] num_cols: u32,
+    #[comptime] num_units: u32,
+    #[comptime] line_size: u32,
+    #[comptime] tile_size: u32,
+) {
+    let rank = tensor.rank();
+    let stride_row = tensor.stride(rank - 2);
+    let stride_col = tensor.stride(rank - 1);
+    let num_lines = comptime!(tile_size * num_cols / line_size);
+
+    for line_index in range_stepped(unit, num_lines, num_units) {
+        let element = line_index * line_size;
+        let row = element / num_cols;
+        let col = element % num_cols;
+        let position =
+            (col / tile_size) * tile_size * tile_size + row * tile_size + col % tile_size;
+
+        let mut line = Line::empty(line_size).fill(EI::from_int(0));
+        if first_row + row < num_rows {
+            line =
+                tensor[(offset + (first_row + row) * stride_row + (first_col + col) * stride_col)
+                    / line_size];
+        }
+
+        #[unroll]
+        for i in 0..line_size {
+            slice[position + i] = Line::new(ES::cast_from(line[i]));
+        }
+    }
+}
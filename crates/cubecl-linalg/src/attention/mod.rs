@@ -0,0 +1,10 @@
+mod base;
+mod config;
+mod kernel;
+
+pub use base::*;
+pub use config::*;
+
+/// Tests for attention kernels
+#[cfg(feature = "export_tests")]
+pub mod tests;
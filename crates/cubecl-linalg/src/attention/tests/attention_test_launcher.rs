@@ -0,0 +1,243 @@
+use std::fmt::Display;
+
+use cubecl_core::prelude::*;
+use cubecl_core::CubeElement;
+
+use crate::attention::{self, AttentionOptions};
+use crate::matmul::tests::test_utils::assert_equals_approx;
+use crate::matmul::tests::test_utils::generate_random_data;
+
+/// Sizes and options of an attention test
+#[derive(Clone, Debug)]
+pub struct AttentionProblem {
+    pub batch_size: usize,
+    pub heads: usize,
+    pub seq_q: usize,
+    pub seq_k: usize,
+    pub head_dim: usize,
+    pub causal: bool,
+    /// Number of valid keys of each batch
+    pub key_lengths: Option<Vec<u32>>,
+    /// Shape of the bias, broadcast to the scores
+    pub bias_shape: Option<Vec<usize>>,
+    pub alibi: bool,
+}
+
+/// Test the correctness of the attention on the given device,
+/// against a naive CPU implementation over the given problem
+pub fn test_attention<EI: Float + CubeElement + Display, R: Runtime>(
+    problem: AttentionProblem,
+    disable_cmma: bool,
+    device: &R::Device,
+) {
+    let client: ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel> = R::client(device);
+
+    if !attention::is_available::<R, EI>(&client) {
+        // Can't execute the test.
+        return;
+    }
+
+    let query_shape = vec![
+        problem.batch_size,
+        problem.heads,
+        problem.seq_q,
+        problem.head_dim,
+    ];
+    let key_shape = vec![
+        problem.batch_size,
+        problem.heads,
+        problem.seq_k,
+        problem.head_dim,
+    ];
+
+    let query: Vec<EI> = generate_random_data(query_shape.iter().product(), 1234);
+    let key: Vec<EI> = generate_random_data(key_shape.iter().product(), 5678);
+    let value: Vec<EI> = generate_random_data(key_shape.iter().product(), 91011);
+    let bias: Option<Vec<EI>> = problem
+        .bias_shape
+        .as_ref()
+        .map(|shape| generate_random_data(shape.iter().product(), 1213));
+    let alibi_slopes: Option<Vec<f32>> = problem.alibi.then(|| {
+        (0..problem.heads)
+            .map(|head| 0.5f32.powi(head as i32 + 1))
+            .collect()
+    });
+
+    let expected = attention_cpu_reference(
+        &query,
+        &key,
+        &value,
+        bias.as_deref(),
+        alibi_slopes.as_deref(),
+        &problem,
+    );
+
+    let query_handle = client.create(EI::as_bytes(&query));
+    let key_handle = client.create(EI::as_bytes(&key));
+    let value_handle = client.create(EI::as_bytes(&value));
+    let out_handle = client.empty(expected.len() * EI::as_elem().size());
+    let bias_handle = bias.as_ref().map(|bias| client.create(EI::as_bytes(bias)));
+    let alibi_handle = alibi_slopes
+        .as_ref()
+        .map(|slopes| client.create(f32::as_bytes(slopes)));
+    let lengths_handle = problem
+        .key_lengths
+        .as_ref()
+        .map(|lengths| client.create(u32::as_bytes(lengths)));
+
+    let query_strides = contiguous_strides(&query_shape);
+    let key_strides = contiguous_strides(&key_shape);
+    let bias_strides = problem.bias_shape.as_deref().map(contiguous_strides);
+    let alibi_shape = [problem.heads];
+    let lengths_shape = [problem.batch_size];
+    let vector_strides = [1];
+
+    let handle_ref = |handle, strides, shape, elem_size| unsafe {
+        TensorHandleRef::<R>::from_raw_parts(handle, strides, shape, elem_size)
+    };
+    let options = AttentionOptions {
+        scale: None,
+        causal: problem.causal,
+        key_lengths: lengths_handle.as_ref().map(|handle| {
+            handle_ref(
+                handle,
+                &vector_strides,
+                &lengths_shape,
+                u32::as_elem().size(),
+            )
+        }),
+        bias: bias_handle.as_ref().map(|handle| {
+            handle_ref(
+                handle,
+                bias_strides.as_ref().unwrap(),
+                problem.bias_shape.as_ref().unwrap(),
+                EI::as_elem().size(),
+            )
+        }),
+        alibi_slopes: alibi_handle
+            .as_ref()
+            .map(|handle| handle_ref(handle, &vector_strides, &alibi_shape, f32::as_elem().size())),
+    };
+
+    attention::launch_ref::<R, EI, EI>(
+        &client,
+        handle_ref(
+            &query_handle,
+            &query_strides,
+            &query_shape,
+            EI::as_elem().size(),
+        ),
+        handle_ref(&key_handle, &key_strides, &key_shape, EI::as_elem().size()),
+        handle_ref(
+            &value_handle,
+            &key_strides,
+            &key_shape,
+            EI::as_elem().size(),
+        ),
+        handle_ref(
+            &out_handle,
+            &query_strides,
+            &query_shape,
+            EI::as_elem().size(),
+        ),
+        &options,
+        disable_cmma,
+    );
+
+    if let Err(e) = assert_equals_approx::<R, EI>(&client, out_handle, &expected, 10e-2) {
+        panic!("{}", e);
+    }
+}
+
+fn contiguous_strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for i in (0..shape.len() - 1).rev() {
+        strides[i] = strides[i + 1] * shape[i + 1];
+    }
+    strides
+}
+
+/// Index in a contiguous tensor of the given shape, broadcast to `[batch, heads, seq_q, seq_k]`
+fn broadcast_index(shape: &[usize], position: [usize; 4]) -> usize {
+    let skipped = position.len() - shape.len();
+
+    shape
+        .iter()
+        .zip(&position[skipped..])
+        .fold(0, |index, (dim, pos)| index * dim + pos % dim)
+}
+
+/// Computes the attention of the queries to the keys with a softmax over all scores
+///
+/// This is a naive CPU implementation, very slow on large payloads,
+/// not designed to be used for other purposes than testing.
+fn attention_cpu_reference<EI: Float + CubeElement>(
+    query: &[EI],
+    key: &[EI],
+    value: &[EI],
+    bias: Option<&[EI]>,
+    alibi_slopes: Option<&[f32]>,
+    problem: &AttentionProblem,
+) -> Vec<EI> {
+    let (seq_q, seq_k, head_dim) = (problem.seq_q, problem.seq_k, problem.head_dim);
+    let scale = 1. / (head_dim as f32).sqrt();
+    let mut out = vec![0f32; query.len()];
+
+    for b in 0..problem.batch_size {
+        let num_keys = problem
+            .key_lengths
+            .as_ref()
+            .map_or(seq_k, |lengths| (lengths[b] as usize).min(seq_k));
+
+        for h in 0..problem.heads {
+            let nth_batch = b * problem.heads + h;
+
+            for i in 0..seq_q {
+                let mut scores = Vec::new();
+
+                for j in 0..num_keys {
+                    if problem.causal && j > i {
+                        break;
+                    }
+
+                    let mut score = 0.;
+                    for d in 0..head_dim {
+                        let q = query[(nth_batch * seq_q + i) * head_dim + d]
+                            .to_f32()
+                            .unwrap();
+                        let k = key[(nth_batch * seq_k + j) * head_dim + d]
+                            .to_f32()
+                            .unwrap();
+                        score += q * k;
+                    }
+                    score *= scale;
+
+                    if let (Some(bias), Some(shape)) = (bias, &problem.bias_shape) {
+                        score += bias[broadcast_index(shape, [b, h, i, j])].to_f32().unwrap();
+                    }
+                    if let Some(slopes) = alibi_slopes {
+                        score -= slopes[h] * (i as f32 - j as f32).abs();
+                    }
+
+                    scores.push(score);
+                }
+
+                // Queries attending to no key have an output of zero.
+                let max = scores.iter().copied().fold(f32::MIN, f32::max);
+                let probs: Vec<f32> = scores.iter().map(|score| (score - max).exp()).collect();
+                let sum: f32 = probs.iter().sum();
+
+                for (j, prob) in probs.iter().enumerate() {
+                    for d in 0..head_dim {
+                        let v = value[(nth_batch * seq_k + j) * head_dim + d]
+                            .to_f32()
+                            .unwrap();
+                        out[(nth_batch * seq_q + i) * head_dim + d] += prob / sum * v;
+                    }
+                }
+            }
+        }
+    }
+
+    out.into_iter().map(EI::new).collect()
+}
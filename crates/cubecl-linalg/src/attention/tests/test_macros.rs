@@ -0,0 +1,104 @@
+#[macro_export]
+macro_rules! testgen_attention {
+    () => {
+        mod attention {
+            $crate::testgen_attention!(f32);
+        }
+    };
+
+    ($float:ident) => {
+        use super::*;
+        use cubecl_linalg::attention::tests::attention_test_launcher::{
+            test_attention, AttentionProblem,
+        };
+
+        pub type FloatT = $float;
+
+        fn problem(seq_q: usize, seq_k: usize, head_dim: usize) -> AttentionProblem {
+            AttentionProblem {
+                batch_size: 2,
+                heads: 3,
+                seq_q,
+                seq_k,
+                head_dim,
+                causal: false,
+                key_lengths: None,
+                bias_shape: None,
+                alibi: false,
+            }
+        }
+
+        #[test]
+        pub fn test_attention_unmasked() {
+            test_attention::<FloatT, TestRuntime>(problem(37, 45, 32), false, &Default::default());
+        }
+
+        #[test]
+        pub fn test_attention_unmasked_no_cmma() {
+            test_attention::<FloatT, TestRuntime>(problem(20, 70, 16), true, &Default::default());
+        }
+
+        #[test]
+        pub fn test_attention_causal() {
+            test_attention::<FloatT, TestRuntime>(
+                AttentionProblem {
+                    causal: true,
+                    ..problem(80, 80, 64)
+                },
+                false,
+                &Default::default(),
+            );
+        }
+
+        #[test]
+        pub fn test_attention_key_lengths() {
+            test_attention::<FloatT, TestRuntime>(
+                AttentionProblem {
+                    causal: true,
+                    key_lengths: Some(vec![50, 23]),
+                    ..problem(50, 50, 32)
+                },
+                false,
+                &Default::default(),
+            );
+        }
+
+        #[test]
+        pub fn test_attention_bias() {
+            test_attention::<FloatT, TestRuntime>(
+                AttentionProblem {
+                    bias_shape: Some(vec![3, 1, 29]),
+                    ..problem(33, 29, 16)
+                },
+                false,
+                &Default::default(),
+            );
+        }
+
+        #[test]
+        pub fn test_attention_alibi() {
+            test_attention::<FloatT, TestRuntime>(
+                AttentionProblem {
+                    alibi: true,
+                    bias_shape: Some(vec![2, 3, 40, 40]),
+                    ..problem(40, 40, 48)
+                },
+                false,
+                &Default::default(),
+            );
+        }
+    };
+
+    ([$($float:ident),*]) => {
+        mod attention {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_attention!($float);
+                })*
+            }
+        }
+    };
+}
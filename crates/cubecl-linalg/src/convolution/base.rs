@@ -0,0 +1,514 @@
+use cubecl_core::prelude::*;
+use cubecl_core::tensor_line_size;
+
+use crate::matmul::components::global::{homogeneous, EpilogueInputsLaunch};
+use crate::matmul::components::stage::{
+    self,
+    multi_buffer::{LhsReader, RhsReader},
+};
+use crate::matmul::components::{MatmulKernel, MatmulProblem, MatrixLayout};
+use crate::matmul::kernels::matmul::cmma::Cmma;
+use crate::matmul::kernels::matmul::plane_mma::PlaneMma;
+use crate::matmul::kernels::matmul::{AdvancedConfig, MixedAlgorithm};
+
+use super::config::{ConvConfig, ConvPass, ConvShapeLaunch};
+use super::kernel;
+
+/// Memory layout convention of the tensors of a convolution
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConvLayout {
+    /// Activations of shape `[n, c, h, w]` and weights of shape `[oc, c / groups, kh, kw]`
+    Nchw,
+    /// Activations of shape `[n, h, w, c]` and weights of shape `[oc, kh, kw, c / groups]`
+    Nhwc,
+}
+
+/// Options of a 2D convolution, with the height before the width
+#[derive(Clone, Debug)]
+pub struct Conv2dOptions {
+    pub stride: [usize; 2],
+    pub padding: [usize; 2],
+    pub dilation: [usize; 2],
+    /// Number of groups the channels are split into, each output channel
+    /// only depending on the input channels of its group
+    pub groups: usize,
+    pub layout: ConvLayout,
+}
+
+impl Default for Conv2dOptions {
+    fn default() -> Self {
+        Self {
+            stride: [1, 1],
+            padding: [0, 0],
+            dilation: [1, 1],
+            groups: 1,
+            layout: ConvLayout::Nchw,
+        }
+    }
+}
+
+impl Conv2dOptions {
+    /// Height and width of the output of a convolution with the given input and kernel sizes.
+    ///
+    /// Returns an error if the stride is zero, or if the dilated kernel is larger than the
+    /// padded input.
+    pub fn output_size(
+        &self,
+        in_size: [usize; 2],
+        kernel_size: [usize; 2],
+    ) -> Result<[usize; 2], &'static str> {
+        let mut out_size = [0; 2];
+
+        for i in 0..2 {
+            if self.stride[i] == 0 {
+                return Err("The stride of the convolution should be at least 1");
+            }
+
+            let kernel_extent = kernel_size[i]
+                .checked_sub(1)
+                .map(|size| self.dilation[i] * size + 1)
+                .ok_or("The kernel of the convolution should not be empty")?;
+            let span = (in_size[i] + 2 * self.padding[i])
+                .checked_sub(kernel_extent)
+                .ok_or("The dilated kernel is larger than the padded input")?;
+
+            out_size[i] = span / self.stride[i] + 1;
+        }
+
+        Ok(out_size)
+    }
+
+    /// Position of the height, width and channel dimensions of a tensor in the layout
+    fn dims(&self) -> [usize; 3] {
+        match self.layout {
+            ConvLayout::Nchw => [2, 3, 1],
+            ConvLayout::Nhwc => [1, 2, 3],
+        }
+    }
+}
+
+/// 2D convolution of `input` by `weight`, written to `out`.
+///
+/// The convolution is computed as an implicit matmul, where the input is gathered
+/// into im2col tiles when loaded to shared memory, so it is never unfolded in global memory.
+///
+/// The output should be contiguous over its height and width.
+///
+/// Cmma will be used if available and enabled,
+/// otherwise it will fall back on a non-cmma implementation
+pub fn conv2d<R: Runtime, EG: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    weight: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    options: &Conv2dOptions,
+    disable_cmma: bool,
+) {
+    let input_layout = canonical(&input, options);
+    let weight_layout = canonical(&weight, options);
+    let out_layout = canonical(&out, options);
+    let sizes = ConvSizes::new(&input_layout.0, &weight_layout.0, &out_layout.0, options);
+    let out_view = activation_view(&out_layout, sizes.out_channels, sizes.groups);
+
+    launch::<R, EG>(
+        client,
+        view(&input, &input_layout),
+        view(&weight, &weight_layout),
+        view(&out, &out_view),
+        &sizes,
+        conv_config(ConvPass::Forward, options),
+        disable_cmma,
+    );
+}
+
+/// Gradient of a 2D convolution with regard to its input, written to `input_grad`.
+///
+/// The output gradient is gathered when loaded to shared memory, only reading the output
+/// positions whose window covers each input position.
+///
+/// The input gradient should be contiguous over its height and width.
+pub fn conv2d_backward_data<R: Runtime, EG: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    out_grad: TensorHandleRef<'_, R>,
+    weight: TensorHandleRef<'_, R>,
+    input_grad: TensorHandleRef<'_, R>,
+    options: &Conv2dOptions,
+    disable_cmma: bool,
+) {
+    let out_grad_layout = canonical(&out_grad, options);
+    let weight_layout = canonical(&weight, options);
+    let input_grad_layout = canonical(&input_grad, options);
+    let sizes = ConvSizes::new(
+        &input_grad_layout.0,
+        &weight_layout.0,
+        &out_grad_layout.0,
+        options,
+    );
+    let input_grad_view = activation_view(&input_grad_layout, sizes.channels, sizes.groups);
+
+    launch::<R, EG>(
+        client,
+        view(&out_grad, &out_grad_layout),
+        view(&weight, &weight_layout),
+        view(&input_grad, &input_grad_view),
+        &sizes,
+        conv_config(ConvPass::BackwardData, options),
+        disable_cmma,
+    );
+}
+
+/// Gradient of a 2D convolution with regard to its weight, written to `weight_grad`.
+///
+/// The input is gathered into im2col tiles when loaded to shared memory,
+/// and the batches are summed over in the k dimension of the matmul.
+///
+/// The weight gradient should be contiguous over its channels and kernel positions.
+pub fn conv2d_backward_weight<R: Runtime, EG: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    out_grad: TensorHandleRef<'_, R>,
+    weight_grad: TensorHandleRef<'_, R>,
+    options: &Conv2dOptions,
+    disable_cmma: bool,
+) {
+    let input_layout = canonical(&input, options);
+    let out_grad_layout = canonical(&out_grad, options);
+    let weight_grad_layout = canonical(&weight_grad, options);
+    let sizes = ConvSizes::new(
+        &input_layout.0,
+        &weight_grad_layout.0,
+        &out_grad_layout.0,
+        options,
+    );
+
+    // Each group is a matrix with a row per output channel and a column per filter position,
+    // ordered like in memory.
+    let (shape, strides) = &weight_grad_layout;
+    let filter_dims: &[usize] = match options.layout {
+        ConvLayout::Nchw => &[3, 1, 2],
+        ConvLayout::Nhwc => &[1, 2, 3],
+    };
+    let filter_stride = merged_stride(shape, strides, filter_dims)
+        .expect("The weight gradient should be contiguous over its channels and kernel positions");
+    let weight_grad_view = (
+        vec![
+            sizes.groups,
+            sizes.out_channels,
+            sizes.kernel_h * sizes.kernel_w * sizes.channels,
+        ],
+        vec![sizes.out_channels * strides[0], strides[0], filter_stride],
+    );
+
+    launch::<R, EG>(
+        client,
+        view(&out_grad, &out_grad_layout),
+        view(&input, &input_layout),
+        view(&weight_grad, &weight_grad_view),
+        &sizes,
+        conv_config(ConvPass::BackwardWeight, options),
+        disable_cmma,
+    );
+}
+
+/// Sizes of a convolution, with the channels per group
+#[derive(Debug)]
+struct ConvSizes {
+    batch_size: usize,
+    groups: usize,
+    in_h: usize,
+    in_w: usize,
+    out_h: usize,
+    out_w: usize,
+    kernel_h: usize,
+    kernel_w: usize,
+    channels: usize,
+    out_channels: usize,
+}
+
+impl ConvSizes {
+    /// Sizes of the convolution of the given tensors, indexed as `[n, h, w, c]`
+    /// and `[oc, kh, kw, c]`
+    fn new(input: &[usize], weight: &[usize], out: &[usize], options: &Conv2dOptions) -> Self {
+        let groups = options.groups;
+        assert!(groups > 0, "The number of groups should be positive");
+
+        let sizes = ConvSizes {
+            batch_size: input[0],
+            groups,
+            in_h: input[1],
+            in_w: input[2],
+            out_h: out[1],
+            out_w: out[2],
+            kernel_h: weight[1],
+            kernel_w: weight[2],
+            channels: weight[3],
+            out_channels: weight[0] / groups,
+        };
+
+        assert_eq!(
+            out[0], input[0],
+            "The input and output batch sizes should match"
+        );
+        assert_eq!(
+            input[3],
+            sizes.channels * groups,
+            "The weight should have the input channels of a single group"
+        );
+        assert_eq!(
+            weight[0] % groups,
+            0,
+            "The number of groups should divide the output channels"
+        );
+        assert_eq!(
+            out[3], weight[0],
+            "The output should have an output channel per filter"
+        );
+        assert_eq!(
+            Ok([sizes.out_h, sizes.out_w]),
+            options.output_size([sizes.in_h, sizes.in_w], [sizes.kernel_h, sizes.kernel_w]),
+            "The output size doesn't match the options of the convolution"
+        );
+
+        sizes
+    }
+
+    fn launch_arg<'a, R: Runtime>(&self) -> ConvShapeLaunch<'a, R> {
+        ConvShapeLaunch::new(
+            ScalarArg::new(self.batch_size as u32),
+            ScalarArg::new(self.groups as u32),
+            ScalarArg::new(self.in_h as u32),
+            ScalarArg::new(self.in_w as u32),
+            ScalarArg::new(self.out_h as u32),
+            ScalarArg::new(self.out_w as u32),
+            ScalarArg::new(self.kernel_h as u32),
+            ScalarArg::new(self.kernel_w as u32),
+            ScalarArg::new(self.channels as u32),
+            ScalarArg::new(self.out_channels as u32),
+        )
+    }
+}
+
+fn conv_config(pass: ConvPass, options: &Conv2dOptions) -> ConvConfig {
+    ConvConfig {
+        pass,
+        stride_h: options.stride[0] as u32,
+        stride_w: options.stride[1] as u32,
+        padding_h: options.padding[0] as u32,
+        padding_w: options.padding[1] as u32,
+        dilation_h: options.dilation[0] as u32,
+        dilation_w: options.dilation[1] as u32,
+        channels_last: options.layout == ConvLayout::Nhwc,
+    }
+}
+
+/// Shape and strides of the tensor permuted to be indexed as `[n, h, w, c]` for activations,
+/// or `[oc, kh, kw, c]` for weights
+fn canonical<R: Runtime>(
+    tensor: &TensorHandleRef<'_, R>,
+    options: &Conv2dOptions,
+) -> (Vec<usize>, Vec<usize>) {
+    assert_eq!(
+        tensor.shape.len(),
+        4,
+        "Convolution tensors should have 4 dimensions"
+    );
+
+    let dims = [0].into_iter().chain(options.dims());
+    dims.map(|dim| (tensor.shape[dim], tensor.strides[dim]))
+        .unzip()
+}
+
+/// Batched matrix view `[n, groups, h·w, c]` of an activation indexed as `[n, h, w, c]`,
+/// where `c` is the number of channels per group
+fn activation_view(
+    (shape, strides): &(Vec<usize>, Vec<usize>),
+    channels: usize,
+    groups: usize,
+) -> (Vec<usize>, Vec<usize>) {
+    let spatial_stride = merged_stride(shape, strides, &[1, 2])
+        .expect("The output should be contiguous over its height and width");
+
+    (
+        vec![shape[0], groups, shape[1] * shape[2], channels],
+        vec![
+            strides[0],
+            channels * strides[3],
+            spatial_stride,
+            strides[3],
+        ],
+    )
+}
+
+/// Stride of the given dimensions merged into a single one, ordered from the outermost
+/// to the innermost, or `None` if they are not contiguous with each other
+fn merged_stride(shape: &[usize], strides: &[usize], dims: &[usize]) -> Option<usize> {
+    let mut stride = None;
+    let mut expected = None;
+
+    // Dimensions of size 1 can have any stride.
+    for &dim in dims.iter().rev().filter(|dim| shape[**dim] != 1) {
+        if expected.is_some_and(|expected| strides[dim] != expected) {
+            return None;
+        }
+
+        stride.get_or_insert(strides[dim]);
+        expected = Some(strides[dim] * shape[dim]);
+    }
+
+    Some(stride.unwrap_or(1))
+}
+
+/// Returns the tensor with the given shape and strides
+fn view<'a, R: Runtime>(
+    tensor: &TensorHandleRef<'a, R>,
+    (shape, strides): &'a (Vec<usize>, Vec<usize>),
+) -> TensorHandleRef<'a, R> {
+    unsafe { TensorHandleRef::from_raw_parts(tensor.handle, strides, shape, tensor.elem_size) }
+}
+
+fn launch<R: Runtime, EG: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    sizes: &ConvSizes,
+    conv: ConvConfig,
+    disable_cmma: bool,
+) {
+    let (m, n, k) = match conv.pass {
+        ConvPass::Forward => (
+            sizes.out_h * sizes.out_w,
+            sizes.out_channels,
+            sizes.kernel_h * sizes.kernel_w * sizes.channels,
+        ),
+        ConvPass::BackwardData => (
+            sizes.in_h * sizes.in_w,
+            sizes.channels,
+            sizes.kernel_h * sizes.kernel_w * sizes.out_channels,
+        ),
+        ConvPass::BackwardWeight => (
+            sizes.out_channels,
+            sizes.kernel_h * sizes.kernel_w * sizes.channels,
+            sizes.batch_size * sizes.out_h * sizes.out_w,
+        ),
+    };
+
+    let rank = out.shape.len();
+    let problem = MatmulProblem {
+        m,
+        n,
+        k,
+        batches: out.shape[..rank - 2].to_vec(),
+        // The operands are gathered one value at a time, and staged as row major matrices.
+        lhs_layout: MatrixLayout::RowMajor,
+        rhs_layout: MatrixLayout::RowMajor,
+        lhs_line_size: 1,
+        rhs_line_size: 1,
+        out_line_size: tensor_line_size(
+            R::supported_line_sizes(),
+            out.shape,
+            out.strides,
+            rank - 1,
+        ),
+    };
+
+    if disable_cmma
+        || <Cmma<(EG, EG, half::f16, f32, EG)> as MixedAlgorithm>::check_availability::<R>(client)
+            .is_err()
+    {
+        launch_conv::<R, PlaneMma<(EG, EG, f32, f32, EG)>, _>(
+            client, lhs, rhs, out, sizes, problem, conv,
+        );
+    } else {
+        launch_conv::<R, Cmma<(EG, EG, half::f16, f32, EG)>, _>(
+            client, lhs, rhs, out, sizes, problem, conv,
+        );
+    }
+}
+
+fn launch_conv<
+    R: Runtime,
+    A: MixedAlgorithm<StageMatmul = SMM>,
+    SMM: stage::Matmul<A::ES, A::EO, LhsReader = LhsReader<A::ES>, RhsReader = RhsReader<A::ES>>,
+>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    sizes: &ConvSizes,
+    problem: MatmulProblem,
+    conv: ConvConfig,
+) {
+    let advanced_config = AdvancedConfig::default();
+
+    let cube_dim = A::cube_dim();
+    let cube_count = A::cube_count(&problem, &advanced_config);
+    let config = homogeneous::Matmul::<A::EL, A::EL, A::EO, A::ES, SMM>::make_config(
+        &problem,
+        &cube_dim,
+        &cube_count,
+        &advanced_config,
+    );
+    homogeneous::Matmul::<A::EL, A::EL, A::EO, A::ES, SMM>::check_config(config);
+
+    // The epilogue is disabled, so its tensors are never read.
+    let placeholder = client.empty(A::EO::as_elem().size());
+    let epilogue_arg =
+        || unsafe { TensorArg::<R>::from_raw_parts::<A::EO>(&placeholder, &[1], &[1], 1) };
+
+    unsafe {
+        kernel::launch::launch_unchecked::<A::EL, A::EO, A::ES, SMM, R>(
+            client,
+            cube_count,
+            cube_dim,
+            TensorArg::<R>::from_raw_parts::<A::EL>(lhs.handle, lhs.strides, lhs.shape, 1),
+            TensorArg::<R>::from_raw_parts::<A::EL>(rhs.handle, rhs.strides, rhs.shape, 1),
+            TensorArg::<R>::from_raw_parts::<A::EO>(
+                out.handle,
+                out.strides,
+                out.shape,
+                problem.out_line_size,
+            ),
+            sizes.launch_arg(),
+            EpilogueInputsLaunch::new(
+                ScalarArg::new(1.),
+                ScalarArg::new(0.),
+                epilogue_arg(),
+                epilogue_arg(),
+                epilogue_arg(),
+            ),
+            conv,
+            config,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_size_with_padding_stride_and_dilation() {
+        let options = Conv2dOptions {
+            stride: [2, 1],
+            padding: [1, 0],
+            dilation: [1, 2],
+            ..Default::default()
+        };
+
+        assert_eq!(options.output_size([7, 9], [3, 3]), Ok([4, 5]));
+    }
+
+    #[test]
+    fn output_size_with_kernel_larger_than_input() {
+        let options = Conv2dOptions {
+            dilation: [3, 1],
+            ..Default::default()
+        };
+
+        assert!(options.output_size([4, 8], [3, 3]).is_err());
+        assert!(Conv2dOptions::default()
+            .output_size([4, 8], [3, 0])
+            .is_err());
+    }
+}
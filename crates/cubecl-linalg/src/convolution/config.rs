@@ -0,0 +1,60 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[derive(CubeType, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+/// Which pass of the convolution is computed as an implicit matmul
+///
+/// The sizes below are per group: `c` and `oc` are the input and output channels of a group.
+pub enum ConvPass {
+    /// Output from the input and the weight, as `[oh·ow, k] x [k, oc]` with `k = kh·kw·c`,
+    /// for each batch and group
+    Forward,
+    /// Input gradient from the output gradient and the weight, as `[h·w, k] x [k, c]`
+    /// with `k = kh·kw·oc`, for each batch and group
+    BackwardData,
+    /// Weight gradient from the output gradient and the input, as `[oc, k] x [k, kh·kw·c]`
+    /// with `k = n·oh·ow`, for each group
+    BackwardWeight,
+}
+
+#[derive(CubeType, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+/// Comptime parameters of the convolution
+pub struct ConvConfig {
+    pub pass: ConvPass,
+    pub stride_h: u32,
+    pub stride_w: u32,
+    pub padding_h: u32,
+    pub padding_w: u32,
+    pub dilation_h: u32,
+    pub dilation_w: u32,
+    /// Whether positions within the filter are ordered as `(kh, kw, c)` rather than `(c, kh, kw)`,
+    /// following the memory layout of the weight
+    pub channels_last: bool,
+}
+
+#[derive(CubeLaunch)]
+/// Sizes of the convolution, given to the convolution kernel alongside its operands.
+///
+/// The channels are the number of channels in each group.
+pub struct ConvShape {
+    pub batch_size: u32,
+    pub groups: u32,
+    pub in_h: u32,
+    pub in_w: u32,
+    pub out_h: u32,
+    pub out_w: u32,
+    pub kernel_h: u32,
+    pub kernel_w: u32,
+    pub channels: u32,
+    pub out_channels: u32,
+}
+
+#[cube]
+/// Size of the k dimension of the implicit matmul
+pub fn reduction_size(shape: &ConvShape, #[comptime] conv: ConvConfig) -> u32 {
+    match comptime!(conv.pass) {
+        ConvPass::Forward => shape.kernel_h * shape.kernel_w * shape.channels,
+        ConvPass::BackwardData => shape.kernel_h * shape.kernel_w * shape.out_channels,
+        ConvPass::BackwardWeight => shape.batch_size * shape.out_h * shape.out_w,
+    }
+}
@@ -0,0 +1,173 @@
+use crate::matmul::components::global::homogeneous;
+use crate::matmul::components::global::unloader::Unloader;
+use crate::matmul::components::global::{Config as _, Epilogue, EpilogueInputs, FusedEpilogue};
+use crate::matmul::components::stage;
+use crate::matmul::components::stage::multi_buffer::{LhsReader, RhsReader};
+use crate::matmul::components::Ident;
+
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+use std::marker::PhantomData;
+
+use super::config::{reduction_size, ConvConfig, ConvShape};
+use super::loader::ConvLoader;
+
+/// Performs a convolution pass at the global level, as a matmul whose operands are
+/// gathered from the convolution tensors when loaded to the stage.
+///
+/// Apart from the loaders, it behaves like the homogeneous global matmul:
+/// all planes load data to the stage, and all planes are used in the stage matmul computation.
+pub struct ConvMatmul<
+    EG: Numeric,
+    EO: Numeric,
+    ES: Numeric,
+    SMM: stage::Matmul<ES, EO>,
+    E: Epilogue<EO> = FusedEpilogue<EO>,
+> {
+    _eg: PhantomData<EG>,
+    _eo: PhantomData<EO>,
+    _es: PhantomData<ES>,
+    _stage_matmul: PhantomData<SMM>,
+    _epilogue: PhantomData<E>,
+}
+
+#[cube]
+impl<EG, EO, ES, SMM, E> ConvMatmul<EG, EO, ES, SMM, E>
+where
+    EG: Numeric,
+    EO: Numeric,
+    ES: Numeric,
+    SMM: stage::Matmul<ES, EO, LhsReader = LhsReader<ES>, RhsReader = RhsReader<ES>>,
+    E: Epilogue<EO>,
+{
+    pub fn execute(
+        mut lhs_loader: ConvLoader<EG, ES, SMM::Config>,
+        mut rhs_loader: ConvLoader<EG, ES, SMM::Config>,
+        mut out_unloader: Unloader<EO, E>,
+        acc: &mut SMM::Accumulator,
+        shape: &ConvShape,
+        #[comptime] conv: ConvConfig,
+        #[comptime] config: homogeneous::Config<SMM::Config>,
+    ) {
+        let k_step = SMM::K;
+        let num_loops = (reduction_size(shape, conv) + k_step - 1) / k_step;
+
+        let (mut lhs_tile, mut rhs_tile) = SMM::init_tile_inputs(config.to_smm_config());
+
+        for _ in 0..num_loops {
+            lhs_loader.fill_stage(shape, conv, Ident::Lhs, config);
+            rhs_loader.fill_stage(shape, conv, Ident::Rhs, config);
+            let lhs_stage_reader = &LhsReader::new(lhs_loader.stage);
+            let rhs_stage_reader = &RhsReader::new(rhs_loader.stage);
+
+            sync_units();
+
+            SMM::execute(
+                lhs_stage_reader,
+                rhs_stage_reader,
+                &mut lhs_tile,
+                &mut rhs_tile,
+                acc,
+                config.to_smm_config(),
+            );
+
+            sync_units();
+
+            lhs_loader.advance_view(k_step, Ident::Lhs);
+            rhs_loader.advance_view(k_step, Ident::Rhs);
+        }
+
+        SMM::read_accumulator::<Unloader<EO, E>, homogeneous::Config<SMM::Config>>(
+            acc,
+            &mut out_unloader,
+            config.to_smm_config(),
+            config,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn init_loader(
+        tensor: &Tensor<Line<EG>>,
+        row_offset: u32,
+        col_offset: u32,
+        nth_batch: u32,
+        shape: &ConvShape,
+        #[comptime] conv: ConvConfig,
+        #[comptime] ident: Ident,
+        #[comptime] config: homogeneous::Config<SMM::Config>,
+    ) -> ConvLoader<EG, ES, SMM::Config> {
+        ConvLoader::new::<homogeneous::Config<SMM::Config>>(
+            tensor, row_offset, col_offset, nth_batch, shape, conv, ident, config,
+        )
+    }
+
+    pub fn init_unloader(
+        out: &mut Tensor<Line<EO>>,
+        x_offset: u32,
+        y_offset: u32,
+        batch_offset: u32,
+        epilogue: &EpilogueInputs<EO>,
+    ) -> Unloader<EO, E> {
+        Unloader::new(out, x_offset, y_offset, batch_offset, epilogue)
+    }
+
+    pub fn init_accumulator(
+        #[comptime] config: homogeneous::Config<SMM::Config>,
+    ) -> SMM::Accumulator {
+        SMM::init_accumulator(config.to_smm_config())
+    }
+}
+
+#[cube(launch_unchecked)]
+/// Executes a convolution pass as an implicit matmul,
+/// assigning each cube to a single global matmul.
+///
+/// The lhs and rhs are the 4D tensors the operands are gathered from,
+/// and the output is a batched matrix view of the result.
+/// Cubes are dispatched like in the one-to-one batch matmul with the natural dispatch.
+pub(crate) fn launch<
+    EG: Numeric,
+    EO: Numeric,
+    ES: Numeric,
+    SMM: stage::Matmul<ES, EO, LhsReader = LhsReader<ES>, RhsReader = RhsReader<ES>>,
+>(
+    lhs: &Tensor<Line<EG>>,
+    rhs: &Tensor<Line<EG>>,
+    out: &mut Tensor<Line<EO>>,
+    shape: &ConvShape,
+    epilogue: &EpilogueInputs<EO>,
+    #[comptime] conv: ConvConfig,
+    #[comptime] config: homogeneous::Config<SMM::Config>,
+) {
+    let x_offset = CUBE_POS_X * config.stage_dim(Ident::Lhs).height();
+    let y_offset = CUBE_POS_Y * config.stage_dim(Ident::Rhs).width();
+    let nth_batch = CUBE_POS_Z;
+
+    ConvMatmul::<EG, EO, ES, SMM>::execute(
+        ConvMatmul::<EG, EO, ES, SMM>::init_loader(
+            lhs,
+            x_offset,
+            0,
+            nth_batch,
+            shape,
+            conv,
+            Ident::Lhs,
+            config,
+        ),
+        ConvMatmul::<EG, EO, ES, SMM>::init_loader(
+            rhs,
+            0,
+            y_offset,
+            nth_batch,
+            shape,
+            conv,
+            Ident::Rhs,
+            config,
+        ),
+        ConvMatmul::<EG, EO, ES, SMM>::init_unloader(out, x_offset, y_offset, nth_batch, epilogue),
+        &mut ConvMatmul::<EG, EO, ES, SMM>::init_accumulator(config),
+        shape,
+        conv,
+        config,
+    );
+}
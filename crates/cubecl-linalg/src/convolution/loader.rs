@@ -0,0 +1,331 @@
+use std::marker::PhantomData;
+
+use crate::matmul::components::global::homogeneous;
+use crate::matmul::components::global::{self, Config as _};
+use crate::matmul::components::stage::{
+    self, ColMajorTiling, RowMajorTiling, Stage, TilingOrder, TilingOrderConfig,
+};
+use crate::matmul::components::{Ident, InputIdent};
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+use super::config::{reduction_size, ConvConfig, ConvPass, ConvShape};
+
+#[derive(CubeType)]
+/// Loads an operand of the implicit matmul to the stage, gathering each of its values
+/// from the 4D tensor it is made of, so the operand is never materialized in global memory.
+///
+/// The tensor is indexed as `[n, h, w, c]` for activations and as `[oc, kh, kw, c]` for weights,
+/// whatever its memory layout.
+pub struct ConvLoader<EG: Numeric, ES: Numeric, S: stage::Config> {
+    pub tensor: *const Tensor<Line<EG>>,
+    pub row_offset: u32,
+    pub col_offset: u32,
+    /// Stride of the batch for activations, or of the output channel for weights
+    pub stride_outer: u32,
+    pub stride_h: u32,
+    pub stride_w: u32,
+    pub stride_channel: u32,
+    pub batch: u32,
+    pub group: u32,
+    pub stage: Stage<ES>,
+    _config: PhantomData<S>,
+}
+
+unsafe impl<EG: Numeric, ES: Numeric, S: stage::Config> Sync for ConvLoader<EG, ES, S> {}
+unsafe impl<EG: Numeric, ES: Numeric, S: stage::Config> Send for ConvLoader<EG, ES, S> {}
+
+#[cube]
+impl<EG: Numeric, ES: Numeric, S: stage::Config> ConvLoader<EG, ES, S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<G: global::Config>(
+        tensor: &Tensor<Line<EG>>,
+        row_offset: u32,
+        col_offset: u32,
+        nth_batch: u32,
+        shape: &ConvShape,
+        #[comptime] conv: ConvConfig,
+        #[comptime] ident: Ident,
+        #[comptime] config: G,
+    ) -> Self {
+        let stage = Stage::new::<G::SmmConfig>(ident, config.to_smm_config());
+
+        // The weight gradient sums over the batches, so its matmuls are only over the groups.
+        let (batch, group) = match comptime!(conv.pass) {
+            ConvPass::BackwardWeight => (0, nth_batch),
+            _ => (nth_batch / shape.groups, nth_batch % shape.groups),
+        };
+
+        ConvLoader::<EG, ES, S> {
+            tensor,
+            row_offset,
+            col_offset,
+            stride_outer: tensor.stride(0),
+            stride_h: tensor.stride(1),
+            stride_w: tensor.stride(2),
+            stride_channel: tensor.stride(3),
+            batch,
+            group,
+            stage,
+            _config: PhantomData::<S>.runtime(),
+        }
+    }
+
+    /// Fills the stage with the values of the current view,
+    /// each unit loading values the same way as the cyclic loading of the homogeneous matmul.
+    ///
+    /// Values that fall in the padding or out of bounds are translated to zeros.
+    pub fn fill_stage(
+        &mut self,
+        shape: &ConvShape,
+        #[comptime] conv: ConvConfig,
+        #[comptime] ident: Ident,
+        #[comptime] config: homogeneous::Config<S>,
+    ) {
+        let stage_dim = config.stage_dim(ident);
+
+        let num_stage_elements = stage_dim.total_elements();
+        let total_units = comptime!(config.num_planes() * config.plane_dim());
+        let num_loads_per_unit = num_stage_elements / total_units;
+
+        let unit_id = UNIT_POS_Y * config.plane_dim() + UNIT_POS_X;
+        let mut slice = self.stage.as_slice_mut();
+
+        for i in 0..num_loads_per_unit {
+            let unit_position = unit_id + i * total_units;
+
+            let tile_num_elements = stage_dim.tile_num_elements();
+            let nth_tile = unit_position / tile_num_elements;
+            let pos_within_tile = unit_position % tile_num_elements;
+
+            let (tile_x, tile_y) = match config.tiling_order(ident) {
+                TilingOrderConfig::RowMajor => RowMajorTiling::to_x_y(
+                    nth_tile,
+                    stage_dim.num_tiles_x_dim(),
+                    stage_dim.num_tiles_y_dim(),
+                ),
+                TilingOrderConfig::ColMajor => ColMajorTiling::to_x_y(
+                    nth_tile,
+                    stage_dim.num_tiles_x_dim(),
+                    stage_dim.num_tiles_y_dim(),
+                ),
+            };
+
+            let tile_size_y = stage_dim.tile_size_y_dim();
+            let row = self.row_offset
+                + tile_x * stage_dim.tile_size_x_dim()
+                + pos_within_tile / tile_size_y;
+            let col = self.col_offset + tile_y * tile_size_y + pos_within_tile % tile_size_y;
+
+            slice[unit_position] = Line::cast_from(self.gather(row, col, shape, conv, ident));
+        }
+    }
+
+    /// Advance the view along the k dimension by a specified offset, `k_offset`.
+    pub fn advance_view(&mut self, k_offset: u32, #[comptime] ident: Ident) {
+        match ident.as_input() {
+            InputIdent::Lhs => {
+                self.col_offset += k_offset;
+            }
+            InputIdent::Rhs => {
+                self.row_offset += k_offset;
+            }
+        }
+    }
+
+    /// Reads the value at `row` and `col` of the operand
+    fn gather(
+        &self,
+        row: u32,
+        col: u32,
+        shape: &ConvShape,
+        #[comptime] conv: ConvConfig,
+        #[comptime] ident: Ident,
+    ) -> Line<EG> {
+        let k_size = reduction_size(shape, conv);
+        let mut value = Line::empty(1).fill(EG::from_int(0));
+
+        match comptime!((conv.pass, ident.as_input())) {
+            (ConvPass::Forward, InputIdent::Lhs) => {
+                // Input, with a row per output position and a column per filter position.
+                let oh = row / shape.out_w;
+                let ow = row % shape.out_w;
+                let in_bounds = row < shape.out_h * shape.out_w && col < k_size;
+
+                value = self.read_input(self.batch, oh, ow, col, in_bounds, shape, conv);
+            }
+            (ConvPass::Forward, InputIdent::Rhs) => {
+                // Weight, with a row per filter position and a column per output channel.
+                let (kh, kw, c) = filter_position(
+                    row,
+                    shape.kernel_h,
+                    shape.kernel_w,
+                    shape.channels,
+                    conv.channels_last,
+                );
+
+                if row < k_size && col < shape.out_channels {
+                    value = self.read(
+                        (self.group * shape.out_channels + col) * self.stride_outer
+                            + kh * self.stride_h
+                            + kw * self.stride_w
+                            + c * self.stride_channel,
+                    );
+                }
+            }
+            (ConvPass::BackwardData, InputIdent::Lhs) => {
+                // Output gradient, with a row per input position and a column per
+                // filter position over the output channels.
+                let ih = row / shape.in_w + conv.padding_h;
+                let iw = row % shape.in_w + conv.padding_w;
+                let (kh, kw, o) = filter_position(
+                    col,
+                    shape.kernel_h,
+                    shape.kernel_w,
+                    shape.out_channels,
+                    conv.channels_last,
+                );
+                let kh_offset = kh * conv.dilation_h;
+                let kw_offset = kw * conv.dilation_w;
+
+                // Only the output positions whose window reaches the input position contribute.
+                let mut in_bounds = row < shape.in_h * shape.in_w
+                    && col < k_size
+                    && ih >= kh_offset
+                    && iw >= kw_offset;
+                if in_bounds {
+                    in_bounds = (ih - kh_offset) % conv.stride_h == 0
+                        && (iw - kw_offset) % conv.stride_w == 0
+                        && (ih - kh_offset) / conv.stride_h < shape.out_h
+                        && (iw - kw_offset) / conv.stride_w < shape.out_w;
+                }
+
+                if in_bounds {
+                    value = self.read(
+                        self.batch * self.stride_outer
+                            + (ih - kh_offset) / conv.stride_h * self.stride_h
+                            + (iw - kw_offset) / conv.stride_w * self.stride_w
+                            + (self.group * shape.out_channels + o) * self.stride_channel,
+                    );
+                }
+            }
+            (ConvPass::BackwardData, InputIdent::Rhs) => {
+                // Weight, with a row per filter position over the output channels
+                // and a column per input channel.
+                let (kh, kw, o) = filter_position(
+                    row,
+                    shape.kernel_h,
+                    shape.kernel_w,
+                    shape.out_channels,
+                    conv.channels_last,
+                );
+
+                if row < k_size && col < shape.channels {
+                    value = self.read(
+                        (self.group * shape.out_channels + o) * self.stride_outer
+                            + kh * self.stride_h
+                            + kw * self.stride_w
+                            + col * self.stride_channel,
+                    );
+                }
+            }
+            (ConvPass::BackwardWeight, InputIdent::Lhs) => {
+                // Output gradient, with a row per output channel
+                // and a column per batch and output position.
+                let ow = col % shape.out_w;
+                let oh = (col / shape.out_w) % shape.out_h;
+                let batch = col / (shape.out_w * shape.out_h);
+
+                if row < shape.out_channels && col < k_size {
+                    value = self.read(
+                        batch * self.stride_outer
+                            + oh * self.stride_h
+                            + ow * self.stride_w
+                            + (self.group * shape.out_channels + row) * self.stride_channel,
+                    );
+                }
+            }
+            (ConvPass::BackwardWeight, InputIdent::Rhs) => {
+                // Input, with a row per batch and output position
+                // and a column per filter position.
+                let ow = row % shape.out_w;
+                let oh = (row / shape.out_w) % shape.out_h;
+                let batch = row / (shape.out_w * shape.out_h);
+                let in_bounds =
+                    row < k_size && col < shape.kernel_h * shape.kernel_w * shape.channels;
+
+                value = self.read_input(batch, oh, ow, col, in_bounds, shape, conv);
+            }
+        }
+
+        value
+    }
+
+    /// Reads the input value multiplied by the filter position `filter` for the output position
+    /// `oh`, `ow`, or zero if it falls in the padding
+    #[allow(clippy::too_many_arguments)]
+    fn read_input(
+        &self,
+        batch: u32,
+        oh: u32,
+        ow: u32,
+        filter: u32,
+        in_bounds: bool,
+        shape: &ConvShape,
+        #[comptime] conv: ConvConfig,
+    ) -> Line<EG> {
+        let (kh, kw, c) = filter_position(
+            filter,
+            shape.kernel_h,
+            shape.kernel_w,
+            shape.channels,
+            conv.channels_last,
+        );
+
+        // Positions in the padded input, which start `padding` values before the input.
+        let ih = oh * conv.stride_h + kh * conv.dilation_h;
+        let iw = ow * conv.stride_w + kw * conv.dilation_w;
+
+        let mut value = Line::empty(1).fill(EG::from_int(0));
+        if in_bounds
+            && ih >= conv.padding_h
+            && iw >= conv.padding_w
+            && ih < shape.in_h + conv.padding_h
+            && iw < shape.in_w + conv.padding_w
+        {
+            value = self.read(
+                batch * self.stride_outer
+                    + (ih - conv.padding_h) * self.stride_h
+                    + (iw - conv.padding_w) * self.stride_w
+                    + (self.group * shape.channels + c) * self.stride_channel,
+            );
+        }
+
+        value
+    }
+
+    fn read(&self, position: u32) -> Line<EG> {
+        unsafe { *(*self.tensor).index_unchecked(position) }
+    }
+}
+
+#[cube]
+/// Returns the kernel row, kernel column and channel of the position `index` within the filter
+fn filter_position(
+    index: u32,
+    kernel_h: u32,
+    kernel_w: u32,
+    channels: u32,
+    #[comptime] channels_last: bool,
+) -> (u32, u32, u32) {
+    match comptime!(channels_last) {
+        true => {
+            let rest = index / channels;
+            (rest / kernel_w, rest % kernel_w, index % channels)
+        }
+        false => {
+            let rest = index / kernel_w;
+            (rest % kernel_h, index % kernel_w, rest / kernel_h)
+        }
+    }
+}
@@ -0,0 +1,12 @@
+mod base;
+mod config;
+mod kernel;
+mod loader;
+
+pub use base::*;
+pub use config::*;
+pub use loader::*;
+
+/// Tests for convolution kernels
+#[cfg(feature = "export_tests")]
+pub mod tests;
@@ -0,0 +1,238 @@
+use std::fmt::Display;
+
+use cubecl_core::prelude::*;
+use cubecl_core::CubeElement;
+use cubecl_core::Feature;
+
+use crate::convolution::{self, Conv2dOptions, ConvLayout, ConvPass};
+use crate::matmul::tests::test_utils::assert_equals_approx;
+use crate::matmul::tests::test_utils::generate_random_data;
+
+/// Sizes of a 2D convolution test, with the total number of channels
+#[derive(Clone, Debug)]
+pub struct Conv2dProblem {
+    pub batch_size: usize,
+    pub channels: usize,
+    pub out_channels: usize,
+    pub in_size: [usize; 2],
+    pub kernel_size: [usize; 2],
+}
+
+/// Test the correctness of the given pass of the convolution on the given device,
+/// against a naive CPU implementation over the given problem
+pub fn test_conv2d<EG: Float + CubeElement + Display, R: Runtime>(
+    problem: Conv2dProblem,
+    options: Conv2dOptions,
+    pass: ConvPass,
+    disable_cmma: bool,
+    device: &R::Device,
+) {
+    let client: ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel> = R::client(device);
+
+    if !(client.properties().feature_enabled(Feature::Plane)
+        && client
+            .properties()
+            .feature_enabled(Feature::Type(EG::as_elem())))
+    {
+        // Can't execute the test.
+        return;
+    }
+
+    let shapes = Shapes::new(&problem, &options);
+    let input: Vec<EG> = generate_random_data(shapes.input.iter().product(), 1234);
+    let weight: Vec<EG> = generate_random_data(shapes.weight.iter().product(), 5678);
+    let out_grad: Vec<EG> = generate_random_data(shapes.out.iter().product(), 91011);
+
+    let (out_grad_ref, input_grad_ref, weight_grad_ref) =
+        conv2d_cpu_reference(&input, &weight, &out_grad, &shapes, &options);
+
+    let (lhs, rhs, out_shape, expected) = match pass {
+        ConvPass::Forward => (
+            (&input, &shapes.input),
+            (&weight, &shapes.weight),
+            &shapes.out,
+            out_grad_ref,
+        ),
+        ConvPass::BackwardData => (
+            (&out_grad, &shapes.out),
+            (&weight, &shapes.weight),
+            &shapes.input,
+            input_grad_ref,
+        ),
+        ConvPass::BackwardWeight => (
+            (&input, &shapes.input),
+            (&out_grad, &shapes.out),
+            &shapes.weight,
+            weight_grad_ref,
+        ),
+    };
+
+    let lhs_handle = client.create(EG::as_bytes(lhs.0));
+    let rhs_handle = client.create(EG::as_bytes(rhs.0));
+    let out_handle = client.empty(expected.len() * EG::as_elem().size());
+    let lhs_strides = contiguous_strides(lhs.1);
+    let rhs_strides = contiguous_strides(rhs.1);
+    let out_strides = contiguous_strides(out_shape);
+
+    let handle_ref = |handle, strides, shape| unsafe {
+        TensorHandleRef::<R>::from_raw_parts(handle, strides, shape, EG::as_elem().size())
+    };
+    let lhs = handle_ref(&lhs_handle, &lhs_strides, lhs.1);
+    let rhs = handle_ref(&rhs_handle, &rhs_strides, rhs.1);
+    let out = handle_ref(&out_handle, &out_strides, out_shape);
+
+    match pass {
+        ConvPass::Forward => {
+            convolution::conv2d::<R, EG>(&client, lhs, rhs, out, &options, disable_cmma)
+        }
+        ConvPass::BackwardData => convolution::conv2d_backward_data::<R, EG>(
+            &client,
+            lhs,
+            rhs,
+            out,
+            &options,
+            disable_cmma,
+        ),
+        ConvPass::BackwardWeight => convolution::conv2d_backward_weight::<R, EG>(
+            &client,
+            lhs,
+            rhs,
+            out,
+            &options,
+            disable_cmma,
+        ),
+    }
+
+    if let Err(e) = assert_equals_approx::<R, EG>(&client, out_handle, &expected, 10e-2) {
+        panic!("{}", e);
+    }
+}
+
+/// Shapes of the tensors of the convolution, in the layout of the options
+struct Shapes {
+    input: Vec<usize>,
+    weight: Vec<usize>,
+    out: Vec<usize>,
+    out_size: [usize; 2],
+}
+
+impl Shapes {
+    fn new(problem: &Conv2dProblem, options: &Conv2dOptions) -> Self {
+        let [h, w] = problem.in_size;
+        let [kh, kw] = problem.kernel_size;
+        let out_size = options
+            .output_size(problem.in_size, problem.kernel_size)
+            .unwrap();
+        let [oh, ow] = out_size;
+        let (n, c, oc) = (problem.batch_size, problem.channels, problem.out_channels);
+        let cg = c / options.groups;
+
+        match options.layout {
+            ConvLayout::Nchw => Shapes {
+                input: vec![n, c, h, w],
+                weight: vec![oc, cg, kh, kw],
+                out: vec![n, oc, oh, ow],
+                out_size,
+            },
+            ConvLayout::Nhwc => Shapes {
+                input: vec![n, h, w, c],
+                weight: vec![oc, kh, kw, cg],
+                out: vec![n, oh, ow, oc],
+                out_size,
+            },
+        }
+    }
+}
+
+fn contiguous_strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for i in (0..shape.len() - 1).rev() {
+        strides[i] = strides[i + 1] * shape[i + 1];
+    }
+    strides
+}
+
+/// Index in a contiguous tensor of the given layout of the value at `[n, h, w, c]`
+/// for activations, or at `[oc, kh, kw, c]` for weights
+fn index(shape: &[usize], [n, h, w, c]: [usize; 4], layout: ConvLayout) -> usize {
+    match layout {
+        ConvLayout::Nchw => ((n * shape[1] + c) * shape[2] + h) * shape[3] + w,
+        ConvLayout::Nhwc => ((n * shape[1] + h) * shape[2] + w) * shape[3] + c,
+    }
+}
+
+/// Computes the output of the convolution, and the gradients of the input and of the weight
+/// for the given output gradient
+///
+/// This is a naive CPU implementation, very slow on large payloads,
+/// not designed to be used for other purposes than testing.
+fn conv2d_cpu_reference<EG: Float + CubeElement>(
+    input: &[EG],
+    weight: &[EG],
+    out_grad: &[EG],
+    shapes: &Shapes,
+    options: &Conv2dOptions,
+) -> (Vec<EG>, Vec<EG>, Vec<EG>) {
+    let layout = options.layout;
+    let (batch_size, out_channels) = (shapes.out[0], shapes.weight[0]);
+    let (channels, [kernel_h, kernel_w], [in_h, in_w]) = match layout {
+        ConvLayout::Nchw => (
+            shapes.weight[1],
+            [shapes.weight[2], shapes.weight[3]],
+            [shapes.input[2], shapes.input[3]],
+        ),
+        ConvLayout::Nhwc => (
+            shapes.weight[3],
+            [shapes.weight[1], shapes.weight[2]],
+            [shapes.input[1], shapes.input[2]],
+        ),
+    };
+    let out_channels_per_group = out_channels / options.groups;
+    let [out_h, out_w] = shapes.out_size;
+
+    let mut out = vec![0f32; out_grad.len()];
+    let mut input_grad = vec![0f32; input.len()];
+    let mut weight_grad = vec![0f32; weight.len()];
+
+    for n in 0..batch_size {
+        for oh in 0..out_h {
+            for ow in 0..out_w {
+                for oc in 0..out_channels {
+                    let group = oc / out_channels_per_group;
+                    let out_index = index(&shapes.out, [n, oh, ow, oc], layout);
+
+                    for kh in 0..kernel_h {
+                        for kw in 0..kernel_w {
+                            let ih = (oh * options.stride[0] + kh * options.dilation[0])
+                                .checked_sub(options.padding[0])
+                                .filter(|ih| *ih < in_h);
+                            let iw = (ow * options.stride[1] + kw * options.dilation[1])
+                                .checked_sub(options.padding[1])
+                                .filter(|iw| *iw < in_w);
+                            let (Some(ih), Some(iw)) = (ih, iw) else {
+                                continue;
+                            };
+
+                            for c in 0..channels {
+                                let input_index =
+                                    index(&shapes.input, [n, ih, iw, group * channels + c], layout);
+                                let weight_index = index(&shapes.weight, [oc, kh, kw, c], layout);
+
+                                let x = input[input_index].to_f32().unwrap();
+                                let w = weight[weight_index].to_f32().unwrap();
+                                let dy = out_grad[out_index].to_f32().unwrap();
+
+                                out[out_index] += x * w;
+                                input_grad[input_index] += dy * w;
+                                weight_grad[weight_index] += dy * x;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let cast = |values: Vec<f32>| values.into_iter().map(EG::new).collect();
+    (cast(out), cast(input_grad), cast(weight_grad))
+}
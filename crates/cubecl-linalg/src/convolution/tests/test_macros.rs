@@ -0,0 +1,121 @@
+#[macro_export]
+macro_rules! testgen_conv2d {
+    () => {
+        mod conv2d {
+            $crate::testgen_conv2d!(f32);
+        }
+    };
+
+    ($float:ident) => {
+        use super::*;
+        use cubecl_linalg::convolution::tests::conv2d_test_launcher::{test_conv2d, Conv2dProblem};
+        use cubecl_linalg::convolution::{Conv2dOptions, ConvLayout, ConvPass};
+
+        pub type FloatT = $float;
+
+        fn cases() -> Vec<(Conv2dProblem, Conv2dOptions)> {
+            vec![
+                (
+                    Conv2dProblem {
+                        batch_size: 2,
+                        channels: 4,
+                        out_channels: 6,
+                        in_size: [9, 7],
+                        kernel_size: [3, 3],
+                    },
+                    Conv2dOptions {
+                        stride: [2, 1],
+                        padding: [1, 1],
+                        dilation: [1, 2],
+                        groups: 1,
+                        layout: ConvLayout::Nchw,
+                    },
+                ),
+                (
+                    Conv2dProblem {
+                        batch_size: 2,
+                        channels: 8,
+                        out_channels: 16,
+                        in_size: [6, 10],
+                        kernel_size: [3, 2],
+                    },
+                    Conv2dOptions {
+                        stride: [1, 2],
+                        padding: [2, 1],
+                        dilation: [2, 1],
+                        groups: 2,
+                        layout: ConvLayout::Nhwc,
+                    },
+                ),
+                (
+                    Conv2dProblem {
+                        batch_size: 1,
+                        channels: 6,
+                        out_channels: 9,
+                        in_size: [17, 17],
+                        kernel_size: [5, 5],
+                    },
+                    Conv2dOptions {
+                        stride: [2, 2],
+                        padding: [2, 2],
+                        dilation: [1, 1],
+                        groups: 3,
+                        layout: ConvLayout::Nchw,
+                    },
+                ),
+            ]
+        }
+
+        #[test]
+        pub fn test_conv2d_forward() {
+            for (problem, options) in cases() {
+                test_conv2d::<FloatT, TestRuntime>(
+                    problem,
+                    options,
+                    ConvPass::Forward,
+                    false,
+                    &Default::default(),
+                );
+            }
+        }
+
+        #[test]
+        pub fn test_conv2d_backward_data() {
+            for (problem, options) in cases() {
+                test_conv2d::<FloatT, TestRuntime>(
+                    problem,
+                    options,
+                    ConvPass::BackwardData,
+                    false,
+                    &Default::default(),
+                );
+            }
+        }
+
+        #[test]
+        pub fn test_conv2d_backward_weight() {
+            for (problem, options) in cases() {
+                test_conv2d::<FloatT, TestRuntime>(
+                    problem,
+                    options,
+                    ConvPass::BackwardWeight,
+                    false,
+                    &Default::default(),
+                );
+            }
+        }
+    };
+
+    ([$($float:ident),*]) => {
+        mod conv2d {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_conv2d!($float);
+                })*
+            }
+        }
+    };
+}
@@ -1,3 +1,9 @@
+/// Contains attention kernels built on the matmul components
+pub mod attention;
+
+/// Contains convolution kernels built on the matmul components
+pub mod convolution;
+
 /// Contains matmul kernels and Cube components
 pub mod matmul;
 
@@ -8,14 +8,25 @@ use crate::tensor::TensorHandle;
 
 use super::kernels::{
     cmma_old::{self, config::PredefinedCmmaConfig, is_available, CmmaConfig},
-    matmul,
+    gemv, matmul,
     tiling2d::{self, Tiling2dConfig},
 };
+use super::tune::launch_autotune;
 
 #[derive(Debug)]
 pub enum Strategy {
+    /// Benchmark the available kernels the first time a problem shape is seen,
+    /// and use the fastest one for all problems of similar shapes.
+    /// Matrix-vector products (m == 1 or n == 1) are computed by the
+    /// [gemv](super::kernels::gemv) kernel instead.
+    Auto,
     Accelerated,
     PlaneMma,
+    /// Accelerated, with the k dimension of each output stage split in the given number of ranges
+    SplitK(u32),
+    /// Accelerated, with the iterations along k of all output stages
+    /// distributed evenly over the given number of cubes
+    StreamK(u32),
     CmmaOld(CmmaConfig),
     Tiling2D(Tiling2dConfig),
 }
@@ -28,13 +39,58 @@ pub fn launch<R: Runtime, EG: Float>(
     out: TensorHandle<R, EG>,
 ) {
     match strategy {
+        // Tiles would mostly be wasted on matrix-vector products.
+        Strategy::Auto if gemv::is_available::<R>(client, &lhs.shape, &rhs.shape) => {
+            gemv::launch::<R, EG, f32>(client, lhs, rhs, out)
+        }
+        Strategy::Auto => launch_autotune::<R, EG>(client, lhs, rhs, out),
         Strategy::Accelerated => matmul::launch(client, lhs, rhs, out, false),
         Strategy::PlaneMma => matmul::launch(client, lhs, rhs, out, true),
+        Strategy::SplitK(splits) => launch_decomposition(
+            client,
+            lhs,
+            rhs,
+            out,
+            matmul::Decomposition::SplitK { splits: *splits },
+        ),
+        Strategy::StreamK(cubes) => launch_decomposition(
+            client,
+            lhs,
+            rhs,
+            out,
+            matmul::Decomposition::StreamK { cubes: *cubes },
+        ),
         Strategy::CmmaOld(config) => cmma_old::launch(client, lhs, rhs, out, config.clone()),
         Strategy::Tiling2D(config) => tiling2d::launch(client, lhs, rhs, out, config.clone()),
     };
 }
 
+/// Launch the accelerated matmul, with the work partitioned between cubes according to the
+/// decomposition.
+fn launch_decomposition<R: Runtime, EG: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandle<R, EG>,
+    rhs: TensorHandle<R, EG>,
+    out: TensorHandle<R, EG>,
+    decomposition: matmul::Decomposition,
+) -> TensorHandle<R, EG> {
+    matmul::launch_ref_default_precision::<R, EG>(
+        client,
+        lhs.as_ref(),
+        rhs.as_ref(),
+        out.as_ref(),
+        matmul::MatmulOptions {
+            selection: matmul::MatmulSelection {
+                decomposition,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .expect("Should launch without an epilogue to check");
+    out
+}
+
 pub fn launch_ref<R: Runtime, EG: Float>(
     client: &ComputeClient<R::Server, R::Channel>,
     lhs: TensorHandleRef<R>,
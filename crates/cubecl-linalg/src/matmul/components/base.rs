@@ -1,5 +1,6 @@
 use cubecl_core::prelude::*;
 
+use crate::matmul::components::global::EpilogueInputsLaunch;
 use crate::matmul::kernels::matmul::AdvancedConfig;
 
 use super::{config::MatmulConfig, MatmulProblem};
@@ -33,13 +34,23 @@ pub trait MatmulLaunch<I: Numeric, O: Numeric>: MatmulKernel<I, O> {
     /// # Safety
     ///
     /// Out-of-bounds can happen
-    unsafe fn launch_unchecked<R: Runtime>(
+    unsafe fn launch_unchecked<'a, R: Runtime>(
         client: &ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel>,
         cube_dim: CubeDim,
         cube_count: CubeCount,
-        lhs: TensorArg<'_, R>,
-        rhs: TensorArg<'_, R>,
-        out: TensorArg<'_, R>,
+        args: MatmulLaunchArgs<'a, O, R>,
         config: <Self as MatmulKernel<I, O>>::Config,
     );
 }
+
+/// Tensors given to a matmul launch
+pub struct MatmulLaunchArgs<'a, O: Numeric, R: Runtime> {
+    /// Left hand side of the matmul
+    pub lhs: TensorArg<'a, R>,
+    /// Right hand side of the matmul
+    pub rhs: TensorArg<'a, R>,
+    /// Output of the matmul
+    pub out: TensorArg<'a, R>,
+    /// Inputs of the epilogue applied to the output
+    pub epilogue: EpilogueInputsLaunch<'a, O, R>,
+}
@@ -2,9 +2,8 @@ use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
 use crate::matmul::components::batch;
-use crate::matmul::components::{
-    config::MatmulConfig, global, Ident, MatmulKernel, MatmulLaunch, StageDim,
-};
+use crate::matmul::components::global::EpilogueInputs;
+use crate::matmul::components::{config::MatmulConfig, global, Ident, MatmulKernel, StageDim};
 
 #[cube]
 /// Provides matrix multiplication operations at the batch level.
@@ -14,6 +13,7 @@ use crate::matmul::components::{
 ///  - All Cubes can collaborate to solve the problem
 ///  - Dimensions M, N and K can be arbitrary large,
 ///    as well as the number of batches.
+///  - Lhs, rhs and out may have different element types.
 ///
 /// # Assumptions
 /// - Line sizes of the inputs evenly divide the dimension they are aligned with.
@@ -24,14 +24,15 @@ use crate::matmul::components::{
 /// It is not assumed that the matmul's dimensions match its inputs dimensions perfectly.
 /// It is therefore important to use an underlying global matmul that performs check bounds,
 /// and to not launch more Cubes than necessary.
-pub trait Matmul<EG: Numeric>:
-    'static + Send + Sync + MatmulKernel<EG, EG, Config: Config> + MatmulLaunch<EG, EG>
+pub trait Matmul<EL: Numeric, ER: Numeric, EO: Numeric>:
+    'static + Send + Sync + MatmulKernel<EL, EO, Config: Config>
 {
     /// Performs batchwise matrix multiplication over tensors.
     fn execute(
-        lhs: &Tensor<Line<EG>>,
-        rhs: &Tensor<Line<EG>>,
-        out: &mut Tensor<Line<EG>>,
+        lhs: &Tensor<Line<EL>>,
+        rhs: &Tensor<Line<ER>>,
+        out: &mut Tensor<Line<EO>>,
+        epilogue: &EpilogueInputs<EO>,
         #[comptime] config: Self::Config,
     );
 }
@@ -58,11 +59,12 @@ pub trait Config: MatmulConfig {
 }
 
 #[cube(launch_unchecked)]
-pub(crate) fn launch<EG: Numeric, BMM: batch::Matmul<EG>>(
-    lhs: &Tensor<Line<EG>>,
-    rhs: &Tensor<Line<EG>>,
-    out: &mut Tensor<Line<EG>>,
+pub(crate) fn launch<EL: Numeric, ER: Numeric, EO: Numeric, BMM: batch::Matmul<EL, ER, EO>>(
+    lhs: &Tensor<Line<EL>>,
+    rhs: &Tensor<Line<ER>>,
+    out: &mut Tensor<Line<EO>>,
+    epilogue: &EpilogueInputs<EO>,
     #[comptime] config: BMM::Config,
 ) {
-    BMM::execute(lhs, rhs, out, config);
+    BMM::execute(lhs, rhs, out, epilogue, config);
 }
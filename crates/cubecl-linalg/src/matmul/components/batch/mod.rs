@@ -1,11 +1,16 @@
 pub mod one_to_many;
 pub mod one_to_one;
+pub mod quantized;
+pub mod split_k;
+pub mod stream_k;
 
 mod base;
 mod cube_dispatch;
+mod partial_sums;
 mod shared;
 mod span;
 
 pub use base::*;
 pub use cube_dispatch::*;
+pub use partial_sums::{PartialSums, PartialSumsMatmul, WithPartialSums};
 pub use span::*;
@@ -1,9 +1,11 @@
 use std::marker::PhantomData;
 
 use crate::matmul::components::batch::span::{Span, SpanDim, SpanMatmul};
+use crate::matmul::components::global::EpilogueInputs;
 use crate::matmul::components::MatmulProblem;
 use crate::matmul::components::{
-    batch, config::MatmulConfig, global, Ident, MatmulKernel, MatmulLaunch, StageDim,
+    batch, config::MatmulConfig, global, Ident, MatmulKernel, MatmulLaunch, MatmulLaunchArgs,
+    StageDim,
 };
 use crate::matmul::kernels::matmul::AdvancedConfig;
 use cubecl_core as cubecl;
@@ -17,13 +19,17 @@ use super::{Config as _, CubeDispatch};
 /// The algorithm supports any number of cubes,
 /// looping as needed to process all data.
 pub struct Matmul<
-    EG: Numeric,
+    EL: Numeric,
+    ER: Numeric,
+    EO: Numeric,
     ES: Numeric,
-    GMM: global::Matmul<EG, ES>,
+    GMM: global::Matmul<EL, ER, EO, ES>,
     S: SpanMatmul,
     C: CubeDispatch,
 > {
-    _eg: PhantomData<EG>,
+    _el: PhantomData<EL>,
+    _er: PhantomData<ER>,
+    _eo: PhantomData<EO>,
     _es: PhantomData<ES>,
     _gmm: PhantomData<GMM>,
     _s: PhantomData<S>,
@@ -31,13 +37,21 @@ pub struct Matmul<
 }
 
 #[cube]
-impl<EG: Numeric, ES: Numeric, GMM: global::Matmul<EG, ES>, S: SpanMatmul, C: CubeDispatch>
-    batch::Matmul<EG> for Matmul<EG, ES, GMM, S, C>
+impl<
+        EL: Numeric,
+        ER: Numeric,
+        EO: Numeric,
+        ES: Numeric,
+        GMM: global::Matmul<EL, ER, EO, ES>,
+        S: SpanMatmul,
+        C: CubeDispatch,
+    > batch::Matmul<EL, ER, EO> for Matmul<EL, ER, EO, ES, GMM, S, C>
 {
     fn execute(
-        lhs: &Tensor<Line<EG>>,
-        rhs: &Tensor<Line<EG>>,
-        out: &mut Tensor<Line<EG>>,
+        lhs: &Tensor<Line<EL>>,
+        rhs: &Tensor<Line<ER>>,
+        out: &mut Tensor<Line<EO>>,
+        epilogue: &EpilogueInputs<EO>,
         #[comptime] config: Self::Config,
     ) {
         let rank = out.rank();
@@ -68,12 +82,19 @@ impl<EG: Numeric, ES: Numeric, GMM: global::Matmul<EG, ES>, S: SpanMatmul, C: Cu
 
         let gmm_config = config.to_gmm_config();
         let acc = GMM::init_accumulator(gmm_config);
-        S::execute::<EG, ES, GMM>(lhs, rhs, out, span, acc, k_range, gmm_config);
+        S::execute::<EL, ER, EO, ES, GMM>(lhs, rhs, out, epilogue, span, acc, k_range, gmm_config);
     }
 }
 
-impl<EG: Numeric, ES: Numeric, GMM: global::Matmul<EG, ES>, S: SpanMatmul, C: CubeDispatch>
-    MatmulKernel<EG, EG> for Matmul<EG, ES, GMM, S, C>
+impl<
+        EL: Numeric,
+        ER: Numeric,
+        EO: Numeric,
+        ES: Numeric,
+        GMM: global::Matmul<EL, ER, EO, ES>,
+        S: SpanMatmul,
+        C: CubeDispatch,
+    > MatmulKernel<EL, EO> for Matmul<EL, ER, EO, ES, GMM, S, C>
 {
     type Config = Config<GMM::Config, C>;
 
@@ -104,21 +125,33 @@ impl<EG: Numeric, ES: Numeric, GMM: global::Matmul<EG, ES>, S: SpanMatmul, C: Cu
     }
 }
 
-impl<EG: Numeric, ES: Numeric, GMM: global::Matmul<EG, ES>, S: SpanMatmul, C: CubeDispatch>
-    MatmulLaunch<EG, EG> for Matmul<EG, ES, GMM, S, C>
+impl<
+        EL: Numeric,
+        ER: Numeric,
+        EO: Numeric,
+        ES: Numeric,
+        GMM: global::Matmul<EL, ER, EO, ES>,
+        S: SpanMatmul,
+        C: CubeDispatch,
+    > MatmulLaunch<EL, EO> for Matmul<EL, ER, EO, ES, GMM, S, C>
 {
-    unsafe fn launch_unchecked<R: Runtime>(
+    unsafe fn launch_unchecked<'a, R: Runtime>(
         client: &ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel>,
         cube_dim: CubeDim,
         cube_count: CubeCount,
-        lhs: TensorArg<'_, R>,
-        rhs: TensorArg<'_, R>,
-        out: TensorArg<'_, R>,
+        args: MatmulLaunchArgs<'a, EO, R>,
         config: Self::Config,
     ) {
         Self::check_config(config);
-        super::launch::launch_unchecked::<EG, Self, R>(
-            client, cube_count, cube_dim, lhs, rhs, out, config,
+        super::launch::launch_unchecked::<EL, ER, EO, Self, R>(
+            client,
+            cube_count,
+            cube_dim,
+            args.lhs,
+            args.rhs,
+            args.out,
+            args.epilogue,
+            config,
         );
     }
 }
@@ -1,9 +1,11 @@
 use std::marker::PhantomData;
 
 use crate::matmul::components::batch::shared::gmm_execute;
+use crate::matmul::components::global::EpilogueInputs;
 use crate::matmul::components::MatmulProblem;
 use crate::matmul::components::{
-    batch, config::MatmulConfig, global, Ident, MatmulKernel, MatmulLaunch, StageDim,
+    batch, config::MatmulConfig, global, Ident, MatmulKernel, MatmulLaunch, MatmulLaunchArgs,
+    StageDim,
 };
 use crate::matmul::kernels::matmul::AdvancedConfig;
 use cubecl_core as cubecl;
@@ -16,21 +18,37 @@ use super::{Config as _, CubeDispatch};
 ///
 /// Note: This algorithm requires one cube per global matmul;
 /// insufficient cubes will result in incomplete computations.
-pub struct Matmul<EG: Numeric, ES: Numeric, GMM: global::Matmul<EG, ES>, C: CubeDispatch> {
-    _eg: PhantomData<EG>,
+pub struct Matmul<
+    EL: Numeric,
+    ER: Numeric,
+    EO: Numeric,
+    ES: Numeric,
+    GMM: global::Matmul<EL, ER, EO, ES>,
+    C: CubeDispatch,
+> {
+    _el: PhantomData<EL>,
+    _er: PhantomData<ER>,
+    _eo: PhantomData<EO>,
     _es: PhantomData<ES>,
     _gmm: PhantomData<GMM>,
     _c: PhantomData<C>,
 }
 
 #[cube]
-impl<EG: Numeric, ES: Numeric, GMM: global::Matmul<EG, ES>, C: CubeDispatch> batch::Matmul<EG>
-    for Matmul<EG, ES, GMM, C>
+impl<
+        EL: Numeric,
+        ER: Numeric,
+        EO: Numeric,
+        ES: Numeric,
+        GMM: global::Matmul<EL, ER, EO, ES>,
+        C: CubeDispatch,
+    > batch::Matmul<EL, ER, EO> for Matmul<EL, ER, EO, ES, GMM, C>
 {
     fn execute(
-        lhs: &Tensor<Line<EG>>,
-        rhs: &Tensor<Line<EG>>,
-        out: &mut Tensor<Line<EG>>,
+        lhs: &Tensor<Line<EL>>,
+        rhs: &Tensor<Line<ER>>,
+        out: &mut Tensor<Line<EO>>,
+        epilogue: &EpilogueInputs<EO>,
         #[comptime] config: Self::Config,
     ) {
         let (x_index, y_index) = C::x_y_indices();
@@ -40,13 +58,14 @@ impl<EG: Numeric, ES: Numeric, GMM: global::Matmul<EG, ES>, C: CubeDispatch> bat
         let k_range = (0, lhs.shape(lhs.rank() - 1));
 
         let gmm_config = config.to_gmm_config();
-        gmm_execute::<EG, ES, GMM>(
+        gmm_execute::<EL, ER, EO, ES, GMM>(
             lhs,
             rhs,
             out,
             x_offset,
             y_offset,
             nth_batch,
+            epilogue,
             &mut GMM::init_accumulator(gmm_config),
             k_range,
             gmm_config,
@@ -54,8 +73,14 @@ impl<EG: Numeric, ES: Numeric, GMM: global::Matmul<EG, ES>, C: CubeDispatch> bat
     }
 }
 
-impl<EG: Numeric, ES: Numeric, GMM: global::Matmul<EG, ES>, C: CubeDispatch> MatmulKernel<EG, EG>
-    for Matmul<EG, ES, GMM, C>
+impl<
+        EL: Numeric,
+        ER: Numeric,
+        EO: Numeric,
+        ES: Numeric,
+        GMM: global::Matmul<EL, ER, EO, ES>,
+        C: CubeDispatch,
+    > MatmulKernel<EL, EO> for Matmul<EL, ER, EO, ES, GMM, C>
 {
     type Config = Config<GMM::Config, C>;
 
@@ -86,21 +111,32 @@ impl<EG: Numeric, ES: Numeric, GMM: global::Matmul<EG, ES>, C: CubeDispatch> Mat
     }
 }
 
-impl<EG: Numeric, ES: Numeric, GMM: global::Matmul<EG, ES>, C: CubeDispatch> MatmulLaunch<EG, EG>
-    for Matmul<EG, ES, GMM, C>
+impl<
+        EL: Numeric,
+        ER: Numeric,
+        EO: Numeric,
+        ES: Numeric,
+        GMM: global::Matmul<EL, ER, EO, ES>,
+        C: CubeDispatch,
+    > MatmulLaunch<EL, EO> for Matmul<EL, ER, EO, ES, GMM, C>
 {
-    unsafe fn launch_unchecked<R: Runtime>(
+    unsafe fn launch_unchecked<'a, R: Runtime>(
         client: &ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel>,
         cube_dim: CubeDim,
         cube_count: CubeCount,
-        lhs: TensorArg<'_, R>,
-        rhs: TensorArg<'_, R>,
-        out: TensorArg<'_, R>,
+        args: MatmulLaunchArgs<'a, EO, R>,
         config: Self::Config,
     ) {
         Self::check_config(config);
-        super::launch::launch_unchecked::<EG, Self, R>(
-            client, cube_count, cube_dim, lhs, rhs, out, config,
+        super::launch::launch_unchecked::<EL, ER, EO, Self, R>(
+            client,
+            cube_count,
+            cube_dim,
+            args.lhs,
+            args.rhs,
+            args.out,
+            args.epilogue,
+            config,
         );
     }
 }
@@ -0,0 +1,248 @@
+use std::marker::PhantomData;
+
+use cubecl_core as cubecl;
+use cubecl_core::calculate_cube_count_elemwise;
+use cubecl_core::prelude::*;
+
+use crate::matmul::components::global::tensor_view::batch_offset;
+use crate::matmul::components::global::{
+    self, Epilogue, EpilogueInputs, EpilogueInputsLaunch, FusedEpilogue,
+};
+use crate::matmul::components::{
+    batch, Ident, MatmulKernel, MatmulLaunch, MatmulLaunchArgs, MatmulProblem,
+};
+use crate::matmul::kernels::matmul::AdvancedConfig;
+
+/// Describes which cubes computed the partial sums of each stage of the output.
+///
+/// The k dimension of each stage is divided in `iters_per_stage` iterations, and the iterations
+/// of all stages are given in order to the cubes, `iters_per_cube` at a time. The partial sums of
+/// a stage are therefore computed by cubes `first..=last`, where
+/// `first = nth_stage * iters_per_stage / iters_per_cube` and
+/// `last = ((nth_stage + 1) * iters_per_stage - 1) / iters_per_cube`.
+///
+/// The partial sum of the cube `first + j` is stored in the batch `nth_batch * num_slots + j`
+/// of the workspace.
+pub struct PartialSums {
+    pub num_slots: u32,
+    pub iters_per_stage: u32,
+    pub iters_per_cube: u32,
+}
+
+/// Batch matmul writing partial sums, as described by [PartialSums], to a workspace of the
+/// accumulator type `EA`.
+pub trait PartialSumsMatmul<EL: Numeric, ER: Numeric, EA: Numeric>:
+    batch::Matmul<EL, ER, EA>
+{
+    /// Config of the global matmul, with the epilogue of the problem
+    type ReduceConfig: global::Config;
+
+    /// Returns which cubes compute the partial sums of each stage
+    fn partial_sums(config: Self::Config, lhs_shape: &[usize], out_shape: &[usize]) -> PartialSums;
+
+    /// Returns the config used to sum the partial sums and apply the epilogue
+    fn reduce_config(config: Self::Config) -> Self::ReduceConfig;
+}
+
+/// Launches a [PartialSumsMatmul], followed by the kernel summing its partial sums into the
+/// output, which also applies the epilogue.
+///
+/// Partial sums are stored and summed as `EA`, and only the final sum is cast to `EO`.
+pub struct WithPartialSums<EL, ER, EA, EO, BMM> {
+    _el: PhantomData<EL>,
+    _er: PhantomData<ER>,
+    _ea: PhantomData<EA>,
+    _eo: PhantomData<EO>,
+    _bmm: PhantomData<BMM>,
+}
+
+impl<EL, ER, EA, EO, BMM> MatmulKernel<EL, EO> for WithPartialSums<EL, ER, EA, EO, BMM>
+where
+    EL: Numeric,
+    ER: Numeric,
+    EA: Numeric,
+    EO: Numeric,
+    BMM: PartialSumsMatmul<EL, ER, EA>,
+{
+    type Config = <BMM as MatmulKernel<EL, EA>>::Config;
+
+    fn check_config(config: Self::Config) {
+        BMM::check_config(config)
+    }
+
+    fn check_availability<R: Runtime>(
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) -> Result<(), &str> {
+        BMM::check_availability::<R>(client)
+    }
+
+    fn make_config(
+        problem: &MatmulProblem,
+        cube_dim: &CubeDim,
+        cube_count: &CubeCount,
+        advanced_config: &AdvancedConfig,
+    ) -> Self::Config {
+        BMM::make_config(problem, cube_dim, cube_count, advanced_config)
+    }
+}
+
+impl<EL, ER, EA, EO, BMM> MatmulLaunch<EL, EO> for WithPartialSums<EL, ER, EA, EO, BMM>
+where
+    EL: Numeric,
+    ER: Numeric,
+    EA: Numeric,
+    EO: Numeric,
+    BMM: PartialSumsMatmul<EL, ER, EA>,
+{
+    unsafe fn launch_unchecked<'a, R: Runtime>(
+        client: &ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel>,
+        cube_dim: CubeDim,
+        cube_count: CubeCount,
+        args: MatmulLaunchArgs<'a, EO, R>,
+        config: Self::Config,
+    ) {
+        Self::check_config(config);
+
+        let MatmulLaunchArgs {
+            lhs,
+            rhs,
+            out,
+            epilogue,
+        } = args;
+        let (lhs_shape, _) = tensor_parts(&lhs);
+        let (shape, line_size) = tensor_parts(&out);
+        let partial_sums = BMM::partial_sums(config, lhs_shape, shape);
+
+        let rank = shape.len();
+        let num_batches: usize = shape[..rank - 2].iter().product();
+        let workspace_shape = [
+            num_batches * partial_sums.num_slots as usize,
+            shape[rank - 2],
+            shape[rank - 1],
+        ];
+        let workspace_strides = [shape[rank - 2] * shape[rank - 1], shape[rank - 1], 1];
+        let workspace =
+            client.empty(workspace_shape.iter().product::<usize>() * EA::as_elem().size());
+
+        // The partial sums are written without the epilogue, whose tensors are never read.
+        let placeholder = client.empty(EA::as_elem().size());
+
+        super::launch::launch_unchecked::<EL, ER, EA, BMM, R>(
+            client,
+            cube_count,
+            cube_dim,
+            lhs,
+            rhs,
+            TensorArg::from_raw_parts::<EA>(
+                &workspace,
+                &workspace_strides,
+                &workspace_shape,
+                line_size,
+            ),
+            EpilogueInputsLaunch::new(
+                ScalarArg::new(1.),
+                ScalarArg::new(1.),
+                TensorArg::from_raw_parts::<EA>(&placeholder, &[1], &[1], 1),
+                TensorArg::from_raw_parts::<EA>(&placeholder, &[1], &[1], 1),
+                TensorArg::from_raw_parts::<EA>(&placeholder, &[1], &[1], 1),
+            ),
+            config,
+        );
+
+        let num_lines = num_batches * shape[rank - 2] * shape[rank - 1] / line_size as usize;
+        let reduce_cube_dim = CubeDim::default();
+
+        // Rebuilt so that its lifetime can be shortened to the one of the workspace.
+        let epilogue = EpilogueInputsLaunch::new(
+            epilogue.alpha,
+            epilogue.beta,
+            epilogue.c,
+            epilogue.bias,
+            epilogue.residual,
+        );
+
+        reduce_partial_sums::launch_unchecked::<EA, EO, BMM::ReduceConfig, R>(
+            client,
+            calculate_cube_count_elemwise(num_lines, reduce_cube_dim),
+            reduce_cube_dim,
+            TensorArg::from_raw_parts::<EA>(
+                &workspace,
+                &workspace_strides,
+                &workspace_shape,
+                line_size,
+            ),
+            out,
+            epilogue,
+            ScalarArg::new(partial_sums.num_slots),
+            ScalarArg::new(partial_sums.iters_per_stage),
+            ScalarArg::new(partial_sums.iters_per_cube),
+            BMM::reduce_config(config),
+        );
+    }
+}
+
+/// Returns the shape and the line size of a tensor argument of the matmul
+fn tensor_parts<'a, R: Runtime>(tensor: &TensorArg<'a, R>) -> (&'a [usize], u8) {
+    match tensor {
+        TensorArg::Handle {
+            handle,
+            vectorization_factor,
+        } => (handle.shape, *vectorization_factor),
+        TensorArg::Alias { .. } => {
+            panic!("Matmuls with partial sums don't support aliased tensors")
+        }
+    }
+}
+
+#[cube(launch_unchecked)]
+/// Sums the partial sums of each line of the output, as described by [PartialSums],
+/// and writes it to the output after applying the epilogue.
+fn reduce_partial_sums<EA: Numeric, EO: Numeric, G: global::Config>(
+    partials: &Tensor<Line<EA>>,
+    out: &mut Tensor<Line<EO>>,
+    epilogue: &EpilogueInputs<EO>,
+    num_slots: u32,
+    iters_per_stage: u32,
+    iters_per_cube: u32,
+    #[comptime] config: G,
+) {
+    let line_size = config.global_line_size(Ident::Out);
+    let rank = out.rank();
+    let shape_x = out.shape(rank - 2);
+    let shape_y = out.shape(rank - 1);
+    let num_lines_y = shape_y / line_size;
+    let num_batches = partials.shape(0) / num_slots;
+
+    if ABSOLUTE_POS < num_batches * shape_x * num_lines_y {
+        let view_x = (ABSOLUTE_POS / num_lines_y) % shape_x;
+        let view_y = (ABSOLUTE_POS % num_lines_y) * line_size;
+        let nth_batch = ABSOLUTE_POS / (num_lines_y * shape_x);
+
+        let stage_x = config.stage_dim(Ident::Out).height();
+        let stage_y = config.stage_dim(Ident::Out).width();
+        let num_stages_x = (shape_x + stage_x - 1) / stage_x;
+        let num_stages_y = (shape_y + stage_y - 1) / stage_y;
+        let nth_stage =
+            (nth_batch * num_stages_x + view_x / stage_x) * num_stages_y + view_y / stage_y;
+
+        let first = nth_stage * iters_per_stage / iters_per_cube;
+        let last = ((nth_stage + 1) * iters_per_stage - 1) / iters_per_cube;
+
+        let offset = view_x * partials.stride(1) + view_y;
+        let mut sum = Line::<EA>::empty(line_size).fill(EA::from_int(0));
+
+        for slot in 0..last - first + 1 {
+            let position = (nth_batch * num_slots + slot) * partials.stride(0) + offset;
+            sum += partials[position / line_size];
+        }
+
+        let fused = FusedEpilogue::<EO>::new(epilogue, nth_batch);
+        let value =
+            FusedEpilogue::<EO>::apply::<G>(&fused, Line::cast_from(sum), view_x, view_y, config);
+
+        let position = batch_offset(out, nth_batch)
+            + view_x * out.stride(rank - 2)
+            + view_y * out.stride(rank - 1);
+        out[position / line_size] = value;
+    }
+}
@@ -0,0 +1,59 @@
+use crate::matmul::components::global::homogeneous;
+use crate::matmul::components::global::quantized::{self, QuantizationConfig, QuantizationInputs};
+use crate::matmul::components::global::{Config as _, EpilogueInputs};
+use crate::matmul::components::stage::{
+    self,
+    multi_buffer::{LhsReader, RhsReader},
+};
+use crate::matmul::components::Ident;
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube(launch_unchecked)]
+/// Executes matrix multiplication with a quantized rhs at the batch level,
+/// assigning each cube to a single global matmul.
+///
+/// Cubes are dispatched like in the one-to-one batch matmul with the natural dispatch.
+pub(crate) fn launch<
+    EL: Numeric,
+    EQ: Numeric,
+    ESc: Numeric,
+    EO: Numeric,
+    ES: Numeric,
+    SMM: stage::Matmul<ES, EO, LhsReader = LhsReader<ES>, RhsReader = RhsReader<ES>>,
+>(
+    lhs: &Tensor<Line<EL>>,
+    rhs: &Tensor<Line<EQ>>,
+    quantization_inputs: &QuantizationInputs<ESc>,
+    out: &mut Tensor<Line<EO>>,
+    epilogue: &EpilogueInputs<EO>,
+    #[comptime] quantization: QuantizationConfig,
+    #[comptime] config: homogeneous::Config<SMM::Config>,
+) {
+    let x_offset = CUBE_POS_X * config.stage_dim(Ident::Lhs).height();
+    let y_offset = CUBE_POS_Y * config.stage_dim(Ident::Rhs).width();
+    let nth_batch = CUBE_POS_Z;
+    let k_range = (0, lhs.shape(lhs.rank() - 1));
+
+    quantized::Matmul::<EL, EQ, ESc, EO, ES, SMM>::execute(
+        quantized::Matmul::<EL, EQ, ESc, EO, ES, SMM>::init_lhs_loader(
+            lhs, x_offset, 0, nth_batch, config,
+        ),
+        quantized::Matmul::<EL, EQ, ESc, EO, ES, SMM>::init_rhs_loader(
+            rhs,
+            quantization_inputs,
+            0,
+            y_offset,
+            nth_batch,
+            quantization,
+            config,
+        ),
+        quantized::Matmul::<EL, EQ, ESc, EO, ES, SMM>::init_unloader(
+            out, x_offset, y_offset, nth_batch, epilogue,
+        ),
+        &mut quantized::Matmul::<EL, EQ, ESc, EO, ES, SMM>::init_accumulator(config),
+        k_range,
+        quantization,
+        config,
+    );
+}
@@ -2,17 +2,25 @@ use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
 use crate::matmul::components::global;
+use crate::matmul::components::global::EpilogueInputs;
 
 #[cube]
 /// Execute global matmul on lhs, rhs, writing in out.
 /// x and y offsets are absolute rows and columns
-pub(crate) fn gmm_execute<EG: Numeric, ES: Numeric, GMM: global::Matmul<EG, ES>>(
-    lhs: &Tensor<Line<EG>>,
-    rhs: &Tensor<Line<EG>>,
-    out: &mut Tensor<Line<EG>>,
+pub(crate) fn gmm_execute<
+    EL: Numeric,
+    ER: Numeric,
+    EO: Numeric,
+    ES: Numeric,
+    GMM: global::Matmul<EL, ER, EO, ES>,
+>(
+    lhs: &Tensor<Line<EL>>,
+    rhs: &Tensor<Line<ER>>,
+    out: &mut Tensor<Line<EO>>,
     x_offset: u32,
     y_offset: u32,
     nth_batch: u32,
+    epilogue: &EpilogueInputs<EO>,
     acc: &mut GMM::Accumulator,
     k_range: (u32, u32),
     #[comptime] config: GMM::Config,
@@ -20,7 +28,7 @@ pub(crate) fn gmm_execute<EG: Numeric, ES: Numeric, GMM: global::Matmul<EG, ES>>
     GMM::execute(
         GMM::init_lhs_loader(lhs, x_offset, k_range.0, nth_batch, config),
         GMM::init_rhs_loader(rhs, k_range.0, y_offset, nth_batch, config),
-        GMM::init_unloader(out, x_offset, y_offset, nth_batch),
+        GMM::init_unloader(out, x_offset, y_offset, nth_batch, epilogue),
         acc,
         k_range,
         config,
@@ -0,0 +1,220 @@
+use std::marker::PhantomData;
+
+use crate::matmul::components::batch::partial_sums::{PartialSums, PartialSumsMatmul};
+use crate::matmul::components::global::EpilogueInputs;
+use crate::matmul::components::MatmulProblem;
+use crate::matmul::components::{
+    batch, config::MatmulConfig, global, Ident, MatmulKernel, StageDim,
+};
+use crate::matmul::kernels::matmul::{AdvancedConfig, Decomposition};
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+use super::{Config as _, CubeDispatch};
+
+/// Executes matrix multiplication at the batch level,
+/// splitting the k dimension of each global matmul between several cubes.
+///
+/// Each cube computes the partial sum of one output stage over its range of k, and writes it
+/// to a workspace. A second kernel then sums the partial sums and applies the epilogue.
+///
+/// Note: This algorithm requires one cube per split of each global matmul;
+/// insufficient cubes will result in incomplete computations.
+pub struct Matmul<
+    EL: Numeric,
+    ER: Numeric,
+    EA: Numeric,
+    ES: Numeric,
+    GMM: global::Matmul<EL, ER, EA, ES>,
+    C: CubeDispatch,
+> {
+    _el: PhantomData<EL>,
+    _er: PhantomData<ER>,
+    _ea: PhantomData<EA>,
+    _es: PhantomData<ES>,
+    _gmm: PhantomData<GMM>,
+    _c: PhantomData<C>,
+}
+
+#[cube]
+impl<
+        EL: Numeric,
+        ER: Numeric,
+        EA: Numeric,
+        ES: Numeric,
+        GMM: global::Matmul<EL, ER, EA, ES>,
+        C: CubeDispatch,
+    > batch::Matmul<EL, ER, EA> for Matmul<EL, ER, EA, ES, GMM, C>
+{
+    fn execute(
+        lhs: &Tensor<Line<EL>>,
+        rhs: &Tensor<Line<ER>>,
+        out: &mut Tensor<Line<EA>>,
+        epilogue: &EpilogueInputs<EA>,
+        #[comptime] config: Self::Config,
+    ) {
+        let (x_index, y_index) = C::x_y_indices();
+        let x_offset = x_index * config.stage_dim(Ident::Lhs).height();
+        let y_offset = y_index * config.stage_dim(Ident::Rhs).width();
+
+        let num_splits = config.num_splits();
+        let nth_partial = C::batch_index();
+        let nth_batch = nth_partial / num_splits;
+        let nth_split = nth_partial % num_splits;
+
+        // Splits are aligned with the stages, so only the last one can be incomplete.
+        let k = lhs.shape(lhs.rank() - 1);
+        let k_step = config.stage_dim(Ident::Lhs).width();
+        let num_steps = (k + k_step - 1) / k_step;
+        let k_per_split = (num_steps + num_splits - 1) / num_splits * k_step;
+        let k_start = Min::min(nth_split * k_per_split, k);
+        let k_end = Min::min(k_start + k_per_split, k);
+
+        let gmm_config = config.to_gmm_config();
+        GMM::execute(
+            GMM::init_lhs_loader(lhs, x_offset, k_start, nth_batch, gmm_config),
+            GMM::init_rhs_loader(rhs, k_start, y_offset, nth_batch, gmm_config),
+            GMM::init_unloader(out, x_offset, y_offset, nth_partial, epilogue),
+            &mut GMM::init_accumulator(gmm_config),
+            (k_start, k_end),
+            gmm_config,
+        );
+    }
+}
+
+impl<
+        EL: Numeric,
+        ER: Numeric,
+        EA: Numeric,
+        ES: Numeric,
+        GMM: global::Matmul<EL, ER, EA, ES>,
+        C: CubeDispatch,
+    > MatmulKernel<EL, EA> for Matmul<EL, ER, EA, ES, GMM, C>
+{
+    type Config = Config<GMM::Config, C>;
+
+    fn check_config(config: Self::Config) {
+        GMM::check_config(config.to_gmm_config())
+    }
+
+    fn check_availability<R: Runtime>(
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) -> Result<(), &str> {
+        GMM::check_availability::<R>(client)
+    }
+
+    fn make_config(
+        problem: &MatmulProblem,
+        cube_dim: &CubeDim,
+        cube_count: &CubeCount,
+        advanced_config: &AdvancedConfig,
+    ) -> Self::Config {
+        let num_splits = match advanced_config.decomposition {
+            Decomposition::SplitK { splits } => splits,
+            _ => panic!("Split-K matmul should be used with the split-K decomposition"),
+        };
+        assert!(num_splits > 0, "Split-K needs at least one split");
+
+        let gmm_config = GMM::make_config(
+            problem,
+            cube_dim,
+            cube_count,
+            &advanced_config.without_epilogue(),
+        );
+        let reduce_config = GMM::make_config(problem, cube_dim, cube_count, advanced_config);
+        let cube_count = if let CubeCount::Static(x, y, z) = cube_count {
+            (*x, *y, *z)
+        } else {
+            panic!("Dynamic cube count unsupported")
+        };
+
+        Config::<GMM::Config, C>::new(gmm_config, reduce_config, cube_count, num_splits)
+    }
+}
+
+impl<
+        EL: Numeric,
+        ER: Numeric,
+        EA: Numeric,
+        ES: Numeric,
+        GMM: global::Matmul<EL, ER, EA, ES>,
+        C: CubeDispatch,
+    > PartialSumsMatmul<EL, ER, EA> for Matmul<EL, ER, EA, ES, GMM, C>
+{
+    type ReduceConfig = GMM::Config;
+
+    fn partial_sums(
+        config: Self::Config,
+        _lhs_shape: &[usize],
+        _out_shape: &[usize],
+    ) -> PartialSums {
+        // Each split is an iteration of the stage, and each cube computes one of them.
+        PartialSums {
+            num_slots: config.num_splits,
+            iters_per_stage: config.num_splits,
+            iters_per_cube: 1,
+        }
+    }
+
+    fn reduce_config(config: Self::Config) -> Self::ReduceConfig {
+        config.reduce_config
+    }
+}
+
+#[derive(CubeType, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+/// Configuration for the SplitKBatchMatmul
+pub struct Config<G: global::Config, C: CubeDispatch> {
+    gmm_config: G,
+    reduce_config: G,
+    cube_count: (u32, u32, u32),
+    num_splits: u32,
+    _c: PhantomData<C>,
+}
+
+impl<G: global::Config, C: CubeDispatch> batch::Config for Config<G, C> {
+    type GmmConfig = G;
+
+    fn to_gmm_config(&self) -> Self::GmmConfig {
+        self.gmm_config
+    }
+
+    fn stage_dim(&self, ident: Ident) -> Box<dyn StageDim> {
+        self.gmm_config.stage_dim(ident)
+    }
+
+    fn max_m(&self) -> u32 {
+        C::max_x(self.cube_count) * self.stage_dim(Ident::Out).height()
+    }
+
+    fn max_n(&self) -> u32 {
+        C::max_y(self.cube_count) * self.stage_dim(Ident::Out).width()
+    }
+
+    fn max_batches(&self) -> u32 {
+        C::max_batches(self.cube_count) / self.num_splits
+    }
+}
+
+impl<G: global::Config, C: CubeDispatch> MatmulConfig for Config<G, C> {}
+
+impl<G: global::Config, C: CubeDispatch> Config<G, C> {
+    pub fn new(
+        gmm_config: G,
+        reduce_config: G,
+        cube_count: (u32, u32, u32),
+        num_splits: u32,
+    ) -> Self {
+        Self {
+            gmm_config,
+            reduce_config,
+            cube_count,
+            num_splits,
+            _c: PhantomData,
+        }
+    }
+
+    /// Returns the number of cubes sharing the k dimension of each global matmul
+    pub fn num_splits(&self) -> u32 {
+        self.num_splits
+    }
+}
@@ -0,0 +1,231 @@
+use std::marker::PhantomData;
+
+use crate::matmul::components::batch::partial_sums::{PartialSums, PartialSumsMatmul};
+use crate::matmul::components::global::EpilogueInputs;
+use crate::matmul::components::MatmulProblem;
+use crate::matmul::components::{
+    batch, config::MatmulConfig, global, Ident, MatmulKernel, StageDim,
+};
+use crate::matmul::kernels::matmul::{AdvancedConfig, Decomposition};
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+use super::Config as _;
+
+/// Executes matrix multiplication at the batch level,
+/// distributing the iterations along k of all global matmuls evenly between cubes.
+///
+/// The iterations of all output stages are laid out one stage after the other, and each cube
+/// receives the same number of consecutive iterations. A cube therefore computes partial sums
+/// for a few stages, which are written to a workspace. A second kernel then sums the partial
+/// sums and applies the epilogue.
+///
+/// The algorithm supports any number of cubes, launched along the x dimension.
+pub struct Matmul<
+    EL: Numeric,
+    ER: Numeric,
+    EA: Numeric,
+    ES: Numeric,
+    GMM: global::Matmul<EL, ER, EA, ES>,
+> {
+    _el: PhantomData<EL>,
+    _er: PhantomData<ER>,
+    _ea: PhantomData<EA>,
+    _es: PhantomData<ES>,
+    _gmm: PhantomData<GMM>,
+}
+
+#[cube]
+impl<EL: Numeric, ER: Numeric, EA: Numeric, ES: Numeric, GMM: global::Matmul<EL, ER, EA, ES>>
+    batch::Matmul<EL, ER, EA> for Matmul<EL, ER, EA, ES, GMM>
+{
+    fn execute(
+        lhs: &Tensor<Line<EL>>,
+        rhs: &Tensor<Line<ER>>,
+        out: &mut Tensor<Line<EA>>,
+        epilogue: &EpilogueInputs<EA>,
+        #[comptime] config: Self::Config,
+    ) {
+        let rank = lhs.rank();
+        let shape_x = lhs.shape(rank - 2);
+        let shape_y = rhs.shape(rank - 1);
+        let k = lhs.shape(rank - 1);
+
+        let mut num_batches = 1;
+        for b in 0..rank - 2 {
+            num_batches *= lhs.shape(b);
+        }
+        let num_slots = out.shape(0) / num_batches;
+
+        let stage_x = config.stage_dim(Ident::Out).height();
+        let stage_y = config.stage_dim(Ident::Out).width();
+        let k_step = config.stage_dim(Ident::Lhs).width();
+        let num_stages_x = (shape_x + stage_x - 1) / stage_x;
+        let num_stages_y = (shape_y + stage_y - 1) / stage_y;
+
+        let iters_per_stage = (k + k_step - 1) / k_step;
+        let num_iters = num_batches * num_stages_x * num_stages_y * iters_per_stage;
+        let iters_per_cube = (num_iters + CUBE_COUNT - 1) / CUBE_COUNT;
+
+        let mut iter = CUBE_POS * iters_per_cube;
+        let end = Min::min(iter + iters_per_cube, num_iters);
+
+        let gmm_config = config.to_gmm_config();
+        let mut acc = GMM::init_accumulator(gmm_config);
+
+        while iter < end {
+            let nth_stage = iter / iters_per_stage;
+            let stage_start = nth_stage * iters_per_stage;
+            let segment_end = Min::min(stage_start + iters_per_stage, end);
+
+            let x_offset = (nth_stage / num_stages_y) % num_stages_x * stage_x;
+            let y_offset = nth_stage % num_stages_y * stage_y;
+            let nth_batch = nth_stage / (num_stages_x * num_stages_y);
+            let nth_slot = CUBE_POS - stage_start / iters_per_cube;
+
+            let k_start = (iter - stage_start) * k_step;
+            let k_end = Min::min((segment_end - stage_start) * k_step, k);
+
+            GMM::zero_accumulator(&mut acc, gmm_config);
+            GMM::execute(
+                GMM::init_lhs_loader(lhs, x_offset, k_start, nth_batch, gmm_config),
+                GMM::init_rhs_loader(rhs, k_start, y_offset, nth_batch, gmm_config),
+                GMM::init_unloader(
+                    out,
+                    x_offset,
+                    y_offset,
+                    nth_batch * num_slots + nth_slot,
+                    epilogue,
+                ),
+                &mut acc,
+                (k_start, k_end),
+                gmm_config,
+            );
+
+            iter = segment_end;
+        }
+    }
+}
+
+impl<EL: Numeric, ER: Numeric, EA: Numeric, ES: Numeric, GMM: global::Matmul<EL, ER, EA, ES>>
+    MatmulKernel<EL, EA> for Matmul<EL, ER, EA, ES, GMM>
+{
+    type Config = Config<GMM::Config>;
+
+    fn check_config(config: Self::Config) {
+        GMM::check_config(config.to_gmm_config())
+    }
+
+    fn check_availability<R: Runtime>(
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) -> Result<(), &str> {
+        GMM::check_availability::<R>(client)
+    }
+
+    fn make_config(
+        problem: &MatmulProblem,
+        cube_dim: &CubeDim,
+        cube_count: &CubeCount,
+        advanced_config: &AdvancedConfig,
+    ) -> Self::Config {
+        assert!(
+            matches!(advanced_config.decomposition, Decomposition::StreamK { .. }),
+            "Stream-K matmul should be used with the stream-K decomposition"
+        );
+        let gmm_config = GMM::make_config(
+            problem,
+            cube_dim,
+            cube_count,
+            &advanced_config.without_epilogue(),
+        );
+        let reduce_config = GMM::make_config(problem, cube_dim, cube_count, advanced_config);
+        let num_cubes = if let CubeCount::Static(x, y, z) = cube_count {
+            x * y * z
+        } else {
+            panic!("Dynamic cube count unsupported")
+        };
+
+        Config::new(gmm_config, reduce_config, num_cubes)
+    }
+}
+
+impl<EL: Numeric, ER: Numeric, EA: Numeric, ES: Numeric, GMM: global::Matmul<EL, ER, EA, ES>>
+    PartialSumsMatmul<EL, ER, EA> for Matmul<EL, ER, EA, ES, GMM>
+{
+    type ReduceConfig = GMM::Config;
+
+    fn partial_sums(config: Self::Config, lhs_shape: &[usize], out_shape: &[usize]) -> PartialSums {
+        // Same distribution of the iterations as in the kernel.
+        let rank = out_shape.len();
+        let num_batches = out_shape[..rank - 2].iter().product::<usize>() as u32;
+        let num_stages_x =
+            (out_shape[rank - 2] as u32).div_ceil(config.stage_dim(Ident::Out).height());
+        let num_stages_y =
+            (out_shape[rank - 1] as u32).div_ceil(config.stage_dim(Ident::Out).width());
+        let k_step = config.stage_dim(Ident::Lhs).width();
+
+        let iters_per_stage = (lhs_shape[rank - 1] as u32).div_ceil(k_step);
+        let num_iters = num_batches * num_stages_x * num_stages_y * iters_per_stage;
+        let iters_per_cube = num_iters.div_ceil(config.num_cubes);
+
+        // A stage spans at most this many cubes.
+        let num_slots = Ord::min(
+            iters_per_stage.saturating_sub(1).div_ceil(iters_per_cube) + 1,
+            config.num_cubes,
+        );
+
+        PartialSums {
+            num_slots,
+            iters_per_stage,
+            iters_per_cube,
+        }
+    }
+
+    fn reduce_config(config: Self::Config) -> Self::ReduceConfig {
+        config.reduce_config
+    }
+}
+
+#[derive(CubeType, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+/// Configuration for the StreamKBatchMatmul
+pub struct Config<G: global::Config> {
+    gmm_config: G,
+    reduce_config: G,
+    num_cubes: u32,
+}
+
+impl<G: global::Config> batch::Config for Config<G> {
+    type GmmConfig = G;
+
+    fn to_gmm_config(&self) -> Self::GmmConfig {
+        self.gmm_config
+    }
+
+    fn stage_dim(&self, ident: Ident) -> Box<dyn StageDim> {
+        self.gmm_config.stage_dim(ident)
+    }
+
+    fn max_m(&self) -> u32 {
+        u32::maximum_value()
+    }
+
+    fn max_n(&self) -> u32 {
+        u32::maximum_value()
+    }
+
+    fn max_batches(&self) -> u32 {
+        u32::maximum_value()
+    }
+}
+
+impl<G: global::Config> MatmulConfig for Config<G> {}
+
+impl<G: global::Config> Config<G> {
+    pub fn new(gmm_config: G, reduce_config: G, num_cubes: u32) -> Self {
+        Self {
+            gmm_config,
+            reduce_config,
+            num_cubes,
+        }
+    }
+}
@@ -2,6 +2,7 @@ use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
 use crate::matmul::components::config::MatmulConfig;
+use crate::matmul::components::global::{EpilogueConfig, EpilogueInputs};
 use crate::matmul::components::stage::{self, StageWriter, TilingOrderConfig};
 use crate::matmul::components::MatmulKernel;
 use crate::matmul::components::StageDim;
@@ -26,12 +27,12 @@ use crate::matmul::components::{Ident, MatrixLayout};
 /// It is not assumed that the matmul's dimensions match its inputs dimensions perfectly.
 /// It is therefore important that Loaders and Unloaders perform checks to avoid out-of-bounds
 /// before loading data.
-pub trait Matmul<EG: Numeric, ES: Numeric>:
-    'static + Send + Sync + MatmulKernel<EG, EG, Config: Config>
+pub trait Matmul<EL: Numeric, ER: Numeric, EO: Numeric, ES: Numeric>:
+    'static + Send + Sync + MatmulKernel<EL, EO, Config: Config>
 {
-    type LhsLoader: Loader<EG, ES, Self::Config>;
-    type RhsLoader: Loader<EG, ES, Self::Config>;
-    type Out: Unloader<EG>;
+    type LhsLoader: Loader<EL, ES, Self::Config>;
+    type RhsLoader: Loader<ER, ES, Self::Config>;
+    type Out: Unloader<EO>;
     type Accumulator: CubeType;
 
     /// Performs the matrix multiplication over data loaded by the
@@ -51,7 +52,7 @@ pub trait Matmul<EG: Numeric, ES: Numeric>:
 
     /// Initialize the loader for Lhs, starting at row m and column k
     fn init_lhs_loader(
-        lhs: &Tensor<Line<EG>>,
+        lhs: &Tensor<Line<EL>>,
         m_offset: u32,
         k_offset: u32,
         nth_batch: u32,
@@ -60,19 +61,20 @@ pub trait Matmul<EG: Numeric, ES: Numeric>:
 
     /// Initialize the loader for Rhs, starting at row k and column n
     fn init_rhs_loader(
-        rhs: &Tensor<Line<EG>>,
+        rhs: &Tensor<Line<ER>>,
         k_offset: u32,
         n_offset: u32,
         nth_batch: u32,
         #[comptime] config: Self::Config,
     ) -> Self::RhsLoader;
 
-    /// Initialize the unloader at row m and column n
+    /// Initialize the unloader at row m and column n, applying the epilogue
     fn init_unloader(
-        out: &mut Tensor<Line<EG>>,
+        out: &mut Tensor<Line<EO>>,
         m_offset: u32,
         n_offset: u32,
         batch_offset: u32,
+        epilogue: &EpilogueInputs<EO>,
     ) -> Self::Out;
 
     /// Initialize the accumulator without data
@@ -149,4 +151,7 @@ pub trait Config: MatmulConfig {
 
     /// Whether we transpose data when loading to the stage
     fn transpose_load(&self, ident: Ident) -> bool;
+
+    /// Returns which operations of the epilogue are enabled
+    fn epilogue(&self) -> EpilogueConfig;
 }
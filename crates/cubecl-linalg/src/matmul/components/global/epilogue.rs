@@ -0,0 +1,218 @@
+use cubecl_core as cubecl;
+use cubecl_core::ir::{Elem, FloatKind};
+use cubecl_core::prelude::*;
+
+use crate::matmul::components::global::tensor_view::batch_offset;
+use crate::matmul::components::global::Config;
+use crate::matmul::components::Ident;
+
+#[cube]
+/// Operations applied to the output of the matmul before it is written to global memory.
+pub trait Epilogue<EG: Numeric>: CubeType + Clone + IntoRuntime + 'static + Send + Sync {
+    /// Create the epilogue for the given batch, from the inputs of the kernel.
+    fn new(inputs: &EpilogueInputs<EG>, nth_batch: u32) -> Self;
+
+    /// Applies the epilogue to a line of the output, at row `view_x` and column `view_y`.
+    ///
+    /// Only called on lines that are within the bounds of the output.
+    fn apply<G: Config>(
+        this: &Self,
+        value: Line<EG>,
+        view_x: u32,
+        view_y: u32,
+        #[comptime] config: G,
+    ) -> Line<EG>;
+}
+
+#[derive(CubeLaunch)]
+/// Inputs of the epilogue, given to the matmul kernel alongside lhs, rhs and out.
+///
+/// Tensors that are disabled in the [EpilogueConfig] are never read.
+pub struct EpilogueInputs<EG: Numeric> {
+    /// Scale of the accumulator
+    pub alpha: f32,
+    /// Scale of c
+    pub beta: f32,
+    /// Tensor with the same shape as the output
+    pub c: Tensor<Line<EG>>,
+    /// Vector added to every row of the output
+    pub bias: Tensor<Line<EG>>,
+    /// Tensor with the same shape as the output, added after the activation
+    pub residual: Tensor<Line<EG>>,
+}
+
+#[derive(CubeType, Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+/// Activation function of the epilogue
+pub enum Activation {
+    #[default]
+    None,
+    ReLU,
+    GELU,
+    SiLU,
+}
+
+#[derive(CubeType, Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+/// Which operations of the [FusedEpilogue] are enabled
+pub struct EpilogueConfig {
+    /// Whether the accumulator is scaled by alpha
+    pub alpha: bool,
+    /// Whether beta * c is added
+    pub c: bool,
+    /// Whether the bias is added
+    pub bias: bool,
+    /// Activation applied after the additions
+    pub activation: Activation,
+    /// Whether the residual is added after the activation
+    pub residual: bool,
+}
+
+#[derive(CubeType, Clone)]
+/// Computes `activation(alpha * acc + beta * c + bias) + residual`,
+/// where every operation disabled in the [EpilogueConfig] is skipped.
+///
+/// Float outputs are computed in f32, or in f64 for f64 outputs, and integer outputs are computed
+/// in their own type so they stay exact. With the default config, the accumulator is written as
+/// is.
+pub struct FusedEpilogue<EG: Numeric> {
+    pub alpha: f32,
+    pub beta: f32,
+    pub c: *const Tensor<Line<EG>>,
+    pub bias: *const Tensor<Line<EG>>,
+    pub residual: *const Tensor<Line<EG>>,
+    pub nth_batch: u32,
+}
+
+unsafe impl<EG: Numeric> Sync for FusedEpilogue<EG> {}
+unsafe impl<EG: Numeric> Send for FusedEpilogue<EG> {}
+
+#[cube]
+impl<EG: Numeric> Epilogue<EG> for FusedEpilogue<EG> {
+    fn new(inputs: &EpilogueInputs<EG>, nth_batch: u32) -> Self {
+        FusedEpilogue::<EG> {
+            alpha: inputs.alpha,
+            beta: inputs.beta,
+            c: &inputs.c,
+            bias: &inputs.bias,
+            residual: &inputs.residual,
+            nth_batch,
+        }
+    }
+
+    fn apply<G: Config>(
+        this: &Self,
+        value: Line<EG>,
+        view_x: u32,
+        view_y: u32,
+        #[comptime] config: G,
+    ) -> Line<EG> {
+        if comptime!(EG::as_elem() == Elem::Float(FloatKind::F64)) {
+            this.compute::<f64, G>(value, view_x, view_y, config)
+        } else if comptime!(matches!(EG::as_elem(), Elem::Float(_))) {
+            this.compute::<f32, G>(value, view_x, view_y, config)
+        } else {
+            this.compute::<EG, G>(value, view_x, view_y, config)
+        }
+    }
+}
+
+#[cube]
+impl<EG: Numeric> FusedEpilogue<EG> {
+    /// Applies the epilogue with every operation computed in `C`.
+    fn compute<C: Numeric, G: Config>(
+        &self,
+        value: Line<EG>,
+        view_x: u32,
+        view_y: u32,
+        #[comptime] config: G,
+    ) -> Line<EG> {
+        let epilogue = config.epilogue();
+        let line_size = config.global_line_size(Ident::Out);
+
+        let mut acc = Line::<C>::cast_from(value);
+
+        if comptime!(epilogue.alpha) {
+            acc *= Line::empty(line_size).fill(C::cast_from(self.alpha));
+        }
+
+        if comptime!(epilogue.c) {
+            let c = Line::<C>::cast_from(self.read_matrix(self.c, view_x, view_y, line_size));
+            acc += Line::empty(line_size).fill(C::cast_from(self.beta)) * c;
+        }
+
+        if comptime!(epilogue.bias) {
+            acc += Line::cast_from(unsafe { *(*self.bias).index_unchecked(view_y / line_size) });
+        }
+
+        match comptime!(epilogue.activation) {
+            Activation::None => {}
+            Activation::ReLU => {
+                acc = Max::max(acc, Line::empty(line_size).fill(C::from_int(0)));
+            }
+            Activation::GELU => {
+                acc = float_activation::<C>(acc, Activation::GELU, line_size);
+            }
+            Activation::SiLU => {
+                acc = float_activation::<C>(acc, Activation::SiLU, line_size);
+            }
+        }
+
+        if comptime!(epilogue.residual) {
+            acc += Line::cast_from(self.read_matrix(self.residual, view_x, view_y, line_size));
+        }
+
+        Line::cast_from(acc)
+    }
+
+    /// Reads a line of a tensor shaped like the output.
+    fn read_matrix(
+        &self,
+        tensor: *const Tensor<Line<EG>>,
+        view_x: u32,
+        view_y: u32,
+        #[comptime] line_size: u32,
+    ) -> Line<EG> {
+        let rank = unsafe { (*tensor).rank() };
+        let stride_x = unsafe { (*tensor).stride(rank - 2) };
+        let stride_y = unsafe { (*tensor).stride(rank - 1) };
+        let batch_offset = batch_offset(unsafe { &*tensor }, self.nth_batch);
+        let position = (view_x * stride_x + view_y * stride_y + batch_offset) / line_size;
+
+        unsafe { *(*tensor).index_unchecked(position) }
+    }
+}
+
+/// Applies an activation that needs floats to a value computed in `C`, in f64 if `C` is f64 and
+/// in f32 otherwise.
+#[cube]
+fn float_activation<C: Numeric>(
+    acc: Line<C>,
+    #[comptime] activation: Activation,
+    #[comptime] line_size: u32,
+) -> Line<C> {
+    if comptime!(C::as_elem() == Elem::Float(FloatKind::F64)) {
+        Line::cast_from(activate::<f64>(Line::cast_from(acc), activation, line_size))
+    } else {
+        Line::cast_from(activate::<f32>(Line::cast_from(acc), activation, line_size))
+    }
+}
+
+#[cube]
+fn activate<F: Float>(
+    acc: Line<F>,
+    #[comptime] activation: Activation,
+    #[comptime] line_size: u32,
+) -> Line<F> {
+    let zeros = Line::empty(line_size).fill(F::new(0.));
+    let ones = Line::empty(line_size).fill(F::new(1.));
+
+    match comptime!(activation) {
+        Activation::GELU => {
+            let frac_1_sqrt_2 =
+                Line::empty(line_size).fill(F::new(std::f32::consts::FRAC_1_SQRT_2));
+            let half = Line::empty(line_size).fill(F::new(0.5));
+            half * acc * (ones + Erf::erf(acc * frac_1_sqrt_2))
+        }
+        Activation::SiLU => acc / (ones + Exp::exp(zeros - acc)),
+        _ => acc,
+    }
+}
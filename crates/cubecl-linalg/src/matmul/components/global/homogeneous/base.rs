@@ -1,6 +1,8 @@
 use crate::matmul::components::config::MatmulConfig;
 use crate::matmul::components::global::unloader::Unloader;
-use crate::matmul::components::global::{Config as _, Loader};
+use crate::matmul::components::global::{
+    Config as _, Epilogue, EpilogueConfig, EpilogueInputs, FusedEpilogue, Loader,
+};
 use crate::matmul::components::stage;
 use crate::matmul::components::stage::multi_buffer::{LhsReader, RhsReader};
 use crate::matmul::components::stage::TilingOrderConfig;
@@ -19,22 +21,35 @@ use super::loader::{LhsLoader, RhsLoader};
 /// Performs matrix multiplication at the global level, with each plane sharing the same responsibilities
 /// - All planes load data to the stage
 /// - All planes are used in the stage matmul computation
-pub struct Matmul<EG: Numeric, ES: Numeric, SMM: stage::Matmul<ES, EG>> {
-    _eg: PhantomData<EG>,
+pub struct Matmul<
+    EL: Numeric,
+    ER: Numeric,
+    EO: Numeric,
+    ES: Numeric,
+    SMM: stage::Matmul<ES, EO>,
+    E: Epilogue<EO> = FusedEpilogue<EO>,
+> {
+    _el: PhantomData<EL>,
+    _er: PhantomData<ER>,
+    _eo: PhantomData<EO>,
     _es: PhantomData<ES>,
     _stage_matmul: PhantomData<SMM>,
+    _epilogue: PhantomData<E>,
 }
 
 #[cube]
-impl<EG, ES, SMM> global::Matmul<EG, ES> for Matmul<EG, ES, SMM>
+impl<EL, ER, EO, ES, SMM, E> global::Matmul<EL, ER, EO, ES> for Matmul<EL, ER, EO, ES, SMM, E>
 where
-    EG: Numeric,
+    EL: Numeric,
+    ER: Numeric,
+    EO: Numeric,
     ES: Numeric,
-    SMM: stage::Matmul<ES, EG, LhsReader = LhsReader<ES>, RhsReader = RhsReader<ES>>,
+    SMM: stage::Matmul<ES, EO, LhsReader = LhsReader<ES>, RhsReader = RhsReader<ES>>,
+    E: Epilogue<EO>,
 {
-    type LhsLoader = LhsLoader<EG, ES, SMM::Config>;
-    type RhsLoader = RhsLoader<EG, ES, SMM::Config>;
-    type Out = Unloader<EG>;
+    type LhsLoader = LhsLoader<EL, ES, SMM::Config>;
+    type RhsLoader = RhsLoader<ER, ES, SMM::Config>;
+    type Out = Unloader<EO, E>;
     type Accumulator = SMM::Accumulator;
 
     fn execute(
@@ -81,7 +96,7 @@ where
     }
 
     fn init_lhs_loader(
-        lhs: &Tensor<Line<EG>>,
+        lhs: &Tensor<Line<EL>>,
         x_offset: u32,
         y_offset: u32,
         nth_batch: u32,
@@ -91,7 +106,7 @@ where
     }
 
     fn init_rhs_loader(
-        rhs: &Tensor<Line<EG>>,
+        rhs: &Tensor<Line<ER>>,
         x_offset: u32,
         y_offset: u32,
         nth_batch: u32,
@@ -101,12 +116,13 @@ where
     }
 
     fn init_unloader(
-        out: &mut Tensor<Line<EG>>,
+        out: &mut Tensor<Line<EO>>,
         x_offset: u32,
         y_offset: u32,
         batch_offset: u32,
+        epilogue: &EpilogueInputs<EO>,
     ) -> Self::Out {
-        Self::Out::new(out, x_offset, y_offset, batch_offset)
+        Self::Out::new(out, x_offset, y_offset, batch_offset, epilogue)
     }
 
     fn init_accumulator(#[comptime] config: Self::Config) -> Self::Accumulator {
@@ -118,11 +134,14 @@ where
     }
 }
 
-impl<EG, ES, SMM> MatmulKernel<EG, EG> for Matmul<EG, ES, SMM>
+impl<EL, ER, EO, ES, SMM, E> MatmulKernel<EL, EO> for Matmul<EL, ER, EO, ES, SMM, E>
 where
-    EG: Numeric,
+    EL: Numeric,
+    ER: Numeric,
+    EO: Numeric,
     ES: Numeric,
-    SMM: stage::Matmul<ES, EG>,
+    SMM: stage::Matmul<ES, EO>,
+    E: Epilogue<EO>,
 {
     type Config = Config<SMM::Config>;
 
@@ -154,6 +173,7 @@ where
             problem.lhs_line_size as u32,
             problem.rhs_line_size as u32,
             problem.out_line_size as u32,
+            advanced_config.epilogue,
         )
     }
 }
@@ -170,6 +190,7 @@ pub struct Config<S: stage::Config> {
     lhs_line_size: u32,
     rhs_line_size: u32,
     out_line_size: u32,
+    epilogue: EpilogueConfig,
 }
 
 impl<S: stage::Config> global::Config for Config<S> {
@@ -230,6 +251,10 @@ impl<S: stage::Config> global::Config for Config<S> {
     fn transpose_load(&self, ident: Ident) -> bool {
         self.layout(ident) != self.smm_config.layout(ident)
     }
+
+    fn epilogue(&self) -> EpilogueConfig {
+        self.epilogue
+    }
 }
 
 impl<S: stage::Config> MatmulConfig for Config<S> {}
@@ -246,6 +271,7 @@ impl<S: stage::Config> Config<S> {
         lhs_line_size: u32,
         rhs_line_size: u32,
         out_line_size: u32,
+        epilogue: EpilogueConfig,
     ) -> Self {
         Self {
             smm_config,
@@ -257,6 +283,7 @@ impl<S: stage::Config> Config<S> {
             lhs_line_size,
             rhs_line_size,
             out_line_size,
+            epilogue,
         }
     }
 }
@@ -3,3 +3,4 @@ mod cyclic_loading;
 mod loader;
 
 pub use base::*;
+pub use loader::{LhsLoader, RhsLoader};
@@ -1,9 +1,12 @@
 pub mod homogeneous;
 pub mod producer_consumer;
+pub mod quantized;
 pub mod tensor_view;
+pub mod unloader;
 
 mod base;
+mod epilogue;
 mod tilewise_unloading;
-mod unloader;
 
 pub use base::*;
+pub use epilogue::*;
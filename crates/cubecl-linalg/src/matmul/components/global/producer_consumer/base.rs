@@ -1,6 +1,8 @@
 use crate::matmul::components::config::MatmulConfig;
 use crate::matmul::components::global::unloader::Unloader;
-use crate::matmul::components::global::{Config as _, Loader};
+use crate::matmul::components::global::{
+    Config as _, Epilogue, EpilogueConfig, EpilogueInputs, FusedEpilogue, Loader,
+};
 use crate::matmul::components::stage;
 use crate::matmul::components::stage::single_buffer::{LhsBufferReader, RhsBufferReader};
 use crate::matmul::components::stage::TilingOrderConfig;
@@ -21,22 +23,35 @@ use super::loader::{LhsBufferLoader, RhsBufferLoader};
 /// - Remaining planes load data to the stage
 ///
 /// Both roles alternate the buffer (tile index in dimension k) they are working on
-pub struct Matmul<EG: Numeric, ES: Numeric, SMM: stage::Matmul<ES, EG>> {
-    _eg: PhantomData<EG>,
+pub struct Matmul<
+    EL: Numeric,
+    ER: Numeric,
+    EO: Numeric,
+    ES: Numeric,
+    SMM: stage::Matmul<ES, EO>,
+    E: Epilogue<EO> = FusedEpilogue<EO>,
+> {
+    _el: PhantomData<EL>,
+    _er: PhantomData<ER>,
+    _eo: PhantomData<EO>,
     _es: PhantomData<ES>,
     _stage_matmul: PhantomData<SMM>,
+    _epilogue: PhantomData<E>,
 }
 
 #[cube]
-impl<EG, ES, SMM> global::Matmul<EG, ES> for Matmul<EG, ES, SMM>
+impl<EL, ER, EO, ES, SMM, E> global::Matmul<EL, ER, EO, ES> for Matmul<EL, ER, EO, ES, SMM, E>
 where
-    EG: Numeric,
+    EL: Numeric,
+    ER: Numeric,
+    EO: Numeric,
     ES: Numeric,
-    SMM: stage::Matmul<ES, EG, LhsReader = LhsBufferReader<ES>, RhsReader = RhsBufferReader<ES>>,
+    SMM: stage::Matmul<ES, EO, LhsReader = LhsBufferReader<ES>, RhsReader = RhsBufferReader<ES>>,
+    E: Epilogue<EO>,
 {
-    type LhsLoader = LhsBufferLoader<EG, ES, SMM::Config>;
-    type RhsLoader = RhsBufferLoader<EG, ES, SMM::Config>;
-    type Out = Unloader<EG>;
+    type LhsLoader = LhsBufferLoader<EL, ES, SMM::Config>;
+    type RhsLoader = RhsBufferLoader<ER, ES, SMM::Config>;
+    type Out = Unloader<EO, E>;
     type Accumulator = SMM::Accumulator;
 
     fn execute(
@@ -91,7 +106,7 @@ where
     }
 
     fn init_lhs_loader(
-        lhs: &Tensor<Line<EG>>,
+        lhs: &Tensor<Line<EL>>,
         x_offset: u32,
         y_offset: u32,
         nth_batch: u32,
@@ -108,7 +123,7 @@ where
     }
 
     fn init_rhs_loader(
-        rhs: &Tensor<Line<EG>>,
+        rhs: &Tensor<Line<ER>>,
         x_offset: u32,
         y_offset: u32,
         nth_batch: u32,
@@ -125,12 +140,13 @@ where
     }
 
     fn init_unloader(
-        out: &mut Tensor<Line<EG>>,
+        out: &mut Tensor<Line<EO>>,
         x_offset: u32,
         y_offset: u32,
         batch_offset: u32,
+        epilogue: &EpilogueInputs<EO>,
     ) -> Self::Out {
-        Self::Out::new(out, x_offset, y_offset, batch_offset)
+        Self::Out::new(out, x_offset, y_offset, batch_offset, epilogue)
     }
 
     fn init_accumulator(#[comptime] config: Self::Config) -> Self::Accumulator {
@@ -143,17 +159,28 @@ where
 }
 
 #[cube]
-impl<EG: Numeric, ES: Numeric, SMM: stage::Matmul<ES, EG>> Matmul<EG, ES, SMM> {
-    fn is_consumer(#[comptime] config: <Self as MatmulKernel<EG, EG>>::Config) -> bool {
+impl<
+        EL: Numeric,
+        ER: Numeric,
+        EO: Numeric,
+        ES: Numeric,
+        SMM: stage::Matmul<ES, EO>,
+        E: Epilogue<EO>,
+    > Matmul<EL, ER, EO, ES, SMM, E>
+{
+    fn is_consumer(#[comptime] config: <Self as MatmulKernel<EL, EO>>::Config) -> bool {
         UNIT_POS_Y < config.num_consumers()
     }
 }
 
-impl<EG, ES, SMM> MatmulKernel<EG, EG> for Matmul<EG, ES, SMM>
+impl<EL, ER, EO, ES, SMM, E> MatmulKernel<EL, EO> for Matmul<EL, ER, EO, ES, SMM, E>
 where
-    EG: Numeric,
+    EL: Numeric,
+    ER: Numeric,
+    EO: Numeric,
     ES: Numeric,
-    SMM: stage::Matmul<ES, EG>,
+    SMM: stage::Matmul<ES, EO>,
+    E: Epilogue<EO>,
 {
     type Config = Config<SMM::Config>;
 
@@ -191,6 +218,7 @@ where
             problem.rhs_line_size as u32,
             problem.out_line_size as u32,
             cube_dim.y,
+            advanced_config.epilogue,
         )
     }
 }
@@ -208,6 +236,7 @@ pub struct Config<S: stage::Config> {
     rhs_line_size: u32,
     out_line_size: u32,
     num_planes: u32,
+    epilogue: EpilogueConfig,
 }
 
 impl<S: stage::Config> global::Config for Config<S> {
@@ -268,6 +297,10 @@ impl<S: stage::Config> global::Config for Config<S> {
     fn transpose_load(&self, ident: Ident) -> bool {
         self.layout(ident) != self.smm_config.layout(ident)
     }
+
+    fn epilogue(&self) -> EpilogueConfig {
+        self.epilogue
+    }
 }
 
 impl<S: stage::Config> MatmulConfig for Config<S> {}
@@ -285,6 +318,7 @@ impl<S: stage::Config> Config<S> {
         rhs_line_size: u32,
         out_line_size: u32,
         num_planes: u32,
+        epilogue: EpilogueConfig,
     ) -> Self {
         Self {
             smm_config,
@@ -297,6 +331,7 @@ impl<S: stage::Config> Config<S> {
             rhs_line_size,
             out_line_size,
             num_planes,
+            epilogue,
         }
     }
 
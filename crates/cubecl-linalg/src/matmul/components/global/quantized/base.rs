@@ -0,0 +1,180 @@
+use crate::matmul::components::global::homogeneous::{self, LhsLoader};
+use crate::matmul::components::global::unloader::Unloader;
+use crate::matmul::components::global::{
+    Config as _, Epilogue, EpilogueInputs, FusedEpilogue, Loader,
+};
+use crate::matmul::components::stage;
+use crate::matmul::components::stage::multi_buffer::{LhsReader, RhsReader};
+
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+use std::marker::PhantomData;
+
+use super::loader::QuantizedRhsLoader;
+
+#[derive(CubeType, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+/// How the quantized values of the rhs are stored
+pub enum QuantizedFormat {
+    /// One signed 8-bit value per i8 element
+    Int8,
+    /// Eight signed 4-bit values per u32 element, packed along n starting from the lowest bits
+    Int4,
+}
+
+#[derive(CubeType, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+/// Storage of the quantized rhs and of its dequantization parameters
+pub struct QuantizationConfig {
+    pub format: QuantizedFormat,
+    /// Whether zero points are subtracted from the values before they are scaled
+    pub zero_points: bool,
+}
+
+impl QuantizationConfig {
+    /// Number of quantized values stored in each element of the rhs tensor
+    pub fn values_per_elem(&self) -> u32 {
+        match self.format {
+            QuantizedFormat::Int8 => 1,
+            QuantizedFormat::Int4 => 8,
+        }
+    }
+}
+
+#[derive(CubeLaunch)]
+/// Dequantization parameters of the rhs, given to the quantized matmul kernel.
+///
+/// Both tensors have shape `[..batches, groups, n]`, each of the `groups` rows applying to
+/// `k / groups` consecutive rows of the rhs. A single group gives per-channel quantization.
+///
+/// The zero points are never read if they are disabled in the [QuantizationConfig].
+pub struct QuantizationInputs<ESc: Numeric> {
+    pub scales: Tensor<Line<ESc>>,
+    pub zero_points: Tensor<Line<ESc>>,
+}
+
+/// Performs matrix multiplication at the global level with a quantized rhs,
+/// which is dequantized to the stage element when loaded.
+///
+/// Apart from the rhs loader, it behaves like the homogeneous global matmul:
+/// all planes load data to the stage, and all planes are used in the stage matmul computation.
+pub struct Matmul<
+    EL: Numeric,
+    EQ: Numeric,
+    ESc: Numeric,
+    EO: Numeric,
+    ES: Numeric,
+    SMM: stage::Matmul<ES, EO>,
+    E: Epilogue<EO> = FusedEpilogue<EO>,
+> {
+    _el: PhantomData<EL>,
+    _eq: PhantomData<EQ>,
+    _esc: PhantomData<ESc>,
+    _eo: PhantomData<EO>,
+    _es: PhantomData<ES>,
+    _stage_matmul: PhantomData<SMM>,
+    _epilogue: PhantomData<E>,
+}
+
+#[cube]
+impl<EL, EQ, ESc, EO, ES, SMM, E> Matmul<EL, EQ, ESc, EO, ES, SMM, E>
+where
+    EL: Numeric,
+    EQ: Numeric,
+    ESc: Numeric,
+    EO: Numeric,
+    ES: Numeric,
+    SMM: stage::Matmul<ES, EO, LhsReader = LhsReader<ES>, RhsReader = RhsReader<ES>>,
+    E: Epilogue<EO>,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn execute(
+        mut lhs_loader: LhsLoader<EL, ES, SMM::Config>,
+        mut rhs_loader: QuantizedRhsLoader<EQ, ESc, ES, SMM::Config>,
+        mut out_unloader: Unloader<EO, E>,
+        acc: &mut SMM::Accumulator,
+        k_range: (u32, u32),
+        #[comptime] quantization: QuantizationConfig,
+        #[comptime] config: homogeneous::Config<SMM::Config>,
+    ) {
+        let k_step = SMM::K;
+        let range = k_range.1 - k_range.0;
+        let num_loops = (range + k_step - 1) / k_step;
+
+        let (mut lhs_tile, mut rhs_tile) = SMM::init_tile_inputs(config.to_smm_config());
+
+        for _ in 0..num_loops {
+            let lhs_stage_reader = &LhsLoader::fill_stage(&mut lhs_loader, config);
+            let rhs_stage_reader = &rhs_loader.fill_stage(quantization, config);
+
+            sync_units();
+
+            SMM::execute(
+                lhs_stage_reader,
+                rhs_stage_reader,
+                &mut lhs_tile,
+                &mut rhs_tile,
+                acc,
+                config.to_smm_config(),
+            );
+
+            sync_units();
+
+            LhsLoader::advance_view(&mut lhs_loader, k_step);
+            rhs_loader.advance_view(k_step);
+        }
+
+        SMM::read_accumulator::<Unloader<EO, E>, homogeneous::Config<SMM::Config>>(
+            acc,
+            &mut out_unloader,
+            config.to_smm_config(),
+            config,
+        );
+    }
+
+    pub fn init_lhs_loader(
+        lhs: &Tensor<Line<EL>>,
+        x_offset: u32,
+        y_offset: u32,
+        nth_batch: u32,
+        #[comptime] config: homogeneous::Config<SMM::Config>,
+    ) -> LhsLoader<EL, ES, SMM::Config> {
+        LhsLoader::new::<homogeneous::Config<SMM::Config>>(
+            lhs, x_offset, y_offset, nth_batch, config,
+        )
+    }
+
+    pub fn init_rhs_loader(
+        rhs: &Tensor<Line<EQ>>,
+        inputs: &QuantizationInputs<ESc>,
+        x_offset: u32,
+        y_offset: u32,
+        nth_batch: u32,
+        #[comptime] quantization: QuantizationConfig,
+        #[comptime] config: homogeneous::Config<SMM::Config>,
+    ) -> QuantizedRhsLoader<EQ, ESc, ES, SMM::Config> {
+        QuantizedRhsLoader::new::<homogeneous::Config<SMM::Config>>(
+            rhs,
+            inputs,
+            x_offset,
+            y_offset,
+            nth_batch,
+            quantization,
+            config,
+        )
+    }
+
+    pub fn init_unloader(
+        out: &mut Tensor<Line<EO>>,
+        x_offset: u32,
+        y_offset: u32,
+        batch_offset: u32,
+        epilogue: &EpilogueInputs<EO>,
+    ) -> Unloader<EO, E> {
+        Unloader::new(out, x_offset, y_offset, batch_offset, epilogue)
+    }
+
+    pub fn init_accumulator(
+        #[comptime] config: homogeneous::Config<SMM::Config>,
+    ) -> SMM::Accumulator {
+        SMM::init_accumulator(config.to_smm_config())
+    }
+}
@@ -0,0 +1,200 @@
+use std::marker::PhantomData;
+
+use crate::matmul::components::global::homogeneous;
+use crate::matmul::components::global::tensor_view::batch_offset;
+use crate::matmul::components::global::{self, Config as _};
+use crate::matmul::components::stage::multi_buffer::RhsReader;
+use crate::matmul::components::stage::{
+    self, ColMajorTiling, RowMajorTiling, Stage, TilingOrder, TilingOrderConfig,
+};
+use crate::matmul::components::Ident;
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+use super::{QuantizationConfig, QuantizationInputs, QuantizedFormat};
+
+#[derive(CubeType)]
+/// Loads the quantized rhs to the stage, dequantizing each value with the scale
+/// and zero point of its group and output channel.
+pub struct QuantizedRhsLoader<EQ: Numeric, ESc: Numeric, ES: Numeric, S: stage::Config> {
+    pub values: *const Tensor<Line<EQ>>,
+    pub scales: *const Tensor<Line<ESc>>,
+    pub zero_points: *const Tensor<Line<ESc>>,
+    pub k_offset: u32,
+    pub n_offset: u32,
+    pub shape_k: u32,
+    pub shape_n: u32,
+    pub stride_k: u32,
+    pub stride_n: u32,
+    pub batch_offset: u32,
+    pub group_size: u32,
+    pub scales_stride_group: u32,
+    pub scales_stride_n: u32,
+    pub scales_batch_offset: u32,
+    pub stage: Stage<ES>,
+    _config: PhantomData<S>,
+}
+
+unsafe impl<EQ: Numeric, ESc: Numeric, ES: Numeric, S: stage::Config> Sync
+    for QuantizedRhsLoader<EQ, ESc, ES, S>
+{
+}
+unsafe impl<EQ: Numeric, ESc: Numeric, ES: Numeric, S: stage::Config> Send
+    for QuantizedRhsLoader<EQ, ESc, ES, S>
+{
+}
+
+#[cube]
+impl<EQ: Numeric, ESc: Numeric, ES: Numeric, S: stage::Config> QuantizedRhsLoader<EQ, ESc, ES, S> {
+    pub fn new<G: global::Config>(
+        values: &Tensor<Line<EQ>>,
+        inputs: &QuantizationInputs<ESc>,
+        k_offset: u32,
+        n_offset: u32,
+        nth_batch: u32,
+        #[comptime] quantization: QuantizationConfig,
+        #[comptime] config: G,
+    ) -> Self {
+        let stage = Stage::new::<G::SmmConfig>(Ident::Rhs, config.to_smm_config());
+
+        let rank = values.rank();
+        let shape_k = values.shape(rank - 2);
+        let groups = inputs.scales.shape(rank - 2);
+
+        QuantizedRhsLoader::<EQ, ESc, ES, S> {
+            values,
+            scales: &inputs.scales,
+            zero_points: &inputs.zero_points,
+            k_offset,
+            n_offset,
+            shape_k,
+            shape_n: values.shape(rank - 1) * quantization.values_per_elem(),
+            stride_k: values.stride(rank - 2),
+            stride_n: values.stride(rank - 1),
+            batch_offset: batch_offset(values, nth_batch),
+            group_size: shape_k / groups,
+            scales_stride_group: inputs.scales.stride(rank - 2),
+            scales_stride_n: inputs.scales.stride(rank - 1),
+            scales_batch_offset: batch_offset(&inputs.scales, nth_batch),
+            stage,
+            _config: PhantomData::<S>.runtime(),
+        }
+    }
+
+    /// Fills the stage with the dequantized values of the current view,
+    /// each unit loading lines the same way as the cyclic loading of the homogeneous matmul.
+    ///
+    /// Out-of-bounds values are translated to zeros.
+    pub fn fill_stage(
+        &mut self,
+        #[comptime] quantization: QuantizationConfig,
+        #[comptime] config: homogeneous::Config<S>,
+    ) -> RhsReader<ES> {
+        let stage_dim = config.stage_dim(Ident::Rhs);
+        let line_size = config.global_line_size(Ident::Rhs);
+
+        let num_stage_elements = stage_dim.total_elements();
+        let total_units = comptime!(config.num_planes() * config.plane_dim());
+        let jump_length = comptime!(total_units * line_size);
+        let num_loads_per_unit = num_stage_elements / jump_length;
+
+        let unit_id = UNIT_POS_Y * config.plane_dim() + UNIT_POS_X;
+        let unit_position_base = unit_id * line_size;
+        let mut slice = self.stage.as_slice_mut();
+
+        for i in 0..num_loads_per_unit {
+            let unit_position = unit_position_base + i * jump_length;
+
+            let tile_num_elements = stage_dim.tile_num_elements();
+            let nth_tile = unit_position / tile_num_elements;
+            let pos_within_tile = unit_position % tile_num_elements;
+
+            let (tile_x, tile_y) = match config.tiling_order(Ident::Rhs) {
+                TilingOrderConfig::RowMajor => RowMajorTiling::to_x_y(
+                    nth_tile,
+                    stage_dim.num_tiles_x_dim(),
+                    stage_dim.num_tiles_y_dim(),
+                ),
+                TilingOrderConfig::ColMajor => ColMajorTiling::to_x_y(
+                    nth_tile,
+                    stage_dim.num_tiles_x_dim(),
+                    stage_dim.num_tiles_y_dim(),
+                ),
+            };
+
+            let tile_size_y = stage_dim.tile_size_y_dim();
+            let k = self.k_offset
+                + tile_x * stage_dim.tile_size_x_dim()
+                + pos_within_tile / tile_size_y;
+            let n = self.n_offset + tile_y * tile_size_y + pos_within_tile % tile_size_y;
+
+            let mut line = Line::empty(line_size).fill(ES::from_int(0));
+            if k < self.shape_k && n < self.shape_n {
+                line = self.dequantize(k, n, quantization, line_size);
+            }
+
+            slice[unit_position / line_size] = line;
+        }
+
+        RhsReader::new(self.stage)
+    }
+
+    /// Advance the view along the k dimension by a specified offset, `k_offset`.
+    pub fn advance_view(&mut self, k_offset: u32) {
+        self.k_offset += k_offset;
+    }
+
+    /// Reads and dequantizes the line of values starting at row `k` and column `n`
+    fn dequantize(
+        &self,
+        k: u32,
+        n: u32,
+        #[comptime] quantization: QuantizationConfig,
+        #[comptime] line_size: u32,
+    ) -> Line<ES> {
+        let mut line = Line::empty(line_size);
+        let scales_position =
+            self.scales_batch_offset + (k / self.group_size) * self.scales_stride_group;
+
+        let mut values = Line::<f32>::empty(line_size);
+        match comptime!(quantization.format) {
+            QuantizedFormat::Int8 => {
+                let position = self.batch_offset + k * self.stride_k + n * self.stride_n;
+                values = Line::cast_from(unsafe {
+                    *(*self.values).index_unchecked(position / line_size)
+                });
+            }
+            QuantizedFormat::Int4 => {
+                #[unroll]
+                for i in 0..line_size {
+                    let n_i = n + i;
+                    let position =
+                        self.batch_offset + k * self.stride_k + (n_i / 8) * self.stride_n;
+                    let word =
+                        u32::cast_from(unsafe { *(*self.values).index_unchecked(position) }[0]);
+                    let nibble = (word >> ((n_i % 8) * 4)) & 15;
+
+                    // The values are signed, in two's complement.
+                    let value = f32::cast_from(nibble);
+                    values[i] = select(nibble >= 8, value - 16., value);
+                }
+            }
+        }
+
+        #[unroll]
+        for i in 0..line_size {
+            let position = scales_position + (n + i) * self.scales_stride_n;
+            let scale = f32::cast_from(unsafe { *(*self.scales).index_unchecked(position) }[0]);
+            let mut value = values[i];
+
+            if comptime!(quantization.zero_points) {
+                value -=
+                    f32::cast_from(unsafe { *(*self.zero_points).index_unchecked(position) }[0]);
+            }
+
+            line[i] = ES::cast_from(value * scale);
+        }
+
+        line
+    }
+}
@@ -0,0 +1,5 @@
+mod base;
+mod loader;
+
+pub use base::*;
+pub use loader::*;
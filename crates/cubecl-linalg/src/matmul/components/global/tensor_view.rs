@@ -1,5 +1,6 @@
 use crate::matmul::components::config::InputIdent;
 use crate::matmul::components::global;
+use crate::matmul::components::global::Epilogue;
 use crate::matmul::components::{Ident, MatrixLayout};
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
@@ -48,7 +49,6 @@ impl<EG: Numeric> TensorReader<EG> {
         let stride_y = tensor.stride(rank - 1);
         let shape_x = tensor.shape(rank - 2);
         let shape_y = tensor.shape(rank - 1);
-        let stride_b = tensor.stride(rank - 3);
 
         TensorReader::<EG> {
             tensor,
@@ -58,7 +58,7 @@ impl<EG: Numeric> TensorReader<EG> {
             stride_y,
             shape_x,
             shape_y,
-            batch_offset: nth_batch * stride_b,
+            batch_offset: batch_offset(tensor, nth_batch),
         }
     }
 
@@ -153,7 +153,6 @@ impl<EG: Numeric> TensorWriter<EG> {
         let stride_y = tensor.stride(rank - 1);
         let shape_x = tensor.shape(rank - 2);
         let shape_y = tensor.shape(rank - 1);
-        let stride_b = tensor.stride(rank - 3);
 
         TensorWriter::<EG> {
             tensor,
@@ -163,19 +162,22 @@ impl<EG: Numeric> TensorWriter<EG> {
             stride_y,
             shape_x,
             shape_y,
-            batch_offset: nth_batch * stride_b,
+            batch_offset: batch_offset(tensor, nth_batch),
         }
     }
 
     /// Writes data into the tensor view at the specified coordinates (write_x, write_y).
     ///
     /// Each unit writes one line in a coalesced manner for improved efficiency, assuming row-major layout.
-    pub fn write_coalesced<ES: Numeric, G: global::Config>(
+    /// The epilogue is applied to lines that are within bounds.
+    #[allow(clippy::too_many_arguments)]
+    pub fn write_coalesced<ES: Numeric, E: Epilogue<EG>, G: global::Config>(
         &mut self,
         tile_x: u32,
         tile_y: u32,
         unit_id: u32,
         value: Line<ES>,
+        epilogue: &E,
         #[comptime] config: G,
     ) {
         let stage_dim = config.stage_dim(Ident::Out);
@@ -193,21 +195,33 @@ impl<EG: Numeric> TensorWriter<EG> {
         match comptime!((config.check_m_bounds(), config.check_n_bounds())) {
             (true, true) => {
                 if view_x < self.shape_x && view_y < self.shape_y {
-                    self.write(write_position, Line::cast_from(value));
+                    self.write(
+                        write_position,
+                        E::apply::<G>(epilogue, Line::cast_from(value), view_x, view_y, config),
+                    );
                 }
             }
             (true, false) => {
                 if view_x < self.shape_x {
-                    self.write(write_position, Line::cast_from(value));
+                    self.write(
+                        write_position,
+                        E::apply::<G>(epilogue, Line::cast_from(value), view_x, view_y, config),
+                    );
                 }
             }
             (false, true) => {
                 if view_y < self.shape_y {
-                    self.write(write_position, Line::cast_from(value));
+                    self.write(
+                        write_position,
+                        E::apply::<G>(epilogue, Line::cast_from(value), view_x, view_y, config),
+                    );
                 }
             }
             (false, false) => {
-                self.write(write_position, Line::cast_from(value));
+                self.write(
+                    write_position,
+                    E::apply::<G>(epilogue, Line::cast_from(value), view_x, view_y, config),
+                );
             }
         }
     }
@@ -216,3 +230,24 @@ impl<EG: Numeric> TensorWriter<EG> {
         unsafe { (*self.tensor).index_assign_unchecked(position, value) }
     }
 }
+
+/// Returns the offset of the `nth_batch` matrix of the tensor, the batches being ordered
+/// like in a contiguous tensor of the same shape.
+///
+/// Broadcast batch dimensions, with a stride of 0, always map to the same matrix.
+#[cube]
+pub fn batch_offset<E: Numeric>(tensor: &Tensor<Line<E>>, nth_batch: u32) -> u32 {
+    let rank = tensor.rank();
+    let mut remaining = nth_batch;
+    let mut offset = 0;
+
+    for i in 0..rank - 2 {
+        let axis = rank - 3 - i;
+        let shape = tensor.shape(axis);
+
+        offset += (remaining % shape) * tensor.stride(axis);
+        remaining /= shape;
+    }
+
+    offset
+}
@@ -1,5 +1,5 @@
 use crate::matmul::components::global::tensor_view::TensorWriter;
-use crate::matmul::components::global::Config;
+use crate::matmul::components::global::{Config, Epilogue};
 use crate::matmul::components::Ident;
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
@@ -11,8 +11,9 @@ pub struct TilewiseUnloading {}
 
 #[cube]
 impl TilewiseUnloading {
-    pub fn unload_from_slice<EG: Numeric, ES: Numeric, G: Config>(
+    pub fn unload_from_slice<EG: Numeric, ES: Numeric, E: Epilogue<EG>, G: Config>(
         write_view: &mut TensorWriter<EG>,
+        epilogue: &E,
         slice: Slice<Line<ES>>,
         tile_x: u32,
         tile_y: u32,
@@ -33,7 +34,8 @@ impl TilewiseUnloading {
             let unit_write = UNIT_POS_X * out_line_size + i * unit_step;
 
             let value = slice[unit_write / out_line_size];
-            write_view.write_coalesced::<ES, G>(tile_x, tile_y, unit_write, value, config);
+            write_view
+                .write_coalesced::<ES, E, G>(tile_x, tile_y, unit_write, value, epilogue, config);
         }
     }
 }
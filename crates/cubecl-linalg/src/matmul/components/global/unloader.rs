@@ -4,15 +4,17 @@ use cubecl_core::prelude::*;
 use crate::matmul::components::global;
 use crate::matmul::components::global::tensor_view::TensorWriter;
 use crate::matmul::components::global::tilewise_unloading::TilewiseUnloading;
+use crate::matmul::components::global::{Epilogue, EpilogueInputs};
 use crate::matmul::components::stage::StageWriter;
 
 #[derive(CubeType)]
-pub struct Unloader<EG: Numeric> {
+pub struct Unloader<EG: Numeric, E: Epilogue<EG>> {
     pub tensor_view: TensorWriter<EG>,
+    pub epilogue: E,
 }
 
 #[cube]
-impl<EG: Numeric> global::Unloader<EG> for Unloader<EG> {
+impl<EG: Numeric, E: Epilogue<EG>> global::Unloader<EG> for Unloader<EG, E> {
     type StageWriter = Self;
 
     fn as_stage_writer<G: global::Config>(this: Self) -> Self::StageWriter {
@@ -21,21 +23,23 @@ impl<EG: Numeric> global::Unloader<EG> for Unloader<EG> {
 }
 
 #[cube]
-impl<EG: Numeric> Unloader<EG> {
+impl<EG: Numeric, E: Epilogue<EG>> Unloader<EG, E> {
     pub fn new(
         tensor: &mut Tensor<Line<EG>>,
         x_offset: u32,
         y_offset: u32,
         batch_offset: u32,
+        epilogue: &EpilogueInputs<EG>,
     ) -> Self {
-        Unloader::<EG> {
+        Unloader::<EG, E> {
             tensor_view: TensorWriter::new(tensor, x_offset, y_offset, batch_offset),
+            epilogue: E::new(epilogue, batch_offset),
         }
     }
 }
 
 #[cube]
-impl<EG: Numeric> StageWriter<EG> for Unloader<EG> {
+impl<EG: Numeric, E: Epilogue<EG>> StageWriter<EG> for Unloader<EG, E> {
     fn write<ES: Numeric, G: global::Config>(
         this: &mut Self,
         slice: Slice<Line<ES>>,
@@ -43,8 +47,9 @@ impl<EG: Numeric> StageWriter<EG> for Unloader<EG> {
         accumulator_offset: u32,
         #[comptime] config: G,
     ) {
-        TilewiseUnloading::unload_from_slice::<EG, ES, G>(
+        TilewiseUnloading::unload_from_slice::<EG, ES, E, G>(
             &mut this.tensor_view,
+            &this.epilogue,
             slice,
             compute_plane_offset,
             accumulator_offset,
@@ -7,16 +7,8 @@ use crate::matmul::components::{
 use crate::matmul::kernels::matmul::AdvancedConfig;
 use cubecl_core::{self as cubecl, Feature};
 use cubecl_core::{cmma, prelude::*};
-use half::{bf16, f16};
 use std::marker::PhantomData;
 
-/// Implementations are pairs of element types that are allowed for CMMA
-pub trait CmmaValid<I: Numeric, O: Numeric> {}
-
-impl CmmaValid<f16, f16> for (f16, f16) {}
-impl CmmaValid<f16, f32> for (f16, f32) {}
-impl CmmaValid<bf16, f32> for (bf16, f32) {}
-
 #[derive(CubeType)]
 /// Wrapper over a CMMA matrix, containing the stride which implies the layout
 pub struct Fragment<T: Numeric> {
@@ -32,10 +24,7 @@ macro_rules! instruction {
         }
 
         #[cube]
-        impl<I: Numeric, O: Numeric> tile::Matmul<I, O> for $name<I, O>
-        where
-            (I, O): CmmaValid<I, O>,
-        {
+        impl<I: Numeric, O: Numeric> tile::Matmul<I, O> for $name<I, O> {
             const M: u32 = $m;
             const N: u32 = $n;
             const K: u32 = $k;
@@ -86,10 +75,7 @@ macro_rules! instruction {
             }
         }
 
-        impl<I: Numeric, O: Numeric> MatmulKernel<I, O> for $name<I, O>
-        where
-            (I, O): CmmaValid<I, O>,
-        {
+        impl<I: Numeric, O: Numeric> MatmulKernel<I, O> for $name<I, O> {
             type Config = Config;
 
             fn check_config(config: Self::Config) {
@@ -0,0 +1,190 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+use crate::matmul::components::global::tensor_view::batch_offset;
+
+#[derive(CubeType, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+/// Which operand of the matmul is the vector
+pub enum GemvKind {
+    /// The lhs is a row vector (m == 1), multiplied by the rhs matrix
+    VectorMatrix,
+    /// The rhs is a column vector (n == 1), multiplying the lhs matrix
+    MatrixVector,
+}
+
+#[derive(CubeType, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+/// How the matrix is read by the units
+pub enum GemvStrategy {
+    /// The matrix is contiguous along k: each plane computes one output value,
+    /// its units reading lines along k before summing their partial dot products
+    /// with a plane reduction. The cube must be exactly one plane wide.
+    PlaneReduce,
+    /// The matrix is contiguous along its rows: each unit computes a line of output values,
+    /// iterating over the whole k dimension.
+    UnitRows,
+}
+
+#[derive(CubeType, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+/// Configuration of the gemv kernel
+pub struct GemvConfig {
+    pub kind: GemvKind,
+    pub strategy: GemvStrategy,
+    /// Number of planes in each cube with [GemvStrategy::PlaneReduce],
+    /// each of them computing one output value
+    pub rows_per_cube: u32,
+    /// Line size of the vectorized operands
+    pub line_size: u32,
+}
+
+#[cube(launch_unchecked)]
+/// Multiplies a matrix by a vector, with one batch per cube along z.
+/// Products are accumulated as `EA`.
+///
+/// With [GemvStrategy::PlaneReduce], the matrix and the vector share the same line size along k,
+/// and the output is not vectorized.
+/// With [GemvStrategy::UnitRows], the matrix and the output share the same line size along the rows,
+/// and the vector is not vectorized.
+pub(crate) fn gemv_kernel<EL: Numeric, ER: Numeric, EA: Numeric, EO: Numeric>(
+    lhs: &Tensor<Line<EL>>,
+    rhs: &Tensor<Line<ER>>,
+    out: &mut Tensor<Line<EO>>,
+    #[comptime] config: GemvConfig,
+) {
+    let rank = out.rank();
+    let lhs_offset = batch_offset(lhs, CUBE_POS_Z);
+    let rhs_offset = batch_offset(rhs, CUBE_POS_Z);
+    let out_offset = batch_offset(out, CUBE_POS_Z);
+
+    match comptime!(config.kind) {
+        GemvKind::VectorMatrix => {
+            // The rows of the matrix are the columns of the rhs.
+            let view = GemvView {
+                num_rows: rhs.shape(rank - 1),
+                k: rhs.shape(rank - 2),
+                matrix_offset: rhs_offset,
+                matrix_stride_row: rhs.stride(rank - 1),
+                matrix_stride_k: rhs.stride(rank - 2),
+                vector_offset: lhs_offset,
+                vector_stride_k: lhs.stride(rank - 1),
+                out_offset,
+                out_stride_row: out.stride(rank - 1),
+            };
+
+            gemv::<ER, EL, EA, EO>(rhs, lhs, out, view, config);
+        }
+        GemvKind::MatrixVector => {
+            let view = GemvView {
+                num_rows: lhs.shape(rank - 2),
+                k: lhs.shape(rank - 1),
+                matrix_offset: lhs_offset,
+                matrix_stride_row: lhs.stride(rank - 2),
+                matrix_stride_k: lhs.stride(rank - 1),
+                vector_offset: rhs_offset,
+                vector_stride_k: rhs.stride(rank - 2),
+                out_offset,
+                out_stride_row: out.stride(rank - 2),
+            };
+
+            gemv::<EL, ER, EA, EO>(lhs, rhs, out, view, config);
+        }
+    }
+}
+
+#[derive(CubeType)]
+/// Shapes, strides and batch offsets of the operands, seen as a matrix of `num_rows` rows
+/// multiplied by a vector of length `k`
+struct GemvView {
+    num_rows: u32,
+    k: u32,
+    matrix_offset: u32,
+    matrix_stride_row: u32,
+    matrix_stride_k: u32,
+    vector_offset: u32,
+    vector_stride_k: u32,
+    out_offset: u32,
+    out_stride_row: u32,
+}
+
+#[cube]
+fn gemv<EM: Numeric, EV: Numeric, EA: Numeric, EO: Numeric>(
+    matrix: &Tensor<Line<EM>>,
+    vector: &Tensor<Line<EV>>,
+    out: &mut Tensor<Line<EO>>,
+    view: GemvView,
+    #[comptime] config: GemvConfig,
+) {
+    match comptime!(config.strategy) {
+        GemvStrategy::PlaneReduce => {
+            plane_reduce::<EM, EV, EA, EO>(matrix, vector, out, view, config)
+        }
+        GemvStrategy::UnitRows => unit_rows::<EM, EV, EA, EO>(matrix, vector, out, view, config),
+    }
+}
+
+#[cube]
+fn plane_reduce<EM: Numeric, EV: Numeric, EA: Numeric, EO: Numeric>(
+    matrix: &Tensor<Line<EM>>,
+    vector: &Tensor<Line<EV>>,
+    out: &mut Tensor<Line<EO>>,
+    view: GemvView,
+    #[comptime] config: GemvConfig,
+) {
+    let line_size = config.line_size;
+    let row = CUBE_POS_X * config.rows_per_cube + UNIT_POS_Y;
+
+    // The condition is uniform within a plane, so all its units take part in the reduction.
+    if row < view.num_rows {
+        let matrix_row_offset = view.matrix_offset + row * view.matrix_stride_row;
+        let num_lines = view.k / line_size;
+        let mut acc = Line::<EA>::empty(line_size).fill(EA::from_int(0));
+
+        for line in range_stepped(UNIT_POS_X, num_lines, CUBE_DIM_X) {
+            let k = line * line_size;
+            let matrix_line = matrix[(matrix_row_offset + k * view.matrix_stride_k) / line_size];
+            let vector_line = vector[(view.vector_offset + k * view.vector_stride_k) / line_size];
+
+            acc += Line::cast_from(matrix_line) * Line::cast_from(vector_line);
+        }
+
+        let mut sum = EA::from_int(0);
+        #[unroll]
+        for i in 0..line_size {
+            sum += acc[i];
+        }
+        sum = plane_sum(sum);
+
+        if UNIT_POS_X == 0 {
+            out[view.out_offset + row * view.out_stride_row] = Line::cast_from(sum);
+        }
+    }
+}
+
+#[cube]
+fn unit_rows<EM: Numeric, EV: Numeric, EA: Numeric, EO: Numeric>(
+    matrix: &Tensor<Line<EM>>,
+    vector: &Tensor<Line<EV>>,
+    out: &mut Tensor<Line<EO>>,
+    view: GemvView,
+    #[comptime] config: GemvConfig,
+) {
+    let line_size = config.line_size;
+    let row = (CUBE_POS_X * CUBE_DIM + UNIT_POS) * line_size;
+
+    if row < view.num_rows {
+        let mut acc = Line::<EA>::empty(line_size).fill(EA::from_int(0));
+        let mut matrix_position = view.matrix_offset + row * view.matrix_stride_row;
+        let mut vector_position = view.vector_offset;
+
+        for _ in 0..view.k {
+            let matrix_line = matrix[matrix_position / line_size];
+            let value = EA::cast_from(vector[vector_position][0]);
+
+            acc += Line::cast_from(matrix_line) * Line::empty(line_size).fill(value);
+
+            matrix_position += view.matrix_stride_k;
+            vector_position += view.vector_stride_k;
+        }
+
+        out[(view.out_offset + row * view.out_stride_row) / line_size] = Line::cast_from(acc);
+    }
+}
@@ -0,0 +1,189 @@
+use cubecl_core::prelude::*;
+use cubecl_core::{tensor_line_size, Feature};
+
+use crate::tensor::{
+    broadcast_batches, into_contiguous, matrix_layout, MatrixLayout, TensorHandle,
+};
+
+use super::base::{gemv_kernel, GemvConfig, GemvKind, GemvStrategy};
+
+/// Number of planes in each cube with [GemvStrategy::PlaneReduce]
+const PLANES_PER_CUBE: u32 = 8;
+
+/// Whether the matmul of the given shapes is a matrix-vector product that can run on the client,
+/// i.e. m == 1 or n == 1.
+pub fn is_available<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs_shape: &[usize],
+    rhs_shape: &[usize],
+) -> bool {
+    lhs_shape.len() >= 2
+        && rhs_shape.len() >= 2
+        && (lhs_shape[lhs_shape.len() - 2] == 1 || rhs_shape[rhs_shape.len() - 1] == 1)
+        && client.properties().feature_enabled(Feature::Plane)
+}
+
+/// Matrix-vector multiplication, where m == 1 or n == 1, accumulated as `EA`.
+pub fn launch<R: Runtime, EG: Numeric, EA: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandle<R, EG>,
+    rhs: TensorHandle<R, EG>,
+    out: TensorHandle<R, EG>,
+) -> TensorHandle<R, EG> {
+    launch_ref::<R, EG, EG, EA, EG>(client, lhs.as_ref(), rhs.as_ref(), out.as_ref());
+
+    out
+}
+
+/// Matrix-vector multiplication, where m == 1 or n == 1.
+///
+/// The inputs are cast to `EA` when multiplied, and the dot products are cast to the output element.
+pub fn launch_ref<R: Runtime, EL: Numeric, ER: Numeric, EA: Numeric, EO: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+) {
+    // Batch dimensions missing or of size 1 are broadcast with a stride of 0.
+    let batches = &out.shape[..out.shape.len() - 2];
+    let (lhs_shape, lhs_strides) = broadcast_batches(lhs.shape, lhs.strides, batches);
+    let (rhs_shape, rhs_strides) = broadcast_batches(rhs.shape, rhs.strides, batches);
+    let lhs = unsafe {
+        TensorHandleRef::from_raw_parts(lhs.handle, &lhs_strides, &lhs_shape, lhs.elem_size)
+    };
+    let rhs = unsafe {
+        TensorHandleRef::from_raw_parts(rhs.handle, &rhs_strides, &rhs_shape, rhs.elem_size)
+    };
+
+    let rank = out.shape.len();
+    let kind = match (lhs.shape[rank - 2], rhs.shape[rank - 1]) {
+        (_, 1) => GemvKind::MatrixVector,
+        (1, _) => GemvKind::VectorMatrix,
+        _ => panic!("Gemv requires m == 1 or n == 1"),
+    };
+
+    // The vector is read with its strides, so only the matrix needs a usable layout.
+    let make_contiguous = |tensor: &TensorHandleRef<'_, R>| {
+        matches!(matrix_layout(tensor.strides), MatrixLayout::HighlyPermuted)
+    };
+
+    match kind {
+        GemvKind::MatrixVector if make_contiguous(&lhs) => gemv_ref_no_check::<R, EL, ER, EA, EO>(
+            client,
+            into_contiguous::<R, EL>(client, lhs).as_ref(),
+            rhs,
+            out,
+            kind,
+        ),
+        GemvKind::VectorMatrix if make_contiguous(&rhs) => gemv_ref_no_check::<R, EL, ER, EA, EO>(
+            client,
+            lhs,
+            into_contiguous::<R, ER>(client, rhs).as_ref(),
+            out,
+            kind,
+        ),
+        _ => gemv_ref_no_check::<R, EL, ER, EA, EO>(client, lhs, rhs, out, kind),
+    }
+}
+
+fn gemv_ref_no_check<R: Runtime, EL: Numeric, ER: Numeric, EA: Numeric, EO: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    kind: GemvKind,
+) {
+    let rank = out.shape.len();
+    let num_batches = out.shape[..rank - 2].iter().product::<usize>() as u32;
+
+    // Axes of the rows and of k in the matrix, and of k in the vector.
+    let (matrix, vector, row_axis, k_axis, vector_k_axis) = match kind {
+        GemvKind::MatrixVector => (&lhs, &rhs, rank - 2, rank - 1, rank - 2),
+        GemvKind::VectorMatrix => (&rhs, &lhs, rank - 1, rank - 2, rank - 1),
+    };
+    let num_rows = matrix.shape[row_axis] as u32;
+
+    // The plane reduction needs cubes exactly one plane wide, so a known plane size.
+    let hardware = client.properties().hardware_properties();
+    let plane_dim = hardware.plane_size_min;
+
+    let strategy = match matrix.strides[k_axis] == 1
+        && matrix.shape[k_axis] > 1
+        && hardware.plane_size_min == hardware.plane_size_max
+    {
+        true => GemvStrategy::PlaneReduce,
+        false => GemvStrategy::UnitRows,
+    };
+
+    let (matrix_line_size, vector_line_size, out_line_size) = match strategy {
+        GemvStrategy::PlaneReduce => {
+            let line_size = Ord::min(
+                line_size(matrix, k_axis, row_axis),
+                line_size(vector, vector_k_axis, vector_k_axis),
+            );
+            (line_size, line_size, 1)
+        }
+        GemvStrategy::UnitRows => {
+            let line_size = Ord::min(
+                line_size(matrix, row_axis, k_axis),
+                line_size(&out, row_axis, row_axis),
+            );
+            (line_size, 1, line_size)
+        }
+    };
+    let (lhs_line_size, rhs_line_size) = match kind {
+        GemvKind::MatrixVector => (matrix_line_size, vector_line_size),
+        GemvKind::VectorMatrix => (vector_line_size, matrix_line_size),
+    };
+
+    let (cube_dim, cube_count) = match strategy {
+        GemvStrategy::PlaneReduce => (
+            CubeDim::new(plane_dim, PLANES_PER_CUBE, 1),
+            CubeCount::Static(num_rows.div_ceil(PLANES_PER_CUBE), 1, num_batches),
+        ),
+        GemvStrategy::UnitRows => {
+            let cube_dim = CubeDim::default();
+            let num_lines = num_rows / matrix_line_size as u32;
+
+            (
+                cube_dim,
+                CubeCount::Static(num_lines.div_ceil(cube_dim.num_elems()), 1, num_batches),
+            )
+        }
+    };
+
+    let config = GemvConfig {
+        kind,
+        strategy,
+        rows_per_cube: PLANES_PER_CUBE,
+        line_size: matrix_line_size as u32,
+    };
+
+    unsafe {
+        gemv_kernel::launch_unchecked::<EL, ER, EA, EO, R>(
+            client,
+            cube_count,
+            cube_dim,
+            TensorArg::<R>::from_raw_parts::<EL>(lhs.handle, lhs.strides, lhs.shape, lhs_line_size),
+            TensorArg::<R>::from_raw_parts::<ER>(rhs.handle, rhs.strides, rhs.shape, rhs_line_size),
+            TensorArg::<R>::from_raw_parts::<EO>(out.handle, out.strides, out.shape, out_line_size),
+            config,
+        );
+    }
+}
+
+/// Largest line size along `axis`, such that moving along `other_axis` stays aligned on lines
+fn line_size<R: Runtime>(tensor: &TensorHandleRef<'_, R>, axis: usize, other_axis: usize) -> u8 {
+    let mut line_size = tensor_line_size(
+        R::supported_line_sizes(),
+        tensor.shape,
+        tensor.strides,
+        axis,
+    );
+
+    while tensor.strides[other_axis] % line_size as usize != 0 {
+        line_size /= 2;
+    }
+
+    line_size
+}
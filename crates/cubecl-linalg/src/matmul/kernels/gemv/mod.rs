@@ -0,0 +1,5 @@
+mod base;
+mod launch;
+
+pub use base::{GemvConfig, GemvKind, GemvStrategy};
+pub use launch::{is_available, launch, launch_ref};
@@ -2,25 +2,60 @@ use cubecl_core::prelude::*;
 
 use crate::matmul::components::stage::{self};
 use crate::matmul::components::{batch, global, tile};
-use crate::matmul::components::{MatmulKernel, MatmulProblem};
+use crate::matmul::components::{MatmulKernel, MatmulLaunch, MatmulProblem};
 use crate::matmul::kernels::matmul::AdvancedConfig;
 
-type LhsStageReader<GMM, EG, ES> = <<GMM as global::Matmul<EG, ES>>::LhsLoader as global::Loader<
-    EG,
-    ES,
-    <GMM as MatmulKernel<EG, EG>>::Config,
->>::StageReader;
-type RhsStageReader<GMM, EG, ES> = <<GMM as global::Matmul<EG, ES>>::RhsLoader as global::Loader<
-    EG,
-    ES,
-    <GMM as MatmulKernel<EG, EG>>::Config,
->>::StageReader;
-
-/// Specifications for a matmul algorithm
-pub trait Algorithm<EG: Numeric> {
+type LhsStageReader<GMM, EL, ER, EO, ES> =
+    <<GMM as global::Matmul<EL, ER, EO, ES>>::LhsLoader as global::Loader<
+        EL,
+        ES,
+        <GMM as MatmulKernel<EL, EO>>::Config,
+    >>::StageReader;
+type RhsStageReader<GMM, EL, ER, EO, ES> =
+    <<GMM as global::Matmul<EL, ER, EO, ES>>::RhsLoader as global::Loader<
+        ER,
+        ES,
+        <GMM as MatmulKernel<EL, EO>>::Config,
+    >>::StageReader;
+
+/// Element types of a matmul
+///
+/// Lhs and rhs are read as `EL` and `ER` and cast to `ES` in shared memory,
+/// products are accumulated as `EA`, and the output is written as `EO`.
+pub trait MatmulPrecision: Send + Sync + 'static {
+    type EL: Numeric;
+    type ER: Numeric;
+    type ES: Numeric;
+    type EA: Numeric;
+    type EO: Numeric;
+}
+
+impl<EL: Numeric, ER: Numeric, ES: Numeric, EA: Numeric, EO: Numeric> MatmulPrecision
+    for (EL, ER, ES, EA, EO)
+{
+    type EL = EL;
+    type ER = ER;
+    type ES = ES;
+    type EA = EA;
+    type EO = EO;
+}
+
+/// Precision of the partial sums of `P`, which are written as its accumulator type
+pub type PartialSumPrecision<P> = (
+    <P as MatmulPrecision>::EL,
+    <P as MatmulPrecision>::ER,
+    <P as MatmulPrecision>::ES,
+    <P as MatmulPrecision>::EA,
+    <P as MatmulPrecision>::EA,
+);
+
+/// Specifications for a matmul algorithm, with its own element types for lhs, rhs and output
+pub trait MixedAlgorithm {
     const PLANE_DIM: u32;
 
-    type EG: Numeric;
+    type EL: Numeric;
+    type ER: Numeric;
+    type EO: Numeric;
     type ES: Numeric;
     type EA: Numeric;
 
@@ -28,24 +63,24 @@ pub trait Algorithm<EG: Numeric> {
 
     type StageMatmul: stage::Matmul<
             Self::ES,
-            Self::EG,
-            LhsReader = LhsStageReader<Self::GlobalMatmul, Self::EG, Self::ES>,
-            RhsReader = RhsStageReader<Self::GlobalMatmul, Self::EG, Self::ES>,
-        > + MatmulKernel<Self::ES, Self::EG>;
+            Self::EO,
+            LhsReader = LhsStageReader<Self::GlobalMatmul, Self::EL, Self::ER, Self::EO, Self::ES>,
+            RhsReader = RhsStageReader<Self::GlobalMatmul, Self::EL, Self::ER, Self::EO, Self::ES>,
+        > + MatmulKernel<Self::ES, Self::EO>;
 
-    type GlobalMatmul: global::Matmul<Self::EG, Self::ES>;
+    type GlobalMatmul: global::Matmul<Self::EL, Self::ER, Self::EO, Self::ES>;
 
-    type BatchMatmul: batch::Matmul<Self::EG> + MatmulKernel<Self::EG, Self::EG>;
+    type BatchMatmul: MatmulLaunch<Self::EL, Self::EO, Config: batch::Config>;
 
     fn cube_dim() -> CubeDim;
-    fn cube_count(problem: &MatmulProblem) -> CubeCount;
+    fn cube_count(problem: &MatmulProblem, advanced_config: &AdvancedConfig) -> CubeCount;
 
     fn make_config(
         problem: &MatmulProblem,
         cube_dim: &CubeDim,
         cube_count: &CubeCount,
         advanced_config: &AdvancedConfig,
-    ) -> <Self::BatchMatmul as MatmulKernel<Self::EG, Self::EG>>::Config {
+    ) -> <Self::BatchMatmul as MatmulKernel<Self::EL, Self::EO>>::Config {
         let config = Self::BatchMatmul::make_config(problem, cube_dim, cube_count, advanced_config);
         problem.check_config(&config);
         config
@@ -57,3 +92,11 @@ pub trait Algorithm<EG: Numeric> {
         Self::BatchMatmul::check_availability::<R>(client)
     }
 }
+
+/// Matmul algorithm reading lhs and rhs and writing the output as `EG`
+///
+/// Alias of [MixedAlgorithm] for algorithms with a single global element type, which
+/// is the precision `(EG, EG, ES, EA, EG)`.
+pub trait Algorithm<EG: Numeric>: MixedAlgorithm<EL = EG, ER = EG, EO = EG> {}
+
+impl<EG: Numeric, A: MixedAlgorithm<EL = EG, ER = EG, EO = EG>> Algorithm<EG> for A {}
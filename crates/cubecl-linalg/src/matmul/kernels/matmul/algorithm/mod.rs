@@ -3,6 +3,8 @@ mod selection;
 
 pub mod cmma;
 pub mod plane_mma;
+pub mod split_k;
+pub mod stream_k;
 
-pub use base::Algorithm;
+pub use base::{Algorithm, MatmulPrecision, MixedAlgorithm};
 pub use selection::*;
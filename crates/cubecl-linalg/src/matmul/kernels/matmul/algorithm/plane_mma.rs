@@ -7,36 +7,51 @@ use crate::matmul::components::tile::plane::PlaneMma16x16x16;
 use crate::matmul::components::tile::Matmul;
 use crate::matmul::components::MatmulProblem;
 use crate::matmul::components::{batch, global};
+use crate::matmul::kernels::matmul::AdvancedConfig;
 
-use super::base;
+use super::base::{self, MatmulPrecision};
 
-pub struct PlaneMma<EG> {
-    pub _eg: PhantomData<EG>,
+/// Matmul using plane operations, with a stage made of `Stage` tiles in each dimension
+pub struct PlaneMma<P: MatmulPrecision, Stage: StageSize = S4x4x2> {
+    pub _precision: PhantomData<P>,
+    pub _stage: PhantomData<Stage>,
 }
 
-impl<EG: Numeric> base::Algorithm<EG> for PlaneMma<EG> {
+/// Precision of [PlaneMma] when lhs, rhs and out are `EG`, staged as f32 and accumulated as f32
+pub type PlaneMmaPrecision<EG> = (EG, EG, f32, f32, EG);
+
+impl<P: MatmulPrecision, Stage: StageSize> base::MixedAlgorithm for PlaneMma<P, Stage> {
     const PLANE_DIM: u32 = 32;
-    type EG = EG;
-    type ES = f32;
-    type EA = f32;
+    type EL = P::EL;
+    type ER = P::ER;
+    type EO = P::EO;
+    type ES = P::ES;
+    type EA = P::EA;
 
     type TileMatmul = PlaneMma16x16x16<Self::ES, Self::EA>;
 
     type StageMatmul =
-        stage::multi_buffer::Matmul<Self::ES, Self::EG, Self::EA, Self::TileMatmul, S4x4x2>;
+        stage::multi_buffer::Matmul<Self::ES, Self::EO, Self::EA, Self::TileMatmul, Stage>;
 
-    type GlobalMatmul = global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+    type GlobalMatmul =
+        global::homogeneous::Matmul<Self::EL, Self::ER, Self::EO, Self::ES, Self::StageMatmul>;
 
-    type BatchMatmul =
-        batch::one_to_one::Matmul<Self::EG, Self::ES, Self::GlobalMatmul, batch::NaturalDispatch>;
+    type BatchMatmul = batch::one_to_one::Matmul<
+        Self::EL,
+        Self::ER,
+        Self::EO,
+        Self::ES,
+        Self::GlobalMatmul,
+        batch::NaturalDispatch,
+    >;
 
     fn cube_dim() -> CubeDim {
-        CubeDim::new(Self::PLANE_DIM, S4x4x2::NUM_M, 1)
+        CubeDim::new(Self::PLANE_DIM, Stage::NUM_M, 1)
     }
 
-    fn cube_count(problem: &MatmulProblem) -> CubeCount {
-        let m_stage = S4x4x2::NUM_M * Self::TileMatmul::M;
-        let n_stage = S4x4x2::NUM_N * Self::TileMatmul::K;
+    fn cube_count(problem: &MatmulProblem, _advanced_config: &AdvancedConfig) -> CubeCount {
+        let m_stage = Stage::NUM_M * Self::TileMatmul::M;
+        let n_stage = Stage::NUM_N * Self::TileMatmul::K;
         let cubes_needed_m = (problem.m as u32 + m_stage - 1) / m_stage;
         let cubes_needed_n = (problem.n as u32 + n_stage - 1) / n_stage;
 
@@ -1,39 +1,128 @@
-use cubecl_core::{
-    client::ComputeClient,
-    prelude::{Numeric, TensorHandleRef},
-    Runtime,
-};
+use cubecl_core::{client::ComputeClient, prelude::TensorHandleRef, Runtime};
 
-use crate::matmul::{components::MatmulProblem, kernels::matmul::base::matmul_cube_preparation};
+use crate::matmul::{
+    components::{
+        stage::{S2x2x2, S4x4x2, S8x8x1, StageSize},
+        MatmulProblem,
+    },
+    kernels::matmul::{
+        base::matmul_cube_preparation, Decomposition, MatmulEpilogue, MatmulSelection,
+        StageSelection,
+    },
+};
 
-use super::{cmma::Cmma, plane_mma::PlaneMma};
+use super::{
+    base::{MatmulPrecision, PartialSumPrecision},
+    cmma::Cmma,
+    plane_mma::PlaneMma,
+    split_k::SplitK,
+    stream_k::StreamK,
+};
 
 pub struct CmmaSelector;
 
 impl CmmaSelector {
-    pub fn select_kernel<R: Runtime, EG: Numeric>(
+    pub fn select_kernel<R: Runtime, P: MatmulPrecision>(
         client: &ComputeClient<R::Server, R::Channel>,
         lhs: TensorHandleRef<'_, R>,
         rhs: TensorHandleRef<'_, R>,
         out: TensorHandleRef<'_, R>,
+        epilogue: &MatmulEpilogue<'_, R>,
+        selection: &MatmulSelection,
         problem: MatmulProblem,
     ) {
-        // TODO if problem.m < problem.n...
-        matmul_cube_preparation::<R, EG, Cmma<EG>>(client, lhs, rhs, out, problem);
+        match selection.stage {
+            StageSelection::S2x2x2 => Self::select_decomposition::<R, P, S2x2x2>(
+                client, lhs, rhs, out, epilogue, selection, problem,
+            ),
+            StageSelection::S4x4x2 => Self::select_decomposition::<R, P, S4x4x2>(
+                client, lhs, rhs, out, epilogue, selection, problem,
+            ),
+            StageSelection::S8x8x1 => Self::select_decomposition::<R, P, S8x8x1>(
+                client, lhs, rhs, out, epilogue, selection, problem,
+            ),
+        }
+    }
+
+    fn select_decomposition<R: Runtime, P: MatmulPrecision, Stage: StageSize>(
+        client: &ComputeClient<R::Server, R::Channel>,
+        lhs: TensorHandleRef<'_, R>,
+        rhs: TensorHandleRef<'_, R>,
+        out: TensorHandleRef<'_, R>,
+        epilogue: &MatmulEpilogue<'_, R>,
+        selection: &MatmulSelection,
+        problem: MatmulProblem,
+    ) {
+        match selection.decomposition {
+            Decomposition::DataParallel => matmul_cube_preparation::<R, Cmma<P, Stage>>(
+                client, lhs, rhs, out, epilogue, selection, problem,
+            ),
+            Decomposition::SplitK { .. } => {
+                matmul_cube_preparation::<
+                    R,
+                    SplitK<Cmma<P, Stage>, Cmma<PartialSumPrecision<P>, Stage>>,
+                >(client, lhs, rhs, out, epilogue, selection, problem)
+            }
+            Decomposition::StreamK { .. } => {
+                matmul_cube_preparation::<
+                    R,
+                    StreamK<Cmma<P, Stage>, Cmma<PartialSumPrecision<P>, Stage>>,
+                >(client, lhs, rhs, out, epilogue, selection, problem)
+            }
+        }
     }
 }
 
 pub struct PlaneMmaSelector;
 
 impl PlaneMmaSelector {
-    pub fn select_kernel<R: Runtime, EG: Numeric>(
+    pub fn select_kernel<R: Runtime, P: MatmulPrecision>(
+        client: &ComputeClient<R::Server, R::Channel>,
+        lhs: TensorHandleRef<'_, R>,
+        rhs: TensorHandleRef<'_, R>,
+        out: TensorHandleRef<'_, R>,
+        epilogue: &MatmulEpilogue<'_, R>,
+        selection: &MatmulSelection,
+        problem: MatmulProblem,
+    ) {
+        match selection.stage {
+            StageSelection::S2x2x2 => Self::select_decomposition::<R, P, S2x2x2>(
+                client, lhs, rhs, out, epilogue, selection, problem,
+            ),
+            StageSelection::S4x4x2 => Self::select_decomposition::<R, P, S4x4x2>(
+                client, lhs, rhs, out, epilogue, selection, problem,
+            ),
+            StageSelection::S8x8x1 => Self::select_decomposition::<R, P, S8x8x1>(
+                client, lhs, rhs, out, epilogue, selection, problem,
+            ),
+        }
+    }
+
+    fn select_decomposition<R: Runtime, P: MatmulPrecision, Stage: StageSize>(
         client: &ComputeClient<R::Server, R::Channel>,
         lhs: TensorHandleRef<'_, R>,
         rhs: TensorHandleRef<'_, R>,
         out: TensorHandleRef<'_, R>,
+        epilogue: &MatmulEpilogue<'_, R>,
+        selection: &MatmulSelection,
         problem: MatmulProblem,
     ) {
-        // TODO if problem.m < problem.n...
-        matmul_cube_preparation::<R, EG, PlaneMma<EG>>(client, lhs, rhs, out, problem);
+        match selection.decomposition {
+            Decomposition::DataParallel => matmul_cube_preparation::<R, PlaneMma<P, Stage>>(
+                client, lhs, rhs, out, epilogue, selection, problem,
+            ),
+            Decomposition::SplitK { .. } => {
+                matmul_cube_preparation::<
+                    R,
+                    SplitK<PlaneMma<P, Stage>, PlaneMma<PartialSumPrecision<P>, Stage>>,
+                >(client, lhs, rhs, out, epilogue, selection, problem)
+            }
+            Decomposition::StreamK { .. } => {
+                matmul_cube_preparation::<
+                    R,
+                    StreamK<PlaneMma<P, Stage>, PlaneMma<PartialSumPrecision<P>, Stage>>,
+                >(client, lhs, rhs, out, epilogue, selection, problem)
+            }
+        }
     }
 }
@@ -0,0 +1,57 @@
+use std::marker::PhantomData;
+
+use cubecl_core::prelude::*;
+
+use crate::matmul::components::batch;
+use crate::matmul::components::MatmulProblem;
+use crate::matmul::kernels::matmul::{AdvancedConfig, Decomposition};
+
+use super::base;
+
+/// Runs the underlying algorithm with the iterations along k of all global matmuls
+/// evenly distributed between a fixed number of cubes
+///
+/// The partial sums are computed by `P`, the same algorithm as `A` with its output written as
+/// the accumulator type, and are summed into the output of `A`.
+pub struct StreamK<A, P> {
+    pub _a: PhantomData<A>,
+    pub _p: PhantomData<P>,
+}
+
+impl<A, P> base::MixedAlgorithm for StreamK<A, P>
+where
+    A: base::MixedAlgorithm,
+    P: base::MixedAlgorithm<EL = A::EL, ER = A::ER, ES = A::ES, EO = A::EA>,
+{
+    const PLANE_DIM: u32 = A::PLANE_DIM;
+    type EL = A::EL;
+    type ER = A::ER;
+    type EO = A::EO;
+    type ES = A::ES;
+    type EA = A::EA;
+
+    type TileMatmul = A::TileMatmul;
+
+    type StageMatmul = A::StageMatmul;
+
+    type GlobalMatmul = A::GlobalMatmul;
+
+    type BatchMatmul = batch::WithPartialSums<
+        Self::EL,
+        Self::ER,
+        Self::EA,
+        Self::EO,
+        batch::stream_k::Matmul<Self::EL, Self::ER, Self::EA, Self::ES, P::GlobalMatmul>,
+    >;
+
+    fn cube_dim() -> CubeDim {
+        A::cube_dim()
+    }
+
+    fn cube_count(_problem: &MatmulProblem, advanced_config: &AdvancedConfig) -> CubeCount {
+        match advanced_config.decomposition {
+            Decomposition::StreamK { cubes } => CubeCount::Static(cubes, 1, 1),
+            _ => panic!("Stream-K algorithm should be used with the stream-K decomposition"),
+        }
+    }
+}
@@ -1,19 +1,79 @@
+use std::fmt::Display;
+
 use cubecl_core::prelude::*;
 
 use cubecl_core::{
     client::ComputeClient,
     frontend::{TensorArg, TensorHandleRef},
+    server::Handle,
     tensor_line_size, Runtime,
 };
 
 use crate::matmul;
-use crate::matmul::components::{MatmulLaunch, MatmulProblem};
-use crate::tensor::{into_contiguous, matrix_layout, MatrixLayout, TensorHandle};
+use crate::matmul::components::global::EpilogueInputsLaunch;
+use crate::matmul::components::{MatmulLaunch, MatmulLaunchArgs, MatmulProblem};
+use crate::tensor::{
+    broadcast_batches, into_contiguous, matrix_layout, MatrixLayout, TensorHandle,
+};
+
+use super::algorithm::{CmmaSelector, MatmulPrecision, PlaneMmaSelector};
+use super::cmma::{Cmma, CmmaPrecision};
+use super::config::{AdvancedConfig, MatmulSelection};
+use super::epilogue::MatmulEpilogue;
+use super::plane_mma::PlaneMmaPrecision;
+use super::MixedAlgorithm;
+
+/// Options of a matrix multiplication launch, see [launch_ref].
+///
+/// The default options compute `lhs @ rhs` with the default [MatmulSelection], using cmma if
+/// available.
+pub struct MatmulOptions<'a, R: Runtime> {
+    /// Operations applied to the output in the same kernel
+    pub epilogue: MatmulEpilogue<'a, R>,
+    /// Stage size, tiling orders and decomposition of the kernel
+    pub selection: MatmulSelection,
+    /// Use the non-cmma implementation even when cmma is available
+    pub disable_cmma: bool,
+}
+
+impl<R: Runtime> Default for MatmulOptions<'_, R> {
+    fn default() -> Self {
+        Self {
+            epilogue: MatmulEpilogue::default(),
+            selection: MatmulSelection::default(),
+            disable_cmma: false,
+        }
+    }
+}
+
+/// Why a matrix multiplication couldn't be launched, see [launch_ref].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatmulLaunchError {
+    /// A tensor of the epilogue doesn't have the shape expected from the output
+    EpilogueShape {
+        /// Name of the tensor in the [epilogue](MatmulEpilogue)
+        tensor: &'static str,
+        /// Shape expected from the output
+        expected: Vec<usize>,
+        /// Shape of the tensor
+        actual: Vec<usize>,
+    },
+}
 
-use super::algorithm::{CmmaSelector, PlaneMmaSelector};
-use super::cmma::Cmma;
-use super::config::AdvancedConfig;
-use super::Algorithm;
+impl Display for MatmulLaunchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatmulLaunchError::EpilogueShape {
+                tensor,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "The {tensor} tensor of the epilogue has shape {actual:?}, expected {expected:?}"
+            ),
+        }
+    }
+}
 
 /// Launch a matrix multiplication kernel.
 ///
@@ -26,27 +86,80 @@ pub fn launch<R: Runtime, EG: Numeric>(
     out: TensorHandle<R, EG>,
     disable_cmma: bool,
 ) -> TensorHandle<R, EG> {
-    launch_ref::<R, EG>(
+    launch_ref_default_precision::<R, EG>(
         client,
         lhs.as_ref(),
         rhs.as_ref(),
         out.as_ref(),
-        disable_cmma || Cmma::<EG>::check_availability::<R>(client).is_err(),
-    );
+        MatmulOptions {
+            disable_cmma,
+            ..Default::default()
+        },
+    )
+    .expect("Should launch without an epilogue to check");
     out
 }
 
-/// Launch a matrix multiplication kernel.
+/// Launch a matrix multiplication kernel with lhs, rhs and out of type `EG`, in the default
+/// precision of the implementation in use: [CmmaPrecision], or [PlaneMmaPrecision] when cmma is
+/// disabled or unavailable.
+pub(crate) fn launch_ref_default_precision<R: Runtime, EG: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    options: MatmulOptions<'_, R>,
+) -> Result<(), MatmulLaunchError> {
+    match options.disable_cmma
+        || Cmma::<CmmaPrecision<EG>>::check_availability::<R>(client).is_err()
+    {
+        false => launch_ref::<R, CmmaPrecision<EG>>(client, lhs, rhs, out, &options),
+        true => launch_ref::<R, PlaneMmaPrecision<EG>>(
+            client,
+            lhs,
+            rhs,
+            out,
+            &MatmulOptions {
+                disable_cmma: true,
+                ..options
+            },
+        ),
+    }
+}
+
+/// Launch a matrix multiplication kernel with the element types of the precision,
+/// applying the epilogue of the options to the output in the same kernel.
+///
+/// Lhs and rhs are cast when loaded to shared memory and the accumulator is cast when written
+/// to the output, so no upcasted copy of the inputs is ever materialized.
 ///
 /// Cmma will be used if available and enabled,
-/// otherwise it will fall back on a non-cmma implementation
-pub fn launch_ref<R: Runtime, EG: Numeric>(
+/// or it will fall back on a non-cmma implementation
+///
+/// Returns an error without launching anything if the tensors of the epilogue don't match
+/// the output.
+pub fn launch_ref<R: Runtime, P: MatmulPrecision>(
     client: &ComputeClient<R::Server, R::Channel>,
     lhs: TensorHandleRef<'_, R>,
     rhs: TensorHandleRef<'_, R>,
     out: TensorHandleRef<'_, R>,
-    disable_cmma: bool,
-) {
+    options: &MatmulOptions<'_, R>,
+) -> Result<(), MatmulLaunchError> {
+    options.epilogue.check(out.shape)?;
+
+    // Batch dimensions missing or of size 1 are broadcast with a stride of 0.
+    let batches = &out.shape[..out.shape.len() - 2];
+    let (lhs_shape, lhs_strides) = broadcast_batches(lhs.shape, lhs.strides, batches);
+    let (rhs_shape, rhs_strides) = broadcast_batches(rhs.shape, rhs.strides, batches);
+    let lhs = unsafe {
+        TensorHandleRef::from_raw_parts(lhs.handle, &lhs_strides, &lhs_shape, lhs.elem_size)
+    };
+    let rhs = unsafe {
+        TensorHandleRef::from_raw_parts(rhs.handle, &rhs_strides, &rhs_shape, rhs.elem_size)
+    };
+
+    let disable_cmma = options.disable_cmma || Cmma::<P>::check_availability::<R>(client).is_err();
+
     let check_layout = |tensor: &TensorHandleRef<'_, R>| match matrix_layout(tensor.strides) {
         MatrixLayout::Contiguous => (false, false),
         MatrixLayout::MildlyPermuted {
@@ -60,49 +173,61 @@ pub fn launch_ref<R: Runtime, EG: Numeric>(
     let (rhs_make_contiguous, rhs_transposed) = check_layout(&rhs);
 
     match (lhs_make_contiguous, rhs_make_contiguous) {
-        (false, false) => matmul_cmma_ref_no_check::<R, EG>(
+        (false, false) => matmul_cmma_ref_no_check::<R, P>(
             client,
             lhs,
             rhs,
             out,
+            options,
             (lhs_transposed, rhs_transposed),
             disable_cmma,
         ),
-        (false, true) => matmul_cmma_ref_no_check::<R, EG>(
+        (false, true) => matmul_cmma_ref_no_check::<R, P>(
             client,
             lhs,
-            into_contiguous::<R, EG>(client, rhs).as_ref(),
+            into_contiguous::<R, P::ER>(client, rhs).as_ref(),
             out,
+            options,
             (lhs_transposed, rhs_transposed),
             disable_cmma,
         ),
-        (true, false) => matmul_cmma_ref_no_check::<R, EG>(
+        (true, false) => matmul_cmma_ref_no_check::<R, P>(
             client,
-            into_contiguous::<R, EG>(client, lhs).as_ref(),
+            into_contiguous::<R, P::EL>(client, lhs).as_ref(),
             rhs,
             out,
+            options,
             (lhs_transposed, rhs_transposed),
             disable_cmma,
         ),
-        (true, true) => matmul_cmma_ref_no_check::<R, EG>(
+        (true, true) => matmul_cmma_ref_no_check::<R, P>(
             client,
-            into_contiguous::<R, EG>(client, lhs).as_ref(),
-            into_contiguous::<R, EG>(client, rhs).as_ref(),
+            into_contiguous::<R, P::EL>(client, lhs).as_ref(),
+            into_contiguous::<R, P::ER>(client, rhs).as_ref(),
             out,
+            options,
             (lhs_transposed, rhs_transposed),
             disable_cmma,
         ),
     }
+
+    Ok(())
 }
 
-fn matmul_cmma_ref_no_check<R: Runtime, EG: Numeric>(
+fn matmul_cmma_ref_no_check<R: Runtime, P: MatmulPrecision>(
     client: &ComputeClient<R::Server, R::Channel>,
     lhs: TensorHandleRef<'_, R>,
     rhs: TensorHandleRef<'_, R>,
     out: TensorHandleRef<'_, R>,
+    options: &MatmulOptions<'_, R>,
     transposed: (bool, bool),
     disable_cmma: bool,
 ) {
+    let MatmulOptions {
+        epilogue,
+        selection,
+        disable_cmma: _,
+    } = options;
     let rank = lhs.strides.len();
 
     let m = lhs.shape[rank - 2] as u32;
@@ -116,6 +241,10 @@ fn matmul_cmma_ref_no_check<R: Runtime, EG: Numeric>(
         tensor_line_size(available_vectorizations, rhs.shape, rhs.strides, rank - 1);
     let out_line_size =
         tensor_line_size(available_vectorizations, out.shape, out.strides, rank - 1);
+    // The tensors of the epilogue are read with the line size of the output.
+    let out_line_size = epilogue.line_size().map_or(out_line_size, |line_size| {
+        Ord::min(line_size, out_line_size)
+    });
 
     let problem = MatmulProblem {
         m: m as usize,
@@ -136,29 +265,40 @@ fn matmul_cmma_ref_no_check<R: Runtime, EG: Numeric>(
     };
 
     if disable_cmma {
-        PlaneMmaSelector::select_kernel::<R, EG>(client, lhs, rhs, out, problem);
+        PlaneMmaSelector::select_kernel::<R, P>(
+            client, lhs, rhs, out, epilogue, selection, problem,
+        );
     } else {
-        CmmaSelector::select_kernel::<R, EG>(client, lhs, rhs, out, problem);
+        CmmaSelector::select_kernel::<R, P>(client, lhs, rhs, out, epilogue, selection, problem);
     }
 }
 
-pub(crate) fn matmul_cube_preparation<R: Runtime, EG: Numeric, D: Algorithm<EG>>(
+pub(crate) fn matmul_cube_preparation<R: Runtime, D: MixedAlgorithm>(
     client: &ComputeClient<R::Server, R::Channel>,
     lhs: TensorHandleRef<'_, R>,
     rhs: TensorHandleRef<'_, R>,
     out: TensorHandleRef<'_, R>,
+    epilogue: &MatmulEpilogue<'_, R>,
+    selection: &MatmulSelection,
     problem: MatmulProblem,
 ) {
-    let cube_dim = D::cube_dim();
-    let cube_count = D::cube_count(&problem);
+    let advanced_config = AdvancedConfig {
+        lhs_tiling_order: selection.lhs_tiling_order,
+        rhs_tiling_order: selection.rhs_tiling_order,
+        epilogue: epilogue.config(),
+        decomposition: selection.decomposition,
+        ..Default::default()
+    };
 
-    let advanced_config = Default::default();
+    let cube_dim = D::cube_dim();
+    let cube_count = D::cube_count(&problem, &advanced_config);
 
-    launch_matmul::<R, EG, D>(
+    launch_matmul::<R, D>(
         client,
         lhs,
         rhs,
         out,
+        epilogue,
         problem,
         cube_dim,
         cube_count,
@@ -167,11 +307,12 @@ pub(crate) fn matmul_cube_preparation<R: Runtime, EG: Numeric, D: Algorithm<EG>>
 }
 
 #[allow(clippy::too_many_arguments)]
-fn launch_matmul<R: Runtime, EG: Numeric, D: Algorithm<EG>>(
+fn launch_matmul<R: Runtime, D: MixedAlgorithm>(
     client: &ComputeClient<R::Server, R::Channel>,
     lhs: TensorHandleRef<'_, R>,
     rhs: TensorHandleRef<'_, R>,
     out: TensorHandleRef<'_, R>,
+    epilogue: &MatmulEpilogue<'_, R>,
     problem: MatmulProblem,
     cube_dim: CubeDim,
     cube_count: CubeCount,
@@ -179,30 +320,62 @@ fn launch_matmul<R: Runtime, EG: Numeric, D: Algorithm<EG>>(
 ) {
     let config = D::make_config(&problem, &cube_dim, &cube_count, &advanced_config);
 
+    // Disabled tensors of the epilogue are never read, so they are bound to a placeholder.
+    let placeholder = client.empty(D::EO::as_elem().size());
+
     unsafe {
         D::BatchMatmul::launch_unchecked::<R>(
             client,
             cube_dim,
             cube_count,
-            TensorArg::<R>::from_raw_parts::<D::EG>(
-                lhs.handle,
-                lhs.strides,
-                lhs.shape,
-                problem.lhs_line_size,
-            ),
-            TensorArg::<R>::from_raw_parts::<D::EG>(
-                rhs.handle,
-                rhs.strides,
-                rhs.shape,
-                problem.rhs_line_size,
-            ),
-            TensorArg::<R>::from_raw_parts::<D::EG>(
-                out.handle,
-                out.strides,
-                out.shape,
-                problem.out_line_size,
-            ),
+            MatmulLaunchArgs {
+                lhs: TensorArg::<R>::from_raw_parts::<D::EL>(
+                    lhs.handle,
+                    lhs.strides,
+                    lhs.shape,
+                    problem.lhs_line_size,
+                ),
+                rhs: TensorArg::<R>::from_raw_parts::<D::ER>(
+                    rhs.handle,
+                    rhs.strides,
+                    rhs.shape,
+                    problem.rhs_line_size,
+                ),
+                out: TensorArg::<R>::from_raw_parts::<D::EO>(
+                    out.handle,
+                    out.strides,
+                    out.shape,
+                    problem.out_line_size,
+                ),
+                epilogue: EpilogueInputsLaunch::new(
+                    ScalarArg::new(epilogue.alpha),
+                    ScalarArg::new(epilogue.beta),
+                    epilogue_arg::<R, D::EO>(&epilogue.c, &placeholder, problem.out_line_size),
+                    epilogue_arg::<R, D::EO>(&epilogue.bias, &placeholder, problem.out_line_size),
+                    epilogue_arg::<R, D::EO>(
+                        &epilogue.residual,
+                        &placeholder,
+                        problem.out_line_size,
+                    ),
+                ),
+            },
             config,
         );
     }
 }
+
+pub(crate) unsafe fn epilogue_arg<'a, R: Runtime, EG: Numeric>(
+    tensor: &'a Option<TensorHandleRef<'_, R>>,
+    placeholder: &'a Handle,
+    line_size: u8,
+) -> TensorArg<'a, R> {
+    match tensor {
+        Some(tensor) => TensorArg::<R>::from_raw_parts::<EG>(
+            tensor.handle,
+            tensor.strides,
+            tensor.shape,
+            line_size,
+        ),
+        None => TensorArg::<R>::from_raw_parts::<EG>(placeholder, &[1], &[1], 1),
+    }
+}
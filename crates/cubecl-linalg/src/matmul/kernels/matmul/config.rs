@@ -1,3 +1,4 @@
+use crate::matmul::components::global::EpilogueConfig;
 use crate::matmul::components::stage;
 use crate::matmul::components::LhsStageDim;
 use crate::matmul::components::MatrixLayout;
@@ -20,6 +21,65 @@ pub struct AdvancedConfig {
     /// transpose will be done at loading from global memory to stage,
     /// and stage will not be vectorized.
     pub enforced_tile_layout: (Option<MatrixLayout>, Option<MatrixLayout>),
+    /// Operations fused after the matmul, applied when writing the output
+    pub epilogue: EpilogueConfig,
+    /// How the work is partitioned between cubes
+    pub decomposition: Decomposition,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+/// How the work of a matmul is partitioned between cubes
+pub enum Decomposition {
+    /// Each cube computes whole output stages, iterating over the entire k dimension
+    #[default]
+    DataParallel,
+    /// The k dimension is split in `splits` ranges, each computed by a different cube.
+    ///
+    /// Partial sums are written to a workspace and summed in a second pass.
+    /// Useful when m and n are too small to occupy the device.
+    SplitK { splits: u32 },
+    /// The iterations along k of all output stages are evenly distributed over `cubes` cubes,
+    /// so that no cube stays idle at the end of the computation.
+    ///
+    /// Partial sums are written to a workspace and summed in a second pass.
+    StreamK { cubes: u32 },
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+/// Number of tiles in the stage along m, n and k
+pub enum StageSelection {
+    /// 2x2x2 tiles, computed by 2 planes
+    S2x2x2,
+    /// 4x4x2 tiles, computed by 4 planes
+    #[default]
+    S4x4x2,
+    /// 8x8x1 tiles, computed by 8 planes
+    S8x8x1,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// Choices made when launching a matmul that don't depend on the problem,
+/// but may impact performance
+pub struct MatmulSelection {
+    /// Size of the stage of the matmul algorithm
+    pub stage: StageSelection,
+    /// Order in which tiles should be in lhs shared memory
+    pub lhs_tiling_order: stage::TilingOrderConfig,
+    /// Order in which tiles should be in rhs shared memory
+    pub rhs_tiling_order: stage::TilingOrderConfig,
+    /// How the work is partitioned between cubes
+    pub decomposition: Decomposition,
+}
+
+impl Default for MatmulSelection {
+    fn default() -> Self {
+        Self {
+            stage: StageSelection::default(),
+            lhs_tiling_order: stage::TilingOrderConfig::RowMajor,
+            rhs_tiling_order: stage::TilingOrderConfig::RowMajor,
+            decomposition: Decomposition::default(),
+        }
+    }
 }
 
 impl Default for AdvancedConfig {
@@ -28,6 +88,21 @@ impl Default for AdvancedConfig {
             lhs_tiling_order: stage::TilingOrderConfig::RowMajor,
             rhs_tiling_order: stage::TilingOrderConfig::RowMajor,
             enforced_tile_layout: (None, None),
+            epilogue: EpilogueConfig::default(),
+            decomposition: Decomposition::default(),
+        }
+    }
+}
+
+impl AdvancedConfig {
+    /// Returns the same config, without any operation fused after the matmul
+    pub(crate) fn without_epilogue(&self) -> Self {
+        Self {
+            lhs_tiling_order: self.lhs_tiling_order,
+            rhs_tiling_order: self.rhs_tiling_order,
+            enforced_tile_layout: self.enforced_tile_layout,
+            epilogue: EpilogueConfig::default(),
+            decomposition: self.decomposition,
         }
     }
 }
@@ -0,0 +1,98 @@
+use cubecl_core::prelude::*;
+use cubecl_core::tensor_line_size;
+
+use crate::matmul::components::global::{Activation, EpilogueConfig};
+
+use super::base::MatmulLaunchError;
+
+/// Operations fused after a matmul, so that the kernel computes
+/// `activation(alpha * lhs @ rhs + beta * c + bias) + residual`.
+///
+/// The default epilogue writes `lhs @ rhs` as is.
+pub struct MatmulEpilogue<'a, R: Runtime> {
+    /// Scale of lhs @ rhs
+    pub alpha: f32,
+    /// Scale of c
+    pub beta: f32,
+    /// Tensor with the same shape as the output
+    pub c: Option<TensorHandleRef<'a, R>>,
+    /// Vector of length n, added to every row of the output
+    pub bias: Option<TensorHandleRef<'a, R>>,
+    /// Activation applied after the additions
+    pub activation: Activation,
+    /// Tensor with the same shape as the output, added after the activation
+    pub residual: Option<TensorHandleRef<'a, R>>,
+}
+
+impl<R: Runtime> Default for MatmulEpilogue<'_, R> {
+    fn default() -> Self {
+        Self {
+            alpha: 1.,
+            beta: 1.,
+            c: None,
+            bias: None,
+            activation: Activation::None,
+            residual: None,
+        }
+    }
+}
+
+impl<R: Runtime> MatmulEpilogue<'_, R> {
+    /// Returns which operations are enabled in the kernel
+    pub fn config(&self) -> EpilogueConfig {
+        EpilogueConfig {
+            alpha: self.alpha != 1.,
+            c: self.c.is_some(),
+            bias: self.bias.is_some(),
+            activation: self.activation,
+            residual: self.residual.is_some(),
+        }
+    }
+
+    /// Checks that the tensors of the epilogue match an output of the given shape
+    pub(crate) fn check(&self, out_shape: &[usize]) -> Result<(), MatmulLaunchError> {
+        let n = out_shape[out_shape.len() - 1];
+        let expected = [
+            ("c", &self.c, out_shape),
+            ("bias", &self.bias, &[n][..]),
+            ("residual", &self.residual, out_shape),
+        ];
+
+        for (tensor, handle, expected) in expected {
+            match handle {
+                Some(handle) if handle.shape != expected => {
+                    return Err(MatmulLaunchError::EpilogueShape {
+                        tensor,
+                        expected: expected.to_vec(),
+                        actual: handle.shape.to_vec(),
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the largest line size supported by the tensors of the epilogue, if any
+    pub(crate) fn line_size(&self) -> Option<u8> {
+        let available_vectorizations = R::supported_line_sizes();
+        let matrix_line_size = |tensor: &TensorHandleRef<'_, R>| {
+            tensor_line_size(
+                available_vectorizations,
+                tensor.shape,
+                tensor.strides,
+                tensor.shape.len() - 1,
+            )
+        };
+
+        [
+            self.c.as_ref().map(matrix_line_size),
+            self.bias.as_ref().map(matrix_line_size),
+            self.residual.as_ref().map(matrix_line_size),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+    }
+}
@@ -1,8 +1,17 @@
 mod base;
 mod config;
+mod epilogue;
+mod quantized;
 
 mod algorithm;
 
-pub use algorithm::{cmma, plane_mma, Algorithm};
-pub use base::{launch, launch_ref};
-pub use config::{create_stage_dim, AdvancedConfig};
+pub use algorithm::{
+    cmma, plane_mma, split_k, stream_k, Algorithm, MatmulPrecision, MixedAlgorithm,
+};
+pub(crate) use base::launch_ref_default_precision;
+pub use base::{launch, launch_ref, MatmulLaunchError, MatmulOptions};
+pub use config::{
+    create_stage_dim, AdvancedConfig, Decomposition, MatmulSelection, StageSelection,
+};
+pub use epilogue::MatmulEpilogue;
+pub use quantized::{is_quantized_available, launch_ref_quantized, QuantizedRhs};
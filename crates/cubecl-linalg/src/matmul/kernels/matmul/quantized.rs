@@ -0,0 +1,383 @@
+use cubecl_core::prelude::*;
+
+use cubecl_core::{
+    client::ComputeClient,
+    frontend::{TensorArg, TensorHandleRef},
+    tensor_line_size, Feature, Runtime,
+};
+
+use crate::matmul;
+use crate::matmul::components::batch;
+use crate::matmul::components::global::quantized::{
+    QuantizationConfig, QuantizationInputsLaunch, QuantizedFormat,
+};
+use crate::matmul::components::global::{homogeneous, EpilogueInputsLaunch};
+use crate::matmul::components::stage::{
+    self,
+    multi_buffer::{LhsReader, RhsReader},
+};
+use crate::matmul::components::{MatmulKernel, MatmulProblem};
+use crate::tensor::{broadcast_batches, into_contiguous, matrix_layout, MatrixLayout};
+
+use super::algorithm::MixedAlgorithm;
+use super::base::{epilogue_arg, MatmulLaunchError};
+use super::cmma::Cmma;
+use super::config::AdvancedConfig;
+use super::epilogue::MatmulEpilogue;
+use super::plane_mma::PlaneMma;
+
+/// Quantized rhs of a matmul, with its dequantization parameters
+///
+/// A quantized value `q` at row `k` and column `n` is dequantized as
+/// `(q - zero_point) * scale`, with the scale and zero point of column `n`
+/// in the group of row `k`.
+pub struct QuantizedRhs<'a, R: Runtime> {
+    /// Row major quantized values, of shape `[..batches, k, n]` for int8 stored as i8,
+    /// or `[..batches, k, n / 8]` for int4 packed in u32.
+    ///
+    /// Like the other tensors, its batch dimensions may be broadcast.
+    pub values: TensorHandleRef<'a, R>,
+    /// How the values are stored
+    pub format: QuantizedFormat,
+    /// Scales, of shape `[..batches, groups, n]` where `groups` divides `k`.
+    ///
+    /// A single group gives per-channel quantization.
+    pub scales: TensorHandleRef<'a, R>,
+    /// Zero points, with the same shape and element type as the scales
+    pub zero_points: Option<TensorHandleRef<'a, R>>,
+}
+
+/// Launch a matrix multiplication kernel where the rhs is quantized,
+/// applying the epilogue to the output in the same kernel.
+///
+/// The rhs is dequantized with scales of element `ESc` when loaded to shared memory,
+/// so the dequantized rhs is never materialized in global memory.
+///
+/// Cmma will be used if available and enabled,
+/// otherwise it will fall back on a non-cmma implementation
+///
+/// Returns an error without launching anything if the tensors of the epilogue don't match
+/// the output.
+pub fn launch_ref_quantized<R: Runtime, EL: Numeric, ESc: Numeric, EO: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: &QuantizedRhs<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    epilogue: &MatmulEpilogue<'_, R>,
+    disable_cmma: bool,
+) -> Result<(), MatmulLaunchError> {
+    epilogue.check(out.shape)?;
+
+    // Batch dimensions missing or of size 1 are broadcast with a stride of 0,
+    // so the same quantized weights can be shared by all batches.
+    let batches = &out.shape[..out.shape.len() - 2];
+    let broadcast =
+        |tensor: &TensorHandleRef<'_, R>| broadcast_batches(tensor.shape, tensor.strides, batches);
+    let lhs_layout = broadcast(&lhs);
+    let values_layout = broadcast(&rhs.values);
+    let scales_layout = broadcast(&rhs.scales);
+    let zero_points_layout = rhs.zero_points.as_ref().map(broadcast);
+
+    let lhs = view(&lhs, &lhs_layout);
+    let rhs = &QuantizedRhs {
+        values: view(&rhs.values, &values_layout),
+        format: rhs.format,
+        scales: view(&rhs.scales, &scales_layout),
+        zero_points: rhs
+            .zero_points
+            .as_ref()
+            .zip(zero_points_layout.as_ref())
+            .map(|(zero_points, layout)| view(zero_points, layout)),
+    };
+
+    match rhs.format {
+        QuantizedFormat::Int8 => launch_ref_quantized_values::<R, EL, i8, ESc, EO>(
+            client,
+            lhs,
+            rhs,
+            out,
+            epilogue,
+            disable_cmma,
+        ),
+        QuantizedFormat::Int4 => launch_ref_quantized_values::<R, EL, u32, ESc, EO>(
+            client,
+            lhs,
+            rhs,
+            out,
+            epilogue,
+            disable_cmma,
+        ),
+    }
+
+    Ok(())
+}
+
+/// Returns the tensor with the given shape and strides
+fn view<'a, R: Runtime>(
+    tensor: &TensorHandleRef<'a, R>,
+    (shape, strides): &'a (Vec<usize>, Vec<usize>),
+) -> TensorHandleRef<'a, R> {
+    unsafe { TensorHandleRef::from_raw_parts(tensor.handle, strides, shape, tensor.elem_size) }
+}
+
+fn launch_ref_quantized_values<R: Runtime, EL: Numeric, EQ: Numeric, ESc: Numeric, EO: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: &QuantizedRhs<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    epilogue: &MatmulEpilogue<'_, R>,
+    disable_cmma: bool,
+) {
+    let (lhs_make_contiguous, lhs_transposed) = match matrix_layout(lhs.strides) {
+        MatrixLayout::Contiguous => (false, false),
+        MatrixLayout::MildlyPermuted {
+            transposed,
+            batch_swap: _,
+        } => (false, transposed),
+        MatrixLayout::HighlyPermuted => (true, false),
+    };
+
+    match lhs_make_contiguous {
+        false => matmul_quantized_ref_no_check::<R, EL, EQ, ESc, EO>(
+            client,
+            lhs,
+            rhs,
+            out,
+            epilogue,
+            lhs_transposed,
+            disable_cmma,
+        ),
+        true => matmul_quantized_ref_no_check::<R, EL, EQ, ESc, EO>(
+            client,
+            into_contiguous::<R, EL>(client, lhs).as_ref(),
+            rhs,
+            out,
+            epilogue,
+            lhs_transposed,
+            disable_cmma,
+        ),
+    }
+}
+
+fn matmul_quantized_ref_no_check<
+    R: Runtime,
+    EL: Numeric,
+    EQ: Numeric,
+    ESc: Numeric,
+    EO: Numeric,
+>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: &QuantizedRhs<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    epilogue: &MatmulEpilogue<'_, R>,
+    lhs_transposed: bool,
+    disable_cmma: bool,
+) {
+    assert!(
+        matches!(
+            matrix_layout(rhs.values.strides),
+            MatrixLayout::Contiguous
+                | MatrixLayout::MildlyPermuted {
+                    transposed: false,
+                    ..
+                }
+        ),
+        "The quantized values of the rhs should be row major"
+    );
+
+    let quantization = QuantizationConfig {
+        format: rhs.format,
+        zero_points: rhs.zero_points.is_some(),
+    };
+    let values_per_elem = quantization.values_per_elem() as usize;
+
+    let rank = lhs.strides.len();
+    let m = lhs.shape[rank - 2];
+    let k = lhs.shape[rank - 1];
+    let n = rhs.values.shape[rank - 1] * values_per_elem;
+
+    assert!(
+        k % rhs.scales.shape[rank - 2] == 0,
+        "The number of groups of the scales should divide k"
+    );
+
+    // The rhs is seen as a row major matrix of n values, each of its lines being
+    // read from a single element of the values tensor for int4.
+    let mut rhs_shape = rhs.values.shape.to_vec();
+    rhs_shape[rank - 1] = n;
+    let mut rhs_strides = rhs.values.strides.to_vec();
+    rhs_strides[rank - 2] *= values_per_elem;
+
+    let available_vectorizations = R::supported_line_sizes()
+        .iter()
+        .copied()
+        .filter(|line_size| *line_size as usize <= values_per_elem || values_per_elem == 1)
+        .collect::<Vec<_>>();
+    let lhs_line_size =
+        tensor_line_size(R::supported_line_sizes(), lhs.shape, lhs.strides, rank - 1);
+    let rhs_line_size = tensor_line_size(
+        &available_vectorizations,
+        &rhs_shape,
+        &rhs_strides,
+        rank - 1,
+    );
+    let out_line_size =
+        tensor_line_size(R::supported_line_sizes(), out.shape, out.strides, rank - 1);
+    // The tensors of the epilogue are read with the line size of the output.
+    let out_line_size = epilogue.line_size().map_or(out_line_size, |line_size| {
+        Ord::min(line_size, out_line_size)
+    });
+
+    let problem = MatmulProblem {
+        m,
+        n,
+        k,
+        batches: out.shape[..out.shape.len() - 2].to_vec(),
+        lhs_layout: match lhs_transposed {
+            true => matmul::components::MatrixLayout::ColMajor,
+            false => matmul::components::MatrixLayout::RowMajor,
+        },
+        rhs_layout: matmul::components::MatrixLayout::RowMajor,
+        lhs_line_size,
+        rhs_line_size,
+        out_line_size,
+    };
+
+    if disable_cmma
+        || <Cmma<(EL, EL, half::f16, f32, EO)> as MixedAlgorithm>::check_availability::<R>(client)
+            .is_err()
+    {
+        launch_quantized_matmul::<R, EQ, ESc, PlaneMma<(EL, EL, f32, f32, EO)>, _>(
+            client,
+            lhs,
+            rhs,
+            out,
+            epilogue,
+            problem,
+            quantization,
+        );
+    } else {
+        launch_quantized_matmul::<R, EQ, ESc, Cmma<(EL, EL, half::f16, f32, EO)>, _>(
+            client,
+            lhs,
+            rhs,
+            out,
+            epilogue,
+            problem,
+            quantization,
+        );
+    }
+}
+
+fn launch_quantized_matmul<
+    R: Runtime,
+    EQ: Numeric,
+    ESc: Numeric,
+    A: MixedAlgorithm<StageMatmul = SMM>,
+    SMM: stage::Matmul<A::ES, A::EO, LhsReader = LhsReader<A::ES>, RhsReader = RhsReader<A::ES>>,
+>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: &QuantizedRhs<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    epilogue: &MatmulEpilogue<'_, R>,
+    problem: MatmulProblem,
+    quantization: QuantizationConfig,
+) {
+    let advanced_config = AdvancedConfig {
+        epilogue: epilogue.config(),
+        ..Default::default()
+    };
+
+    let cube_dim = A::cube_dim();
+    let cube_count = A::cube_count(&problem, &advanced_config);
+    let config = homogeneous::Matmul::<A::EL, A::EL, A::EO, A::ES, SMM>::make_config(
+        &problem,
+        &cube_dim,
+        &cube_count,
+        &advanced_config,
+    );
+    homogeneous::Matmul::<A::EL, A::EL, A::EO, A::ES, SMM>::check_config(config);
+
+    // Disabled tensors are never read, so they are bound to a placeholder.
+    let placeholder = client.empty(A::EO::as_elem().size());
+    let zero_points_placeholder = client.empty(ESc::as_elem().size());
+    let values_line_size = match quantization.format {
+        QuantizedFormat::Int8 => problem.rhs_line_size,
+        QuantizedFormat::Int4 => 1,
+    };
+
+    unsafe {
+        batch::quantized::launch::launch_unchecked::<A::EL, EQ, ESc, A::EO, A::ES, SMM, R>(
+            client,
+            cube_count,
+            cube_dim,
+            TensorArg::<R>::from_raw_parts::<A::EL>(
+                lhs.handle,
+                lhs.strides,
+                lhs.shape,
+                problem.lhs_line_size,
+            ),
+            TensorArg::<R>::from_raw_parts::<EQ>(
+                rhs.values.handle,
+                rhs.values.strides,
+                rhs.values.shape,
+                values_line_size,
+            ),
+            QuantizationInputsLaunch::new(
+                TensorArg::<R>::from_raw_parts::<ESc>(
+                    rhs.scales.handle,
+                    rhs.scales.strides,
+                    rhs.scales.shape,
+                    1,
+                ),
+                match &rhs.zero_points {
+                    Some(zero_points) => TensorArg::<R>::from_raw_parts::<ESc>(
+                        zero_points.handle,
+                        zero_points.strides,
+                        zero_points.shape,
+                        1,
+                    ),
+                    None => TensorArg::<R>::from_raw_parts::<ESc>(
+                        &zero_points_placeholder,
+                        &[1],
+                        &[1],
+                        1,
+                    ),
+                },
+            ),
+            TensorArg::<R>::from_raw_parts::<A::EO>(
+                out.handle,
+                out.strides,
+                out.shape,
+                problem.out_line_size,
+            ),
+            EpilogueInputsLaunch::new(
+                ScalarArg::new(epilogue.alpha),
+                ScalarArg::new(epilogue.beta),
+                epilogue_arg::<R, A::EO>(&epilogue.c, &placeholder, problem.out_line_size),
+                epilogue_arg::<R, A::EO>(&epilogue.bias, &placeholder, problem.out_line_size),
+                epilogue_arg::<R, A::EO>(&epilogue.residual, &placeholder, problem.out_line_size),
+            ),
+            quantization,
+            config,
+        );
+    }
+}
+
+/// Whether the quantized matmul can run on the client with the given elements
+pub fn is_quantized_available<R: Runtime, ESc: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    format: QuantizedFormat,
+) -> bool {
+    let values = match format {
+        QuantizedFormat::Int8 => i8::as_elem(),
+        QuantizedFormat::Int4 => u32::as_elem(),
+    };
+
+    client.properties().feature_enabled(Feature::Plane)
+        && [values, ESc::as_elem()]
+            .into_iter()
+            .all(|elem| client.properties().feature_enabled(Feature::Type(elem)))
+}
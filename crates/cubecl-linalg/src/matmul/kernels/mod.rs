@@ -1,5 +1,7 @@
 /// Matmul using Accelerator
 pub mod cmma_old;
+/// Matrix-vector multiplication, for matmuls where m == 1 or n == 1
+pub mod gemv;
 /// Matmul using Accelerator or PlaneMma
 pub mod matmul;
 /// Non-cooperative Matmul
@@ -6,5 +6,6 @@ pub mod kernels;
 /// Tests for matmul kernels
 #[cfg(feature = "export_tests")]
 pub mod tests;
+mod tune;
 
 pub use base::*;
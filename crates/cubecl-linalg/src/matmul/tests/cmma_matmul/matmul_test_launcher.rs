@@ -5,14 +5,23 @@ use cubecl_core::server::Handle;
 use cubecl_core::CubeElement;
 use cubecl_core::Feature;
 
+use crate::matmul::components::global::quantized::QuantizedFormat;
+use crate::matmul::components::global::{Activation, EpilogueInputsLaunch};
 use crate::matmul::components::Ident;
 use crate::matmul::components::MatmulLaunch;
+use crate::matmul::components::MatmulLaunchArgs;
 use crate::matmul::components::MatmulProblem;
 use crate::matmul::components::MatrixLayout;
 use crate::matmul::kernels::matmul;
 use crate::matmul::kernels::matmul::AdvancedConfig;
 use crate::matmul::kernels::matmul::Algorithm;
+use crate::matmul::kernels::matmul::Decomposition;
+use crate::matmul::kernels::matmul::MatmulEpilogue;
+use crate::matmul::kernels::matmul::MatmulLaunchError;
+use crate::matmul::kernels::matmul::MatmulOptions;
+use crate::matmul::kernels::matmul::MatmulSelection;
 use crate::matmul::tests::test_utils::CastInto;
+use crate::matmul::Strategy;
 use crate::tensor::TensorHandle;
 
 use crate::matmul::tests::test_utils::assert_equals_approx;
@@ -50,32 +59,42 @@ pub fn test_matmul_algorithm<A, EG, ES, R>(
     let out = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Out);
 
     let cube_dim = A::cube_dim();
-    let cube_count = A::cube_count(&problem);
+    let cube_count = A::cube_count(&problem, &advanced_config);
     let config = A::make_config(&problem, &cube_dim, &cube_count, &advanced_config);
+    let placeholder = client.empty(EG::as_elem().size());
 
     unsafe {
         A::BatchMatmul::launch_unchecked(
             &client,
             cube_dim,
             cube_count,
-            TensorArg::<R>::from_raw_parts::<EG>(
-                &lhs.handle,
-                &lhs.strides,
-                &lhs.shape,
-                problem.lhs_line_size,
-            ),
-            TensorArg::<R>::from_raw_parts::<EG>(
-                &rhs.handle,
-                &rhs.strides,
-                &rhs.shape,
-                problem.rhs_line_size,
-            ),
-            TensorArg::<R>::from_raw_parts::<EG>(
-                &out.handle,
-                &out.strides,
-                &out.shape,
-                problem.out_line_size,
-            ),
+            MatmulLaunchArgs {
+                lhs: TensorArg::<R>::from_raw_parts::<EG>(
+                    &lhs.handle,
+                    &lhs.strides,
+                    &lhs.shape,
+                    problem.lhs_line_size,
+                ),
+                rhs: TensorArg::<R>::from_raw_parts::<EG>(
+                    &rhs.handle,
+                    &rhs.strides,
+                    &rhs.shape,
+                    problem.rhs_line_size,
+                ),
+                out: TensorArg::<R>::from_raw_parts::<EG>(
+                    &out.handle,
+                    &out.strides,
+                    &out.shape,
+                    problem.out_line_size,
+                ),
+                epilogue: EpilogueInputsLaunch::new(
+                    ScalarArg::new(1.),
+                    ScalarArg::new(1.),
+                    TensorArg::<R>::from_raw_parts::<EG>(&placeholder, &[1], &[1], 1),
+                    TensorArg::<R>::from_raw_parts::<EG>(&placeholder, &[1], &[1], 1),
+                    TensorArg::<R>::from_raw_parts::<EG>(&placeholder, &[1], &[1], 1),
+                ),
+            },
             config,
         );
     }
@@ -97,38 +116,592 @@ pub fn test_matmul_launch<EG: Float + CubeElement + Display + CastInto<EG>, R: R
     disable_cmma: bool,
     device: &R::Device,
 ) {
+    test_launch::<EG, EG, EG, R>(
+        &problem,
+        [&problem.batches; 2],
+        device,
+        |client| client.properties().feature_enabled(Feature::Plane),
+        |client, lhs, rhs, out| {
+            matmul::launch_ref_default_precision::<R, EG>(
+                client,
+                lhs,
+                rhs,
+                out,
+                MatmulOptions {
+                    disable_cmma,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        },
+        |lhs, rhs| matmul_cpu_reference::<f32, f32>(lhs, rhs, &problem),
+    );
+}
+
+/// Test the correctness of the high-level Matmul on the given device when lhs and rhs have
+/// their own batch dimensions, broadcast to the batches of the problem,
+/// against a naive CPU implementation over the expanded inputs
+pub fn test_matmul_launch_broadcast<
+    EG: Float + CubeElement + Display + CastInto<EG>,
+    R: Runtime,
+>(
+    problem: MatmulProblem,
+    lhs_batches: Vec<usize>,
+    rhs_batches: Vec<usize>,
+    device: &R::Device,
+) {
+    test_launch::<EG, EG, EG, R>(
+        &problem,
+        [&lhs_batches, &rhs_batches],
+        device,
+        |client| client.properties().feature_enabled(Feature::Plane),
+        |client, lhs, rhs, out| {
+            matmul::launch_ref_default_precision::<R, EG>(
+                client,
+                lhs,
+                rhs,
+                out,
+                MatmulOptions::default(),
+            )
+            .unwrap()
+        },
+        |lhs, rhs| matmul_cpu_reference::<f32, f32>(lhs, rhs, &problem),
+    );
+}
+
+/// Test the correctness of the high-level Matmul with the given decomposition on the given device,
+/// against a naive CPU implementation over the given problem
+pub fn test_matmul_launch_decomposition<
+    EG: Float + CubeElement + Display + CastInto<EG>,
+    R: Runtime,
+>(
+    problem: MatmulProblem,
+    decomposition: Decomposition,
+    device: &R::Device,
+) {
+    test_launch::<EG, EG, EG, R>(
+        &problem,
+        [&problem.batches; 2],
+        device,
+        |client| client.properties().feature_enabled(Feature::Plane),
+        |client, lhs, rhs, out| {
+            matmul::launch_ref_default_precision::<R, EG>(
+                client,
+                lhs,
+                rhs,
+                out,
+                MatmulOptions {
+                    selection: MatmulSelection {
+                        decomposition,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        },
+        |lhs, rhs| matmul_cpu_reference::<f32, f32>(lhs, rhs, &problem),
+    );
+}
+
+/// Test the correctness of the high-level Matmul with the given selection on the given device,
+/// against a naive CPU implementation over the given problem
+pub fn test_matmul_launch_selection<
+    EG: Float + CubeElement + Display + CastInto<EG>,
+    R: Runtime,
+>(
+    problem: MatmulProblem,
+    selection: MatmulSelection,
+    disable_cmma: bool,
+    device: &R::Device,
+) {
+    test_launch::<EG, EG, EG, R>(
+        &problem,
+        [&problem.batches; 2],
+        device,
+        |client| client.properties().feature_enabled(Feature::Plane),
+        |client, lhs, rhs, out| {
+            matmul::launch_ref_default_precision::<R, EG>(
+                client,
+                lhs,
+                rhs,
+                out,
+                MatmulOptions {
+                    selection,
+                    disable_cmma,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        },
+        |lhs, rhs| matmul_cpu_reference::<f32, f32>(lhs, rhs, &problem),
+    );
+}
+
+/// Test the correctness of the high-level Matmul with different lhs, rhs and out elements
+/// on the given device, against a naive CPU implementation over the given problem
+///
+/// The inputs are staged as f16 and accumulated as f32.
+pub fn test_matmul_launch_precision<EL, ER, EO, R>(
+    problem: MatmulProblem,
+    disable_cmma: bool,
+    device: &R::Device,
+) where
+    EL: Float + CubeElement,
+    ER: Float + CubeElement,
+    EO: Float + CubeElement + Display,
+    R: Runtime,
+{
+    test_launch::<EL, ER, EO, R>(
+        &problem,
+        [&problem.batches; 2],
+        device,
+        |client| {
+            client.properties().feature_enabled(Feature::Plane)
+                && client
+                    .properties()
+                    .feature_enabled(Feature::Type(half::f16::as_elem()))
+        },
+        |client, lhs, rhs, out| {
+            matmul::launch_ref::<R, (EL, ER, half::f16, f32, EO)>(
+                client,
+                lhs,
+                rhs,
+                out,
+                &MatmulOptions {
+                    disable_cmma,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        },
+        |lhs, rhs| matmul_cpu_reference::<f32, f32>(lhs, rhs, &problem),
+    );
+}
+
+/// Test the correctness of the quantized Matmul on the given device,
+/// against a naive CPU implementation over the dequantized rhs
+pub fn test_matmul_launch_quantized<EG, ESc, R>(
+    problem: MatmulProblem,
+    format: QuantizedFormat,
+    groups: usize,
+    zero_points: bool,
+    disable_cmma: bool,
+    device: &R::Device,
+) where
+    EG: Float + CubeElement + Display,
+    ESc: Float + CubeElement,
+    R: Runtime,
+{
+    assert_eq!(problem.rhs_layout, MatrixLayout::RowMajor);
+
+    let (min, max) = match format {
+        QuantizedFormat::Int8 => (-127., 127.),
+        QuantizedFormat::Int4 => (-8., 7.),
+    };
+    let values: Vec<i32> = generate_random_data::<f32>(tensor_size(&problem, Ident::Rhs), 5678)
+        .into_iter()
+        .map(|x| ((x + 1.) / 2. * (max - min) + min).round() as i32)
+        .collect();
+
+    let num_params = problem.num_batches() * groups * problem.n;
+    let scales: Vec<ESc> = generate_random_data::<f32>(num_params, 91011)
+        .into_iter()
+        .map(|x| ESc::new((x.abs() + 0.5) / max))
+        .collect();
+    let zeros: Vec<ESc> = generate_random_data::<f32>(num_params, 121314)
+        .into_iter()
+        .map(|x| {
+            ESc::new(match zero_points {
+                true => (x * 2.).round(),
+                false => 0.,
+            })
+        })
+        .collect();
+
+    let mut values_shape = shape(&problem, Ident::Rhs);
+    let mut values_strides = strides(&problem, Ident::Rhs);
+    if let QuantizedFormat::Int4 = format {
+        let rank = values_shape.len();
+        values_shape[rank - 1] /= 8;
+        values_strides
+            .iter_mut()
+            .rev()
+            .skip(1)
+            .for_each(|stride| *stride /= 8);
+    }
+
+    let params_shape = problem
+        .batches
+        .iter()
+        .cloned()
+        .chain([groups, problem.n])
+        .collect::<Vec<_>>();
+    let mut params_strides = vec![1; params_shape.len()];
+    for i in (0..params_shape.len() - 1).rev() {
+        params_strides[i] = params_strides[i + 1] * params_shape[i + 1];
+    }
+
+    // The rhs of the test is replaced by the quantized values.
+    test_launch::<EG, EG, EG, R>(
+        &problem,
+        [&problem.batches; 2],
+        device,
+        |client| matmul::is_quantized_available::<R, ESc>(client, format),
+        |client, lhs, _rhs, out| {
+            let (values_handle, values_elem_size) = match format {
+                QuantizedFormat::Int8 => {
+                    let values = values.iter().map(|x| *x as i8).collect::<Vec<_>>();
+                    (client.create(i8::as_bytes(&values)), 1)
+                }
+                QuantizedFormat::Int4 => {
+                    let values = values
+                        .chunks(8)
+                        .map(|chunk| {
+                            chunk
+                                .iter()
+                                .enumerate()
+                                .fold(0u32, |word, (i, x)| word | (((*x as u32) & 15) << (i * 4)))
+                        })
+                        .collect::<Vec<_>>();
+                    (client.create(u32::as_bytes(&values)), 4)
+                }
+            };
+            let scales_handle = client.create(ESc::as_bytes(&scales));
+            let zeros_handle = client.create(ESc::as_bytes(&zeros));
+
+            let rhs = matmul::QuantizedRhs {
+                values: unsafe {
+                    TensorHandleRef::from_raw_parts(
+                        &values_handle,
+                        &values_strides,
+                        &values_shape,
+                        values_elem_size,
+                    )
+                },
+                format,
+                scales: unsafe {
+                    TensorHandleRef::from_raw_parts(
+                        &scales_handle,
+                        &params_strides,
+                        &params_shape,
+                        ESc::as_elem().size(),
+                    )
+                },
+                zero_points: zero_points.then(|| unsafe {
+                    TensorHandleRef::from_raw_parts(
+                        &zeros_handle,
+                        &params_strides,
+                        &params_shape,
+                        ESc::as_elem().size(),
+                    )
+                }),
+            };
+
+            matmul::launch_ref_quantized::<R, EG, ESc, EG>(
+                client,
+                lhs,
+                &rhs,
+                out,
+                &MatmulEpilogue::default(),
+                disable_cmma,
+            )
+            .unwrap();
+        },
+        |lhs, _rhs| {
+            let group_size = problem.k / groups;
+            let dequantized: Vec<f32> = values
+                .iter()
+                .enumerate()
+                .map(|(i, value)| {
+                    let n = i % problem.n;
+                    let k = (i / problem.n) % problem.k;
+                    let batch = i / (problem.n * problem.k);
+                    let param = (batch * groups + k / group_size) * problem.n + n;
+
+                    (*value as f32 - zeros[param].to_f32().unwrap())
+                        * scales[param].to_f32().unwrap()
+                })
+                .collect();
+
+            matmul_cpu_reference::<f32, f32>(lhs, &dequantized, &problem)
+        },
+    );
+}
+
+/// Test the correctness of the autotuned Matmul on the given device,
+/// against a naive CPU implementation over the given problem
+pub fn test_matmul_launch_auto<EG: Float + CubeElement + Display + CastInto<EG>, R: Runtime>(
+    problem: MatmulProblem,
+    device: &R::Device,
+) {
+    test_launch::<EG, EG, EG, R>(
+        &problem,
+        [&problem.batches; 2],
+        device,
+        |_| true,
+        |client, lhs, rhs, out| {
+            let handle = |tensor: &TensorHandleRef<'_, R>| {
+                TensorHandle::<R, EG>::new(
+                    tensor.shape.to_vec(),
+                    tensor.strides.to_vec(),
+                    tensor.handle.clone(),
+                )
+            };
+
+            // The second launch uses the kernel cached by the first one.
+            for _ in 0..2 {
+                crate::matmul::launch::<R, EG>(
+                    &Strategy::Auto,
+                    client,
+                    handle(&lhs),
+                    handle(&rhs),
+                    handle(&out),
+                );
+            }
+        },
+        |lhs, rhs| matmul_cpu_reference::<f32, f32>(lhs, rhs, &problem),
+    );
+}
+
+/// Test the correctness of the high-level Matmul with a fused epilogue on the given device,
+/// against a naive CPU implementation over the given problem
+pub fn test_matmul_launch_epilogue<EG: Float + CubeElement + Display + CastInto<EG>, R: Runtime>(
+    problem: MatmulProblem,
+    activation: Activation,
+    decomposition: Decomposition,
+    device: &R::Device,
+) {
+    let c_data: Vec<EG> = generate_random_data(tensor_size(&problem, Ident::Out), 42);
+    let bias_data: Vec<EG> = generate_random_data(problem.n, 43);
+    let residual_data: Vec<EG> = generate_random_data(tensor_size(&problem, Ident::Out), 44);
+
+    test_launch::<EG, EG, EG, R>(
+        &problem,
+        [&problem.batches; 2],
+        device,
+        |client| client.properties().feature_enabled(Feature::Plane),
+        |client, lhs, rhs, out| {
+            let c = client.create(EG::as_bytes(&c_data));
+            let bias = client.create(EG::as_bytes(&bias_data));
+            let residual = client.create(EG::as_bytes(&residual_data));
+            let bias_shape = [problem.n];
+
+            let epilogue = unsafe {
+                MatmulEpilogue {
+                    alpha: 0.5,
+                    beta: 2.,
+                    c: Some(TensorHandleRef::from_raw_parts(
+                        &c,
+                        out.strides,
+                        out.shape,
+                        EG::as_elem().size(),
+                    )),
+                    bias: Some(TensorHandleRef::from_raw_parts(
+                        &bias,
+                        &[1],
+                        &bias_shape,
+                        EG::as_elem().size(),
+                    )),
+                    activation,
+                    residual: Some(TensorHandleRef::from_raw_parts(
+                        &residual,
+                        out.strides,
+                        out.shape,
+                        EG::as_elem().size(),
+                    )),
+                }
+            };
+
+            matmul::launch_ref_default_precision::<R, EG>(
+                client,
+                lhs,
+                rhs,
+                out,
+                MatmulOptions {
+                    epilogue,
+                    selection: MatmulSelection {
+                        decomposition,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        },
+        |lhs, rhs| {
+            matmul_cpu_reference::<f32, f32>(lhs, rhs, &problem)
+                .into_iter()
+                .enumerate()
+                .map(|(i, acc)| {
+                    let value = 0.5 * acc
+                        + 2. * c_data[i].to_f32().unwrap()
+                        + bias_data[i % problem.n].to_f32().unwrap();
+                    let value = match activation {
+                        Activation::None => value,
+                        Activation::ReLU => value.max(0.),
+                        Activation::GELU => {
+                            0.5 * value * (1. + erf(value * std::f32::consts::FRAC_1_SQRT_2))
+                        }
+                        Activation::SiLU => value / (1. + (-value).exp()),
+                    };
+                    value + residual_data[i].to_f32().unwrap()
+                })
+                .collect()
+        },
+    );
+}
+
+/// Test that the high-level Matmul refuses to launch with an epilogue whose bias doesn't match
+/// the output
+pub fn test_matmul_launch_epilogue_shape<EG: Float + CubeElement, R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let (m, n, k) = (16, 8, 32);
+    let elem_size = EG::as_elem().size();
+    let lhs = client.empty(m * k * elem_size);
+    let rhs = client.empty(k * n * elem_size);
+    let out = client.empty(m * n * elem_size);
+    let bias = client.empty((n + 1) * elem_size);
+    let (bias_shape, bias_strides) = ([n + 1], [1]);
+
+    let result = unsafe {
+        matmul::launch_ref_default_precision::<R, EG>(
+            &client,
+            TensorHandleRef::from_raw_parts(&lhs, &[k, 1], &[m, k], elem_size),
+            TensorHandleRef::from_raw_parts(&rhs, &[n, 1], &[k, n], elem_size),
+            TensorHandleRef::from_raw_parts(&out, &[n, 1], &[m, n], elem_size),
+            MatmulOptions {
+                epilogue: MatmulEpilogue {
+                    bias: Some(TensorHandleRef::from_raw_parts(
+                        &bias,
+                        &bias_strides,
+                        &bias_shape,
+                        elem_size,
+                    )),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+    };
+
+    assert_eq!(
+        result,
+        Err(MatmulLaunchError::EpilogueShape {
+            tensor: "bias",
+            expected: vec![n],
+            actual: vec![n + 1],
+        })
+    );
+}
+
+/// Test the correctness of a high-level Matmul on the given device,
+/// against a naive CPU implementation over the given problem
+///
+/// Lhs and rhs are created with the given batches, broadcast to the ones of the problem.
+/// `launch` computes the matmul of lhs and rhs into out, and `reference` returns the
+/// expected output from the broadcast data of lhs and rhs.
+/// The test is skipped if the elements aren't supported or if `available` returns false.
+fn test_launch<EL, ER, EO, R>(
+    problem: &MatmulProblem,
+    input_batches: [&[usize]; 2],
+    device: &R::Device,
+    available: impl FnOnce(&ComputeClient<R::Server, R::Channel>) -> bool,
+    launch: impl FnOnce(
+        &ComputeClient<R::Server, R::Channel>,
+        TensorHandleRef<'_, R>,
+        TensorHandleRef<'_, R>,
+        TensorHandleRef<'_, R>,
+    ),
+    reference: impl FnOnce(&[f32], &[f32]) -> Vec<f32>,
+) where
+    EL: Float + CubeElement,
+    ER: Float + CubeElement,
+    EO: Float + CubeElement + Display,
+    R: Runtime,
+{
     let client: ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel> = R::client(device);
 
-    if !(client.properties().feature_enabled(Feature::Plane)
-        && client
-            .properties()
-            .feature_enabled(Feature::Type(EG::as_elem())))
-    {
+    let elems_supported = [EL::as_elem(), ER::as_elem(), EO::as_elem()]
+        .into_iter()
+        .all(|elem| client.properties().feature_enabled(Feature::Type(elem)));
+    if !(elems_supported && available(&client)) {
         // Can't execute the test.
         return;
     }
 
-    let lhs = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Lhs);
-    let rhs = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Rhs);
-    let out = tensor_raw_parts::<EG, R>(&client, &problem, Ident::Out);
+    let [lhs_batches, rhs_batches] = input_batches;
+    let lhs_problem = MatmulProblem {
+        batches: lhs_batches.to_vec(),
+        ..problem.clone()
+    };
+    let rhs_problem = MatmulProblem {
+        batches: rhs_batches.to_vec(),
+        ..problem.clone()
+    };
 
-    let out = matmul::launch::<R, EG>(
-        &client,
-        TensorHandle::new(lhs.shape, lhs.strides, lhs.handle),
-        TensorHandle::new(rhs.shape, rhs.strides, rhs.handle),
-        TensorHandle::new(out.shape, out.strides, out.handle),
-        disable_cmma,
-    );
+    let lhs = tensor_raw_parts::<EL, R>(&client, &lhs_problem, Ident::Lhs);
+    let rhs = tensor_raw_parts::<ER, R>(&client, &rhs_problem, Ident::Rhs);
+    let out = tensor_raw_parts::<EO, R>(&client, problem, Ident::Out);
 
-    assert_result::<EG, EG, R>(
-        &lhs.original_data.unwrap(),
-        &rhs.original_data.unwrap(),
-        &problem,
-        &client,
-        out.handle,
-        // We cannot assume the inner precision of the matmul, therefore we need a permissive epsilon
-        Some(10e-2),
+    unsafe {
+        launch(
+            &client,
+            TensorHandleRef::from_raw_parts(
+                &lhs.handle,
+                &lhs.strides,
+                &lhs.shape,
+                EL::as_elem().size(),
+            ),
+            TensorHandleRef::from_raw_parts(
+                &rhs.handle,
+                &rhs.strides,
+                &rhs.shape,
+                ER::as_elem().size(),
+            ),
+            TensorHandleRef::from_raw_parts(
+                &out.handle,
+                &out.strides,
+                &out.shape,
+                EO::as_elem().size(),
+            ),
+        );
+    }
+
+    let lhs_data = broadcast_data(
+        &to_f32(&lhs.original_data.unwrap()),
+        lhs_batches,
+        &problem.batches,
+        problem.m * problem.k,
     );
+    let rhs_data = broadcast_data(
+        &to_f32(&rhs.original_data.unwrap()),
+        rhs_batches,
+        &problem.batches,
+        problem.k * problem.n,
+    );
+    let expected = reference(&lhs_data, &rhs_data)
+        .into_iter()
+        .map(EO::new)
+        .collect::<Vec<_>>();
+
+    // We cannot assume the inner precision of the matmul, therefore we need a permissive epsilon
+    if let Err(e) = assert_equals_approx::<R, EO>(&client, out.handle, &expected, 10e-2) {
+        panic!("{}", e);
+    }
+}
+
+fn to_f32<E: Float>(data: &[E]) -> Vec<f32> {
+    data.iter().map(|x| x.to_f32().unwrap()).collect()
+}
+
+/// Approximation of the error function, with a maximum error of 1.5e-7
+fn erf(x: f32) -> f32 {
+    let t = 1. / (1. + 0.3275911 * x.abs());
+    let poly =
+        t * (0.2548296 + t * (-0.28449672 + t * (1.4214138 + t * (-1.4531521 + t * 1.0614054))));
+    let y = 1. - poly * (-x * x).exp();
+    y.copysign(x)
 }
 
 fn tensor_raw_parts<EG: Float + CubeElement, R: Runtime>(
@@ -186,6 +759,40 @@ fn tensor_raw_parts<EG: Float + CubeElement, R: Runtime>(
     }
 }
 
+/// Repeats the matrices of the data along broadcast batch dimensions, numpy-style
+fn broadcast_data<E: Copy>(
+    data: &[E],
+    batches: &[usize],
+    target_batches: &[usize],
+    matrix_size: usize,
+) -> Vec<E> {
+    let num_missing = target_batches.len() - batches.len();
+    let num_batches: usize = target_batches.iter().product();
+    let mut result = Vec::with_capacity(num_batches * matrix_size);
+
+    for nth_batch in 0..num_batches {
+        let mut remaining = nth_batch;
+        let mut source_batch = 0;
+        let mut source_stride = 1;
+
+        for axis in (0..target_batches.len()).rev() {
+            let coordinate = remaining % target_batches[axis];
+            remaining /= target_batches[axis];
+
+            if axis >= num_missing {
+                let dim = batches[axis - num_missing];
+                source_batch += (coordinate % dim) * source_stride;
+                source_stride *= dim;
+            }
+        }
+
+        let start = source_batch * matrix_size;
+        result.extend_from_slice(&data[start..start + matrix_size]);
+    }
+
+    result
+}
+
 fn transpose<E: Copy>(array: &[E], batches: usize, rows: usize, cols: usize) -> Vec<E> {
     let mut result = vec![array[0]; array.len()];
     for b in 0..batches {
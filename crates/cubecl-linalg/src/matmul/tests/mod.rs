@@ -3,5 +3,5 @@
 pub mod cmma_matmul;
 pub mod cmma_old;
 mod test_macros;
-mod test_utils;
+pub(crate) mod test_utils;
 pub mod tiling2d;
@@ -1,5 +1,5 @@
 // Tests nomenclature:
-// batch: b[o=one_to_one, m=one_to_many][batch dims, optional]
+// batch: b[o=one_to_one, m=one_to_many, sk=split_k, st=stream_k][batch dims, optional]
 // global: g[h=homogeneous, pc=producer_consumer][m]x[n]x[k], with m,n,k the whole matrix dimensions
 // stage: s[m]x[n]x[k], with m,n,k the number of tiles along those dims
 // tile: t[m]x[n]x[k], with m,n,k the tile dimensions. tile algorithm is given by macro arguments
@@ -34,24 +34,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::single_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x3,
                 >;
-                type GlobalMatmul =
-                    global::producer_consumer::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::producer_consumer::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -61,7 +70,10 @@ macro_rules! matmul_test_define {
                     CubeDim::new($plane_dim, 2, 1)
                 }
 
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -88,24 +100,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::TransposedDispatch,
@@ -115,7 +136,10 @@ macro_rules! matmul_test_define {
                     CubeDim::new($plane_dim, 1, 1)
                 }
 
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 64, 1)
                 }
             }
@@ -144,24 +168,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::single_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S4x4x2,
                 >;
-                type GlobalMatmul =
-                    global::producer_consumer::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::producer_consumer::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -170,7 +203,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 8, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(5, 5, 12)
                 }
             }
@@ -203,24 +239,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::single_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x2x2,
                 >;
-                type GlobalMatmul =
-                    global::producer_consumer::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::producer_consumer::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -230,7 +275,10 @@ macro_rules! matmul_test_define {
                     CubeDim::new($plane_dim, 2, 1)
                 }
 
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -263,24 +311,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::single_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S2x1x2,
                 >;
-                type GlobalMatmul =
-                    global::producer_consumer::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::producer_consumer::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -290,7 +347,10 @@ macro_rules! matmul_test_define {
                     CubeDim::new($plane_dim, 3, 1)
                 }
 
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -323,24 +383,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::single_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x2,
                 >;
-                type GlobalMatmul =
-                    global::producer_consumer::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::producer_consumer::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -350,7 +419,10 @@ macro_rules! matmul_test_define {
                     CubeDim::new($plane_dim, 2, 1)
                 }
 
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -383,24 +455,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::single_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x2,
                 >;
-                type GlobalMatmul =
-                    global::producer_consumer::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::producer_consumer::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -410,7 +491,10 @@ macro_rules! matmul_test_define {
                     CubeDim::new($plane_dim, 2, 1)
                 }
 
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -443,24 +527,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_many::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::RowMajorSpanMatmul,
@@ -471,7 +564,10 @@ macro_rules! matmul_test_define {
                     CubeDim::new($plane_dim, 1, 1)
                 }
 
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -500,24 +596,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_many::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::RowMajorSpanMatmul,
@@ -528,7 +633,10 @@ macro_rules! matmul_test_define {
                     CubeDim::new($plane_dim, 1, 1)
                 }
 
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -557,24 +665,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_many::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::RowMajorSpanMatmul,
@@ -585,7 +702,10 @@ macro_rules! matmul_test_define {
                     CubeDim::new($plane_dim, 1, 1)
                 }
 
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -614,24 +734,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_many::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::RowMajorSpanMatmul,
@@ -642,7 +771,10 @@ macro_rules! matmul_test_define {
                     CubeDim::new($plane_dim, 1, 1)
                 }
 
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -671,24 +803,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_many::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::RowMajorSpanMatmul,
@@ -699,7 +840,10 @@ macro_rules! matmul_test_define {
                     CubeDim::new($plane_dim, 1, 1)
                 }
 
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -728,24 +872,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_many::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::ColMajorSpanMatmul,
@@ -756,7 +909,10 @@ macro_rules! matmul_test_define {
                     CubeDim::new($plane_dim, 1, 1)
                 }
 
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -785,24 +941,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_many::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::SwizzleSpanMatmul<2>,
@@ -813,7 +978,10 @@ macro_rules! matmul_test_define {
                     CubeDim::new($plane_dim, 1, 1)
                 }
 
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(2, 2, 2)
                 }
             }
@@ -842,24 +1010,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_many::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::SwizzleSpanMatmul<2>,
@@ -870,7 +1047,10 @@ macro_rules! matmul_test_define {
                     CubeDim::new($plane_dim, 1, 1)
                 }
 
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(2, 2, 2)
                 }
             }
@@ -899,24 +1079,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_many::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::SwizzleSpanMatmul<2>,
@@ -927,7 +1116,10 @@ macro_rules! matmul_test_define {
                     CubeDim::new($plane_dim, 1, 1)
                 }
 
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(10, 16, 2)
                 }
             }
@@ -956,24 +1148,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_many::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::SwizzleSpanMatmul<2>,
@@ -984,7 +1185,10 @@ macro_rules! matmul_test_define {
                     CubeDim::new($plane_dim, 1, 1)
                 }
 
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(16, 10, 2)
                 }
             }
@@ -1013,24 +1217,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_many::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::RowMajorSpanMatmul,
@@ -1041,7 +1254,10 @@ macro_rules! matmul_test_define {
                     CubeDim::new($plane_dim, 1, 1)
                 }
 
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 2)
                 }
             }
@@ -1055,6 +1271,183 @@ macro_rules! matmul_test_define {
             );
         }
 
+        #[test]
+        pub fn bsk2_gh32x32x256_s1x1x1_t16x16x16_rr_ln4_splits3() {
+            let problem = MatmulProblem {
+                m: 32,
+                n: 32,
+                k: 256,
+                batches: vec![2],
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+            };
+
+            struct Test {}
+            impl matmul::MixedAlgorithm for Test {
+                const PLANE_DIM: u32 = $plane_dim;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
+                type ES = $es;
+                type EA = $ea;
+
+                type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
+                type StageMatmul = stage::multi_buffer::Matmul<
+                    Self::ES,
+                    Self::EO,
+                    Self::EA,
+                    Self::TileMatmul,
+                    S1x1x1,
+                >;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
+                type BatchMatmul = batch::WithPartialSums<
+                    Self::EL,
+                    Self::ER,
+                    Self::EA,
+                    Self::EO,
+                    batch::split_k::Matmul<
+                        Self::EL,
+                        Self::ER,
+                        Self::EA,
+                        Self::ES,
+                        global::homogeneous::Matmul<
+                            Self::EL,
+                            Self::ER,
+                            Self::EA,
+                            Self::ES,
+                            stage::multi_buffer::Matmul<
+                                Self::ES,
+                                Self::EA,
+                                Self::EA,
+                                Self::TileMatmul,
+                                S1x1x1,
+                            >,
+                        >,
+                        batch::NaturalDispatch,
+                    >,
+                >;
+
+                fn cube_dim() -> CubeDim {
+                    CubeDim::new($plane_dim, 1, 1)
+                }
+
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
+                    CubeCount::Static(2, 2, 6)
+                }
+            }
+
+            let advanced_config = AdvancedConfig {
+                decomposition: matmul::Decomposition::SplitK { splits: 3 },
+                ..Default::default()
+            };
+
+            test_matmul_algorithm::<Test, $eg, $es, TestRuntime>(
+                problem,
+                advanced_config,
+                &<<TestRuntime as Runtime>::Device>::default(),
+            );
+        }
+
+        #[test]
+        pub fn bst2_gh48x32x256_s1x1x1_t16x16x16_rr_ln4_cubes5() {
+            let problem = MatmulProblem {
+                m: 48,
+                n: 32,
+                k: 256,
+                batches: vec![2],
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+            };
+
+            struct Test {}
+            impl matmul::MixedAlgorithm for Test {
+                const PLANE_DIM: u32 = $plane_dim;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
+                type ES = $es;
+                type EA = $ea;
+
+                type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
+                type StageMatmul = stage::multi_buffer::Matmul<
+                    Self::ES,
+                    Self::EO,
+                    Self::EA,
+                    Self::TileMatmul,
+                    S1x1x1,
+                >;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
+                type BatchMatmul = batch::WithPartialSums<
+                    Self::EL,
+                    Self::ER,
+                    Self::EA,
+                    Self::EO,
+                    batch::stream_k::Matmul<
+                        Self::EL,
+                        Self::ER,
+                        Self::EA,
+                        Self::ES,
+                        global::homogeneous::Matmul<
+                            Self::EL,
+                            Self::ER,
+                            Self::EA,
+                            Self::ES,
+                            stage::multi_buffer::Matmul<
+                                Self::ES,
+                                Self::EA,
+                                Self::EA,
+                                Self::TileMatmul,
+                                S1x1x1,
+                            >,
+                        >,
+                    >,
+                >;
+
+                fn cube_dim() -> CubeDim {
+                    CubeDim::new($plane_dim, 1, 1)
+                }
+
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
+                    CubeCount::Static(5, 1, 1)
+                }
+            }
+
+            let advanced_config = AdvancedConfig {
+                decomposition: matmul::Decomposition::StreamK { cubes: 5 },
+                ..Default::default()
+            };
+
+            test_matmul_algorithm::<Test, $eg, $es, TestRuntime>(
+                problem,
+                advanced_config,
+                &<<TestRuntime as Runtime>::Device>::default(),
+            );
+        }
+
         #[test]
         pub fn bo3x4_gh300x300x300_s4x4x2_t16x16x16_cc_ln4() {
             let problem = MatmulProblem {
@@ -1070,24 +1463,33 @@ macro_rules! matmul_test_define {
             };
 
             struct Test {}
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S4x4x2,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -1096,7 +1498,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 4, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(5, 5, 12)
                 }
             }
@@ -1125,24 +1530,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S4x4x2,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -1151,7 +1565,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 4, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(2, 2, 12)
                 }
             }
@@ -1179,24 +1596,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S4x4x2,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -1205,7 +1631,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 4, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(4, 4, 12)
                 }
             }
@@ -1233,24 +1662,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S4x4x2,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -1259,7 +1697,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 4, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(4, 4, 3)
                 }
             }
@@ -1287,24 +1728,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -1313,7 +1763,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 3)
                 }
             }
@@ -1341,24 +1794,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -1367,7 +1829,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 3)
                 }
             }
@@ -1395,24 +1860,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S4x4x2,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -1421,7 +1895,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 4, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(4, 4, 1)
                 }
             }
@@ -1454,24 +1931,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -1480,7 +1966,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(2, 2, 1)
                 }
             }
@@ -1513,24 +2002,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -1539,7 +2037,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(2, 2, 1)
                 }
             }
@@ -1567,24 +2068,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -1593,7 +2103,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -1621,24 +2134,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -1647,7 +2169,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -1675,24 +2200,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -1701,7 +2235,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -1729,24 +2266,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S4x4x2,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -1755,7 +2301,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 4, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -1783,24 +2332,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -1809,7 +2367,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -1837,24 +2398,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -1863,7 +2433,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -1891,24 +2464,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -1917,7 +2499,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -1945,24 +2530,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -1971,7 +2565,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -1999,24 +2596,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -2025,7 +2631,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -2053,24 +2662,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -2079,7 +2697,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -2107,24 +2728,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -2133,7 +2763,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -2161,24 +2794,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -2187,7 +2829,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -2215,24 +2860,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -2241,7 +2895,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -2273,24 +2930,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -2299,7 +2965,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -2330,24 +2999,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -2356,7 +3034,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -2387,24 +3068,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_32x8x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -2413,7 +3103,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -2444,24 +3137,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_32x8x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -2470,7 +3172,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -2501,24 +3206,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_32x8x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -2527,7 +3241,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -2558,24 +3275,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_32x8x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -2584,7 +3310,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -2615,24 +3344,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_8x32x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -2641,7 +3379,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -2672,24 +3413,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_8x32x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -2698,7 +3448,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -2729,24 +3482,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_8x32x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -2755,7 +3517,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -2786,24 +3551,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_8x32x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -2812,7 +3586,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -2843,24 +3620,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S4x4x2,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -2869,7 +3655,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 4, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(4, 4, 12)
                 }
             }
@@ -2900,24 +3689,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -2926,7 +3724,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -2954,24 +3755,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_32x8x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -2980,7 +3790,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3008,24 +3821,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_32x8x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -3034,7 +3856,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3062,24 +3887,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_32x8x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -3088,7 +3922,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3116,24 +3953,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_32x8x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -3142,7 +3988,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3170,24 +4019,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_8x32x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -3196,7 +4054,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3224,24 +4085,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_8x32x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -3250,7 +4120,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3278,24 +4151,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_8x32x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -3304,7 +4186,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3332,24 +4217,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_8x32x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -3358,7 +4252,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3386,24 +4283,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -3412,7 +4318,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3440,24 +4349,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S2x2x2,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -3466,7 +4384,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 2, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3498,24 +4419,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -3524,7 +4454,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3552,24 +4485,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -3578,7 +4520,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3606,24 +4551,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -3632,7 +4586,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3660,24 +4617,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S2x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -3686,7 +4652,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 2, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3714,24 +4683,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S2x2x2,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -3740,7 +4718,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 2, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3768,24 +4749,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x2x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -3794,7 +4784,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3822,24 +4815,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S2x2x2,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -3848,7 +4850,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 2, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3876,24 +4881,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S2x2x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -3902,7 +4916,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 2, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3930,24 +4947,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S2x2x2,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -3956,7 +4982,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 2, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -3984,24 +5013,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S2x2x2,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -4010,7 +5048,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 2, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -4038,24 +5079,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S2x2x2,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -4064,7 +5114,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 2, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -4092,24 +5145,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S2x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -4118,7 +5180,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 2, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -4146,24 +5211,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_32x8x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S1x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -4172,7 +5246,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 1, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -4200,24 +5277,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S8x1x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -4226,7 +5312,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 8, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -4254,24 +5343,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S4x4x1,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -4280,7 +5378,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 4, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
@@ -4308,24 +5409,33 @@ macro_rules! matmul_test_define {
             };
             struct Test {}
 
-            impl matmul::Algorithm<$eg> for Test {
+            impl matmul::MixedAlgorithm for Test {
                 const PLANE_DIM: u32 = $plane_dim;
-                type EG = $eg;
+                type EL = $eg;
+                type ER = $eg;
+                type EO = $eg;
                 type ES = $es;
                 type EA = $ea;
 
                 type TileMatmul = $t_16x16x16<Self::ES, Self::EA>;
                 type StageMatmul = stage::multi_buffer::Matmul<
                     Self::ES,
-                    Self::EG,
+                    Self::EO,
                     Self::EA,
                     Self::TileMatmul,
                     S4x4x2,
                 >;
-                type GlobalMatmul =
-                    global::homogeneous::Matmul<Self::EG, Self::ES, Self::StageMatmul>;
+                type GlobalMatmul = global::homogeneous::Matmul<
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
+                    Self::ES,
+                    Self::StageMatmul,
+                >;
                 type BatchMatmul = batch::one_to_one::Matmul<
-                    Self::EG,
+                    Self::EL,
+                    Self::ER,
+                    Self::EO,
                     Self::ES,
                     Self::GlobalMatmul,
                     batch::NaturalDispatch,
@@ -4334,7 +5444,10 @@ macro_rules! matmul_test_define {
                 fn cube_dim() -> CubeDim {
                     CubeDim::new($plane_dim, 4, 1)
                 }
-                fn cube_count(_problem: &MatmulProblem) -> CubeCount {
+                fn cube_count(
+                    _problem: &MatmulProblem,
+                    _advanced_config: &AdvancedConfig,
+                ) -> CubeCount {
                     CubeCount::Static(1, 1, 1)
                 }
             }
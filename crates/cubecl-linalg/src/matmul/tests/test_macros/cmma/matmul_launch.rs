@@ -2,6 +2,7 @@
 #[macro_export]
 macro_rules! testgen_matmul_launch {
     ($eg:ty) => {
+        use cubecl_linalg::matmul::kernels::matmul::Decomposition;
         use cubecl_linalg::matmul::tests::cmma_matmul::matmul_test_launcher::test_matmul_launch;
 
         #[test]
@@ -21,5 +22,382 @@ macro_rules! testgen_matmul_launch {
 
             test_matmul_launch::<EG, TestRuntime>(problem, false, &Default::default());
         }
+
+        #[test]
+        pub fn test_launch_matmul_epilogue() {
+            use cubecl_linalg::matmul::components::global::Activation;
+            use cubecl_linalg::matmul::tests::cmma_matmul::matmul_test_launcher::test_matmul_launch_epilogue;
+
+            type EG = $eg;
+            let problem = MatmulProblem {
+                m: 100,
+                n: 64,
+                k: 70,
+                batches: vec![2],
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 1,
+                rhs_line_size: 4,
+                out_line_size: 4,
+            };
+
+            for activation in [
+                Activation::None,
+                Activation::ReLU,
+                Activation::GELU,
+                Activation::SiLU,
+            ] {
+                test_matmul_launch_epilogue::<EG, TestRuntime>(
+                    problem.clone(),
+                    activation,
+                    Decomposition::DataParallel,
+                    &Default::default(),
+                );
+            }
+        }
+
+        #[test]
+        pub fn test_launch_matmul_epilogue_shape() {
+            use cubecl_linalg::matmul::tests::cmma_matmul::matmul_test_launcher::test_matmul_launch_epilogue_shape;
+
+            test_matmul_launch_epilogue_shape::<$eg, TestRuntime>(&Default::default());
+        }
+
+        #[test]
+        pub fn test_launch_matmul_split_k() {
+            use cubecl_linalg::matmul::tests::cmma_matmul::matmul_test_launcher::test_matmul_launch_decomposition;
+
+            type EG = $eg;
+            let problem = MatmulProblem {
+                m: 64,
+                n: 64,
+                k: 1000,
+                batches: vec![2],
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+            };
+
+            test_matmul_launch_decomposition::<EG, TestRuntime>(
+                problem,
+                Decomposition::SplitK { splits: 5 },
+                &Default::default(),
+            );
+        }
+
+        #[test]
+        pub fn test_launch_matmul_stream_k() {
+            use cubecl_linalg::matmul::tests::cmma_matmul::matmul_test_launcher::test_matmul_launch_decomposition;
+
+            type EG = $eg;
+            let problem = MatmulProblem {
+                m: 100,
+                n: 64,
+                k: 1000,
+                batches: vec![2],
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::ColMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+            };
+
+            test_matmul_launch_decomposition::<EG, TestRuntime>(
+                problem,
+                Decomposition::StreamK { cubes: 7 },
+                &Default::default(),
+            );
+        }
+
+        #[test]
+        pub fn test_launch_matmul_split_k_epilogue() {
+            use cubecl_linalg::matmul::components::global::Activation;
+            use cubecl_linalg::matmul::tests::cmma_matmul::matmul_test_launcher::test_matmul_launch_epilogue;
+
+            type EG = $eg;
+            let problem = MatmulProblem {
+                m: 64,
+                n: 64,
+                k: 500,
+                batches: vec![2],
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+            };
+
+            test_matmul_launch_epilogue::<EG, TestRuntime>(
+                problem,
+                Activation::ReLU,
+                Decomposition::SplitK { splits: 3 },
+                &Default::default(),
+            );
+        }
+
+        #[test]
+        pub fn test_launch_matmul_stage_8x8x1_tiling_col_major() {
+            use cubecl_linalg::matmul::components::stage::TilingOrderConfig;
+            use cubecl_linalg::matmul::kernels::matmul::{MatmulSelection, StageSelection};
+            use cubecl_linalg::matmul::tests::cmma_matmul::matmul_test_launcher::test_matmul_launch_selection;
+
+            type EG = $eg;
+            let problem = MatmulProblem {
+                m: 200,
+                n: 150,
+                k: 120,
+                batches: vec![2],
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::ColMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 2,
+            };
+
+            let selection = MatmulSelection {
+                stage: StageSelection::S8x8x1,
+                lhs_tiling_order: TilingOrderConfig::ColMajor,
+                rhs_tiling_order: TilingOrderConfig::ColMajor,
+                ..Default::default()
+            };
+
+            test_matmul_launch_selection::<EG, TestRuntime>(
+                problem,
+                selection,
+                false,
+                &Default::default(),
+            );
+        }
+
+        #[test]
+        pub fn test_launch_matmul_auto() {
+            use cubecl_linalg::matmul::tests::cmma_matmul::matmul_test_launcher::test_matmul_launch_auto;
+
+            type EG = $eg;
+            let problem = MatmulProblem {
+                m: 130,
+                n: 96,
+                k: 300,
+                batches: vec![2],
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+            };
+
+            test_matmul_launch_auto::<EG, TestRuntime>(problem, &Default::default());
+        }
+
+        #[test]
+        pub fn test_launch_matmul_precision_f16_inputs() {
+            use cubecl_linalg::matmul::tests::cmma_matmul::matmul_test_launcher::test_matmul_launch_precision;
+
+            type EG = $eg;
+            let problem = MatmulProblem {
+                m: 100,
+                n: 64,
+                k: 120,
+                batches: vec![2],
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::ColMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+            };
+
+            for disable_cmma in [false, true] {
+                test_matmul_launch_precision::<half::f16, half::f16, EG, TestRuntime>(
+                    problem.clone(),
+                    disable_cmma,
+                    &Default::default(),
+                );
+            }
+        }
+
+        #[test]
+        pub fn test_launch_matmul_precision_f16_rhs() {
+            use cubecl_linalg::matmul::tests::cmma_matmul::matmul_test_launcher::test_matmul_launch_precision;
+
+            type EG = $eg;
+            let problem = MatmulProblem {
+                m: 100,
+                n: 64,
+                k: 120,
+                batches: vec![2],
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+            };
+
+            for disable_cmma in [false, true] {
+                test_matmul_launch_precision::<EG, half::f16, half::f16, TestRuntime>(
+                    problem.clone(),
+                    disable_cmma,
+                    &Default::default(),
+                );
+            }
+        }
+
+        #[test]
+        pub fn test_launch_matmul_quantized_int8_per_channel() {
+            use cubecl_linalg::matmul::components::global::quantized::QuantizedFormat;
+            use cubecl_linalg::matmul::tests::cmma_matmul::matmul_test_launcher::test_matmul_launch_quantized;
+
+            type EG = $eg;
+            let problem = MatmulProblem {
+                m: 100,
+                n: 64,
+                k: 120,
+                batches: vec![2],
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+            };
+
+            for disable_cmma in [false, true] {
+                test_matmul_launch_quantized::<EG, f32, TestRuntime>(
+                    problem.clone(),
+                    QuantizedFormat::Int8,
+                    1,
+                    false,
+                    disable_cmma,
+                    &Default::default(),
+                );
+            }
+        }
+
+        #[test]
+        pub fn test_launch_matmul_quantized_int4_per_group_zero_points() {
+            use cubecl_linalg::matmul::components::global::quantized::QuantizedFormat;
+            use cubecl_linalg::matmul::tests::cmma_matmul::matmul_test_launcher::test_matmul_launch_quantized;
+
+            type EG = $eg;
+            let problem = MatmulProblem {
+                m: 70,
+                n: 96,
+                k: 128,
+                batches: vec![2],
+                lhs_layout: MatrixLayout::ColMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+            };
+
+            for disable_cmma in [false, true] {
+                test_matmul_launch_quantized::<EG, half::f16, TestRuntime>(
+                    problem.clone(),
+                    QuantizedFormat::Int4,
+                    4,
+                    true,
+                    disable_cmma,
+                    &Default::default(),
+                );
+            }
+        }
+
+        #[test]
+        pub fn test_launch_matmul_gemv_vector_matrix() {
+            use cubecl_linalg::matmul::tests::cmma_matmul::matmul_test_launcher::test_matmul_launch_auto;
+
+            type EG = $eg;
+
+            for rhs_layout in [MatrixLayout::RowMajor, MatrixLayout::ColMajor] {
+                let problem = MatmulProblem {
+                    m: 1,
+                    n: 200,
+                    k: 300,
+                    batches: vec![3],
+                    lhs_layout: MatrixLayout::RowMajor,
+                    rhs_layout,
+                    lhs_line_size: 4,
+                    rhs_line_size: 4,
+                    out_line_size: 4,
+                };
+
+                test_matmul_launch_auto::<EG, TestRuntime>(problem, &Default::default());
+            }
+        }
+
+        #[test]
+        pub fn test_launch_matmul_gemv_matrix_vector() {
+            use cubecl_linalg::matmul::tests::cmma_matmul::matmul_test_launcher::test_matmul_launch_auto;
+
+            type EG = $eg;
+
+            for lhs_layout in [MatrixLayout::RowMajor, MatrixLayout::ColMajor] {
+                let problem = MatmulProblem {
+                    m: 130,
+                    n: 1,
+                    k: 260,
+                    batches: vec![2, 2],
+                    lhs_layout,
+                    rhs_layout: MatrixLayout::RowMajor,
+                    lhs_line_size: 4,
+                    rhs_line_size: 1,
+                    out_line_size: 1,
+                };
+
+                test_matmul_launch_auto::<EG, TestRuntime>(problem, &Default::default());
+            }
+        }
+
+        #[test]
+        pub fn test_launch_matmul_broadcast_rhs() {
+            use cubecl_linalg::matmul::tests::cmma_matmul::matmul_test_launcher::test_matmul_launch_broadcast;
+
+            type EG = $eg;
+            let problem = MatmulProblem {
+                m: 100,
+                n: 64,
+                k: 120,
+                batches: vec![3, 2],
+                lhs_layout: MatrixLayout::RowMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 4,
+                rhs_line_size: 4,
+                out_line_size: 4,
+            };
+
+            // Weights shared by all batches.
+            test_matmul_launch_broadcast::<EG, TestRuntime>(
+                problem,
+                vec![3, 2],
+                vec![],
+                &Default::default(),
+            );
+        }
+
+        #[test]
+        pub fn test_launch_matmul_broadcast_both() {
+            use cubecl_linalg::matmul::tests::cmma_matmul::matmul_test_launcher::test_matmul_launch_broadcast;
+
+            type EG = $eg;
+            let problem = MatmulProblem {
+                m: 70,
+                n: 40,
+                k: 64,
+                batches: vec![3, 2],
+                lhs_layout: MatrixLayout::ColMajor,
+                rhs_layout: MatrixLayout::RowMajor,
+                lhs_line_size: 2,
+                rhs_line_size: 4,
+                out_line_size: 4,
+            };
+
+            test_matmul_launch_broadcast::<EG, TestRuntime>(
+                problem,
+                vec![1, 2],
+                vec![3, 1],
+                &Default::default(),
+            );
+        }
     };
 }
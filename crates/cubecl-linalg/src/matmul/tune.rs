@@ -0,0 +1,327 @@
+use cubecl_core::{
+    self as cubecl,
+    client::ComputeClient,
+    ir::Elem,
+    prelude::Float,
+    tune::{AutotuneOperation, AutotuneOperationSet, LocalTuner},
+    AutotuneKey, Runtime,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::tensor::{broadcast_batches, matrix_layout, MatrixLayout, TensorHandle};
+
+use super::components::stage::TilingOrderConfig;
+use super::kernels::{
+    cmma_old::{self, config::PredefinedCmmaConfig},
+    matmul::{
+        self,
+        cmma::{Cmma, CmmaPrecision},
+        plane_mma::{PlaneMma, PlaneMmaPrecision},
+        Decomposition, MatmulOptions, MatmulSelection, MixedAlgorithm, StageSelection,
+    },
+    tiling2d,
+};
+
+static TUNER: LocalTuner<MatmulAutotuneKey, String> = LocalTuner::new(module_path!());
+
+#[derive(AutotuneKey, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Autotune key representative of the shape and layout of a matmul problem
+pub struct MatmulAutotuneKey {
+    #[autotune(anchor)]
+    m: usize,
+    #[autotune(anchor)]
+    k: usize,
+    #[autotune(anchor)]
+    n: usize,
+    #[autotune(anchor(max = 256))]
+    batch: usize,
+    lhs_transposed: bool,
+    rhs_transposed: bool,
+    elem: Elem,
+}
+
+impl MatmulAutotuneKey {
+    fn from_tensors<R: Runtime, EG: Float>(
+        lhs: &TensorHandle<R, EG>,
+        rhs: &TensorHandle<R, EG>,
+        out: &TensorHandle<R, EG>,
+    ) -> Self {
+        let rank = out.shape.len();
+        let transposed = |tensor: &TensorHandle<R, EG>| {
+            matches!(
+                matrix_layout(&tensor.strides),
+                MatrixLayout::MildlyPermuted {
+                    transposed: true,
+                    ..
+                }
+            )
+        };
+
+        Self::new(
+            lhs.shape[rank - 2],
+            lhs.shape[rank - 1],
+            rhs.shape[rank - 1],
+            out.shape[..rank - 2].iter().product(),
+            transposed(lhs),
+            transposed(rhs),
+            EG::as_elem(),
+        )
+    }
+}
+
+/// Launch the fastest matmul kernel for the shape of the problem,
+/// benchmarking all available kernels the first time a shape is seen.
+pub fn launch_autotune<R: Runtime, EG: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandle<R, EG>,
+    rhs: TensorHandle<R, EG>,
+    out: TensorHandle<R, EG>,
+) -> TensorHandle<R, EG> {
+    // All candidates see inputs with the batch dimensions of the output,
+    // broadcast ones having a stride of 0.
+    let batches = &out.shape[..out.shape.len() - 2];
+    let broadcast = |tensor: TensorHandle<R, EG>| {
+        let (shape, strides) = broadcast_batches(&tensor.shape, &tensor.strides, batches);
+        TensorHandle::new(shape, strides, tensor.handle)
+    };
+    let lhs = broadcast(lhs);
+    let rhs = broadcast(rhs);
+
+    let operation_set = Box::new(MatmulAutotuneOperationSet::<R, EG> {
+        key: MatmulAutotuneKey::from_tensors(&lhs, &rhs, &out),
+        client: client.clone(),
+        lhs,
+        rhs,
+        out: out.clone(),
+    });
+
+    TUNER.execute(&R::name().to_string(), client, operation_set);
+
+    out
+}
+
+#[derive(Clone, Copy, Debug)]
+/// A kernel, with its configuration, that can compute a matmul
+enum MatmulCandidate {
+    Tiling2D,
+    CmmaOld,
+    Matmul {
+        disable_cmma: bool,
+        selection: MatmulSelection,
+    },
+}
+
+/// The kernels benchmarked by the tuner.
+///
+/// The first candidate is available everywhere, since it is used while tuning is pending.
+fn candidates() -> Vec<MatmulCandidate> {
+    let mut candidates = vec![MatmulCandidate::Tiling2D, MatmulCandidate::CmmaOld];
+
+    for disable_cmma in [false, true] {
+        let mut push = |selection| {
+            candidates.push(MatmulCandidate::Matmul {
+                disable_cmma,
+                selection,
+            })
+        };
+
+        for stage in [
+            StageSelection::S2x2x2,
+            StageSelection::S4x4x2,
+            StageSelection::S8x8x1,
+        ] {
+            push(MatmulSelection {
+                stage,
+                ..Default::default()
+            });
+        }
+
+        for (lhs_tiling_order, rhs_tiling_order) in [
+            (TilingOrderConfig::RowMajor, TilingOrderConfig::ColMajor),
+            (TilingOrderConfig::ColMajor, TilingOrderConfig::RowMajor),
+            (TilingOrderConfig::ColMajor, TilingOrderConfig::ColMajor),
+        ] {
+            push(MatmulSelection {
+                lhs_tiling_order,
+                rhs_tiling_order,
+                ..Default::default()
+            });
+        }
+
+        for splits in [2, 4, 8] {
+            push(MatmulSelection {
+                decomposition: Decomposition::SplitK { splits },
+                ..Default::default()
+            });
+        }
+
+        for cubes in [32, 128] {
+            push(MatmulSelection {
+                decomposition: Decomposition::StreamK { cubes },
+                ..Default::default()
+            });
+        }
+    }
+
+    candidates
+}
+
+impl MatmulCandidate {
+    fn is_available<R: Runtime, EG: Float>(
+        &self,
+        client: &ComputeClient<R::Server, R::Channel>,
+        key: &MatmulAutotuneKey,
+    ) -> bool {
+        match self {
+            MatmulCandidate::Tiling2D => true,
+            MatmulCandidate::CmmaOld => {
+                cmma_old::is_available::<R, EG>(client, &PredefinedCmmaConfig::M128K16.into())
+                    .is_ok()
+            }
+            MatmulCandidate::Matmul {
+                disable_cmma,
+                selection,
+            } => {
+                let available = match disable_cmma {
+                    true => {
+                        PlaneMma::<PlaneMmaPrecision<EG>>::check_availability::<R>(client).is_ok()
+                    }
+                    false => Cmma::<CmmaPrecision<EG>>::check_availability::<R>(client).is_ok(),
+                };
+
+                // Splitting k only pays off when it dominates the output dimensions.
+                let worth_it = match selection.decomposition {
+                    Decomposition::SplitK { splits } => {
+                        key.k >= splits as usize * Ord::max(key.m, key.n)
+                    }
+                    _ => true,
+                };
+
+                available && worth_it
+            }
+        }
+    }
+
+    fn launch<R: Runtime, EG: Float>(
+        &self,
+        client: &ComputeClient<R::Server, R::Channel>,
+        lhs: &TensorHandle<R, EG>,
+        rhs: &TensorHandle<R, EG>,
+        out: &TensorHandle<R, EG>,
+    ) {
+        match self {
+            MatmulCandidate::Tiling2D => tiling2d::launch_ref::<R, EG>(
+                client,
+                lhs.as_ref(),
+                rhs.as_ref(),
+                out.as_ref(),
+                Default::default(),
+            ),
+            MatmulCandidate::CmmaOld => cmma_old::launch_ref::<R, EG>(
+                client,
+                lhs.as_ref(),
+                rhs.as_ref(),
+                out.as_ref(),
+                PredefinedCmmaConfig::M128K16.into(),
+            ),
+            MatmulCandidate::Matmul {
+                disable_cmma,
+                selection,
+            } => matmul::launch_ref_default_precision::<R, EG>(
+                client,
+                lhs.as_ref(),
+                rhs.as_ref(),
+                out.as_ref(),
+                MatmulOptions {
+                    selection: *selection,
+                    disable_cmma: *disable_cmma,
+                    ..Default::default()
+                },
+            )
+            .expect("Should launch without an epilogue to check"),
+        }
+    }
+}
+
+struct MatmulAutotuneOperationSet<R: Runtime, EG: Float> {
+    key: MatmulAutotuneKey,
+    client: ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandle<R, EG>,
+    rhs: TensorHandle<R, EG>,
+    out: TensorHandle<R, EG>,
+}
+
+impl<R: Runtime, EG: Float> AutotuneOperationSet<MatmulAutotuneKey>
+    for MatmulAutotuneOperationSet<R, EG>
+{
+    fn key(&self) -> MatmulAutotuneKey {
+        self.key.clone()
+    }
+
+    fn autotunables(&self) -> Vec<Box<dyn AutotuneOperation>> {
+        candidates()
+            .into_iter()
+            .map(|candidate| -> Box<dyn AutotuneOperation> {
+                Box::new(MatmulAutotuneOperation::<R, EG> {
+                    name: format!("{candidate:?}"),
+                    candidate,
+                    client: self.client.clone(),
+                    lhs: self.lhs.clone(),
+                    rhs: self.rhs.clone(),
+                    out: self.out.clone(),
+                })
+            })
+            .collect()
+    }
+
+    fn fastest(self: Box<Self>, fastest_index: usize) -> Box<dyn AutotuneOperation> {
+        self.autotunables().swap_remove(fastest_index)
+    }
+
+    fn should_run(&self, key: &MatmulAutotuneKey, index: usize) -> bool {
+        candidates()[index].is_available::<R, EG>(&self.client, key)
+    }
+}
+
+/// Launches the matmul with one of the candidates
+struct MatmulAutotuneOperation<R: Runtime, EG: Float> {
+    name: String,
+    candidate: MatmulCandidate,
+    client: ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandle<R, EG>,
+    rhs: TensorHandle<R, EG>,
+    out: TensorHandle<R, EG>,
+}
+
+impl<R: Runtime, EG: Float> core::fmt::Debug for MatmulAutotuneOperation<R, EG> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MatmulAutotuneOperation")
+            .field("candidate", &self.candidate)
+            .field("lhs", &self.lhs)
+            .field("rhs", &self.rhs)
+            .field("out", &self.out)
+            .finish()
+    }
+}
+
+impl<R: Runtime, EG: Float> AutotuneOperation for MatmulAutotuneOperation<R, EG> {
+    fn execute(self: Box<Self>) {
+        self.candidate
+            .launch::<R, EG>(&self.client, &self.lhs, &self.rhs, &self.out);
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn clone(&self) -> Box<dyn AutotuneOperation> {
+        Box::new(Self {
+            name: self.name.clone(),
+            candidate: self.candidate,
+            client: self.client.clone(),
+            lhs: self.lhs.clone(),
+            rhs: self.rhs.clone(),
+            out: self.out.clone(),
+        })
+    }
+}
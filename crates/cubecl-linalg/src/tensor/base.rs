@@ -1,6 +1,4 @@
-use cubecl_core::calculate_cube_count_elemwise;
 use cubecl_core::prelude::*;
-use cubecl_core::tensor_line_size;
 use cubecl_core::Runtime;
 use cubecl_runtime::server::Handle;
 use std::marker::PhantomData;
@@ -139,42 +137,9 @@ where
     }
 
     pub fn zeros(client: &ComputeClient<R::Server, R::Channel>, shape: Vec<usize>) -> Self {
-        let num_elements: usize = shape.iter().product();
-        let rank = shape.len();
         let output = Self::empty(client, shape);
-
-        let vectorization_factor = tensor_line_size(
-            R::supported_line_sizes(),
-            &output.shape,
-            &output.strides,
-            rank - 1,
-        );
-
-        let cube_dim = CubeDim::default();
-        let cube_count =
-            calculate_cube_count_elemwise(num_elements / vectorization_factor as usize, cube_dim);
-
-        unsafe {
-            init::zeros_array::launch_unchecked::<E, R>(
-                client,
-                cube_count,
-                cube_dim,
-                ArrayArg::from_raw_parts::<E>(&output.handle, num_elements, vectorization_factor),
-            )
-        };
+        client.fill(output.handle.clone().binding(), &[0]);
 
         output
     }
 }
-
-pub(crate) mod init {
-    use cubecl::prelude::*;
-    use cubecl_core as cubecl;
-
-    #[cube(launch_unchecked)]
-    pub fn zeros_array<C: Numeric>(output: &mut Array<C>) {
-        if ABSOLUTE_POS < output.len() {
-            output[ABSOLUTE_POS] = C::from_int(0);
-        }
-    }
-}
@@ -1,3 +1,4 @@
+use super::permute::{into_contiguous_permuted, permuted_axis};
 use super::TensorHandle;
 use cubecl::prelude::*;
 use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, tensor_line_size};
@@ -57,10 +58,19 @@ fn into_contiguous_kernel<N: CubePrimitive>(
 }
 
 /// Make a jit tensor contiguous.
+///
+/// Permutations of contiguous tensors that aren't contiguous along their last dimension,
+/// like transposed matrices, are copied with a tiled kernel.
 pub fn into_contiguous<R: Runtime, E: CubePrimitive>(
     client: &ComputeClient<R::Server, R::Channel>,
     input: TensorHandleRef<'_, R>,
 ) -> TensorHandle<R, E> {
+    if let Some(axis) = permuted_axis(input.shape, input.strides) {
+        if let Some(output) = into_contiguous_permuted(client, &input, axis) {
+            return output;
+        }
+    }
+
     let num_elems: usize = input.shape.iter().product();
     // Vectorization is only enabled when the last dimension is contiguous.
     let rank = input.strides.len();
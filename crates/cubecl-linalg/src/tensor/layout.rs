@@ -62,6 +62,47 @@ pub fn matrix_layout(strides: &[usize]) -> MatrixLayout {
     }
 }
 
+/// Returns the shape and strides of a matrix tensor viewed with the given batch dimensions.
+///
+/// Batch dimensions are broadcast like in numpy: missing leading dimensions and dimensions
+/// of size 1 are repeated over the corresponding batch dimension with a stride of 0.
+///
+/// # Panics
+///
+/// If a batch dimension of the tensor is neither 1 nor equal to the corresponding batch dimension.
+pub fn broadcast_batches(
+    shape: &[usize],
+    strides: &[usize],
+    batches: &[usize],
+) -> (Vec<usize>, Vec<usize>) {
+    let rank = shape.len();
+    assert!(
+        rank >= 2 && rank - 2 <= batches.len(),
+        "Can't broadcast a tensor of shape {shape:?} to the batches {batches:?}"
+    );
+    let num_missing = batches.len() + 2 - rank;
+
+    let mut broadcast_shape = batches.to_vec();
+    let mut broadcast_strides = vec![0; batches.len()];
+
+    for (axis, batch) in batches.iter().enumerate().skip(num_missing) {
+        let dim = shape[axis - num_missing];
+        assert!(
+            dim == *batch || dim == 1,
+            "Can't broadcast a tensor of shape {shape:?} to the batches {batches:?}"
+        );
+
+        if dim == *batch {
+            broadcast_strides[axis] = strides[axis - num_missing];
+        }
+    }
+
+    broadcast_shape.extend_from_slice(&shape[rank - 2..]);
+    broadcast_strides.extend_from_slice(&strides[rank - 2..]);
+
+    (broadcast_shape, broadcast_strides)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,4 +223,24 @@ mod tests {
             unreachable!()
         }
     }
+
+    #[test]
+    fn broadcast_missing_batch_dims() {
+        let (shape, strides) = broadcast_batches(&[3, 4], &[4, 1], &[2, 5]);
+        assert_eq!(shape, vec![2, 5, 3, 4]);
+        assert_eq!(strides, vec![0, 0, 4, 1]);
+    }
+
+    #[test]
+    fn broadcast_batch_dims_of_size_one() {
+        let (shape, strides) = broadcast_batches(&[1, 5, 3, 4], &[60, 12, 4, 1], &[2, 5]);
+        assert_eq!(shape, vec![2, 5, 3, 4]);
+        assert_eq!(strides, vec![0, 12, 4, 1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn broadcast_incompatible_batch_dims() {
+        broadcast_batches(&[3, 3, 4], &[12, 4, 1], &[2]);
+    }
 }
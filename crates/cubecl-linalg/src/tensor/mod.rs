@@ -1,7 +1,13 @@
 mod base;
 mod contiguous;
 mod layout;
+mod permute;
 
 pub use base::*;
 pub use contiguous::*;
 pub use layout::*;
+pub use permute::*;
+
+/// Tests for tensor kernels
+#[cfg(feature = "export_tests")]
+pub mod tests;
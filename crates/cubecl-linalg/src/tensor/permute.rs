@@ -0,0 +1,279 @@
+use super::{into_contiguous, TensorHandle};
+use cubecl::prelude::*;
+use cubecl_core::ir::{Elem, UIntKind};
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, Feature};
+
+/// Size of the square tiles copied by each cube of the permute kernel.
+const TILE_SIZE: u32 = 32;
+
+#[cube(launch_unchecked)]
+fn permute_kernel<E: Numeric>(
+    input: &Tensor<Line<E>>,
+    output: &mut Tensor<Line<E>>,
+    #[comptime] axis: u32,
+    #[comptime] tile_size: u32,
+) {
+    let rank = output.rank();
+    let last = rank - 1;
+    let size_axis = output.shape(axis);
+    let size_last = output.shape(last);
+    let tiles_axis = (size_axis + tile_size - 1) / tile_size;
+    let tiles_last = (size_last + tile_size - 1) / tile_size;
+    let num_tiles = tiles_axis * tiles_last;
+
+    let tile = CUBE_POS % num_tiles;
+    let axis_start = (tile / tiles_last) * tile_size;
+    let last_start = (tile % tiles_last) * tile_size;
+
+    // The remaining dimensions are spread over the cubes, like in a contiguous tensor.
+    let mut batch = CUBE_POS / num_tiles;
+    let mut input_offset = 0;
+    let mut output_offset = 0;
+
+    for i in 0..last {
+        let dim = last - 1 - i;
+
+        if dim != axis {
+            let shape = output.shape(dim);
+
+            input_offset += (batch % shape) * input.stride(dim);
+            output_offset += (batch % shape) * output.stride(dim);
+            batch /= shape;
+        }
+    }
+
+    // Cubes past the last tile have a leftover batch.
+    if batch == 0 {
+        let line_size_in = input.line_size();
+        let line_size_out = output.line_size();
+        let padded_size = comptime!(tile_size + 1);
+
+        // The tile is stored with the axis as rows, so the lines of the output are read from rows.
+        // Rows are padded to avoid bank conflicts when writing columns.
+        let mut tile = SharedMemory::<E>::new(comptime!(tile_size * (tile_size + 1)));
+
+        let lines_per_column = comptime!(tile_size / input.line_size());
+        for index in range_stepped(UNIT_POS, tile_size * lines_per_column, CUBE_DIM) {
+            let col = index / lines_per_column;
+            let row = (index % lines_per_column) * line_size_in;
+            let pos_axis = axis_start + row;
+            let pos_last = last_start + col;
+
+            if pos_axis < size_axis && pos_last < size_last {
+                let line = input[(input_offset
+                    + pos_axis * input.stride(axis)
+                    + pos_last * input.stride(last))
+                    / line_size_in];
+
+                #[unroll]
+                for i in 0..line_size_in {
+                    tile[(row + i) * padded_size + col] = line[i];
+                }
+            }
+        }
+
+        sync_units();
+
+        let lines_per_row = comptime!(tile_size / output.line_size());
+        for index in range_stepped(UNIT_POS, tile_size * lines_per_row, CUBE_DIM) {
+            let row = index / lines_per_row;
+            let col = (index % lines_per_row) * line_size_out;
+            let pos_axis = axis_start + row;
+            let pos_last = last_start + col;
+
+            if pos_axis < size_axis && pos_last < size_last {
+                let mut line = Line::empty(line_size_out);
+
+                #[unroll]
+                for i in 0..line_size_out {
+                    line[i] = tile[row * padded_size + col + i];
+                }
+
+                output[(output_offset
+                    + pos_axis * output.stride(axis)
+                    + pos_last * output.stride(last))
+                    / line_size_out] = line;
+            }
+        }
+    }
+}
+
+/// Permute the dimensions of a jit tensor into a new contiguous tensor,
+/// where the dimension `i` of the output is the dimension `axes[i]` of the input.
+pub fn permute<R: Runtime, E: CubePrimitive>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    axes: &[usize],
+) -> TensorHandle<R, E> {
+    let rank = input.shape.len();
+    let mut sorted = axes.to_vec();
+    sorted.sort();
+    assert!(
+        sorted.iter().copied().eq(0..rank),
+        "The axes {axes:?} should be a permutation of the {rank} dimensions of the tensor"
+    );
+
+    let shape: Vec<usize> = axes.iter().map(|axis| input.shape[*axis]).collect();
+    let strides: Vec<usize> = axes.iter().map(|axis| input.strides[*axis]).collect();
+    let view = unsafe {
+        TensorHandleRef::<R>::from_raw_parts(input.handle, &strides, &shape, input.elem_size)
+    };
+
+    into_contiguous::<R, E>(client, view)
+}
+
+/// Returns the dimension along which the tensor is contiguous, if the tensor is
+/// a permutation of a contiguous tensor that isn't contiguous along its last dimension.
+///
+/// Dimensions of size 1 are ignored, since their stride has no effect.
+pub(crate) fn permuted_axis(shape: &[usize], strides: &[usize]) -> Option<usize> {
+    if shape.len() < 2 || shape.contains(&0) {
+        return None;
+    }
+
+    let mut dims: Vec<usize> = (0..shape.len()).filter(|dim| shape[*dim] != 1).collect();
+    dims.sort_by_key(|dim| strides[*dim]);
+
+    let mut expected = 1;
+    for &dim in dims.iter() {
+        if strides[dim] != expected {
+            return None;
+        }
+        expected *= shape[dim];
+    }
+
+    match dims.first() {
+        Some(&axis) if axis != shape.len() - 1 && shape[shape.len() - 1] != 1 => Some(axis),
+        _ => None,
+    }
+}
+
+/// Make a jit tensor contiguous with the tiled permute kernel, where the input
+/// is a permutation of a contiguous tensor along the given axis.
+///
+/// Each cube copies a square tile over the axis and the last dimension,
+/// reading lines along the axis and writing lines along the last dimension
+/// through shared memory.
+///
+/// The kernel only moves bits, so elements are copied as unsigned integers of the same size.
+/// Returns `None` if the element isn't numeric, or if that integer type isn't supported.
+pub(crate) fn into_contiguous_permuted<R: Runtime, E: CubePrimitive>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    axis: usize,
+) -> Option<TensorHandle<R, E>> {
+    let elem = match E::as_elem() {
+        elem @ (Elem::Float(_) | Elem::Int(_) | Elem::UInt(_)) => elem,
+        _ => return None,
+    };
+    let kind = match elem.size() {
+        1 => UIntKind::U8,
+        2 => UIntKind::U16,
+        4 => UIntKind::U32,
+        8 => UIntKind::U64,
+        _ => return None,
+    };
+    if !client
+        .properties()
+        .feature_enabled(Feature::Type(Elem::UInt(kind)))
+    {
+        return None;
+    }
+
+    let num_elems: usize = input.shape.iter().product();
+    let handle = client.empty(num_elems * elem.size());
+    let output = TensorHandle::new_contiguous(input.shape.to_vec(), handle);
+
+    match kind {
+        UIntKind::U8 => launch_permute::<R, u8>(client, input, &output.as_ref(), axis),
+        UIntKind::U16 => launch_permute::<R, u16>(client, input, &output.as_ref(), axis),
+        UIntKind::U32 => launch_permute::<R, u32>(client, input, &output.as_ref(), axis),
+        UIntKind::U64 => launch_permute::<R, u64>(client, input, &output.as_ref(), axis),
+    }
+
+    Some(output)
+}
+
+fn launch_permute<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    output: &TensorHandleRef<'_, R>,
+    axis: usize,
+) {
+    let rank = input.shape.len();
+    let line_size = |size: usize| {
+        R::supported_line_sizes()
+            .iter()
+            .copied()
+            .find(|line_size| size % *line_size as usize == 0)
+            .unwrap_or(1)
+    };
+    // The other dimensions are outer dimensions in memory, so their strides are divisible as well.
+    let line_size_in = line_size(input.shape[axis]);
+    let line_size_out = line_size(input.shape[rank - 1]);
+
+    let num_elems: usize = input.shape.iter().product();
+    let num_tiles = [axis, rank - 1]
+        .iter()
+        .map(|dim| input.shape[*dim].div_ceil(TILE_SIZE as usize))
+        .product::<usize>();
+    let num_batches = num_elems / (input.shape[axis] * input.shape[rank - 1]);
+    let cube_dim = CubeDim::new(TILE_SIZE, 8, 1);
+    let cube_count = calculate_cube_count_elemwise(
+        num_tiles * num_batches * cube_dim.num_elems() as usize,
+        cube_dim,
+    );
+
+    unsafe {
+        permute_kernel::launch_unchecked::<E, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(line_size_in),
+            output.as_tensor_arg(line_size_out),
+            axis as u32,
+            TILE_SIZE,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transposed_matrix_is_permuted() {
+        assert_eq!(permuted_axis(&[4, 8], &[1, 4]), Some(0));
+    }
+
+    #[test]
+    fn permuted_batches_are_permuted() {
+        // E.g., tensor w/ shape [2, 3, 4] permuted to [4, 2, 3]
+        assert_eq!(permuted_axis(&[4, 2, 3], &[1, 12, 4]), Some(0));
+        // E.g., tensor w/ shape [2, 3, 4] permuted to [2, 4, 3]
+        assert_eq!(permuted_axis(&[2, 4, 3], &[12, 1, 4]), Some(1));
+    }
+
+    #[test]
+    fn contiguous_last_dim_is_not_permuted() {
+        assert_eq!(permuted_axis(&[3, 2, 4], &[4, 12, 1]), None);
+        assert_eq!(permuted_axis(&[2, 3, 4], &[12, 4, 1]), None);
+    }
+
+    #[test]
+    fn unit_dims_are_ignored() {
+        assert_eq!(permuted_axis(&[4, 1, 8], &[1, 1, 4]), Some(0));
+        assert_eq!(permuted_axis(&[4, 8, 1], &[1, 4, 7]), None);
+    }
+
+    #[test]
+    fn strided_tensor_is_not_permuted() {
+        assert_eq!(permuted_axis(&[4, 8], &[1, 8]), None);
+        assert_eq!(permuted_axis(&[4, 8], &[2, 8]), None);
+    }
+
+    #[test]
+    fn broadcast_tensor_is_not_permuted() {
+        assert_eq!(permuted_axis(&[4, 8], &[1, 0]), None);
+    }
+}
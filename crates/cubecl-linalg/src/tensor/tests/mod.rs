@@ -0,0 +1,4 @@
+#![allow(missing_docs)]
+
+pub mod permute_test_launcher;
+mod test_macros;
@@ -0,0 +1,56 @@
+use std::fmt::Debug;
+
+use cubecl_core::prelude::*;
+use cubecl_core::{CubeElement, Feature};
+
+use crate::tensor::{self, permute::permuted_axis, TensorHandle};
+
+/// Test the tiled permute kernel on the given device, against the gather kernel of
+/// [into_contiguous_prefetch](tensor::into_contiguous_prefetch) over the same permuted view
+/// of a contiguous tensor of the given shape
+pub fn test_permute<E: Numeric + CubeElement + Debug, R: Runtime>(
+    shape: &[usize],
+    axes: &[usize],
+    device: &R::Device,
+) {
+    let client: ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel> = R::client(device);
+
+    if !client
+        .properties()
+        .feature_enabled(Feature::Type(E::as_elem()))
+    {
+        // Can't execute the test.
+        return;
+    }
+
+    let num_elems: usize = shape.iter().product();
+    let data: Vec<E> = (0..num_elems)
+        .map(|i| E::from_int((i % 251) as i64))
+        .collect();
+    let input =
+        TensorHandle::<R, E>::new_contiguous(shape.to_vec(), client.create(E::as_bytes(&data)));
+
+    let permuted_shape: Vec<usize> = axes.iter().map(|axis| shape[*axis]).collect();
+    let permuted_strides: Vec<usize> = axes.iter().map(|axis| input.strides[*axis]).collect();
+    let view = unsafe {
+        TensorHandleRef::<R>::from_raw_parts(
+            &input.handle,
+            &permuted_strides,
+            &permuted_shape,
+            E::as_elem().size(),
+        )
+    };
+    assert!(
+        permuted_axis(&permuted_shape, &permuted_strides).is_some(),
+        "The permutation should be copied with the tiled kernel"
+    );
+
+    let expected = tensor::into_contiguous_prefetch::<R, E>(&client, view, 1);
+    let expected = E::from_bytes(&client.read_one(expected.handle.binding())).to_vec();
+
+    let permuted = tensor::permute::<R, E>(&client, input.as_ref(), axes);
+    assert_eq!(permuted.shape, permuted_shape);
+    let actual = E::from_bytes(&client.read_one(permuted.handle.binding())).to_vec();
+
+    assert_eq!(actual, expected);
+}
@@ -0,0 +1,44 @@
+#[macro_export]
+macro_rules! testgen_permute {
+    () => {
+        mod permute {
+            $crate::testgen_permute!(f32);
+        }
+    };
+
+    ($elem:ident) => {
+        use super::*;
+        use cubecl_linalg::tensor::tests::permute_test_launcher::test_permute;
+
+        pub type ElemT = $elem;
+
+        #[test]
+        pub fn test_permute_transpose() {
+            test_permute::<ElemT, TestRuntime>(&[64, 32], &[1, 0], &Default::default());
+        }
+
+        #[test]
+        pub fn test_permute_transpose_odd_sizes() {
+            test_permute::<ElemT, TestRuntime>(&[47, 33], &[1, 0], &Default::default());
+        }
+
+        #[test]
+        pub fn test_permute_nd_odd_sizes() {
+            test_permute::<ElemT, TestRuntime>(&[3, 5, 7, 9], &[0, 3, 1, 2], &Default::default());
+            test_permute::<ElemT, TestRuntime>(&[2, 37, 45], &[2, 0, 1], &Default::default());
+        }
+    };
+
+    ([$($elem:ident),*]) => {
+        mod permute {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$elem _ty>] {
+                    use super::*;
+
+                    $crate::testgen_permute!($elem);
+                })*
+            }
+        }
+    };
+}
@@ -1,7 +1,8 @@
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
 use syn::{
-    AngleBracketedGenericArguments, Ident, Lit, Member, Pat, Path, PathArguments, PathSegment, Type,
+    AngleBracketedGenericArguments, Ident, Lit, LitStr, Member, Pat, Path, PathArguments,
+    PathSegment, Type,
 };
 
 use crate::{
@@ -81,10 +82,13 @@ pub enum Expression {
         var_ty: Option<syn::Type>,
         block: Block,
         scope: Scope,
+        /// Whether the body contains early returns, which must exit the loop in turn.
+        returns: bool,
     },
     Loop {
         block: Block,
         scope: Scope,
+        returns: bool,
     },
     If {
         condition: Box<Expression>,
@@ -95,12 +99,24 @@ pub enum Expression {
         value: Box<Expression>,
         cases: Vec<(Lit, Block)>,
         default: Block,
+        returns: bool,
+    },
+    /// Match on a runtime enum, lowered to a switch on its discriminant.
+    EnumMatch {
+        value: Box<Expression>,
+        arms: Vec<EnumMatchArm>,
+        default: EnumMatchArm,
+        returns: bool,
     },
     Return {
         expr: Option<Box<Expression>>,
         span: Span,
         _ty: Type,
     },
+    /// Runtime `?` operator, returning early from the function.
+    Try {
+        expr: Box<Expression>,
+    },
     Range {
         start: Box<Expression>,
         end: Option<Box<Expression>>,
@@ -137,12 +153,31 @@ pub enum Expression {
     Keyword {
         name: syn::Ident,
     },
+    /// `debug_print!` with runtime arguments.
+    Print {
+        format_string: LitStr,
+        args: Vec<Expression>,
+    },
+    /// `cube_assert!` with a runtime condition and a comptime code.
+    Assert {
+        condition: Box<Expression>,
+        code: syn::Expr,
+    },
     ConstMatch {
         const_expr: syn::Expr,
         arms: Vec<ConstMatchArm>,
     },
 }
 
+#[derive(Clone, Debug)]
+pub struct EnumMatchArm {
+    /// The matched variant, `None` for the wildcard arm.
+    pub variant: Option<Ident>,
+    /// Payload fields bound in the arm, with the binding name and mutability.
+    pub bindings: Vec<(Member, Ident, bool)>,
+    pub block: Block,
+}
+
 #[derive(Clone, Debug)]
 pub struct ConstMatchArm {
     pub pat: syn::Pat,
@@ -178,7 +213,9 @@ impl Expression {
             Expression::Loop { .. } => None,
             Expression::If { then_block, .. } => then_block.ty.clone(),
             Expression::Switch { default, .. } => default.ty.clone(),
+            Expression::EnumMatch { default, .. } => default.block.ty.clone(),
             Expression::Return { expr, .. } => expr.as_ref().and_then(|expr| expr.ty()),
+            Expression::Try { .. } => None,
             Expression::Array { .. } => None,
             Expression::Index { .. } => None,
             Expression::Tuple { .. } => None,
@@ -191,6 +228,8 @@ impl Expression {
             Expression::Keyword { .. } => None,
             Expression::CompilerIntrinsic { .. } => None,
             Expression::ConstMatch { .. } => None,
+            Expression::Print { .. } => None,
+            Expression::Assert { .. } => None,
         }
     }
 
@@ -1,15 +1,21 @@
 use crate::{
-    parse::cube_type::{CubeTypeEnum, CubeTypeVariant, VariantKind},
-    paths::prelude_type,
+    parse::cube_type::{
+        variant_discriminant_ident, variant_field_ident, CubeTypeEnum, CubeTypeVariant, VariantKind,
+    },
+    paths::{frontend_type, prelude_type},
 };
-use proc_macro2::TokenStream;
-use quote::quote;
-use syn::Ident;
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::{Ident, Member, Type};
 
 impl CubeTypeEnum {
     pub fn generate(&self, with_launch: bool) -> TokenStream {
         assert!(!with_launch, "Can't create launchable enum yet.");
 
+        if self.runtime {
+            return self.generate_runtime();
+        }
+
         let expand_ty = self.expand_ty();
         let cube_type_impl = self.cube_type_impl();
         let expand_type_impl = self.expand_type_impl();
@@ -102,6 +108,332 @@ impl CubeTypeEnum {
     }
 }
 
+/// Payload storage of a runtime enum. Fields of different variants with the same type share a
+/// slot, so the expand type holds a union of the variant payloads instead of all of them.
+struct PayloadSlots {
+    types: Vec<Type>,
+    /// For each variant, the slot of each of its fields.
+    variants: Vec<Vec<usize>>,
+}
+
+impl PayloadSlots {
+    fn new(variants: &[CubeTypeVariant]) -> Self {
+        let mut types: Vec<Type> = Vec::new();
+
+        let variants = variants
+            .iter()
+            .map(|variant| {
+                let mut used = Vec::new();
+
+                for field in variant.fields.iter() {
+                    let key = field.ty.to_token_stream().to_string();
+                    let slot = (0..types.len()).find(|slot| {
+                        !used.contains(slot) && types[*slot].to_token_stream().to_string() == key
+                    });
+                    let slot = slot.unwrap_or_else(|| {
+                        types.push(field.ty.clone());
+                        types.len() - 1
+                    });
+                    used.push(slot);
+                }
+
+                used
+            })
+            .collect();
+
+        Self { types, variants }
+    }
+
+    fn ident(slot: usize) -> Ident {
+        format_ident!("__slot_{slot}")
+    }
+}
+
+impl CubeTypeEnum {
+    /// Runtime enums are lowered to a `u32` discriminant and a union of payload slots. Matching on
+    /// them is done with a switch on the discriminant.
+    fn generate_runtime(&self) -> TokenStream {
+        let slots = PayloadSlots::new(&self.variants);
+
+        let expand_ty = self.runtime_expand_ty(&slots);
+        let cube_type_impl = self.cube_type_impl();
+        let expand_impl = self.runtime_expand_impl(&slots);
+        let constructors = self.runtime_constructors(&slots);
+        let into_runtime_impl = self.runtime_into_runtime_impl(&slots);
+
+        quote! {
+            #expand_ty
+            #cube_type_impl
+            #expand_impl
+            #constructors
+            #into_runtime_impl
+        }
+    }
+
+    fn runtime_expand_ty(&self, slots: &PayloadSlots) -> TokenStream {
+        let expand_elem = prelude_type("ExpandElementTyped");
+        let name = &self.name_expand;
+        let generics = &self.generics;
+        let vis = &self.vis;
+        let fields = slots.types.iter().enumerate().map(|(slot, ty)| {
+            let ident = PayloadSlots::ident(slot);
+            quote![#ident: #expand_elem<#ty>]
+        });
+
+        quote! {
+            #[derive(Clone)]
+            #vis struct #name #generics {
+                discriminant: #expand_elem<u32>,
+                #(#fields),*
+            }
+        }
+    }
+
+    fn runtime_expand_impl(&self, slots: &PayloadSlots) -> TokenStream {
+        let context = prelude_type("CubeContext");
+        let expand_elem = prelude_type("ExpandElementTyped");
+        let init = prelude_type("Init");
+        let assign = prelude_type("Assign");
+
+        let name_expand = &self.name_expand;
+        let (generics, generic_names, where_clause) = self.generics.split_for_impl();
+        let slot_idents = (0..slots.types.len())
+            .map(PayloadSlots::ident)
+            .collect::<Vec<_>>();
+        let try_impl = self.runtime_try_impl();
+
+        let accessors = self
+            .variants
+            .iter()
+            .zip(slots.variants.iter())
+            .enumerate()
+            .map(|(index, (variant, variant_slots))| {
+                let discriminant = variant_discriminant_ident(&variant.ident);
+                let index = Literal::u32_suffixed(index as u32);
+                let fields = variant.fields.iter().enumerate().zip(variant_slots).map(
+                    |((i, field), slot)| {
+                        let member = match &field.ident {
+                            Some(name) => Member::Named(name.clone()),
+                            None => Member::Unnamed(i.into()),
+                        };
+                        let method = variant_field_ident(&variant.ident, &member);
+                        let ty = &field.ty;
+                        let slot = PayloadSlots::ident(*slot);
+
+                        quote! {
+                            pub fn #method(&self) -> #expand_elem<#ty> {
+                                self.#slot.clone()
+                            }
+                        }
+                    },
+                );
+
+                quote! {
+                    pub fn #discriminant(&self) -> u32 {
+                        #index
+                    }
+
+                    #(#fields)*
+                }
+            });
+
+        quote! {
+            #[allow(non_snake_case)]
+            impl #generics #name_expand #generic_names #where_clause {
+                pub fn __discriminant(&self) -> #expand_elem<u32> {
+                    self.discriminant.clone()
+                }
+
+                #(#accessors)*
+            }
+
+            impl #generics #init for #name_expand #generic_names #where_clause {
+                fn init(self, context: &mut #context) -> Self {
+                    Self {
+                        discriminant: #init::init(self.discriminant, context),
+                        #(#slot_idents: #init::init(self.#slot_idents, context)),*
+                    }
+                }
+            }
+
+            impl #generics #assign for #name_expand #generic_names #where_clause {
+                fn expand_assign(self, context: &mut #context, output: Self) {
+                    #assign::expand_assign(self.discriminant, context, output.discriminant);
+                    #(#assign::expand_assign(self.#slot_idents, context, output.#slot_idents);)*
+                }
+
+                fn expand_declare(&self, context: &mut #context) -> Self {
+                    Self {
+                        discriminant: #assign::expand_declare(&self.discriminant, context),
+                        #(#slot_idents: #assign::expand_declare(&self.#slot_idents, context)),*
+                    }
+                }
+            }
+
+            #try_impl
+        }
+    }
+
+    /// Implement `?` for the variant marked with `#[expand(try_output)]`, every other variant
+    /// being returned early.
+    fn runtime_try_impl(&self) -> Option<TokenStream> {
+        let context = prelude_type("CubeContext");
+        let expand_elem = prelude_type("ExpandElementTyped");
+        let cube_try = prelude_type("CubeTry");
+        let ne = frontend_type("ne");
+
+        let name_expand = &self.name_expand;
+        let (generics, generic_names, where_clause) = self.generics.split_for_impl();
+        let (index, variant) = self
+            .variants
+            .iter()
+            .enumerate()
+            .find(|(_, variant)| variant.try_output)?;
+
+        let discriminant = Literal::u32_suffixed(index as u32);
+        let field = variant.fields.iter().next()?;
+        let ty = &field.ty;
+        let member = match &field.ident {
+            Some(name) => Member::Named(name.clone()),
+            None => Member::Unnamed(0.into()),
+        };
+        let output = variant_field_ident(&variant.ident, &member);
+
+        let name = &self.ident;
+        let cube_try_output = prelude_type("CubeTryOutput");
+
+        Some(quote! {
+            impl #generics #cube_try_output for #name #generic_names #where_clause {
+                type Output = #ty;
+            }
+
+            impl #generics #cube_try for #name_expand #generic_names #where_clause {
+                type Output = #expand_elem<#ty>;
+
+                fn expand_branch(&self, context: &mut #context) -> (#expand_elem<bool>, Self::Output) {
+                    let cond = #ne::expand(context, self.discriminant.clone(), #expand_elem::from_lit(#discriminant));
+                    (cond, self.#output())
+                }
+            }
+        })
+    }
+
+    /// Construct the expand type of a variant, filling the slots that aren't part of the variant
+    /// payload with zeros.
+    fn runtime_variant_init(
+        &self,
+        slots: &PayloadSlots,
+        index: usize,
+        values: Vec<TokenStream>,
+    ) -> TokenStream {
+        let expand_elem = prelude_type("ExpandElementTyped");
+        let name_expand = &self.name_expand;
+        let discriminant = Literal::u32_suffixed(index as u32);
+
+        let fields = slots.types.iter().enumerate().map(|(slot, ty)| {
+            let ident = PayloadSlots::ident(slot);
+            let value = slots.variants[index]
+                .iter()
+                .position(|used| *used == slot)
+                .map(|field| values[field].clone())
+                .unwrap_or_else(|| quote![#expand_elem::<#ty>::from_lit(0u32)]);
+            quote![#ident: #value]
+        });
+
+        quote! {
+            #name_expand {
+                discriminant: #expand_elem::from_lit(#discriminant),
+                #(#fields),*
+            }
+        }
+    }
+
+    /// Variant constructors called by the expansion of `Enum::Variant(..)` and
+    /// `Enum::Variant { .. }`. Named fields are taken in alphabetical order, since the order of the
+    /// fields at the construction site isn't known.
+    fn runtime_constructors(&self, slots: &PayloadSlots) -> TokenStream {
+        let context = prelude_type("CubeContext");
+        let expand_elem = prelude_type("ExpandElementTyped");
+
+        let name = &self.ident;
+        let name_expand = &self.name_expand;
+        let (generics, generic_names, where_clause) = self.generics.split_for_impl();
+
+        let constructors = self
+            .variants
+            .iter()
+            .enumerate()
+            .filter(|(_, variant)| !matches!(variant.kind, VariantKind::Empty))
+            .map(|(index, variant)| {
+                let method = format_ident!("__expand_{}", variant.ident);
+                let args = variant.field_names.iter().zip(variant.fields.iter());
+                let mut params = args
+                    .clone()
+                    .map(|(name, field)| {
+                        let ty = &field.ty;
+                        (name.to_string(), quote![#name: #expand_elem<#ty>])
+                    })
+                    .collect::<Vec<_>>();
+                if let VariantKind::Named = variant.kind {
+                    params.sort_by(|a, b| a.0.cmp(&b.0));
+                }
+                let params = params.into_iter().map(|(_, param)| param);
+                let values = variant
+                    .field_names
+                    .iter()
+                    .map(|name| quote![#name])
+                    .collect();
+                let body = self.runtime_variant_init(slots, index, values);
+
+                quote! {
+                    pub fn #method(
+                        _context: &mut #context,
+                        #(#params),*
+                    ) -> #name_expand #generic_names {
+                        #body
+                    }
+                }
+            });
+
+        quote! {
+            #[allow(non_snake_case, clippy::too_many_arguments)]
+            impl #generics #name #generic_names #where_clause {
+                #(#constructors)*
+            }
+        }
+    }
+
+    fn runtime_into_runtime_impl(&self, slots: &PayloadSlots) -> TokenStream {
+        let context = prelude_type("CubeContext");
+        let into_runtime = prelude_type("IntoRuntime");
+        let init = prelude_type("Init");
+
+        let name = &self.ident;
+        let (generics, generic_names, where_clause) = self.generics.split_for_impl();
+
+        let arms = self.variants.iter().enumerate().map(|(index, variant)| {
+            let values = variant
+                .field_names
+                .iter()
+                .map(|name| quote![#into_runtime::__expand_runtime_method(#name, context)])
+                .collect();
+            let body = self.runtime_variant_init(slots, index, values);
+            variant.run_on_variants(name, body)
+        });
+
+        quote! {
+            impl #generics #into_runtime for #name #generic_names #where_clause {
+                fn __expand_runtime_method(self, context: &mut #context) -> Self::ExpandType {
+                    let expand = match self {
+                        #(#arms,)*
+                    };
+                    #init::init(expand, context)
+                }
+            }
+        }
+    }
+}
+
 impl CubeTypeVariant {
     fn expand_variant(&self) -> TokenStream {
         let name = &self.ident;
@@ -4,7 +4,7 @@ use syn::{Ident, Type, Visibility};
 
 use crate::{
     parse::cube_type::{CubeTypeStruct, TypeField},
-    paths::prelude_type,
+    paths::{core_type, prelude_type},
 };
 
 impl CubeTypeStruct {
@@ -17,6 +17,7 @@ impl CubeTypeStruct {
         let arg_settings_impl = self.arg_settings_impl();
         let launch_arg_impl = self.launch_arg_impl();
         let expand_type_impl = self.expand_type_impl();
+        let array_element_impl = self.array.is_present().then(|| self.array_element_impl());
 
         if with_launch {
             quote! {
@@ -28,12 +29,14 @@ impl CubeTypeStruct {
                 #arg_settings_impl
                 #launch_arg_impl
                 #expand_type_impl
+                #array_element_impl
             }
         } else {
             quote! {
                 #expand_ty
                 #cube_type_impl
                 #expand_type_impl
+                #array_element_impl
             }
         }
     }
@@ -308,6 +311,72 @@ impl CubeTypeStruct {
     }
 }
 
+impl CubeTypeStruct {
+    /// Store each field in its own array when the struct is an element of a `StructArray`. Since
+    /// all fields are primitives, the struct can also be assigned at runtime.
+    fn array_element_impl(&self) -> TokenStream {
+        if let Some(field) = self.fields.iter().find(|field| field.comptime.is_present()) {
+            return syn::Error::new_spanned(
+                field.ident.as_ref().unwrap(),
+                "Comptime fields can't be stored in an array",
+            )
+            .into_compile_error();
+        }
+
+        let array_element = prelude_type("ArrayElement");
+        let assign = prelude_type("Assign");
+        let context = prelude_type("CubeContext");
+        let cube_primitive = prelude_type("CubePrimitive");
+        let expand_element = prelude_type("ExpandElement");
+        let ir = core_type("ir");
+        let item = quote![#ir::Item];
+
+        let name = &self.ident;
+        let name_expand = &self.name_expand;
+        let (generics, generic_names, where_clause) = self.generics.split_for_impl();
+        let names = self
+            .fields
+            .iter()
+            .map(|field| field.ident.as_ref().unwrap())
+            .collect::<Vec<_>>();
+        let items = self.fields.iter().map(|field| {
+            let ty = &field.ty;
+            quote![#item::new(<#ty as #cube_primitive>::as_elem())]
+        });
+
+        quote! {
+            impl #generics #array_element for #name #generic_names #where_clause {
+                fn field_items() -> Vec<#item> {
+                    vec![#(#items),*]
+                }
+
+                fn __expand_into_fields(value: Self::ExpandType) -> Vec<#expand_element> {
+                    vec![#(value.#names.into()),*]
+                }
+
+                fn __expand_from_fields(fields: Vec<#expand_element>) -> Self::ExpandType {
+                    let mut fields = fields.into_iter();
+                    #name_expand {
+                        #(#names: fields.next().unwrap().into()),*
+                    }
+                }
+            }
+
+            impl #generics #assign for #name_expand #generic_names #where_clause {
+                fn expand_assign(self, context: &mut #context, output: Self) {
+                    #(#assign::expand_assign(self.#names, context, output.#names);)*
+                }
+
+                fn expand_declare(&self, context: &mut #context) -> Self {
+                    Self {
+                        #(#names: #assign::expand_declare(&self.#names, context)),*
+                    }
+                }
+            }
+        }
+    }
+}
+
 impl TypeField {
     pub fn expand_field(&self) -> TokenStream {
         let cube_type = prelude_type("CubeType");
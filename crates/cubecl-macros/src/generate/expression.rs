@@ -1,10 +1,11 @@
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote, quote_spanned};
-use syn::{spanned::Spanned, Member, PathArguments};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{spanned::Spanned, Member, Pat, PathArguments, Type};
 
 use crate::{
-    expression::{Block, ConstMatchArm, Expression},
+    expression::{Block, ConstMatchArm, EnumMatchArm, Expression},
     operator::Operator,
+    parse::cube_type::{variant_discriminant_ident, variant_field_ident},
     paths::{frontend_path, frontend_type, prelude_type},
     scope::Context,
 };
@@ -123,7 +124,7 @@ impl Expression {
                         let _array = #array;
                         let _index = #index;
                         let _value = #right;
-                        #frontend_path::index_assign::expand(context, _array, _index, _value)
+                        #frontend_path::IndexAssignExpand::expand_index_assign(_array, context, _index, _value)
                     }
                 }
             }
@@ -142,12 +143,31 @@ impl Expression {
             Expression::Index { expr, index } => {
                 let expr = expr.to_tokens(context);
                 let index = index.to_tokens(context);
-                let index_fn = frontend_type("index");
+                let index_expand = frontend_type("IndexExpand");
                 quote! {
                     {
                         let _array = #expr;
                         let _index = #index;
-                        #index_fn::expand(context, _array, _index)
+                        #index_expand::expand_index(_array, context, _index)
+                    }
+                }
+            }
+            Expression::FunctionCall {
+                func,
+                args,
+                associated_type: None,
+                ..
+            } if matches!(**func, Expression::Variable(ref var) if !var.is_const) => {
+                // Calling a closure passed as an `impl Fn` parameter, which takes the context.
+                let (args, arg_names) = map_args(args, context);
+                let name = match &**func {
+                    Expression::Variable(var) => &var.name,
+                    _ => unreachable!(),
+                };
+                quote! {
+                    {
+                        #(#args)*
+                        #name(context, #(#arg_names),*)
                     }
                 }
             }
@@ -166,6 +186,47 @@ impl Expression {
                     }
                 }
             }
+            Expression::Print {
+                format_string,
+                args,
+            } => {
+                let frontend = frontend_path();
+                let expand_elem = frontend_type("ExpandElement");
+                let args = args.iter().map(|arg| match arg.as_const(context) {
+                    Some(value) => {
+                        quote_spanned![value.span()=> #expand_elem::Plain((#value).into())]
+                    }
+                    None => {
+                        let value = arg.to_tokens(context);
+                        quote_spanned![value.span()=> #expand_elem::from(#value)]
+                    }
+                });
+                quote! {
+                    {
+                        let _args = vec![#(#args),*];
+                        #frontend::debug::print_expand(context, #format_string, _args)
+                    }
+                }
+            }
+            Expression::Assert { condition, code } => {
+                let frontend = frontend_path();
+                let expand_elem = frontend_type("ExpandElement");
+                let condition = match condition.as_const(context) {
+                    Some(value) => {
+                        quote_spanned![value.span()=> #expand_elem::Plain((#value).into())]
+                    }
+                    None => {
+                        let value = condition.to_tokens(context);
+                        quote_spanned![value.span()=> #expand_elem::from(#value)]
+                    }
+                };
+                quote! {
+                    {
+                        let _condition = #condition;
+                        #frontend::debug::assert_expand(context, _condition, #code)
+                    }
+                }
+            }
             Expression::CompilerIntrinsic { func, args } => {
                 let (args, arg_names) = map_args(args, context);
                 let mut path = func.clone();
@@ -219,11 +280,40 @@ impl Expression {
                 quote![#path::branch::break_expand(context);]
             }
             Expression::Continue(span) => error!(*span, "Continue not supported yet"),
-            Expression::Return { expr, span, .. } => {
-                if expr.is_some() {
-                    error!(*span, "Only void return is supported.")
-                } else {
-                    quote![cubecl::frontend::branch::return_expand(context);]
+            Expression::Return { expr: None, .. } => {
+                quote![cubecl::frontend::branch::return_expand(context);]
+            }
+            Expression::Return {
+                expr: Some(expr),
+                span,
+                ..
+            } => {
+                let value = match expr.as_const(context) {
+                    Some(as_const) => {
+                        let expand_elem = frontend_type("ExpandElementTyped");
+                        quote![#expand_elem::from_lit(#as_const)]
+                    }
+                    None => expr.to_tokens(context),
+                };
+                quote_spanned! {*span=>
+                    {
+                        let _value = #value;
+                        __return.expand_return(context, _value);
+                    }
+                }
+            }
+            Expression::Try { expr } => {
+                let branch = frontend_type("branch");
+                let expr = expr.to_tokens(context);
+                quote! {
+                    {
+                        let _try = #expr;
+                        let (_cond, _output) = #branch::CubeTry::expand_branch(&_try, context);
+                        #branch::if_expand(context, _cond.into(), |context| {
+                            __return.expand_return(context, _try)
+                        });
+                        _output
+                    }
                 }
             }
             Expression::Cast { from, to } => {
@@ -242,6 +332,7 @@ impl Expression {
                 var_ty,
                 block,
                 scope,
+                returns,
             } => {
                 let for_ty = frontend_type("branch");
 
@@ -252,20 +343,30 @@ impl Expression {
                     .unwrap_or(quote![false]);
                 let block = context.in_fn_mut(scope, |ctx| block.to_tokens(ctx));
                 let var_ty = var_ty.as_ref().map(|it| quote![: #it]);
+                let propagate = returns.then(|| quote![__return.expand_propagate(context);]);
 
                 quote! {
                     {
                         let _range = #range;
                         let _unroll = #unroll;
                         #for_ty::for_expand(context, _range, _unroll, |context, #var_name #var_ty| #block);
+                        #propagate
                     }
                 }
             }
-            Expression::Loop { block, scope } => {
+            Expression::Loop {
+                block,
+                scope,
+                returns,
+            } => {
                 let loop_ty = frontend_type("branch");
                 let block = context.in_fn_mut(scope, |ctx| block.to_tokens(ctx));
+                let propagate = returns.then(|| quote![__return.expand_propagate(context);]);
 
-                quote![#loop_ty::loop_expand(context, |context| #block);]
+                quote! {
+                    #loop_ty::loop_expand(context, |context| #block);
+                    #propagate
+                }
             }
             Expression::If {
                 condition,
@@ -331,6 +432,7 @@ impl Expression {
                 value,
                 cases,
                 default,
+                returns,
             } => {
                 let branch = frontend_type("branch");
                 let switch = match default.ret.is_some() {
@@ -346,12 +448,49 @@ impl Expression {
                         quote![.case(context, #val, |context| #block)]
                     })
                     .collect::<Vec<_>>();
+                let propagate = returns.then(|| quote![__return.expand_propagate(context);]);
                 quote! {
                     {
                         let _val = #value;
-                        #branch::#switch(context, _val.into(), |context| #default)
+                        let _out = #branch::#switch(context, _val.into(), |context| #default)
                             #(#blocks)*
-                            .finish(context)
+                            .finish(context);
+                        #propagate
+                        _out
+                    }
+                }
+            }
+            Expression::EnumMatch {
+                value,
+                arms,
+                default,
+                returns,
+            } => {
+                let branch = frontend_type("branch");
+                let switch = match default.block.ret.is_some() {
+                    true => quote![switch_expand_expr],
+                    false => quote![switch_expand],
+                };
+                let value = value.to_tokens(context);
+                let default = default.to_tokens(context);
+                let blocks = arms
+                    .iter()
+                    .map(|arm| {
+                        let variant = arm.variant.as_ref().unwrap();
+                        let discriminant = variant_discriminant_ident(variant);
+                        let block = arm.to_tokens(context);
+                        quote![.case(context, _val.#discriminant(), |context| #block)]
+                    })
+                    .collect::<Vec<_>>();
+                let propagate = returns.then(|| quote![__return.expand_propagate(context);]);
+                quote! {
+                    {
+                        let _val = #value;
+                        let _out = #branch::#switch(context, _val.__discriminant(), |context| #default)
+                            #(#blocks)*
+                            .finish(context);
+                        #propagate
+                        _out
                     }
                 }
             }
@@ -417,6 +556,26 @@ impl Expression {
                     quote![#inner]
                 }
             }
+            Expression::StructInit { path, fields } if is_enum_variant(path) => {
+                let mut fields = fields.clone();
+                fields.sort_by_key(|(member, _)| member.to_token_stream().to_string());
+                let args = fields
+                    .into_iter()
+                    .map(|(_, value)| value)
+                    .collect::<Vec<_>>();
+                let (args, arg_names) = map_args(&args, context);
+
+                let mut path = path.clone();
+                let variant = path.segments.pop().unwrap().into_value();
+                path.segments.pop_punct();
+                let constructor = format_ident!("__expand_{}", variant.ident);
+                quote! {
+                    {
+                        #(#args)*
+                        #path::#constructor(context, #(#arg_names),*)
+                    }
+                }
+            }
             Expression::StructInit { path, fields } => {
                 let cube_type = prelude_type("CubeType");
                 let fields = init_fields(fields, context);
@@ -450,6 +609,7 @@ impl Expression {
             } => {
                 // Without knowing the closure type, we need to assume it's `FnMut`
                 let body = context.in_fn_mut(scope, |ctx| body.to_tokens(ctx));
+                let params = params.iter().map(closure_param);
                 quote![|context, #(#params),*| #body]
             }
             Expression::Verbatim { tokens, .. } => tokens.clone(),
@@ -478,6 +638,29 @@ impl ConstMatchArm {
     }
 }
 
+impl EnumMatchArm {
+    pub fn to_tokens(&self, context: &mut Context) -> TokenStream {
+        let init = frontend_type("Init");
+        let bindings = self.bindings.iter().map(|(member, name, is_mut)| {
+            let variant = self
+                .variant
+                .as_ref()
+                .expect("Wildcard arms can't bind fields");
+            let field = variant_field_ident(variant, member);
+            let mutability = is_mut.then(|| quote![mut]);
+            quote![let #mutability #name = #init::init(_val.#field(), context);]
+        });
+        let block = self.block.to_tokens(context);
+
+        quote! {
+            {
+                #(#bindings)*
+                #block
+            }
+        }
+    }
+}
+
 impl Block {
     pub fn to_tokens(&self, context: &mut Context) -> TokenStream {
         let inner: Vec<_> = self.inner.iter().map(|it| it.to_tokens(context)).collect();
@@ -501,6 +684,25 @@ impl Block {
     }
 }
 
+/// Paths like `Enum::Variant`, where both the enum and the variant are capitalized, are assumed
+/// to be enum variants instead of structs.
+fn is_enum_variant(path: &syn::Path) -> bool {
+    let is_capitalized = |segment: &syn::PathSegment| {
+        segment
+            .ident
+            .to_string()
+            .chars()
+            .next()
+            .map(|ch| ch.is_uppercase())
+            .unwrap_or(false)
+    };
+    let mut segments = path.segments.iter().rev();
+    match (segments.next(), segments.next()) {
+        (Some(variant), Some(ty)) => is_capitalized(variant) && is_capitalized(ty),
+        _ => false,
+    }
+}
+
 fn split_generics(path: &Expression, context: &mut Context) -> (PathArguments, TokenStream) {
     let mut path = match path {
         Expression::Path { path, .. } => path.clone(),
@@ -514,6 +716,18 @@ fn split_generics(path: &Expression, context: &mut Context) -> (PathArguments, T
     (generics, quote![#path])
 }
 
+/// Typed closure parameters take the expand type of their type.
+fn closure_param(param: &Pat) -> TokenStream {
+    match param {
+        Pat::Type(pat) => {
+            let cube_type = prelude_type("CubeType");
+            let (pat, ty) = (&pat.pat, &pat.ty);
+            quote![#pat: <#ty as #cube_type>::ExpandType]
+        }
+        pat => quote![#pat],
+    }
+}
+
 fn map_args(args: &[Expression], context: &mut Context) -> (Vec<TokenStream>, Vec<TokenStream>) {
     let names: Vec<_> = (0..args.len()).map(|i| format_ident!("_arg_{i}")).collect();
     let values = names
@@ -536,6 +750,8 @@ fn map_args(args: &[Expression], context: &mut Context) -> (Vec<TokenStream>, Ve
         .map(|(name, value)| {
             if matches!(value, Expression::Closure { .. }) {
                 value.to_tokens(context)
+            } else if is_closure_var(value) {
+                quote![#name]
             } else {
                 quote![#name.into()]
             }
@@ -544,6 +760,15 @@ fn map_args(args: &[Expression], context: &mut Context) -> (Vec<TokenStream>, Ve
     (values, names)
 }
 
+/// Variables holding an `impl Fn` parameter are passed on as is.
+fn is_closure_var(value: &Expression) -> bool {
+    match value {
+        Expression::Variable(var) => matches!(var.ty, Some(Type::ImplTrait(_))),
+        Expression::Reference { inner } => is_closure_var(inner),
+        _ => false,
+    }
+}
+
 /// Since we no longer (unnecessarily) init immutable locals, we do need to init all struct fields
 /// because of interior mutability.
 fn init_fields<'a>(
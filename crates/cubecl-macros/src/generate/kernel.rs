@@ -1,11 +1,14 @@
 use darling::usage::{CollectLifetimes as _, CollectTypeParams as _, GenericsExt as _, Purpose};
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote, ToTokens};
-use syn::Ident;
+use syn::{Ident, Type};
 
 use crate::{
+    expression::{Block, Expression},
     parse::kernel::{KernelBody, KernelFn, KernelParam, KernelReturns, KernelSignature, Launch},
-    paths::{core_type, prelude_path, prelude_type},
+    paths::{core_type, frontend_type, prelude_path, prelude_type},
+    scope::Context,
+    statement::Statement,
 };
 
 impl KernelFn {
@@ -14,7 +17,10 @@ impl KernelFn {
         let vis = &self.vis;
         let sig = &self.sig;
         let body = match &self.body {
-            KernelBody::Block(block) => &block.to_tokens(&mut self.context),
+            KernelBody::Block(block) if self.context.early_returns > 0 => &restore_source_loc(
+                early_return_body(block, &self.sig.returns, &mut self.context),
+            ),
+            KernelBody::Block(block) => &restore_source_loc(block.to_tokens(&mut self.context)),
             KernelBody::Verbatim(tokens) => tokens,
         };
 
@@ -30,17 +36,26 @@ impl KernelFn {
     }
 }
 
+impl KernelReturns {
+    /// The type returned by the expand function.
+    pub fn expand_ty(&self) -> TokenStream {
+        match self {
+            KernelReturns::ExpandType(ty) => {
+                let cube_type = prelude_type("CubeType");
+                quote![<#ty as #cube_type>::ExpandType]
+            }
+            KernelReturns::Plain(ty) => quote![#ty],
+        }
+    }
+}
+
 impl ToTokens for KernelSignature {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let cube_context = prelude_type("CubeContext");
-        let cube_type = prelude_type("CubeType");
 
         let name = &self.name;
         let generics = &self.generics;
-        let return_type = match &self.returns {
-            KernelReturns::ExpandType(ty) => quote![<#ty as #cube_type>::ExpandType],
-            KernelReturns::Plain(ty) => quote![#ty],
-        };
+        let return_type = self.returns.expand_ty();
         let out = if self
             .parameters
             .first()
@@ -74,7 +89,9 @@ impl ToTokens for KernelParam {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let name = &self.name;
         let ty = &self.normalized_ty;
-        tokens.extend(quote![#name: #ty]);
+        // Closures are the only parameters that are mutated directly during expansion.
+        let mutability = (self.is_mut && matches!(ty, Type::ImplTrait(_))).then(|| quote![mut]);
+        tokens.extend(quote![#mutability #name: #ty]);
     }
 }
 
@@ -275,3 +292,59 @@ impl Launch {
         }
     }
 }
+
+/// Restore the source location of the caller once the body is expanded, so the rest of the
+/// calling statement isn't attributed to the last statement of this function.
+fn restore_source_loc(body: TokenStream) -> TokenStream {
+    quote! {
+        let __source_loc = context.source_loc();
+        let __out = #body;
+        context.set_source_loc(__source_loc);
+        __out
+    }
+}
+
+/// Expand a body with early returns in a scope that every return exits, with the tail
+/// expression of the block as the last return.
+fn early_return_body(block: &Block, returns: &KernelReturns, context: &mut Context) -> TokenStream {
+    let branch = frontend_type("branch");
+    let return_type = returns.expand_ty();
+    let nested = context.nested_returns;
+
+    let mut block = block.clone();
+    let ret = block.ret.take().map(|ret| match *ret {
+        ret @ Expression::Return { .. } => ret,
+        ret if ret.needs_terminator() => Expression::Return {
+            expr: Some(Box::new(ret)),
+            span: Span::call_site(),
+            _ty: context.return_type.clone(),
+        },
+        ret => ret,
+    });
+    let returns_last = matches!(
+        ret.as_ref().or_else(|| match block.inner.last() {
+            Some(Statement::Expression { expression, .. }) => Some(expression),
+            _ => None,
+        }),
+        Some(Expression::Return { .. })
+    );
+    block.inner.extend(ret.map(|ret| Statement::Expression {
+        expression: Box::new(ret),
+        terminated: true,
+    }));
+    if !returns_last {
+        block.inner.push(Statement::Expression {
+            expression: Box::new(Expression::Break),
+            terminated: true,
+        });
+    }
+    let block = block.to_tokens(context);
+
+    quote! {
+        #branch::return_scope_expand::<#return_type>(
+            context,
+            #nested,
+            |context, __return| #block,
+        )
+    }
+}
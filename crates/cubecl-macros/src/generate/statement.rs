@@ -2,7 +2,12 @@ use proc_macro2::{Span, TokenStream};
 use quote::{quote, quote_spanned};
 use syn::{spanned::Spanned, Token};
 
-use crate::{expression::Expression, paths::frontend_type, scope::Context, statement::Statement};
+use crate::{
+    expression::Expression,
+    paths::{frontend_path, frontend_type},
+    scope::Context,
+    statement::Statement,
+};
 
 impl Statement {
     pub fn to_tokens(&self, context: &mut Context) -> TokenStream {
@@ -63,6 +68,12 @@ impl Statement {
                     quote![#expression #terminator]
                 }
             }
+            Statement::SourceLoc(span) => {
+                let debug = frontend_path();
+                quote_spanned! {*span=>
+                    #debug::debug::source_loc_expand(context, file!(), line!(), column!());
+                }
+            }
             Statement::Skip => TokenStream::new(),
         }
     }
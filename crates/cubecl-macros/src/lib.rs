@@ -12,7 +12,7 @@ use parse::{
 };
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{visit_mut::VisitMut, Item};
+use syn::{punctuated::Punctuated, visit_mut::VisitMut, Expr, Item, Token};
 
 mod error;
 mod expression;
@@ -31,6 +31,18 @@ mod statement;
 /// * `debug` - panics after generation to print the output to console
 /// * `create_dummy_kernel` - Generates a function to create a kernel without launching it. Used for testing.
 ///
+/// # Debug symbols
+///
+/// When the `CUBECL_DEBUG_LOG` environment variable is set, the source location of each
+/// statement is recorded and emitted in the generated kernel (comments in WGSL, `#line`
+/// directives in C++ and `OpLine` in SPIR-V).
+///
+/// # Closures
+///
+/// Cube functions can take `impl Fn` parameters, which are monomorphized when the function is
+/// expanded. The closure passed at the call site is expanded in place, and can capture local
+/// variables. Launch kernels can't take closures.
+///
 /// # Example
 ///
 /// ```ignored
@@ -39,6 +51,16 @@ mod statement;
 /// fn my_addition(a: u32, b: u32) -> u32 {
 ///     a + b
 /// }
+///
+/// #[cube]
+/// fn apply<F: Float>(value: F, f: impl Fn(F) -> F) -> F {
+///     f(value)
+/// }
+///
+/// #[cube]
+/// fn scale<F: Float>(value: F, factor: F) -> F {
+///     apply::<F>(value, |x| x * factor)
+/// }
 /// ```
 #[proc_macro_attribute]
 pub fn cube(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -111,6 +133,27 @@ pub fn module_derive_cube_launch(input: TokenStream) -> TokenStream {
 }
 
 /// Derive macro to define a cube type that is not launched
+///
+/// Enums are comptime values by default. Add `#[expand(runtime)]` to make the discriminant and
+/// payload runtime values, so they can be assigned in branches and loops and matched at runtime.
+/// Payload fields must be primitives, and unit variants are created with `Enum::Variant.runtime()`.
+///
+/// Mark a single field variant of a runtime enum with `#[expand(try_output)]` to support the `?`
+/// operator in cube functions returning the enum: every other variant is returned early.
+///
+/// Structs marked with `#[expand(array)]` can be stored in a `StructArray`, with one local or
+/// shared array per field. All fields must be primitives.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(CubeType)]
+/// #[expand(runtime)]
+/// enum Search {
+///     NotFound,
+///     Found { index: u32 },
+/// }
+/// ```
 #[proc_macro_derive(CubeType, attributes(expand))]
 pub fn module_derive_cube_type(input: TokenStream) -> TokenStream {
     gen_cube_type(input, false)
@@ -151,6 +194,57 @@ pub fn comptime(input: TokenStream) -> TokenStream {
     quote![{ #tokens }].into()
 }
 
+/// Print from inside a cube function, for debugging. Only `{}` placeholders are supported, and
+/// arguments must be primitives or vectorized primitives.
+///
+/// Lowers to `printf` on CUDA and HIP, and `DebugPrintf` on SPIR-V (requires the validation layers
+/// to be enabled). On WGSL, the output is written to a buffer and logged by the server on sync.
+///
+/// # Example
+/// ```ignored
+/// #[cube]
+/// fn do_stuff(input: &Array<f32>) {
+///     debug_print!("input[{}] = {}", UNIT_POS, input[UNIT_POS]);
+/// }
+/// ```
+#[proc_macro]
+pub fn debug_print(input: TokenStream) -> TokenStream {
+    let args =
+        match syn::parse::Parser::parse(Punctuated::<Expr, Token![,]>::parse_terminated, input) {
+            Ok(args) => args,
+            Err(err) => return err.into_compile_error().into(),
+        };
+    let args = args.iter();
+    // Outside of a cube context, only make sure the arguments are used.
+    quote![{ let _ = (#(&(#args),)*); }].into()
+}
+
+/// Assert that a condition holds inside a cube function. When it doesn't, the error code, unit
+/// position and cube position are recorded in an error buffer, and the next `try_sync` or
+/// `try_read` on the client returns the `KernelError`s raised. The code must be a comptime `u32`.
+///
+/// Assertions are only compiled in checked mode, and are removed from kernels launched with
+/// `ExecutionMode::Unchecked`.
+///
+/// # Example
+/// ```ignored
+/// #[cube]
+/// fn do_stuff(input: &Array<f32>) {
+///     cube_assert!(input[UNIT_POS] >= 0.0, 1);
+/// }
+/// ```
+#[proc_macro]
+pub fn cube_assert(input: TokenStream) -> TokenStream {
+    let args =
+        match syn::parse::Parser::parse(Punctuated::<Expr, Token![,]>::parse_terminated, input) {
+            Ok(args) => args,
+            Err(err) => return err.into_compile_error().into(),
+        };
+    let args = args.iter();
+    // Outside of a cube context, only make sure the arguments are used.
+    quote![{ let _ = (#(&(#args),)*); }].into()
+}
+
 /// Implements display and initialization for autotune keys.
 ///
 /// # Helper
@@ -1,8 +1,10 @@
 use quote::quote;
-use syn::{spanned::Spanned, Expr, ExprForLoop, ExprIf, ExprLoop, ExprMatch, Ident, Lit, Pat};
+use syn::{
+    spanned::Spanned, Expr, ExprForLoop, ExprIf, ExprLoop, ExprMatch, Ident, Lit, Member, Pat,
+};
 
 use crate::{
-    expression::{Block, Expression},
+    expression::{Block, EnumMatchArm, Expression},
     scope::Context,
     statement::Statement,
 };
@@ -21,6 +23,7 @@ pub fn expand_for_loop(for_loop: ExprForLoop, context: &mut Context) -> syn::Res
         return expand_for_in_loop(var.ident, right, for_loop.body, context);
     }
 
+    let early_returns = context.early_returns;
     let (block, scope) = context.in_scope(|context| {
         context.push_variable(
             var.ident.clone(),
@@ -39,6 +42,7 @@ pub fn expand_for_loop(for_loop: ExprForLoop, context: &mut Context) -> syn::Res
         var_ty: var.ty,
         block,
         scope,
+        returns: context.nest_returns(early_returns),
     })
 }
 
@@ -67,8 +71,14 @@ fn expand_for_in_loop(
 }
 
 pub fn expand_loop(loop_expr: ExprLoop, context: &mut Context) -> syn::Result<Expression> {
+    let early_returns = context.early_returns;
     let (block, scope) = context.in_scope(|ctx| Block::from_block(loop_expr.body, ctx))?;
-    Ok(Expression::Loop { block, scope })
+    let returns = context.nest_returns(early_returns);
+    Ok(Expression::Loop {
+        block,
+        scope,
+        returns,
+    })
 }
 
 pub fn expand_if(if_expr: ExprIf, context: &mut Context) -> syn::Result<Expression> {
@@ -90,6 +100,19 @@ pub fn expand_if(if_expr: ExprIf, context: &mut Context) -> syn::Result<Expressi
     })
 }
 
+/// Whether all arms of the match only use literal patterns.
+pub fn is_numeric_match(mat: &ExprMatch) -> bool {
+    fn is_numeric(pat: &Pat) -> bool {
+        match pat {
+            Pat::Lit(_) | Pat::Wild(_) => true,
+            Pat::Or(or) => or.cases.iter().all(is_numeric),
+            _ => false,
+        }
+    }
+
+    mat.arms.iter().all(|arm| is_numeric(&arm.pat))
+}
+
 pub fn numeric_match(mat: ExprMatch, context: &mut Context) -> Option<Expression> {
     fn parse_pat(pat: Pat) -> Option<Vec<Lit>> {
         match pat {
@@ -129,6 +152,7 @@ pub fn numeric_match(mat: ExprMatch, context: &mut Context) -> Option<Expression
         .map(|arm| arm.guard.is_none().then_some(arm))
         .collect::<Option<Vec<_>>>()?;
 
+    let early_returns = context.early_returns;
     let mut switch_arms = Vec::new();
     let mut default = None;
 
@@ -152,16 +176,153 @@ pub fn numeric_match(mat: ExprMatch, context: &mut Context) -> Option<Expression
         value,
         cases,
         default,
+        returns: context.nest_returns(early_returns),
+    })
+}
+
+/// Match on a runtime enum derived with `#[expand(runtime)]`. Each variant pattern becomes a case
+/// of a switch on the discriminant. Without a wildcard arm, the last arm is used as the default.
+pub fn enum_match(mat: ExprMatch, context: &mut Context) -> syn::Result<Expression> {
+    type Bindings = Vec<(Member, Ident, bool)>;
+
+    fn parse_binding(pat: Pat) -> syn::Result<Option<(Ident, bool)>> {
+        match pat {
+            Pat::Ident(ident) if ident.subpat.is_none() && ident.by_ref.is_none() => {
+                Ok(Some((ident.ident, ident.mutability.is_some())))
+            }
+            Pat::Wild(_) => Ok(None),
+            pat => Err(syn::Error::new_spanned(
+                pat,
+                "Only identifiers and `_` are supported in runtime enum payload patterns",
+            )),
+        }
+    }
+
+    fn parse_pat(pat: Pat) -> syn::Result<Vec<(Option<Ident>, Bindings)>> {
+        let variant = |path: &syn::Path| path.segments.last().map(|it| it.ident.clone());
+
+        let res = match pat {
+            Pat::Wild(_) => vec![(None, vec![])],
+            Pat::Path(pat) => vec![(variant(&pat.path), vec![])],
+            Pat::TupleStruct(pat) => {
+                let mut bindings = Vec::new();
+                for (i, elem) in pat.elems.into_iter().enumerate() {
+                    if let Pat::Rest(_) = elem {
+                        break;
+                    }
+                    if let Some((ident, is_mut)) = parse_binding(elem)? {
+                        bindings.push((Member::Unnamed(i.into()), ident, is_mut));
+                    }
+                }
+                vec![(variant(&pat.path), bindings)]
+            }
+            Pat::Struct(pat) => {
+                let mut bindings = Vec::new();
+                for field in pat.fields {
+                    if let Some((ident, is_mut)) = parse_binding(*field.pat)? {
+                        bindings.push((field.member, ident, is_mut));
+                    }
+                }
+                vec![(variant(&pat.path), bindings)]
+            }
+            Pat::Or(or) => {
+                let mut variants = Vec::new();
+                for case in or.cases {
+                    let span = case.span();
+                    let parsed = parse_pat(case)?;
+                    if parsed.iter().any(|(_, bindings)| !bindings.is_empty()) {
+                        Err(syn::Error::new(
+                            span,
+                            "Bindings aren't supported in or patterns of runtime enums",
+                        ))?
+                    }
+                    variants.extend(parsed);
+                }
+                variants
+            }
+            pat => Err(syn::Error::new_spanned(
+                pat,
+                "Unsupported pattern in runtime enum match",
+            ))?,
+        };
+        Ok(res)
+    }
+
+    fn parse_arm(
+        variant: Option<Ident>,
+        bindings: Bindings,
+        body: Expr,
+        context: &mut Context,
+    ) -> syn::Result<EnumMatchArm> {
+        let (block, _) = context.in_scope(|context| {
+            for (_, ident, is_mut) in bindings.iter() {
+                context.push_variable(ident.clone(), None, false, false, *is_mut);
+            }
+            match body {
+                Expr::Block(block) => Block::from_block(block.block, context),
+                expr => {
+                    let expr = Expression::from_expr(expr, context)?;
+                    Ok(Block {
+                        ty: expr.ty(),
+                        ret: Some(Box::new(expr)),
+                        inner: vec![],
+                    })
+                }
+            }
+        })?;
+
+        Ok(EnumMatchArm {
+            variant,
+            bindings,
+            block,
+        })
+    }
+
+    let span = mat.span();
+    let value = Box::new(Expression::from_expr(*mat.expr, context)?);
+
+    let early_returns = context.early_returns;
+    let mut patterns = Vec::new();
+    for arm in mat.arms {
+        if let Some((_, guard)) = arm.guard {
+            Err(syn::Error::new_spanned(
+                guard,
+                "Match guards aren't supported on runtime enums",
+            ))?
+        }
+        for (variant, bindings) in parse_pat(arm.pat)? {
+            patterns.push((variant, bindings, *arm.body.clone()));
+        }
+    }
+
+    let default_pos = patterns
+        .iter()
+        .position(|(variant, ..)| variant.is_none())
+        .or_else(|| patterns.len().checked_sub(1))
+        .ok_or_else(|| syn::Error::new(span, "Empty match on runtime enum"))?;
+    let (variant, bindings, body) = patterns.remove(default_pos);
+    let default = parse_arm(variant, bindings, body, context)?;
+
+    let arms = patterns
+        .into_iter()
+        .map(|(variant, bindings, body)| parse_arm(variant, bindings, body, context))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(Expression::EnumMatch {
+        value,
+        arms,
+        default,
+        returns: context.nest_returns(early_returns),
     })
 }
 
 impl Block {
     pub fn from_block(block: syn::Block, context: &mut Context) -> syn::Result<Self> {
-        let mut statements = block
-            .stmts
-            .into_iter()
-            .map(|stmt| Statement::from_stmt(stmt, context))
-            .collect::<Result<Vec<_>, _>>()?;
+        let mut statements = Vec::with_capacity(block.stmts.len() * 2);
+        for stmt in block.stmts {
+            statements.push(Statement::SourceLoc(stmt.span()));
+            statements.push(Statement::from_stmt(stmt, context)?);
+        }
         // Pop implicit return if it exists so we can assign it as the block output
         let ret = match statements.pop() {
             Some(Statement::Expression {
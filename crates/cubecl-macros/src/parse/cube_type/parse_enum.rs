@@ -1,6 +1,7 @@
 use darling::FromDeriveInput;
 use proc_macro2::Span;
-use syn::{spanned::Spanned, Ident};
+use quote::format_ident;
+use syn::{spanned::Spanned, Ident, Member};
 
 #[derive(Debug)]
 pub struct CubeTypeEnum {
@@ -9,6 +10,8 @@ pub struct CubeTypeEnum {
     pub variants: Vec<CubeTypeVariant>,
     pub generics: syn::Generics,
     pub vis: syn::Visibility,
+    /// Whether the discriminant and payload are runtime values, set with `#[expand(runtime)]`.
+    pub runtime: bool,
 }
 
 #[derive(Debug)]
@@ -17,6 +20,8 @@ pub struct CubeTypeVariant {
     pub fields: syn::Fields,
     pub field_names: Vec<Ident>,
     pub kind: VariantKind,
+    /// Whether `?` produces the payload of this variant, set with `#[expand(try_output)]`.
+    pub try_output: bool,
 }
 
 #[derive(Debug)]
@@ -28,8 +33,11 @@ pub enum VariantKind {
 
 impl FromDeriveInput for CubeTypeEnum {
     fn from_derive_input(input: &syn::DeriveInput) -> darling::Result<Self> {
+        let runtime = parse_runtime_flag(&input.attrs)?;
+
         match &input.data {
             syn::Data::Enum(data) => Ok(Self {
+                runtime,
                 ident: input.ident.clone(),
                 generics: input.generics.clone(),
                 vis: input.vis.clone(),
@@ -37,7 +45,7 @@ impl FromDeriveInput for CubeTypeEnum {
                 variants: data
                     .variants
                     .iter()
-                    .map(|a| {
+                    .map(|a| -> darling::Result<_> {
                         let mut kind = if a.fields.is_empty() {
                             VariantKind::Empty
                         } else {
@@ -50,8 +58,17 @@ impl FromDeriveInput for CubeTypeEnum {
                             }
                         }
 
-                        CubeTypeVariant {
+                        let try_output = parse_try_output_flag(&a.attrs)?;
+                        if try_output && (!runtime || a.fields.len() != 1) {
+                            return Err(darling::Error::custom(
+                                "`#[expand(try_output)]` requires a runtime enum and a single field",
+                            )
+                            .with_span(&a.ident));
+                        }
+
+                        Ok(CubeTypeVariant {
                             kind,
+                            try_output,
                             ident: a.ident.clone(),
                             field_names: a
                                 .fields
@@ -65,11 +82,58 @@ impl FromDeriveInput for CubeTypeEnum {
                                 })
                                 .collect(),
                             fields: a.fields.clone(),
-                        }
+                        })
                     })
-                    .collect(),
+                    .collect::<darling::Result<_>>()?,
             }),
             _ => Err(darling::Error::custom("Only enum are supported.")),
         }
     }
 }
+
+fn parse_runtime_flag(attrs: &[syn::Attribute]) -> darling::Result<bool> {
+    let mut runtime = false;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("expand")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("runtime") {
+                runtime = true;
+                Ok(())
+            } else {
+                Err(meta.error("Unsupported enum attribute, expected `runtime`"))
+            }
+        })?;
+    }
+
+    Ok(runtime)
+}
+
+fn parse_try_output_flag(attrs: &[syn::Attribute]) -> darling::Result<bool> {
+    let mut try_output = false;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("expand")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("try_output") {
+                try_output = true;
+                Ok(())
+            } else {
+                Err(meta.error("Unsupported variant attribute, expected `try_output`"))
+            }
+        })?;
+    }
+
+    Ok(try_output)
+}
+
+/// Name of the method returning the discriminant of a variant on a runtime enum expand type.
+pub fn variant_discriminant_ident(variant: &Ident) -> Ident {
+    format_ident!("__variant_{variant}")
+}
+
+/// Name of the method returning a payload field of a variant on a runtime enum expand type.
+pub fn variant_field_ident(variant: &Ident, field: &Member) -> Ident {
+    match field {
+        Member::Named(name) => format_ident!("__variant_{variant}_{name}"),
+        Member::Unnamed(index) => format_ident!("__variant_{variant}_{}", index.index),
+    }
+}
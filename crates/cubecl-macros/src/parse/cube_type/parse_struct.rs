@@ -17,6 +17,8 @@ pub struct CubeTypeStruct {
     pub fields: Vec<TypeField>,
     pub generics: Generics,
     pub vis: Visibility,
+    /// Whether the struct can be stored in a `StructArray`, set with `#[expand(array)]`.
+    pub array: Flag,
 }
 
 #[derive(FromField, Clone, Debug)]
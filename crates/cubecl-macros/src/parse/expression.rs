@@ -1,19 +1,24 @@
+use cubecl_common::format::{parse_format_string, FormatSegment};
 use proc_macro2::Span;
 use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::{
-    parse_quote, spanned::Spanned, Expr, ExprUnary, Lit, LitInt, Path, PathSegment, RangeLimits,
-    Type, UnOp,
+    parse_quote, punctuated::Punctuated, spanned::Spanned, Expr, ExprLit, ExprUnary, Lit, LitInt,
+    LitStr, Path, PathSegment, RangeLimits, Token, Type, UnOp,
 };
 
 use crate::{
     expression::{is_intrinsic, Block, ConstMatchArm, Expression},
     operator::Operator,
     scope::Context,
+    statement::Pattern,
 };
 
 use super::{
-    branch::{expand_for_loop, expand_if, expand_loop, numeric_match},
+    branch::{
+        enum_match, expand_for_loop, expand_if, expand_loop, is_numeric_match, numeric_match,
+    },
     operator::{parse_binop, parse_unop},
+    statement::parse_pat,
 };
 
 impl Expression {
@@ -192,6 +197,9 @@ impl Expression {
             Expr::Paren(paren) => Expression::from_expr(*paren.expr, context)?,
             Expr::Return(ret) => {
                 let span = ret.expr.span();
+                if ret.expr.is_some() {
+                    context.early_returns += 1;
+                }
                 Expression::Return {
                     expr: ret
                         .expr
@@ -293,13 +301,12 @@ impl Expression {
                         const_expr: mat.expr.as_ref().clone(),
                         arms,
                     }
-                } else if let Some(switch) = numeric_match(mat, context) {
-                    switch
+                } else if is_numeric_match(&mat) {
+                    numeric_match(mat, context).ok_or_else(|| {
+                        syn::Error::new(span, "Unsupported numeric match expression at runtime")
+                    })?
                 } else {
-                    Err(syn::Error::new(
-                        span,
-                        "Only numeric match expression is supported at runtime",
-                    ))?
+                    enum_match(mat, context)?
                 }
             }
             Expr::Macro(mac) if is_comptime_macro(&mac.mac.path) => {
@@ -308,6 +315,47 @@ impl Expression {
                     tokens: quote![{ #tokens }],
                 }
             }
+            Expr::Macro(mac) if is_debug_print_macro(&mac.mac.path) => {
+                let args = mac
+                    .mac
+                    .parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+                let mut args = args.into_iter();
+                let format_string = match args.next() {
+                    Some(Expr::Lit(ExprLit {
+                        lit: Lit::Str(format_string),
+                        ..
+                    })) => format_string,
+                    _ => Err(syn::Error::new_spanned(
+                        &mac,
+                        "`debug_print!` requires a format string literal",
+                    ))?,
+                };
+                let args = args
+                    .map(|arg| Expression::from_expr(arg, context))
+                    .collect::<Result<Vec<_>, _>>()?;
+                check_format_string(&format_string, args.len())?;
+                Expression::Print {
+                    format_string,
+                    args,
+                }
+            }
+            Expr::Macro(mac) if is_cube_assert_macro(&mac.mac.path) => {
+                let args = mac
+                    .mac
+                    .parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+                let mut args = args.into_iter();
+                let (Some(condition), Some(code), None) = (args.next(), args.next(), args.next())
+                else {
+                    Err(syn::Error::new_spanned(
+                        &mac,
+                        "`cube_assert!` takes a condition and an error code",
+                    ))?
+                };
+                Expression::Assert {
+                    condition: Box::new(Expression::from_expr(condition, context)?),
+                    code,
+                }
+            }
             Expr::Macro(mac) => Expression::Verbatim {
                 tokens: quote![#mac],
             },
@@ -338,10 +386,28 @@ impl Expression {
                 inner: Box::new(Expression::from_expr(*reference.expr, context)?),
             },
             Expr::Closure(expr) => {
-                let (body, scope) =
-                    context.in_scope(|ctx| Expression::from_expr(*expr.body, ctx))?;
+                let span = expr.span();
+                let early_returns = context.early_returns;
+                let params: Vec<_> = expr.inputs.into_iter().collect();
+                let (body, scope) = context.in_scope(|ctx| {
+                    for param in params.iter() {
+                        let Pattern {
+                            ident,
+                            ty,
+                            is_ref,
+                            is_mut,
+                        } = parse_pat(param.clone())?;
+                        ctx.push_variable(ident, ty, false, is_ref, is_mut);
+                    }
+                    Expression::from_expr(*expr.body, ctx)
+                })?;
+                if context.early_returns > early_returns {
+                    Err(syn::Error::new(
+                        span,
+                        "Early returns aren't supported in closures",
+                    ))?;
+                }
                 let body = Box::new(body);
-                let params = expr.inputs.into_iter().collect();
                 Expression::Closure {
                     params,
                     body,
@@ -351,11 +417,17 @@ impl Expression {
 
             Expr::Try(expr) => {
                 let span = expr.span();
-                let expr = Expression::from_expr(*expr.expr, context)?
-                    .as_const(context)
-                    .ok_or_else(|| syn::Error::new(span, "? Operator not supported at runtime"))?;
-                Expression::Verbatim {
-                    tokens: quote_spanned![span=> #expr?],
+                let expr = Expression::from_expr(*expr.expr, context)?;
+                match expr.as_const(context) {
+                    Some(expr) => Expression::Verbatim {
+                        tokens: quote_spanned![span=> #expr?],
+                    },
+                    None => {
+                        context.early_returns += 1;
+                        Expression::Try {
+                            expr: Box::new(expr),
+                        }
+                    }
                 }
             }
             Expr::TryBlock(_) => Err(syn::Error::new_spanned(
@@ -443,9 +515,6 @@ fn is_slice(index: &Expression) -> bool {
 fn fn_associated_type(path: &Expression) -> Option<(Path, PathSegment)> {
     // All supported primitives. Primitives don't start with an uppercase letter
     const PRIMITIVES: &[&str] = &["bool", "i32", "i64", "u32", "f16", "bf16", "f32", "f64"];
-    if !matches!(path, Expression::Path { .. }) {
-        panic!("path: {path:?}");
-    }
     match path {
         Expression::Path { path, .. } => {
             let second_last = path.segments.iter().nth_back(1)?;
@@ -470,3 +539,33 @@ fn is_comptime_macro(path: &Path) -> bool {
     let path = path.to_token_stream().to_string();
     "::cubecl::comptime".ends_with(&path)
 }
+
+pub fn is_debug_print_macro(path: &Path) -> bool {
+    let path = path.to_token_stream().to_string();
+    "::cubecl::debug_print".ends_with(&path)
+}
+
+pub fn is_cube_assert_macro(path: &Path) -> bool {
+    let path = path.to_token_stream().to_string();
+    "::cubecl::cube_assert".ends_with(&path)
+}
+
+/// Check that the format string only has `{}` placeholders, one for each argument.
+fn check_format_string(format_string: &LitStr, num_args: usize) -> syn::Result<()> {
+    let value = format_string.value();
+    let segments = parse_format_string(&value).map_err(|err| {
+        syn::Error::new_spanned(format_string, format!("{err} in `debug_print!`"))
+    })?;
+    let placeholders = segments
+        .iter()
+        .filter(|segment| matches!(segment, FormatSegment::Arg))
+        .count();
+
+    if placeholders != num_args {
+        Err(syn::Error::new_spanned(
+            format_string,
+            format!("Expected {placeholders} arguments for the format string, found {num_args}"),
+        ))?;
+    }
+    Ok(())
+}
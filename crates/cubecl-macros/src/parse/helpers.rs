@@ -5,7 +5,11 @@ use syn::{
     Attribute, Expr, ExprReference,
 };
 
-use crate::{expression::Expression, paths::prelude_path, scope::Context};
+use crate::{
+    expression::Expression,
+    paths::{frontend_type, prelude_path},
+    scope::Context,
+};
 
 pub struct Unroll {
     pub value: Expression,
@@ -86,6 +90,15 @@ impl VisitMut for RemoveHelpers {
         }
         visit_mut::visit_expr_for_loop_mut(self, i);
     }
+
+    fn visit_expr_mut(&mut self, i: &mut Expr) {
+        if let Expr::Try(try_expr) = i {
+            let branch = frontend_type("branch");
+            let expr = &try_expr.expr;
+            *i = parse_quote![#branch::try_unexpanded(#expr)];
+        }
+        visit_mut::visit_expr_mut(self, i);
+    }
 }
 
 pub struct ReplaceIndices;
@@ -5,7 +5,8 @@ use quote::ToTokens;
 use std::iter;
 use syn::{
     parse_quote, punctuated::Punctuated, spanned::Spanned, visit_mut::VisitMut, Expr, FnArg,
-    Generics, Ident, ItemFn, ReturnType, Signature, TraitItemFn, Type, Visibility,
+    Generics, Ident, ItemFn, PathArguments, ReturnType, Signature, TraitItemFn, Type,
+    TypeParamBound, Visibility,
 };
 
 use super::{desugar::Desugar, helpers::is_comptime_attr, statement::parse_pat};
@@ -263,6 +264,20 @@ impl Launch {
             }
         }
 
+        if args.is_launch() {
+            if let Some(param) = func
+                .sig
+                .parameters
+                .iter()
+                .find(|param| matches!(param.ty, Type::ImplTrait(_)))
+            {
+                return Err(syn::Error::new_spanned(
+                    &param.ty,
+                    "This is a launch kernel and cannot take closures. Call a `#[cube]` function taking the closure from the kernel instead.",
+                ));
+            }
+        }
+
         let mut kernel_generics = func.sig.generics.clone();
         kernel_generics.params.push(parse_quote![__R: #runtime]);
         let mut expand_generics = kernel_generics.clone();
@@ -284,11 +299,52 @@ fn normalize_kernel_ty(ty: Type, is_const: bool, is_ref: &mut bool, is_mut: &mut
     let cube_type = prelude_type("CubeType");
     if is_const {
         ty
+    } else if let Some(ty) = normalize_closure_ty(&ty) {
+        ty
     } else {
         parse_quote![<#ty as #cube_type>::ExpandType]
     }
 }
 
+/// Map `impl Fn(A) -> B` parameters to closures over the expand types, which also take the
+/// context. The closure is monomorphized when the function is expanded.
+fn normalize_closure_ty(ty: &Type) -> Option<Type> {
+    let Type::ImplTrait(impl_trait) = ty else {
+        return None;
+    };
+    let cube_type = prelude_type("CubeType");
+    let cube_context = prelude_type("CubeContext");
+    let mut impl_trait = impl_trait.clone();
+    let mut is_closure = false;
+
+    for bound in impl_trait.bounds.iter_mut() {
+        let TypeParamBound::Trait(bound) = bound else {
+            continue;
+        };
+        let Some(segment) = bound.path.segments.last_mut() else {
+            continue;
+        };
+        if !["Fn", "FnMut", "FnOnce"].contains(&segment.ident.to_string().as_str()) {
+            continue;
+        }
+        if let PathArguments::Parenthesized(args) = &mut segment.arguments {
+            let inputs = args.inputs.iter();
+            args.inputs =
+                parse_quote![&mut #cube_context, #(<#inputs as #cube_type>::ExpandType),*];
+            if let ReturnType::Type(_, ty) = &mut args.output {
+                **ty = parse_quote![<#ty as #cube_type>::ExpandType];
+            }
+            is_closure = true;
+        }
+    }
+
+    // Expanded closures only capture references, so they can be passed on to other functions.
+    is_closure.then(|| {
+        impl_trait.bounds.push(parse_quote![Clone]);
+        Type::ImplTrait(impl_trait)
+    })
+}
+
 fn strip_ref(ty: Type, is_ref: &mut bool, is_mut: &mut bool) -> Type {
     match ty {
         Type::Reference(reference) => {
@@ -1,8 +1,9 @@
 use quote::format_ident;
-use syn::{Pat, Stmt, Type, TypeReference};
+use syn::{Expr, ExprMacro, Pat, Stmt, Type, TypeReference};
 
 use crate::{
     expression::Expression,
+    parse::expression::{is_cube_assert_macro, is_debug_print_macro},
     scope::Context,
     statement::{Pattern, Statement},
 };
@@ -35,6 +36,19 @@ impl Statement {
                     expression,
                 }
             }
+            Stmt::Macro(mac)
+                if is_debug_print_macro(&mac.mac.path) || is_cube_assert_macro(&mac.mac.path) =>
+            {
+                let expr = Expr::Macro(ExprMacro {
+                    attrs: mac.attrs,
+                    mac: mac.mac,
+                });
+                let expression = Box::new(Expression::from_expr(expr, context)?);
+                Statement::Expression {
+                    terminated: mac.semi_token.is_some() || !expression.needs_terminator(),
+                    expression,
+                }
+            }
             Stmt::Item(_) => Statement::Skip,
             stmt => Err(syn::Error::new_spanned(stmt, "Unsupported statement"))?,
         };
@@ -39,6 +39,10 @@ type ManagedScope = Vec<ManagedVar>;
 #[derive(Clone)]
 pub struct Context {
     pub return_type: Type,
+    /// Number of returns with a value and runtime `?` operators parsed so far.
+    pub early_returns: usize,
+    /// Whether some of the early returns are nested in loops.
+    pub nested_returns: bool,
     scopes: Vec<ManagedScope>,
     level: usize,
     mut_scope_idx: usize,
@@ -63,6 +67,8 @@ impl Context {
         }));
         Self {
             return_type,
+            early_returns: 0,
+            nested_returns: false,
             scopes: vec![root_scope],
             level: 0,
             mut_scope_idx: 0,
@@ -124,6 +130,14 @@ impl Context {
         res
     }
 
+    /// Whether early returns were parsed since `early_returns` was recorded, in which case they
+    /// are nested in a loop or a runtime match and must be propagated out of it.
+    pub fn nest_returns(&mut self, early_returns: usize) -> bool {
+        let nested = self.early_returns > early_returns;
+        self.nested_returns |= nested;
+        nested
+    }
+
     pub fn variable(&self, name: &Ident) -> Option<ManagedVar> {
         // Walk through each scope backwards until we find the variable.
         let scopes = self.scopes.iter().rev();
@@ -1,4 +1,5 @@
 use crate::{expression::Expression, scope::ManagedVar};
+use proc_macro2::Span;
 use syn::{Ident, Type};
 
 #[derive(Clone, Debug)]
@@ -11,6 +12,8 @@ pub enum Statement {
         expression: Box<Expression>,
         terminated: bool,
     },
+    /// Sets the source location of the following statement.
+    SourceLoc(Span),
     Skip,
 }
 
@@ -117,9 +117,10 @@ impl ValueTable {
             Operation::Operator(operator) => self.create_expr_op(operator, inst.out()),
             Operation::Metadata(metadata) => self.create_expr_meta(metadata, inst.out()),
             Operation::Plane(_) | Operation::Atomic(_) => Err(value_of_var(&inst.out())),
-            Operation::Branch(_) | Operation::Synchronization(_) | Operation::CoopMma(_) => {
-                Err(None)
-            }
+            Operation::Branch(_)
+            | Operation::Synchronization(_)
+            | Operation::CoopMma(_)
+            | Operation::NonSemantic(_) => Err(None),
         }
     }
 
@@ -1,6 +1,6 @@
 use cubecl_core::ir::{
-    AtomicOp, BinaryOperator, CoopMma, Instruction, Metadata, Operation, Operator, Plane,
-    UnaryOperator, Variable,
+    AtomicOp, BinaryOperator, CoopMma, Instruction, Metadata, NonSemantic, Operation, Operator,
+    Plane, UnaryOperator, Variable,
 };
 
 use super::Optimizer;
@@ -44,6 +44,9 @@ impl Optimizer {
             Operation::Synchronization(_) => {}
             Operation::Plane(plane) => self.visit_plane(plane, visit_read),
             Operation::CoopMma(coop_mma) => self.visit_cmma(coop_mma, visit_read),
+            Operation::NonSemantic(non_semantic) => {
+                self.visit_non_semantic(non_semantic, visit_read)
+            }
             Operation::Branch(_) => unreachable!(),
         }
     }
@@ -238,6 +241,21 @@ impl Optimizer {
         }
     }
 
+    fn visit_non_semantic(
+        &mut self,
+        non_semantic: &mut NonSemantic,
+        mut visit_read: impl FnMut(&mut Self, &mut Variable),
+    ) {
+        match non_semantic {
+            NonSemantic::Print { args, .. } => {
+                for arg in args {
+                    visit_read(self, arg);
+                }
+            }
+            NonSemantic::Assert { condition, .. } => visit_read(self, condition),
+        }
+    }
+
     fn visit_unop(
         &mut self,
         unop: &mut UnaryOperator,
@@ -288,7 +288,7 @@ impl Optimizer {
             Box::new(FindConstSliceLen),
             Box::new(InBoundsToUnchecked),
         ];
-        if matches!(self.mode, ExecutionMode::Checked) {
+        if matches!(self.mode, ExecutionMode::Checked | ExecutionMode::Strict) {
             passes.extend(checked_passes);
         }
 
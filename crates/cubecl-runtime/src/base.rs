@@ -1,6 +1,7 @@
 use crate::{channel::ComputeChannel, client::ComputeClient, server::ComputeServer};
 use core::ops::DerefMut;
 use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
 
 /// The compute type has the responsibility to retrieve the correct compute client based on the
 /// given device.
@@ -9,13 +10,16 @@ pub struct ComputeRuntime<Device, Server: ComputeServer, Channel> {
 }
 
 /// The kind of execution to be performed.
-#[derive(Default, Hash, PartialEq, Eq, Clone, Debug, Copy)]
+#[derive(Default, Hash, PartialEq, Eq, Clone, Debug, Copy, Serialize, Deserialize)]
 pub enum ExecutionMode {
     /// Checked kernels are safe.
     #[default]
     Checked,
     /// Unchecked kernels are unsafe.
     Unchecked,
+    /// Strict kernels are checked, and report their out-of-bound reads and writes as
+    /// [kernel errors](crate::server::KernelError) instead of silently masking them.
+    Strict,
 }
 
 pub use cubecl_common::benchmark::{TimestampsError, TimestampsResult};
@@ -2,7 +2,8 @@ use core::future::Future;
 use cubecl_common::benchmark::TimestampsResult;
 
 use crate::{
-    server::{Binding, ComputeServer, CubeCount, Handle},
+    profile::ProfileResult,
+    server::{Binding, ComputeServer, CubeCount, EventId, GraphId, Handle, KernelError, StreamId},
     storage::BindingResource,
     ExecutionMode,
 };
@@ -23,19 +24,51 @@ pub trait ComputeChannel<Server: ComputeServer>: Clone + core::fmt::Debug + Send
     /// Reserves `size` bytes in the storage, and returns a handle over them
     fn empty(&self, size: usize) -> Handle;
 
-    /// Executes the `kernel` over the given `bindings`.
+    /// Copies the bytes of the `src` binding to the start of the `dst` binding, on the stream
+    fn copy(&self, stream: StreamId, src: Binding, dst: Binding);
+
+    /// Fills the binding with the `pattern` of bytes repeated, on the stream
+    fn fill(&self, stream: StreamId, binding: Binding, pattern: &[u8]);
+
+    /// Writes `data` to the binding starting `offset` bytes in, on the stream
+    fn write(&self, stream: StreamId, binding: Binding, offset: u64, data: &[u8]);
+
+    /// Executes the `kernel` over the given `bindings` on the stream.
     ///
     /// # Safety
     ///
     /// When executing with mode [ExecutionMode::Unchecked], out-of-bound reads and writes can happen.
     unsafe fn execute(
         &self,
+        stream: StreamId,
         kernel: Server::Kernel,
         count: CubeCount,
         bindings: Vec<Binding>,
         mode: ExecutionMode,
     );
 
+    /// Create a stream, on which tasks can execute concurrently with the other streams.
+    fn create_stream(&self) -> StreamId;
+
+    /// Record an event on the stream, reached once the tasks already submitted to it complete.
+    fn record_event(&self, stream: StreamId) -> EventId;
+
+    /// Make the tasks submitted to the stream after this call wait until the event is reached.
+    fn wait_event(&self, stream: StreamId, event: EventId);
+
+    /// Start capturing the kernels executed by the server into a graph.
+    fn start_capture(&self);
+
+    /// Stop capturing, returning the graph of the kernels executed since the capture started, if
+    /// the server captures graphs.
+    fn end_capture(&self) -> Option<GraphId>;
+
+    /// Execute the kernels of the graph again.
+    fn replay_graph(&self, graph: GraphId);
+
+    /// Free the graph.
+    fn free_graph(&self, graph: GraphId);
+
     /// Flush outstanding work of the server.
     fn flush(&self);
 
@@ -55,4 +88,14 @@ pub trait ComputeChannel<Server: ComputeServer>: Clone + core::fmt::Debug + Send
 
     /// Disable collecting timestamps.
     fn disable_timestamps(&self);
+
+    /// Start timing each kernel executed on the device.
+    fn start_profile(&self);
+
+    /// Wait for the completion of every task in the server, and return the kernels executed
+    /// since the profile started.
+    fn end_profile(&self) -> impl Future<Output = ProfileResult> + Send;
+
+    /// Take the errors raised by kernels that completed before the last sync or read.
+    fn errors(&self) -> Vec<KernelError>;
 }
@@ -1,5 +1,8 @@
 use super::ComputeChannel;
-use crate::server::{Binding, ComputeServer, CubeCount, Handle};
+use crate::profile::ProfileResult;
+use crate::server::{
+    Binding, ComputeServer, CubeCount, EventId, GraphId, Handle, KernelError, StreamId,
+};
 use crate::storage::BindingResource;
 use crate::ExecutionMode;
 use alloc::sync::Arc;
@@ -64,8 +67,23 @@ where
         self.server.borrow_mut().empty(size)
     }
 
+    fn copy(&self, stream: StreamId, src: Binding, dst: Binding) {
+        self.server.borrow_mut().copy_on(stream, src, dst)
+    }
+
+    fn fill(&self, stream: StreamId, binding: Binding, pattern: &[u8]) {
+        self.server.borrow_mut().fill_on(stream, binding, pattern)
+    }
+
+    fn write(&self, stream: StreamId, binding: Binding, offset: u64, data: &[u8]) {
+        self.server
+            .borrow_mut()
+            .write_on(stream, binding, offset, data)
+    }
+
     unsafe fn execute(
         &self,
+        stream: StreamId,
         kernel_description: Server::Kernel,
         count: CubeCount,
         bindings: Vec<Binding>,
@@ -73,7 +91,35 @@ where
     ) {
         self.server
             .borrow_mut()
-            .execute(kernel_description, count, bindings, kind)
+            .execute_on(stream, kernel_description, count, bindings, kind)
+    }
+
+    fn create_stream(&self) -> StreamId {
+        self.server.borrow_mut().create_stream()
+    }
+
+    fn record_event(&self, stream: StreamId) -> EventId {
+        self.server.borrow_mut().record_event(stream)
+    }
+
+    fn wait_event(&self, stream: StreamId, event: EventId) {
+        self.server.borrow_mut().wait_event(stream, event)
+    }
+
+    fn start_capture(&self) {
+        self.server.borrow_mut().start_capture()
+    }
+
+    fn end_capture(&self) -> Option<GraphId> {
+        self.server.borrow_mut().end_capture()
+    }
+
+    fn replay_graph(&self, graph: GraphId) {
+        self.server.borrow_mut().replay_graph(graph)
+    }
+
+    fn free_graph(&self, graph: GraphId) {
+        self.server.borrow_mut().free_graph(graph)
     }
 
     fn flush(&self) {
@@ -107,6 +153,22 @@ where
     fn disable_timestamps(&self) {
         self.server.borrow_mut().disable_timestamps();
     }
+
+    fn start_profile(&self) {
+        self.server.borrow_mut().start_profile();
+    }
+
+    async fn end_profile(&self) -> ProfileResult {
+        let future = {
+            let mut server = self.server.borrow_mut();
+            server.end_profile()
+        };
+        future.await
+    }
+
+    fn errors(&self) -> Vec<KernelError> {
+        self.server.borrow_mut().errors()
+    }
 }
 
 /// This is unsafe, since no concurrency is supported by the `RefCell` channel.
@@ -0,0 +1,724 @@
+use super::{ComputeChannel, TraceBinding, TraceCubeCount, TraceData};
+use crate::memory_management::{memory_pool::SliceHandle, MemoryUsage};
+use crate::profile::{KernelProfile, ProfileResult};
+use crate::server::{
+    Binding, ComputeServer, CubeCount, EventId, GraphId, Handle, KernelError, KernelErrorKind,
+    RecordableServer, StreamId,
+};
+use crate::storage::BindingResource;
+use crate::ExecutionMode;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::time::Duration;
+use cubecl_common::benchmark::{TimestampsError, TimestampsResult};
+use cubecl_common::reader::read_sync;
+use hashbrown::HashMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// The IpcComputeChannel sends every operation to a compute server living in another process,
+/// through a Unix socket.
+///
+/// Kernels and buffers are serialized, so a crashing driver or a long compilation can't take
+/// down the current process, and several processes can share the same device. The server side
+/// is started with [serve](IpcComputeChannel::serve).
+///
+/// Buffers and graphs are owned by the connection: they are freed on the server once their
+/// handles are dropped, or when the connection is closed. Likewise, the
+/// [errors](ComputeChannel::errors) raised by kernels are only reported to the connection that
+/// launched them.
+///
+/// If the server crashes or the connection breaks, the channel doesn't panic. The device is
+/// considered lost: the next operations are dropped, reads return empty buffers, and
+/// [errors](ComputeChannel::errors) reports a [lost device](KernelErrorKind::DeviceLost), so
+/// `try_read` and `try_sync` on the client return an error.
+pub struct IpcComputeChannel<Server> {
+    connection: Arc<Mutex<Connection>>,
+    _server: PhantomData<fn() -> Server>,
+}
+
+struct Connection {
+    reader: BufReader<UnixStream>,
+    writer: BufWriter<UnixStream>,
+    handles: Vec<SliceHandle>,
+    /// Why the connection was lost, if it was.
+    lost: Option<String>,
+}
+
+/// A message sent by the channel, along with the buffers that were freed since the last one.
+#[derive(Serialize, Deserialize)]
+struct IpcMessage<K> {
+    free: Vec<u64>,
+    request: IpcRequest<K>,
+}
+
+#[derive(Serialize, Deserialize)]
+enum IpcRequest<K> {
+    Create {
+        handle: u64,
+        data: TraceData,
+    },
+    Empty {
+        handle: u64,
+        size: usize,
+    },
+    Copy {
+        stream: StreamId,
+        src: TraceBinding,
+        dst: TraceBinding,
+    },
+    Fill {
+        stream: StreamId,
+        binding: TraceBinding,
+        pattern: TraceData,
+    },
+    Write {
+        stream: StreamId,
+        binding: TraceBinding,
+        offset: u64,
+        data: TraceData,
+    },
+    Execute {
+        stream: StreamId,
+        kernel: K,
+        count: TraceCubeCount,
+        bindings: Vec<TraceBinding>,
+        mode: ExecutionMode,
+    },
+    Read {
+        bindings: Vec<TraceBinding>,
+    },
+    CreateStream,
+    RecordEvent {
+        stream: StreamId,
+    },
+    WaitEvent {
+        stream: StreamId,
+        event: EventId,
+    },
+    StartCapture,
+    EndCapture,
+    ReplayGraph {
+        graph: GraphId,
+    },
+    FreeGraph {
+        graph: GraphId,
+    },
+    Flush,
+    Sync,
+    SyncElapsed,
+    MemoryUsage,
+    EnableTimestamps,
+    DisableTimestamps,
+    StartProfile,
+    EndProfile,
+    Errors,
+}
+
+#[derive(Serialize, Deserialize)]
+enum IpcResponse {
+    Read(Vec<TraceData>),
+    Stream(StreamId),
+    Event(EventId),
+    Graph(Option<GraphId>),
+    Sync,
+    SyncElapsed(Result<Duration, IpcTimestampsError>),
+    MemoryUsage(MemoryUsage),
+    Profile(Result<KernelProfile, IpcTimestampsError>),
+    Errors(Vec<KernelError>),
+}
+
+#[derive(Serialize, Deserialize)]
+enum IpcTimestampsError {
+    Disabled,
+    Unavailable,
+    Unknown(String),
+}
+
+impl From<TimestampsError> for IpcTimestampsError {
+    fn from(err: TimestampsError) -> Self {
+        match err {
+            TimestampsError::Disabled => Self::Disabled,
+            TimestampsError::Unavailable => Self::Unavailable,
+            TimestampsError::Unknown(err) => Self::Unknown(err),
+        }
+    }
+}
+
+impl From<IpcTimestampsError> for TimestampsError {
+    fn from(err: IpcTimestampsError) -> Self {
+        match err {
+            IpcTimestampsError::Disabled => Self::Disabled,
+            IpcTimestampsError::Unavailable => Self::Unavailable,
+            IpcTimestampsError::Unknown(err) => Self::Unknown(err),
+        }
+    }
+}
+
+impl<S> Clone for IpcComputeChannel<S> {
+    fn clone(&self) -> Self {
+        Self {
+            connection: self.connection.clone(),
+            _server: PhantomData,
+        }
+    }
+}
+
+impl<S> core::fmt::Debug for IpcComputeChannel<S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("IpcComputeChannel").finish_non_exhaustive()
+    }
+}
+
+impl<Server> IpcComputeChannel<Server>
+where
+    Server: RecordableServer,
+{
+    /// Connect to the compute server listening on the socket at `path`.
+    pub fn connect(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let stream = UnixStream::connect(path)?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(Connection {
+                reader: BufReader::new(stream.try_clone()?),
+                writer: BufWriter::new(stream),
+                handles: Vec::new(),
+                lost: None,
+            })),
+            _server: PhantomData,
+        })
+    }
+
+    /// Serve the operations of the channels connecting to `listener` with `server`, until the
+    /// listener fails.
+    ///
+    /// Each connection is handled on its own thread, and the server is shared between them.
+    pub fn serve(server: Server, listener: UnixListener) -> std::io::Result<()>
+    where
+        Server: 'static,
+    {
+        let server = Arc::new(Mutex::new(SharedServer::new(server)));
+
+        for (connection, stream) in listener.incoming().enumerate() {
+            let stream = stream?;
+            let server = server.clone();
+
+            std::thread::spawn(move || {
+                if let Err(err) = serve_connection(&server, connection, stream) {
+                    log::warn!("IPC connection closed with an error: {err}");
+                }
+                lock(&server).close(connection);
+            });
+        }
+
+        Ok(())
+    }
+
+    fn send(&self, request: IpcRequest<Server::KernelRecord>) {
+        self.connection.lock().unwrap().send(request);
+    }
+
+    /// Send a request and wait for its response, or `None` if the connection was lost.
+    fn request(&self, request: IpcRequest<Server::KernelRecord>) -> Option<IpcResponse> {
+        let mut connection = self.connection.lock().unwrap();
+        connection.send(request);
+        connection.receive()
+    }
+}
+
+impl Connection {
+    fn register(&mut self) -> SliceHandle {
+        let memory = SliceHandle::new();
+        self.handles.push(memory.clone());
+        memory
+    }
+
+    fn send<K: Serialize>(&mut self, request: IpcRequest<K>) {
+        let mut free = Vec::new();
+        self.handles.retain(|memory| {
+            let is_free = memory.is_free();
+            if is_free {
+                free.push(memory.id().value as u64);
+            }
+            !is_free
+        });
+
+        if self.lost.is_some() {
+            return;
+        }
+        if let Err(err) = write_message(&mut self.writer, &IpcMessage { free, request }) {
+            self.lose(format!("Couldn't send to the IPC compute server: {err}"));
+        }
+    }
+
+    fn receive(&mut self) -> Option<IpcResponse> {
+        if self.lost.is_some() {
+            return None;
+        }
+        match read_message(&mut self.reader) {
+            Ok(Some(response)) => Some(response),
+            Ok(None) => {
+                self.lose("The IPC compute server disconnected".into());
+                None
+            }
+            Err(err) => {
+                self.lose(format!(
+                    "Couldn't receive from the IPC compute server: {err}"
+                ));
+                None
+            }
+        }
+    }
+
+    fn lose(&mut self, reason: String) {
+        log::error!("{reason}");
+        self.lost = Some(reason);
+    }
+}
+
+impl<Server> ComputeChannel<Server> for IpcComputeChannel<Server>
+where
+    Server: RecordableServer,
+{
+    async fn read(&self, bindings: Vec<Binding>) -> Vec<Vec<u8>> {
+        let num_bindings = bindings.len();
+        let bindings = bindings.iter().map(TraceBinding::new).collect();
+        match self.request(IpcRequest::Read { bindings }) {
+            Some(IpcResponse::Read(data)) => data.into_iter().map(|data| data.0).collect(),
+            Some(_) => unexpected_response(),
+            None => alloc::vec![Vec::new(); num_bindings],
+        }
+    }
+
+    fn get_resource(&self, _binding: Binding) -> BindingResource<Server> {
+        panic!("The resources of an IPC compute server live in another process")
+    }
+
+    fn create(&self, data: &[u8]) -> Handle {
+        let mut connection = self.connection.lock().unwrap();
+        let memory = connection.register();
+        connection.send(IpcRequest::<Server::KernelRecord>::Create {
+            handle: memory.id().value as u64,
+            data: TraceData(data.to_vec()),
+        });
+
+        Handle::new(memory, None, None, data.len() as u64)
+    }
+
+    fn empty(&self, size: usize) -> Handle {
+        let mut connection = self.connection.lock().unwrap();
+        let memory = connection.register();
+        connection.send(IpcRequest::<Server::KernelRecord>::Empty {
+            handle: memory.id().value as u64,
+            size,
+        });
+
+        Handle::new(memory, None, None, size as u64)
+    }
+
+    fn copy(&self, stream: StreamId, src: Binding, dst: Binding) {
+        self.send(IpcRequest::Copy {
+            stream,
+            src: TraceBinding::new(&src),
+            dst: TraceBinding::new(&dst),
+        });
+    }
+
+    fn fill(&self, stream: StreamId, binding: Binding, pattern: &[u8]) {
+        self.send(IpcRequest::Fill {
+            stream,
+            binding: TraceBinding::new(&binding),
+            pattern: TraceData(pattern.to_vec()),
+        });
+    }
+
+    fn write(&self, stream: StreamId, binding: Binding, offset: u64, data: &[u8]) {
+        self.send(IpcRequest::Write {
+            stream,
+            binding: TraceBinding::new(&binding),
+            offset,
+            data: TraceData(data.to_vec()),
+        });
+    }
+
+    unsafe fn execute(
+        &self,
+        stream: StreamId,
+        kernel: Server::Kernel,
+        count: CubeCount,
+        bindings: Vec<Binding>,
+        mode: ExecutionMode,
+    ) {
+        let kernel = Server::record_kernel(&kernel)
+            .expect("Only kernels that can be recorded can be sent to an IPC compute server");
+
+        self.send(IpcRequest::Execute {
+            stream,
+            kernel,
+            count: TraceCubeCount::new(&count),
+            bindings: bindings.iter().map(TraceBinding::new).collect(),
+            mode,
+        });
+    }
+
+    fn create_stream(&self) -> StreamId {
+        match self.request(IpcRequest::CreateStream) {
+            Some(IpcResponse::Stream(stream)) => stream,
+            Some(_) => unexpected_response(),
+            None => StreamId::default(),
+        }
+    }
+
+    fn record_event(&self, stream: StreamId) -> EventId {
+        match self.request(IpcRequest::RecordEvent { stream }) {
+            Some(IpcResponse::Event(event)) => event,
+            Some(_) => unexpected_response(),
+            None => EventId::default(),
+        }
+    }
+
+    fn wait_event(&self, stream: StreamId, event: EventId) {
+        self.send(IpcRequest::WaitEvent { stream, event });
+    }
+
+    fn start_capture(&self) {
+        self.send(IpcRequest::StartCapture);
+    }
+
+    fn end_capture(&self) -> Option<GraphId> {
+        match self.request(IpcRequest::EndCapture) {
+            Some(IpcResponse::Graph(graph)) => graph,
+            Some(_) => unexpected_response(),
+            None => None,
+        }
+    }
+
+    fn replay_graph(&self, graph: GraphId) {
+        self.send(IpcRequest::ReplayGraph { graph });
+    }
+
+    fn free_graph(&self, graph: GraphId) {
+        self.send(IpcRequest::FreeGraph { graph });
+    }
+
+    fn flush(&self) {
+        self.send(IpcRequest::Flush);
+    }
+
+    async fn sync(&self) {
+        match self.request(IpcRequest::Sync) {
+            Some(IpcResponse::Sync) | None => {}
+            Some(_) => unexpected_response(),
+        }
+    }
+
+    async fn sync_elapsed(&self) -> TimestampsResult {
+        match self.request(IpcRequest::SyncElapsed) {
+            Some(IpcResponse::SyncElapsed(result)) => result.map_err(TimestampsError::from),
+            Some(_) => unexpected_response(),
+            None => Err(device_lost_timestamps()),
+        }
+    }
+
+    fn memory_usage(&self) -> MemoryUsage {
+        match self.request(IpcRequest::MemoryUsage) {
+            Some(IpcResponse::MemoryUsage(usage)) => usage,
+            Some(_) => unexpected_response(),
+            None => MemoryUsage {
+                number_allocs: 0,
+                bytes_in_use: 0,
+                bytes_padding: 0,
+                bytes_reserved: 0,
+            },
+        }
+    }
+
+    fn enable_timestamps(&self) {
+        self.send(IpcRequest::EnableTimestamps);
+    }
+
+    fn disable_timestamps(&self) {
+        self.send(IpcRequest::DisableTimestamps);
+    }
+
+    fn start_profile(&self) {
+        self.send(IpcRequest::StartProfile);
+    }
+
+    async fn end_profile(&self) -> ProfileResult {
+        match self.request(IpcRequest::EndProfile) {
+            Some(IpcResponse::Profile(result)) => result.map_err(TimestampsError::from),
+            Some(_) => unexpected_response(),
+            None => Err(device_lost_timestamps()),
+        }
+    }
+
+    fn errors(&self) -> Vec<KernelError> {
+        let mut connection = self.connection.lock().unwrap();
+        connection.send(IpcRequest::<Server::KernelRecord>::Errors);
+        match connection.receive() {
+            Some(IpcResponse::Errors(errors)) => errors,
+            Some(_) => unexpected_response(),
+            // The loss is reported by every call, since the device can't be used anymore.
+            None => alloc::vec![KernelError::device_lost(
+                connection.lost.clone().unwrap_or_default()
+            )],
+        }
+    }
+}
+
+/// The server shared by the connections, along with the errors raised by the kernels of each one.
+///
+/// The errors reported by the server are raised by the kernels of the connection that launched
+/// the last ones. When another connection launches kernels, the server is synchronized first so
+/// the errors of the previous kernels are set aside for their connection.
+struct SharedServer<Server> {
+    server: Server,
+    /// The connection that launched the last kernels.
+    launcher: Option<usize>,
+    errors: HashMap<usize, Vec<KernelError>>,
+}
+
+impl<Server: ComputeServer> SharedServer<Server> {
+    fn new(server: Server) -> Self {
+        Self {
+            server,
+            launcher: None,
+            errors: HashMap::new(),
+        }
+    }
+
+    /// The server, to launch kernels for the given connection.
+    fn launch(&mut self, connection: usize) -> &mut Server {
+        if let Some(launcher) = self.launcher.filter(|launcher| *launcher != connection) {
+            read_sync(self.server.sync());
+            self.collect_errors(launcher);
+        }
+        self.launcher = Some(connection);
+        &mut self.server
+    }
+
+    /// Take the errors raised by the kernels of the connection.
+    fn take_errors(&mut self, connection: usize) -> Vec<KernelError> {
+        if self.launcher == Some(connection) {
+            self.collect_errors(connection);
+        }
+        self.errors.remove(&connection).unwrap_or_default()
+    }
+
+    /// Drop the errors of a closed connection, including the ones its kernels still have to
+    /// raise.
+    fn close(&mut self, connection: usize) {
+        if self.launcher == Some(connection) {
+            read_sync(self.server.sync());
+            self.server.errors();
+            self.launcher = None;
+        }
+        self.errors.remove(&connection);
+    }
+
+    fn collect_errors(&mut self, connection: usize) {
+        let errors = self.server.errors();
+        if !errors.is_empty() {
+            KernelError::extend_pending(self.errors.entry(connection).or_default(), errors);
+        }
+    }
+}
+
+impl<Server> Deref for SharedServer<Server> {
+    type Target = Server;
+
+    fn deref(&self) -> &Self::Target {
+        &self.server
+    }
+}
+
+impl<Server> DerefMut for SharedServer<Server> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.server
+    }
+}
+
+fn serve_connection<Server: RecordableServer>(
+    server: &Mutex<SharedServer<Server>>,
+    connection: usize,
+    stream: UnixStream,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = BufWriter::new(stream);
+    let mut handles = HashMap::<u64, Handle>::new();
+    let mut graphs = Vec::<GraphId>::new();
+
+    while let Some(message) = read_message::<IpcMessage<Server::KernelRecord>>(&mut reader)? {
+        for handle in message.free {
+            handles.remove(&handle);
+        }
+
+        // Nb: The server should be unlocked before waiting on its futures, so the other
+        // connections aren't blocked.
+        let response = match message.request {
+            IpcRequest::Create { handle, data } => {
+                handles.insert(handle, lock(server).create(&data.0));
+                None
+            }
+            IpcRequest::Empty { handle, size } => {
+                handles.insert(handle, lock(server).empty(size));
+                None
+            }
+            IpcRequest::Copy { stream, src, dst } => {
+                lock(server).copy_on(stream, src.replay(&handles), dst.replay(&handles));
+                None
+            }
+            IpcRequest::Fill {
+                stream,
+                binding,
+                pattern,
+            } => {
+                lock(server).fill_on(stream, binding.replay(&handles), &pattern.0);
+                None
+            }
+            IpcRequest::Write {
+                stream,
+                binding,
+                offset,
+                data,
+            } => {
+                lock(server).write_on(stream, binding.replay(&handles), offset, &data.0);
+                None
+            }
+            IpcRequest::Execute {
+                stream,
+                kernel,
+                count,
+                bindings,
+                mode,
+            } => {
+                let kernel = Server::replay_kernel(kernel);
+                let count = count.replay(&handles);
+                let bindings = bindings.iter().map(|b| b.replay(&handles)).collect();
+                // SAFETY: The channel that sent the kernel upholds the safety contract of
+                // `execute`.
+                unsafe {
+                    lock(server)
+                        .launch(connection)
+                        .execute_on(stream, kernel, count, bindings, mode)
+                };
+                None
+            }
+            IpcRequest::CreateStream => Some(IpcResponse::Stream(lock(server).create_stream())),
+            IpcRequest::RecordEvent { stream } => {
+                Some(IpcResponse::Event(lock(server).record_event(stream)))
+            }
+            IpcRequest::WaitEvent { stream, event } => {
+                lock(server).wait_event(stream, event);
+                None
+            }
+            IpcRequest::StartCapture => {
+                lock(server).start_capture();
+                None
+            }
+            IpcRequest::EndCapture => {
+                let graph = lock(server).end_capture();
+                graphs.extend(graph);
+                Some(IpcResponse::Graph(graph))
+            }
+            IpcRequest::ReplayGraph { graph } => {
+                lock(server).launch(connection).replay_graph(graph);
+                None
+            }
+            IpcRequest::FreeGraph { graph } => {
+                graphs.retain(|id| *id != graph);
+                lock(server).free_graph(graph);
+                None
+            }
+            IpcRequest::Read { bindings } => {
+                let bindings = bindings.iter().map(|b| b.replay(&handles)).collect();
+                let fut = lock(server).read(bindings);
+                let data = read_sync(fut).into_iter().map(TraceData).collect();
+                Some(IpcResponse::Read(data))
+            }
+            IpcRequest::Flush => {
+                lock(server).flush();
+                None
+            }
+            IpcRequest::Sync => {
+                let fut = lock(server).sync();
+                read_sync(fut);
+                Some(IpcResponse::Sync)
+            }
+            IpcRequest::SyncElapsed => {
+                let fut = lock(server).sync_elapsed();
+                let result = read_sync(fut).map_err(IpcTimestampsError::from);
+                Some(IpcResponse::SyncElapsed(result))
+            }
+            IpcRequest::MemoryUsage => Some(IpcResponse::MemoryUsage(lock(server).memory_usage())),
+            IpcRequest::EnableTimestamps => {
+                lock(server).enable_timestamps();
+                None
+            }
+            IpcRequest::DisableTimestamps => {
+                lock(server).disable_timestamps();
+                None
+            }
+            IpcRequest::StartProfile => {
+                lock(server).start_profile();
+                None
+            }
+            IpcRequest::EndProfile => {
+                let fut = lock(server).end_profile();
+                let result = read_sync(fut).map_err(IpcTimestampsError::from);
+                Some(IpcResponse::Profile(result))
+            }
+            IpcRequest::Errors => Some(IpcResponse::Errors(lock(server).take_errors(connection))),
+        };
+
+        if let Some(response) = response {
+            write_message(&mut writer, &response)?;
+        }
+    }
+
+    // Like buffers, the graphs still alive are owned by the closed connection.
+    for graph in graphs {
+        lock(server).free_graph(graph);
+    }
+
+    Ok(())
+}
+
+/// Lock the server shared by the connections. A panic on one connection, like a kernel failing
+/// to compile, poisons the lock but shouldn't close the other connections.
+fn lock<Server>(server: &Mutex<Server>) -> MutexGuard<'_, Server> {
+    server.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Messages are written as lines of JSON.
+fn write_message<T: Serialize>(writer: &mut impl Write, message: &T) -> std::io::Result<()> {
+    serde_json::to_writer(&mut *writer, message)?;
+    writer.write_all(b"\n")?;
+    writer.flush()
+}
+
+/// Read the next message, or `None` if the other side closed the connection.
+fn read_message<T: DeserializeOwned>(reader: &mut impl BufRead) -> std::io::Result<Option<T>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(serde_json::from_str(&line)?))
+}
+
+fn device_lost_timestamps() -> TimestampsError {
+    TimestampsError::Unknown(KernelErrorKind::DeviceLost.to_string())
+}
+
+fn unexpected_response() -> ! {
+    panic!("The IPC compute server sent an unexpected response")
+}
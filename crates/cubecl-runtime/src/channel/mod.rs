@@ -15,3 +15,13 @@ pub use mpsc::*;
 mod cell;
 #[cfg(feature = "channel-cell")]
 pub use cell::*;
+
+#[cfg(feature = "channel-record")]
+mod record;
+#[cfg(feature = "channel-record")]
+pub use record::*;
+
+#[cfg(all(feature = "channel-ipc", unix))]
+mod ipc;
+#[cfg(all(feature = "channel-ipc", unix))]
+pub use ipc::*;
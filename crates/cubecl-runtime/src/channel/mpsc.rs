@@ -5,7 +5,8 @@ use cubecl_common::benchmark::TimestampsResult;
 use super::ComputeChannel;
 use crate::{
     memory_management::MemoryUsage,
-    server::{Binding, ComputeServer, CubeCount, Handle},
+    profile::ProfileResult,
+    server::{Binding, ComputeServer, CubeCount, EventId, GraphId, Handle, KernelError, StreamId},
     storage::BindingResource,
     ExecutionMode,
 };
@@ -39,13 +40,30 @@ where
     GetResource(Binding, Callback<BindingResource<Server>>),
     Create(Vec<u8>, Callback<Handle>),
     Empty(usize, Callback<Handle>),
-    ExecuteKernel((Server::Kernel, CubeCount, ExecutionMode), Vec<Binding>),
+    Copy(StreamId, Binding, Binding),
+    Fill(StreamId, Binding, Vec<u8>),
+    Write(StreamId, Binding, u64, Vec<u8>),
+    ExecuteKernel(
+        StreamId,
+        (Server::Kernel, CubeCount, ExecutionMode),
+        Vec<Binding>,
+    ),
+    CreateStream(Callback<StreamId>),
+    RecordEvent(StreamId, Callback<EventId>),
+    WaitEvent(StreamId, EventId),
+    StartCapture,
+    EndCapture(Callback<Option<GraphId>>),
+    ReplayGraph(GraphId),
+    FreeGraph(GraphId),
     Flush,
     SyncElapsed(Callback<TimestampsResult>),
     Sync(Callback<()>),
     GetMemoryUsage(Callback<MemoryUsage>),
     EnableTimestamps,
     DisableTimestamps,
+    StartProfile,
+    EndProfile(Callback<ProfileResult>),
+    Errors(Callback<Vec<KernelError>>),
 }
 
 impl<Server> MpscComputeChannel<Server>
@@ -78,9 +96,39 @@ where
                             let handle = server.empty(size);
                             callback.send(handle).await.unwrap();
                         }
-                        Message::ExecuteKernel(kernel, bindings) => unsafe {
-                            server.execute(kernel.0, kernel.1, bindings, kernel.2);
+                        Message::Copy(stream, src, dst) => {
+                            server.copy_on(stream, src, dst);
+                        }
+                        Message::Fill(stream, binding, pattern) => {
+                            server.fill_on(stream, binding, &pattern);
+                        }
+                        Message::Write(stream, binding, offset, data) => {
+                            server.write_on(stream, binding, offset, &data);
+                        }
+                        Message::ExecuteKernel(stream, kernel, bindings) => unsafe {
+                            server.execute_on(stream, kernel.0, kernel.1, bindings, kernel.2);
                         },
+                        Message::CreateStream(callback) => {
+                            callback.send(server.create_stream()).await.unwrap();
+                        }
+                        Message::RecordEvent(stream, callback) => {
+                            callback.send(server.record_event(stream)).await.unwrap();
+                        }
+                        Message::WaitEvent(stream, event) => {
+                            server.wait_event(stream, event);
+                        }
+                        Message::StartCapture => {
+                            server.start_capture();
+                        }
+                        Message::EndCapture(callback) => {
+                            callback.send(server.end_capture()).await.unwrap();
+                        }
+                        Message::ReplayGraph(graph) => {
+                            server.replay_graph(graph);
+                        }
+                        Message::FreeGraph(graph) => {
+                            server.free_graph(graph);
+                        }
                         Message::SyncElapsed(callback) => {
                             let duration = server.sync_elapsed().await;
                             callback.send(duration).await.unwrap();
@@ -101,6 +149,16 @@ where
                         Message::DisableTimestamps => {
                             server.disable_timestamps();
                         }
+                        Message::StartProfile => {
+                            server.start_profile();
+                        }
+                        Message::EndProfile(callback) => {
+                            let profile = server.end_profile().await;
+                            callback.send(profile).await.unwrap();
+                        }
+                        Message::Errors(callback) => {
+                            callback.send(server.errors()).await.unwrap();
+                        }
                     };
                 }
             });
@@ -166,8 +224,30 @@ where
         handle_response(response.recv_blocking())
     }
 
+    fn copy(&self, stream: StreamId, src: Binding, dst: Binding) {
+        self.state
+            .sender
+            .send_blocking(Message::Copy(stream, src, dst))
+            .unwrap()
+    }
+
+    fn fill(&self, stream: StreamId, binding: Binding, pattern: &[u8]) {
+        self.state
+            .sender
+            .send_blocking(Message::Fill(stream, binding, pattern.to_vec()))
+            .unwrap()
+    }
+
+    fn write(&self, stream: StreamId, binding: Binding, offset: u64, data: &[u8]) {
+        self.state
+            .sender
+            .send_blocking(Message::Write(stream, binding, offset, data.to_vec()))
+            .unwrap()
+    }
+
     unsafe fn execute(
         &self,
+        stream: StreamId,
         kernel: Server::Kernel,
         count: CubeCount,
         bindings: Vec<Binding>,
@@ -175,7 +255,66 @@ where
     ) {
         self.state
             .sender
-            .send_blocking(Message::ExecuteKernel((kernel, count, kind), bindings))
+            .send_blocking(Message::ExecuteKernel(
+                stream,
+                (kernel, count, kind),
+                bindings,
+            ))
+            .unwrap()
+    }
+
+    fn create_stream(&self) -> StreamId {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::CreateStream(callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
+
+    fn record_event(&self, stream: StreamId) -> EventId {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::RecordEvent(stream, callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
+
+    fn wait_event(&self, stream: StreamId, event: EventId) {
+        self.state
+            .sender
+            .send_blocking(Message::WaitEvent(stream, event))
+            .unwrap()
+    }
+
+    fn start_capture(&self) {
+        self.state
+            .sender
+            .send_blocking(Message::StartCapture)
+            .unwrap()
+    }
+
+    fn end_capture(&self) -> Option<GraphId> {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::EndCapture(callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
+
+    fn replay_graph(&self, graph: GraphId) {
+        self.state
+            .sender
+            .send_blocking(Message::ReplayGraph(graph))
+            .unwrap()
+    }
+
+    fn free_graph(&self, graph: GraphId) {
+        self.state
+            .sender
+            .send_blocking(Message::FreeGraph(graph))
             .unwrap()
     }
 
@@ -225,6 +364,32 @@ where
             .send_blocking(Message::DisableTimestamps)
             .unwrap();
     }
+
+    fn start_profile(&self) {
+        self.state
+            .sender
+            .send_blocking(Message::StartProfile)
+            .unwrap();
+    }
+
+    async fn end_profile(&self) -> ProfileResult {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send(Message::EndProfile(callback))
+            .await
+            .unwrap();
+        handle_response(response.recv().await)
+    }
+
+    fn errors(&self) -> Vec<KernelError> {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::Errors(callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
 }
 
 fn handle_response<Response, Err: core::fmt::Debug>(response: Result<Response, Err>) -> Response {
@@ -1,5 +1,8 @@
 use super::ComputeChannel;
-use crate::server::{Binding, ComputeServer, CubeCount, Handle};
+use crate::profile::ProfileResult;
+use crate::server::{
+    Binding, ComputeServer, CubeCount, EventId, GraphId, Handle, KernelError, StreamId,
+};
 use crate::storage::BindingResource;
 use crate::ExecutionMode;
 use alloc::sync::Arc;
@@ -59,14 +62,57 @@ where
         self.server.lock().empty(size)
     }
 
+    fn copy(&self, stream: StreamId, src: Binding, dst: Binding) {
+        self.server.lock().copy_on(stream, src, dst)
+    }
+
+    fn fill(&self, stream: StreamId, binding: Binding, pattern: &[u8]) {
+        self.server.lock().fill_on(stream, binding, pattern)
+    }
+
+    fn write(&self, stream: StreamId, binding: Binding, offset: u64, data: &[u8]) {
+        self.server.lock().write_on(stream, binding, offset, data)
+    }
+
     unsafe fn execute(
         &self,
+        stream: StreamId,
         kernel: Server::Kernel,
         count: CubeCount,
         handles: Vec<Binding>,
         kind: ExecutionMode,
     ) {
-        self.server.lock().execute(kernel, count, handles, kind)
+        self.server
+            .lock()
+            .execute_on(stream, kernel, count, handles, kind)
+    }
+
+    fn create_stream(&self) -> StreamId {
+        self.server.lock().create_stream()
+    }
+
+    fn record_event(&self, stream: StreamId) -> EventId {
+        self.server.lock().record_event(stream)
+    }
+
+    fn wait_event(&self, stream: StreamId, event: EventId) {
+        self.server.lock().wait_event(stream, event)
+    }
+
+    fn start_capture(&self) {
+        self.server.lock().start_capture()
+    }
+
+    fn end_capture(&self) -> Option<GraphId> {
+        self.server.lock().end_capture()
+    }
+
+    fn replay_graph(&self, graph: GraphId) {
+        self.server.lock().replay_graph(graph)
+    }
+
+    fn free_graph(&self, graph: GraphId) {
+        self.server.lock().free_graph(graph)
     }
 
     fn flush(&self) {
@@ -104,4 +150,21 @@ where
     fn disable_timestamps(&self) {
         self.server.lock().disable_timestamps();
     }
+
+    fn start_profile(&self) {
+        self.server.lock().start_profile();
+    }
+
+    async fn end_profile(&self) -> ProfileResult {
+        // Nb: The mutex guard has to be dropped before the future is polled.
+        let fut = {
+            let mut server = self.server.lock();
+            server.end_profile()
+        };
+        fut.await
+    }
+
+    fn errors(&self) -> Vec<KernelError> {
+        self.server.lock().errors()
+    }
 }
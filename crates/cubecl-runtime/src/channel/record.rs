@@ -0,0 +1,811 @@
+use super::ComputeChannel;
+use crate::client::ComputeClient;
+use crate::graph::KernelGraph;
+use crate::profile::ProfileResult;
+use crate::server::{
+    Binding, CubeCount, EventId, GraphId, Handle, KernelError, RecordableServer, StreamId,
+};
+use crate::storage::BindingResource;
+use crate::ExecutionMode;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::hash::Hash;
+use core::marker::PhantomData;
+use cubecl_common::benchmark::TimestampsResult;
+use hashbrown::HashMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use spin::Mutex;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// The RecordChannel wraps another channel and records every operation sent to it in a
+/// [trace](Trace), which can be [replayed](Trace::replay) later, possibly on another runtime.
+///
+/// Each operation is written as a line of JSON as soon as it's sent, so the trace of a program
+/// that crashes can still be replayed. Each kernel is only written the first time it's
+/// executed, and referred to by its index afterwards.
+///
+/// Recording never interrupts the program: the first error writing the trace stops the
+/// recording, and is returned by [finish](Self::finish).
+pub struct RecordChannel<Server: RecordableServer, Channel> {
+    channel: Channel,
+    recorder: Arc<Mutex<Recorder<Server::KernelKey>>>,
+    _server: PhantomData<fn() -> Server>,
+}
+
+struct Recorder<Key> {
+    writer: Box<dyn Write + Send>,
+    reads: u64,
+    /// The index of each kernel written to the trace.
+    kernels: HashMap<Key, u64>,
+    /// The first error writing the trace, after which nothing is written.
+    error: Option<std::io::Error>,
+}
+
+impl<S: RecordableServer, C: Clone> Clone for RecordChannel<S, C> {
+    fn clone(&self) -> Self {
+        Self {
+            channel: self.channel.clone(),
+            recorder: self.recorder.clone(),
+            _server: PhantomData,
+        }
+    }
+}
+
+impl<S: RecordableServer, C: core::fmt::Debug> core::fmt::Debug for RecordChannel<S, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RecordChannel")
+            .field("channel", &self.channel)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Server, Channel> RecordChannel<Server, Channel>
+where
+    Server: RecordableServer,
+    Channel: ComputeChannel<Server>,
+{
+    /// Create a record channel writing the trace of the operations sent to `channel` to `writer`.
+    pub fn new(channel: Channel, writer: impl Write + Send + 'static) -> Self {
+        Self {
+            channel,
+            recorder: Arc::new(Mutex::new(Recorder {
+                writer: Box::new(writer),
+                reads: 0,
+                kernels: HashMap::new(),
+                error: None,
+            })),
+            _server: PhantomData,
+        }
+    }
+
+    /// Create a record channel writing the trace of the operations sent to `channel` to a new
+    /// file.
+    pub fn create(channel: Channel, path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = std::fs::File::create(path)?;
+        Ok(Self::new(channel, BufWriter::new(file)))
+    }
+
+    /// Finish the recording, returning the first error writing the trace, if any.
+    ///
+    /// The channel is usually moved into a client, so a clone should be kept to finish the
+    /// recording. The channel and its clones keep working afterwards, but nothing is recorded.
+    pub fn finish(&self) -> std::io::Result<()> {
+        let mut recorder = self.recorder.lock();
+        let mut writer = core::mem::replace(&mut recorder.writer, Box::new(std::io::sink()));
+
+        match recorder.error.take() {
+            Some(err) => Err(err),
+            None => writer.flush(),
+        }
+    }
+}
+
+impl<Key: Hash + Eq> Recorder<Key> {
+    fn write<K: Serialize>(&mut self, entry: &TraceEntry<K>) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let result = serde_json::to_writer(&mut self.writer, entry)
+            .map_err(std::io::Error::from)
+            .and_then(|_| self.writer.write_all(b"\n"))
+            .and_then(|_| self.writer.flush());
+        if let Err(err) = result {
+            self.error = Some(err);
+        }
+    }
+
+    /// The index of the kernel in the trace, writing it the first time it's seen.
+    fn kernel<K: Serialize>(&mut self, key: Key, record: impl FnOnce() -> Option<K>) -> u64 {
+        if let Some(index) = self.kernels.get(&key) {
+            return *index;
+        }
+
+        let index = self.kernels.len() as u64;
+        self.kernels.insert(key, index);
+        self.write(&TraceEntry::Kernel {
+            index,
+            kernel: record(),
+        });
+        index
+    }
+}
+
+impl<Server, Channel> ComputeChannel<Server> for RecordChannel<Server, Channel>
+where
+    Server: RecordableServer,
+    Channel: ComputeChannel<Server>,
+{
+    async fn read(&self, bindings: Vec<Binding>) -> Vec<Vec<u8>> {
+        // Nb: The recorder has to be unlocked before the future is polled.
+        let (read, fut) = {
+            let mut recorder = self.recorder.lock();
+            let read = recorder.reads;
+            recorder.reads += 1;
+            recorder.write(&TraceEntry::<Server::KernelRecord>::Read {
+                bindings: bindings.iter().map(TraceBinding::new).collect(),
+            });
+            (read, self.channel.read(bindings))
+        };
+        let data = fut.await;
+
+        self.recorder
+            .lock()
+            .write(&TraceEntry::<Server::KernelRecord>::ReadData {
+                read,
+                data: data.iter().cloned().map(TraceData).collect(),
+            });
+        data
+    }
+
+    fn get_resource(&self, binding: Binding) -> BindingResource<Server> {
+        self.channel.get_resource(binding)
+    }
+
+    fn create(&self, data: &[u8]) -> Handle {
+        let mut recorder = self.recorder.lock();
+        let handle = self.channel.create(data);
+        recorder.write(&TraceEntry::<Server::KernelRecord>::Create {
+            handle: handle_id(&handle),
+            data: TraceData(data.to_vec()),
+        });
+        handle
+    }
+
+    fn empty(&self, size: usize) -> Handle {
+        let mut recorder = self.recorder.lock();
+        let handle = self.channel.empty(size);
+        recorder.write(&TraceEntry::<Server::KernelRecord>::Empty {
+            handle: handle_id(&handle),
+            size,
+        });
+        handle
+    }
+
+    fn copy(&self, stream: StreamId, src: Binding, dst: Binding) {
+        let mut recorder = self.recorder.lock();
+        let entry = TraceEntry::<Server::KernelRecord>::Copy {
+            stream,
+            src: TraceBinding::new(&src),
+            dst: TraceBinding::new(&dst),
+        };
+        self.channel.copy(stream, src, dst);
+        recorder.write(&entry);
+    }
+
+    fn fill(&self, stream: StreamId, binding: Binding, pattern: &[u8]) {
+        let mut recorder = self.recorder.lock();
+        let entry = TraceEntry::<Server::KernelRecord>::Fill {
+            stream,
+            binding: TraceBinding::new(&binding),
+            pattern: TraceData(pattern.to_vec()),
+        };
+        self.channel.fill(stream, binding, pattern);
+        recorder.write(&entry);
+    }
+
+    fn write(&self, stream: StreamId, binding: Binding, offset: u64, data: &[u8]) {
+        let mut recorder = self.recorder.lock();
+        let entry = TraceEntry::<Server::KernelRecord>::Write {
+            stream,
+            binding: TraceBinding::new(&binding),
+            offset,
+            data: TraceData(data.to_vec()),
+        };
+        self.channel.write(stream, binding, offset, data);
+        recorder.write(&entry);
+    }
+
+    unsafe fn execute(
+        &self,
+        stream: StreamId,
+        kernel: Server::Kernel,
+        count: CubeCount,
+        bindings: Vec<Binding>,
+        mode: ExecutionMode,
+    ) {
+        let mut recorder = self.recorder.lock();
+        let index = recorder.kernel(Server::kernel_key(&kernel), || {
+            Server::record_kernel(&kernel)
+        });
+        let entry = TraceEntry::<Server::KernelRecord>::Execute {
+            stream,
+            kernel: index,
+            count: TraceCubeCount::new(&count),
+            bindings: bindings.iter().map(TraceBinding::new).collect(),
+            mode,
+        };
+        self.channel.execute(stream, kernel, count, bindings, mode);
+        recorder.write(&entry);
+    }
+
+    fn create_stream(&self) -> StreamId {
+        let mut recorder = self.recorder.lock();
+        let stream = self.channel.create_stream();
+        recorder.write(&TraceEntry::<Server::KernelRecord>::CreateStream { stream });
+        stream
+    }
+
+    fn record_event(&self, stream: StreamId) -> EventId {
+        let mut recorder = self.recorder.lock();
+        let event = self.channel.record_event(stream);
+        recorder.write(&TraceEntry::<Server::KernelRecord>::RecordEvent { stream, event });
+        event
+    }
+
+    fn wait_event(&self, stream: StreamId, event: EventId) {
+        let mut recorder = self.recorder.lock();
+        self.channel.wait_event(stream, event);
+        recorder.write(&TraceEntry::<Server::KernelRecord>::WaitEvent { stream, event });
+    }
+
+    fn start_capture(&self) {
+        let mut recorder = self.recorder.lock();
+        self.channel.start_capture();
+        recorder.write(&TraceEntry::<Server::KernelRecord>::StartCapture);
+    }
+
+    fn end_capture(&self) -> Option<GraphId> {
+        let mut recorder = self.recorder.lock();
+        let graph = self.channel.end_capture();
+        recorder.write(&TraceEntry::<Server::KernelRecord>::EndCapture { graph });
+        graph
+    }
+
+    fn replay_graph(&self, graph: GraphId) {
+        let mut recorder = self.recorder.lock();
+        self.channel.replay_graph(graph);
+        recorder.write(&TraceEntry::<Server::KernelRecord>::ReplayGraph { graph });
+    }
+
+    fn free_graph(&self, graph: GraphId) {
+        let mut recorder = self.recorder.lock();
+        self.channel.free_graph(graph);
+        recorder.write(&TraceEntry::<Server::KernelRecord>::FreeGraph { graph });
+    }
+
+    fn flush(&self) {
+        self.channel.flush()
+    }
+
+    async fn sync(&self) {
+        let fut = {
+            let mut recorder = self.recorder.lock();
+            recorder.write(&TraceEntry::<Server::KernelRecord>::Sync);
+            self.channel.sync()
+        };
+        fut.await
+    }
+
+    async fn sync_elapsed(&self) -> TimestampsResult {
+        let fut = {
+            let mut recorder = self.recorder.lock();
+            recorder.write(&TraceEntry::<Server::KernelRecord>::Sync);
+            self.channel.sync_elapsed()
+        };
+        fut.await
+    }
+
+    fn memory_usage(&self) -> crate::memory_management::MemoryUsage {
+        self.channel.memory_usage()
+    }
+
+    fn enable_timestamps(&self) {
+        self.channel.enable_timestamps()
+    }
+
+    fn disable_timestamps(&self) {
+        self.channel.disable_timestamps()
+    }
+
+    fn start_profile(&self) {
+        self.channel.start_profile()
+    }
+
+    async fn end_profile(&self) -> ProfileResult {
+        let fut = {
+            let mut recorder = self.recorder.lock();
+            recorder.write(&TraceEntry::<Server::KernelRecord>::Sync);
+            self.channel.end_profile()
+        };
+        fut.await
+    }
+
+    fn errors(&self) -> Vec<KernelError> {
+        self.channel.errors()
+    }
+}
+
+/// The operations recorded by a [record channel](RecordChannel), with kernels recorded as `K`.
+#[derive(Debug, Clone)]
+pub struct Trace<K> {
+    /// The recorded operations, in the order they were sent.
+    pub entries: Vec<TraceEntry<K>>,
+}
+
+/// An operation recorded in a [trace](Trace).
+///
+/// Buffers are identified by the id of their handle, which is never reused by another handle. A
+/// buffer is always created before it's used. Operations without a stream were recorded on the
+/// default stream.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TraceEntry<K> {
+    /// A buffer was created with the given data.
+    Create {
+        /// The created buffer.
+        handle: u64,
+        /// The data of the buffer.
+        data: TraceData,
+    },
+    /// A buffer of `size` bytes was reserved.
+    Empty {
+        /// The reserved buffer.
+        handle: u64,
+        /// The size of the buffer, in bytes.
+        size: usize,
+    },
+    /// A buffer was copied to another.
+    Copy {
+        /// The stream of the copy.
+        #[serde(default)]
+        stream: StreamId,
+        /// The copied binding.
+        src: TraceBinding,
+        /// The binding copied to.
+        dst: TraceBinding,
+    },
+    /// A buffer was filled with a repeated pattern.
+    Fill {
+        /// The stream of the fill.
+        #[serde(default)]
+        stream: StreamId,
+        /// The filled binding.
+        binding: TraceBinding,
+        /// The repeated bytes.
+        pattern: TraceData,
+    },
+    /// Data was written to a buffer.
+    Write {
+        /// The stream of the write.
+        #[serde(default)]
+        stream: StreamId,
+        /// The written binding.
+        binding: TraceBinding,
+        /// The offset of the data in the binding, in bytes.
+        offset: u64,
+        /// The written data.
+        data: TraceData,
+    },
+    /// A kernel was executed for the first time.
+    Kernel {
+        /// The index of the kernel among all kernels of the trace.
+        index: u64,
+        /// The kernel, or `None` if it couldn't be recorded.
+        kernel: Option<K>,
+    },
+    /// A kernel was executed.
+    Execute {
+        /// The stream of the launch.
+        #[serde(default)]
+        stream: StreamId,
+        /// The index of the kernel, recorded by an earlier [kernel](TraceEntry::Kernel) entry.
+        kernel: u64,
+        /// The number of cubes of the launch.
+        count: TraceCubeCount,
+        /// The bindings of the kernel.
+        bindings: Vec<TraceBinding>,
+        /// The execution mode of the kernel.
+        mode: ExecutionMode,
+    },
+    /// Bindings were read.
+    Read {
+        /// The bindings that were read.
+        bindings: Vec<TraceBinding>,
+    },
+    /// A read completed.
+    ReadData {
+        /// The index of the read among all reads of the trace.
+        read: u64,
+        /// The data of each binding of the read.
+        data: Vec<TraceData>,
+    },
+    /// A stream was created.
+    CreateStream {
+        /// The created stream.
+        stream: StreamId,
+    },
+    /// An event was recorded on a stream.
+    RecordEvent {
+        /// The stream of the event.
+        stream: StreamId,
+        /// The recorded event.
+        event: EventId,
+    },
+    /// A stream waited on an event.
+    WaitEvent {
+        /// The waiting stream.
+        stream: StreamId,
+        /// The awaited event.
+        event: EventId,
+    },
+    /// The server started capturing kernels into a graph.
+    StartCapture,
+    /// The server stopped capturing kernels.
+    EndCapture {
+        /// The captured graph, or `None` if the server doesn't capture graphs, in which case its
+        /// replays are recorded as the kernels they execute.
+        graph: Option<GraphId>,
+    },
+    /// The kernels of a graph were executed again.
+    ReplayGraph {
+        /// The replayed graph.
+        graph: GraphId,
+    },
+    /// A graph was freed.
+    FreeGraph {
+        /// The freed graph.
+        graph: GraphId,
+    },
+    /// The server was synchronized.
+    Sync,
+}
+
+/// A [binding](Binding) recorded in a [trace](Trace).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceBinding {
+    /// The bound buffer.
+    pub handle: u64,
+    /// Memory offset in bytes.
+    pub offset_start: Option<u64>,
+    /// Memory offset in bytes.
+    pub offset_end: Option<u64>,
+}
+
+/// A [cube count](CubeCount) recorded in a [trace](Trace).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TraceCubeCount {
+    /// Dispatch a known count of x, y, z cubes.
+    Static(u32, u32, u32),
+    /// Dispatch an amount based on the values in this buffer.
+    Dynamic(TraceBinding),
+}
+
+/// Bytes recorded in a [trace](Trace), stored as a hexadecimal string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceData(pub Vec<u8>);
+
+/// A read of a replayed [trace](Trace).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayedRead {
+    /// The data read when recording, or `None` if the read didn't complete before the end of
+    /// the trace.
+    pub recorded: Option<Vec<Vec<u8>>>,
+    /// The data read during the replay.
+    pub replayed: Vec<Vec<u8>>,
+}
+
+impl<K: DeserializeOwned> Trace<K> {
+    /// Load a trace written to a file by a [record channel](RecordChannel).
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        Self::read(BufReader::new(file))
+    }
+
+    /// Read a trace written by a [record channel](RecordChannel).
+    pub fn read(reader: impl BufRead) -> std::io::Result<Self> {
+        let entries = reader
+            .lines()
+            .map(|line| Ok(serde_json::from_str(&line?)?))
+            .collect::<std::io::Result<_>>()?;
+
+        Ok(Self { entries })
+    }
+}
+
+impl<K: Clone> Trace<K> {
+    /// Replay the trace with the given client, returning the recorded and replayed data of each
+    /// read.
+    ///
+    /// Unchecked kernels are replayed in checked mode, and every kernel is replayed in
+    /// [strict](ExecutionMode::Strict) mode with a
+    /// [strict client](ComputeClient::with_strict_bounds). Buffers are dropped after their last
+    /// use in the trace.
+    pub fn replay<Server, Channel>(
+        self,
+        client: &ComputeClient<Server, Channel>,
+    ) -> Vec<ReplayedRead>
+    where
+        Server: RecordableServer<KernelRecord = K>,
+        Channel: ComputeChannel<Server>,
+    {
+        let strict = client.clone().with_strict_bounds();
+        let mut handles = HashMap::<u64, Handle>::new();
+        let mut streams = HashMap::<StreamId, StreamId>::new();
+        let mut events = HashMap::<EventId, EventId>::new();
+        let mut graphs = HashMap::<GraphId, KernelGraph<Server, Channel>>::new();
+        let mut reads = Vec::<ReplayedRead>::new();
+        let mut kernels = HashMap::<u64, Option<K>>::new();
+
+        // The buffers to drop after each entry, since they aren't used by the next ones. The
+        // buffers of a graph are used until the graph is freed.
+        let mut last_uses = HashMap::<u64, usize>::new();
+        let mut captured = None::<Vec<u64>>;
+        let mut graph_handles = HashMap::<GraphId, Vec<u64>>::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            entry.for_each_handle(|handle| {
+                last_uses.insert(handle, index);
+                if let Some(captured) = captured.as_mut() {
+                    captured.push(handle);
+                }
+            });
+            match entry {
+                TraceEntry::StartCapture => captured = Some(Vec::new()),
+                TraceEntry::EndCapture { graph } => {
+                    let captured = captured.take().unwrap_or_default();
+                    if let Some(graph) = graph {
+                        graph_handles.insert(*graph, captured);
+                    }
+                }
+                TraceEntry::ReplayGraph { graph } | TraceEntry::FreeGraph { graph } => {
+                    for handle in graph_handles.get(graph).into_iter().flatten() {
+                        last_uses.insert(*handle, index);
+                    }
+                }
+                _ => {}
+            }
+        }
+        let mut drops = alloc::vec![Vec::new(); self.entries.len()];
+        for (handle, index) in last_uses {
+            drops[index].push(handle);
+        }
+
+        // Streams are created before they're used, except the default stream.
+        let on_stream = |client: &ComputeClient<Server, Channel>,
+                         streams: &HashMap<StreamId, StreamId>,
+                         stream: StreamId| {
+            let stream = streams.get(&stream).copied().unwrap_or_default();
+            client.clone().on_stream(stream)
+        };
+
+        for (index, entry) in self.entries.into_iter().enumerate() {
+            match entry {
+                TraceEntry::Create { handle, data } => {
+                    handles.insert(handle, client.create(&data.0));
+                }
+                TraceEntry::Empty { handle, size } => {
+                    handles.insert(handle, client.empty(size));
+                }
+                TraceEntry::Copy { stream, src, dst } => {
+                    on_stream(client, &streams, stream)
+                        .copy(src.replay(&handles), dst.replay(&handles));
+                }
+                TraceEntry::Fill {
+                    stream,
+                    binding,
+                    pattern,
+                } => {
+                    on_stream(client, &streams, stream).fill(binding.replay(&handles), &pattern.0);
+                }
+                TraceEntry::Write {
+                    stream,
+                    binding,
+                    offset,
+                    data,
+                } => {
+                    on_stream(client, &streams, stream).write(
+                        binding.replay(&handles),
+                        offset,
+                        &data.0,
+                    );
+                }
+                TraceEntry::Kernel { index, kernel } => {
+                    kernels.insert(index, kernel);
+                }
+                TraceEntry::Execute {
+                    stream,
+                    kernel,
+                    count,
+                    bindings,
+                    mode,
+                } => {
+                    let kernel = kernels
+                        .get(&kernel)
+                        .expect("Kernels should be recorded before being executed")
+                        .clone()
+                        .unwrap_or_else(|| {
+                            panic!("The kernel of trace entry {index} couldn't be recorded")
+                        });
+                    let kernel = Server::replay_kernel(kernel);
+                    let count = count.replay(&handles);
+                    let bindings = bindings.iter().map(|b| b.replay(&handles)).collect();
+
+                    match mode {
+                        ExecutionMode::Checked | ExecutionMode::Unchecked => {
+                            on_stream(client, &streams, stream).execute(kernel, count, bindings)
+                        }
+                        ExecutionMode::Strict => {
+                            on_stream(&strict, &streams, stream).execute(kernel, count, bindings)
+                        }
+                    }
+                }
+                TraceEntry::CreateStream { stream } => {
+                    streams.insert(stream, client.create_stream());
+                }
+                TraceEntry::RecordEvent { stream, event } => {
+                    let replayed = on_stream(client, &streams, stream).record_event();
+                    events.insert(event, replayed);
+                }
+                TraceEntry::WaitEvent { stream, event } => {
+                    let event = events
+                        .get(&event)
+                        .expect("Events should be recorded before being waited on");
+                    on_stream(client, &streams, stream).wait_event(*event);
+                }
+                TraceEntry::StartCapture => client.start_capture(),
+                TraceEntry::EndCapture { graph } => {
+                    let replayed = client.end_capture();
+                    if let Some(graph) = graph {
+                        graphs.insert(graph, replayed);
+                    }
+                }
+                TraceEntry::ReplayGraph { graph } => {
+                    graphs
+                        .get(&graph)
+                        .expect("Graphs should be captured before being replayed")
+                        .replay();
+                }
+                TraceEntry::FreeGraph { graph } => {
+                    graphs.remove(&graph);
+                }
+                TraceEntry::Read { bindings } => {
+                    let bindings = bindings.iter().map(|b| b.replay(&handles)).collect();
+                    reads.push(ReplayedRead {
+                        recorded: None,
+                        replayed: client.read(bindings),
+                    });
+                }
+                TraceEntry::ReadData { read, data } => {
+                    reads[read as usize].recorded = Some(data.into_iter().map(|d| d.0).collect());
+                }
+                TraceEntry::Sync => cubecl_common::reader::read_sync(client.sync()),
+            }
+
+            for handle in drops[index].iter() {
+                handles.remove(handle);
+            }
+        }
+
+        reads
+    }
+}
+
+impl<K> TraceEntry<K> {
+    /// Call `func` with the id of every buffer used by the entry.
+    fn for_each_handle(&self, mut func: impl FnMut(u64)) {
+        match self {
+            TraceEntry::Create { handle, .. } | TraceEntry::Empty { handle, .. } => func(*handle),
+            TraceEntry::Copy { src, dst, .. } => {
+                func(src.handle);
+                func(dst.handle);
+            }
+            TraceEntry::Fill { binding, .. } | TraceEntry::Write { binding, .. } => {
+                func(binding.handle)
+            }
+            TraceEntry::Execute {
+                count, bindings, ..
+            } => {
+                if let TraceCubeCount::Dynamic(binding) = count {
+                    func(binding.handle);
+                }
+                bindings.iter().for_each(|binding| func(binding.handle));
+            }
+            TraceEntry::Read { bindings } => {
+                bindings.iter().for_each(|binding| func(binding.handle))
+            }
+            TraceEntry::Kernel { .. }
+            | TraceEntry::ReadData { .. }
+            | TraceEntry::CreateStream { .. }
+            | TraceEntry::RecordEvent { .. }
+            | TraceEntry::WaitEvent { .. }
+            | TraceEntry::StartCapture
+            | TraceEntry::EndCapture { .. }
+            | TraceEntry::ReplayGraph { .. }
+            | TraceEntry::FreeGraph { .. }
+            | TraceEntry::Sync => {}
+        }
+    }
+}
+
+impl TraceBinding {
+    pub(super) fn new(binding: &Binding) -> Self {
+        Self {
+            handle: binding.memory.id().value as u64,
+            offset_start: binding.offset_start,
+            offset_end: binding.offset_end,
+        }
+    }
+
+    pub(super) fn replay(&self, handles: &HashMap<u64, Handle>) -> Binding {
+        let handle = handles
+            .get(&self.handle)
+            .expect("Buffers should be created before being used");
+
+        Binding {
+            offset_start: self.offset_start,
+            offset_end: self.offset_end,
+            ..handle.clone().binding()
+        }
+    }
+}
+
+impl TraceCubeCount {
+    pub(super) fn new(count: &CubeCount) -> Self {
+        match count {
+            CubeCount::Static(x, y, z) => Self::Static(*x, *y, *z),
+            CubeCount::Dynamic(binding) => Self::Dynamic(TraceBinding::new(binding)),
+        }
+    }
+
+    pub(super) fn replay(&self, handles: &HashMap<u64, Handle>) -> CubeCount {
+        match self {
+            Self::Static(x, y, z) => CubeCount::Static(*x, *y, *z),
+            Self::Dynamic(binding) => CubeCount::Dynamic(binding.replay(handles)),
+        }
+    }
+}
+
+fn handle_id(handle: &Handle) -> u64 {
+    handle.memory.id().value as u64
+}
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+impl Serialize for TraceData {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut hex = String::with_capacity(self.0.len() * 2);
+        for byte in self.0.iter() {
+            hex.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+            hex.push(HEX_DIGITS[(byte & 0xf) as usize] as char);
+        }
+        serializer.serialize_str(&hex)
+    }
+}
+
+impl<'de> Deserialize<'de> for TraceData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        if hex.len() % 2 != 0 || !hex.is_ascii() {
+            return Err(serde::de::Error::custom("Invalid hexadecimal data"));
+        }
+
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(serde::de::Error::custom))
+            .collect::<Result<_, _>>()
+            .map(TraceData)
+    }
+}
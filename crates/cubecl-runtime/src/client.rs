@@ -2,14 +2,17 @@ use core::future::Future;
 
 use crate::{
     channel::ComputeChannel,
+    graph::{CapturedLaunch, KernelGraph, ScalarBinding},
     memory_management::MemoryUsage,
-    server::{Binding, ComputeServer, CubeCount, Handle},
+    profile::ProfileResult,
+    server::{Binding, ComputeServer, CubeCount, EventId, Handle, KernelError, StreamId},
     storage::BindingResource,
     DeviceProperties, ExecutionMode,
 };
 use alloc::sync::Arc;
 use alloc::vec::Vec;
 use cubecl_common::benchmark::TimestampsResult;
+use spin::Mutex;
 
 /// The ComputeClient is the entry point to require tasks from the ComputeServer.
 /// It should be obtained for a specific device via the Compute struct.
@@ -17,12 +20,16 @@ use cubecl_common::benchmark::TimestampsResult;
 pub struct ComputeClient<Server: ComputeServer, Channel> {
     channel: Channel,
     state: Arc<ComputeClientState<Server>>,
+    mode: ExecutionMode,
+    stream: StreamId,
 }
 
 #[derive(new, Debug)]
 struct ComputeClientState<Server: ComputeServer> {
     properties: DeviceProperties<Server::Feature>,
     timestamp_lock: async_lock::Mutex<()>,
+    /// The kernels executed since the capture started, if kernels are being captured.
+    capture: Mutex<Option<Vec<CapturedLaunch<Server>>>>,
 }
 
 impl<S, C> Clone for ComputeClient<S, C>
@@ -34,6 +41,8 @@ where
         Self {
             channel: self.channel.clone(),
             state: self.state.clone(),
+            mode: self.mode,
+            stream: self.stream,
         }
     }
 }
@@ -45,13 +54,62 @@ where
 {
     /// Create a new client.
     pub fn new(channel: Channel, properties: DeviceProperties<Server::Feature>) -> Self {
-        let state = ComputeClientState::new(properties, async_lock::Mutex::new(()));
+        let state =
+            ComputeClientState::new(properties, async_lock::Mutex::new(()), Mutex::new(None));
         Self {
             channel,
             state: Arc::new(state),
+            mode: ExecutionMode::Checked,
+            stream: StreamId::default(),
         }
     }
 
+    /// Execute the kernels launched by this client in [strict](ExecutionMode::Strict) mode, so
+    /// their out-of-bound reads and writes are returned as [kernel errors](KernelError) by the
+    /// `try_` functions.
+    ///
+    /// Only this client is affected, not its existing clones. SPIR-V kernels don't report
+    /// out-of-bound accesses yet, they are only checked.
+    pub fn with_strict_bounds(mut self) -> Self {
+        self.mode = ExecutionMode::Strict;
+        self
+    }
+
+    /// Submit the copies, fills, writes and kernels of this client to the given stream, where
+    /// they can run concurrently with the tasks of the other streams.
+    ///
+    /// Only this client is affected, not its existing clones. Handles are still created and read
+    /// on the default stream, so streams must be ordered with [events](Self::record_event) as
+    /// described by [StreamId].
+    pub fn on_stream(mut self, stream: StreamId) -> Self {
+        self.stream = stream;
+        self
+    }
+
+    /// The stream on which the tasks of this client are submitted.
+    pub fn stream(&self) -> StreamId {
+        self.stream
+    }
+
+    /// Create a stream, to [submit tasks on](Self::on_stream).
+    ///
+    /// Servers with a single queue, like wgpu, return the default stream.
+    pub fn create_stream(&self) -> StreamId {
+        self.channel.create_stream()
+    }
+
+    /// Record an event on the stream of this client, reached once the tasks already submitted
+    /// to it complete.
+    pub fn record_event(&self) -> EventId {
+        self.channel.record_event(self.stream)
+    }
+
+    /// Make the tasks submitted to the stream of this client after this call wait until the
+    /// event, possibly recorded on another stream, is reached.
+    pub fn wait_event(&self, event: EventId) {
+        self.channel.wait_event(self.stream, event)
+    }
+
     /// Given bindings, returns owned resources as bytes.
     pub async fn read_async(&self, bindings: Vec<Binding>) -> Vec<Vec<u8>> {
         self.channel.read(bindings).await
@@ -79,6 +137,30 @@ where
         cubecl_common::reader::read_sync(self.channel.read([binding].into())).remove(0)
     }
 
+    /// Given bindings, returns owned resources as bytes, or the
+    /// [errors raised by kernels](KernelError) that weren't returned yet.
+    ///
+    /// Kernel errors are kept by the server until they are returned by one of the `try_`
+    /// functions.
+    pub async fn try_read_async(
+        &self,
+        bindings: Vec<Binding>,
+    ) -> Result<Vec<Vec<u8>>, Vec<KernelError>> {
+        let data = self.channel.read(bindings).await;
+        self.check_errors().map(|_| data)
+    }
+
+    /// Given bindings, returns owned resources as bytes, or the
+    /// [errors raised by kernels](KernelError) that weren't returned yet.
+    ///
+    /// # Remarks
+    ///
+    /// Panics if the read operation fails.
+    pub fn try_read(&self, bindings: Vec<Binding>) -> Result<Vec<Vec<u8>>, Vec<KernelError>> {
+        let data = cubecl_common::reader::read_sync(self.channel.read(bindings));
+        self.check_errors().map(|_| data)
+    }
+
     /// Given a resource handle, returns the storage resource.
     pub fn get_resource(&self, binding: Binding) -> BindingResource<Server> {
         self.channel.get_resource(binding)
@@ -94,11 +176,44 @@ where
         self.channel.empty(size)
     }
 
+    /// Copies the bytes of the `src` binding to the start of the `dst` binding, without
+    /// launching a kernel.
+    ///
+    /// The destination must be at least as large as the source.
+    pub fn copy(&self, src: Binding, dst: Binding) {
+        self.channel.copy(self.stream, src, dst)
+    }
+
+    /// Fills the binding with the `pattern` of bytes repeated, without launching a kernel.
+    ///
+    /// The size of the binding must be a multiple of the length of the pattern.
+    pub fn fill(&self, binding: Binding, pattern: &[u8]) {
+        self.channel.fill(self.stream, binding, pattern)
+    }
+
+    /// Writes `data` to the binding starting `offset` bytes in, without reallocating it.
+    pub fn write(&self, binding: Binding, offset: u64, data: &[u8]) {
+        self.channel.write(self.stream, binding, offset, data)
+    }
+
     /// Executes the `kernel` over the given `bindings`.
     pub fn execute(&self, kernel: Server::Kernel, count: CubeCount, bindings: Vec<Binding>) {
+        self.execute_with_scalars(kernel, count, bindings, Vec::new())
+    }
+
+    /// Executes the `kernel` over the given `bindings`, which store its scalar arguments at the
+    /// given locations, so the [graphs](KernelGraph) capturing it can update them.
+    pub fn execute_with_scalars(
+        &self,
+        kernel: Server::Kernel,
+        count: CubeCount,
+        bindings: Vec<Binding>,
+        scalars: Vec<ScalarBinding>,
+    ) {
+        self.capture_launch(&kernel, &count, self.mode, &bindings, scalars);
         unsafe {
             self.channel
-                .execute(kernel, count, bindings, ExecutionMode::Checked)
+                .execute(self.stream, kernel, count, bindings, self.mode)
         }
     }
 
@@ -113,8 +228,89 @@ where
         count: CubeCount,
         bindings: Vec<Binding>,
     ) {
-        self.channel
-            .execute(kernel, count, bindings, ExecutionMode::Unchecked)
+        self.execute_unchecked_with_scalars(kernel, count, bindings, Vec::new())
+    }
+
+    /// Executes the `kernel` over the given `bindings` without performing any bound checks, like
+    /// [execute_with_scalars](Self::execute_with_scalars).
+    ///
+    /// # Safety
+    ///
+    /// Without checks, the out-of-bound reads and writes can happen.
+    pub unsafe fn execute_unchecked_with_scalars(
+        &self,
+        kernel: Server::Kernel,
+        count: CubeCount,
+        bindings: Vec<Binding>,
+        scalars: Vec<ScalarBinding>,
+    ) {
+        self.capture_launch(
+            &kernel,
+            &count,
+            ExecutionMode::Unchecked,
+            &bindings,
+            scalars,
+        );
+        self.channel.execute(
+            self.stream,
+            kernel,
+            count,
+            bindings,
+            ExecutionMode::Unchecked,
+        )
+    }
+
+    /// Capture the kernels executed by `func` into a [graph](KernelGraph), which executes them
+    /// again with a single call.
+    ///
+    /// The kernels are still executed once while they are captured. Every kernel executed on the
+    /// device is captured, including the ones executed by other clients, but copies, fills and
+    /// writes aren't.
+    ///
+    /// On servers without graphs, the graph executes the kernels captured by this client and its
+    /// clones again one by one instead.
+    pub fn capture(&self, func: impl FnOnce()) -> KernelGraph<Server, Channel> {
+        self.start_capture();
+        let guard = CaptureGuard { client: self };
+        func();
+        core::mem::forget(guard);
+
+        self.end_capture()
+    }
+
+    pub(crate) fn start_capture(&self) {
+        let mut capture = self.state.capture.lock();
+        assert!(capture.is_none(), "Kernels are already being captured");
+
+        *capture = Some(Vec::new());
+        self.channel.start_capture();
+    }
+
+    pub(crate) fn end_capture(&self) -> KernelGraph<Server, Channel> {
+        let mut capture = self.state.capture.lock();
+        let launches = capture.take().expect("Kernels should be captured");
+        let graph = self.channel.end_capture();
+
+        KernelGraph::new(self.channel.clone(), graph, launches)
+    }
+
+    fn capture_launch(
+        &self,
+        kernel: &Server::Kernel,
+        count: &CubeCount,
+        mode: ExecutionMode,
+        bindings: &[Binding],
+        scalars: Vec<ScalarBinding>,
+    ) {
+        if let Some(launches) = self.state.capture.lock().as_mut() {
+            launches.push(CapturedLaunch {
+                kernel: kernel.clone(),
+                count: count.clone(),
+                mode,
+                bindings: bindings.to_vec(),
+                scalars,
+            });
+        }
     }
 
     /// Flush all outstanding commands.
@@ -122,11 +318,27 @@ where
         self.channel.flush();
     }
 
-    /// Wait for the completion of every task in the server.
+    /// Wait for the completion of every task in the server, on every stream.
     pub async fn sync(&self) {
         self.channel.sync().await
     }
 
+    /// Wait for the completion of every task in the server, returning the
+    /// [errors raised by kernels](KernelError) that weren't returned yet.
+    pub async fn try_sync(&self) -> Result<(), Vec<KernelError>> {
+        self.channel.sync().await;
+        self.check_errors()
+    }
+
+    fn check_errors(&self) -> Result<(), Vec<KernelError>> {
+        let errors = self.channel.errors();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
     /// Wait for the completion of every task in the server.
     pub async fn sync_elapsed(&self) -> TimestampsResult {
         self.channel.sync_elapsed().await
@@ -173,6 +385,23 @@ where
         output
     }
 
+    /// Start timing each kernel executed on the device, until
+    /// [end_kernel_profile](Self::end_kernel_profile).
+    ///
+    /// Unlike [sync_elapsed](Self::sync_elapsed), which only measures the total time since the
+    /// last sync, every kernel is timed on its own, so the kernels dominating a workload can be
+    /// found. Timing each kernel slows the server down, so the total duration of a profile isn't
+    /// representative.
+    pub fn start_kernel_profile(&self) {
+        self.channel.start_profile();
+    }
+
+    /// Wait for the completion of every task in the server, and return the kernels executed since
+    /// [start_kernel_profile](Self::start_kernel_profile) with their device timings.
+    pub async fn end_kernel_profile(&self) -> ProfileResult {
+        self.channel.end_profile().await
+    }
+
     /// Enable timestamp collection on the server for performance profiling.
     ///
     /// This feature records precise timing data for server operations, which can be used
@@ -195,3 +424,19 @@ where
         self.channel.enable_timestamps();
     }
 }
+
+/// Ends the capture if the function capturing the kernels panics, so the client can capture
+/// again.
+struct CaptureGuard<'a, Server: ComputeServer, Channel: ComputeChannel<Server>> {
+    client: &'a ComputeClient<Server, Channel>,
+}
+
+impl<Server, Channel> Drop for CaptureGuard<'_, Server, Channel>
+where
+    Server: ComputeServer,
+    Channel: ComputeChannel<Server>,
+{
+    fn drop(&mut self) {
+        self.client.end_capture();
+    }
+}
@@ -265,6 +265,57 @@ impl DebugLogger {
     }
 }
 
+/// Where debug information is logged, as set by the `CUBECL_DEBUG_LOG` environment variable.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+enum DebugLogTarget {
+    /// The default log file, with `1` or `true`.
+    DefaultFile,
+    /// The file at the given path.
+    File(String),
+    /// Standard output, with `stdout`.
+    Stdout,
+}
+
+/// The target of debug logging, or none if it is disabled.
+///
+/// `CUBECL_DEBUG_LOG` is only read once, since kernels check it every time they are compiled.
+#[cfg(feature = "std")]
+fn debug_log_target() -> Option<&'static DebugLogTarget> {
+    static TARGET: std::sync::OnceLock<Option<DebugLogTarget>> = std::sync::OnceLock::new();
+
+    TARGET
+        .get_or_init(|| {
+            let flag = std::env::var("CUBECL_DEBUG_LOG").ok()?;
+
+            if let Ok(activated) = str::parse::<u8>(&flag) {
+                return (activated == 1).then_some(DebugLogTarget::DefaultFile);
+            }
+            if let Ok(activated) = str::parse::<bool>(&flag) {
+                return activated.then_some(DebugLogTarget::DefaultFile);
+            }
+
+            match flag.as_str() {
+                "stdout" => Some(DebugLogTarget::Stdout),
+                _ => Some(DebugLogTarget::File(flag)),
+            }
+        })
+        .as_ref()
+}
+
+/// Whether debug logging is enabled with `CUBECL_DEBUG_LOG`, in which case kernels are compiled
+/// with debug symbols such as the source location of their instructions.
+pub fn debug_symbols_enabled() -> bool {
+    #[cfg(feature = "std")]
+    {
+        debug_log_target().is_some()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        false
+    }
+}
+
 impl DebugLoggerKind {
     #[cfg(not(feature = "std"))]
     /// Create a new debug logger.
@@ -275,9 +326,9 @@ impl DebugLoggerKind {
     /// Create a new debug logger.
     #[cfg(feature = "std")]
     pub fn new() -> Self {
-        let flag = match std::env::var("CUBECL_DEBUG_LOG") {
-            Ok(val) => val,
-            Err(_) => return Self::None,
+        let target = match debug_log_target() {
+            Some(target) => target,
+            None => return Self::None,
         };
         let level = match std::env::var("CUBECL_DEBUG_OPTION") {
             Ok(val) => val,
@@ -312,26 +363,10 @@ impl DebugLoggerKind {
             DebugOptions::Debug
         };
 
-        if let Ok(activated) = str::parse::<u8>(&flag) {
-            if activated == 1 {
-                return Self::File(DebugFileLogger::new(None), option);
-            } else {
-                return Self::None;
-            }
-        };
-
-        if let Ok(activated) = str::parse::<bool>(&flag) {
-            if activated {
-                return Self::File(DebugFileLogger::new(None), option);
-            } else {
-                return Self::None;
-            }
-        };
-
-        if let "stdout" = flag.as_str() {
-            Self::Stdout(option)
-        } else {
-            Self::File(DebugFileLogger::new(Some(&flag)), option)
+        match target {
+            DebugLogTarget::DefaultFile => Self::File(DebugFileLogger::new(None), option),
+            DebugLogTarget::File(path) => Self::File(DebugFileLogger::new(Some(path)), option),
+            DebugLogTarget::Stdout => Self::Stdout(option),
         }
     }
 
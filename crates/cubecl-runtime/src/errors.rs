@@ -0,0 +1,120 @@
+use crate::server::KernelError;
+use alloc::{string::String, sync::Arc, vec::Vec};
+use core::hash::Hash;
+use hashbrown::HashMap;
+use spin::Mutex;
+
+/// The error buffers of the kernels that can raise [errors](KernelError), like failed
+/// `cube_assert!`.
+///
+/// Each kernel that can raise errors has its own zeroed buffer of
+/// [BUFFER_WORDS](KernelError::BUFFER_WORDS) words, bound after all other bindings. The server
+/// [reads](Self::read) and clears the buffers of the kernels launched since the last read on sync
+/// and read, and the errors are [collected](PendingErrors::collect) once the reads complete.
+///
+/// Servers only manage the buffers themselves, the key identifies the kernel a buffer belongs to.
+#[derive(Debug)]
+pub struct KernelErrorBuffers<Key, Buffer> {
+    buffers: HashMap<Key, ErrorBuffer<Buffer>>,
+    /// Kernels launched since the last read, in launch order.
+    launched: Vec<Key>,
+    raised: Arc<Mutex<Vec<KernelError>>>,
+}
+
+#[derive(Debug)]
+struct ErrorBuffer<Buffer> {
+    name: String,
+    buffer: Buffer,
+}
+
+/// The error buffers being read by a server, whose errors are collected once the reads complete.
+#[derive(Debug)]
+pub struct PendingErrors {
+    names: Vec<String>,
+    raised: Arc<Mutex<Vec<KernelError>>>,
+}
+
+impl<Key, Buffer> Default for KernelErrorBuffers<Key, Buffer> {
+    fn default() -> Self {
+        Self {
+            buffers: HashMap::new(),
+            launched: Vec::new(),
+            raised: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+impl<Key: Hash + Eq + Clone, Buffer> KernelErrorBuffers<Key, Buffer> {
+    /// Size of an error buffer, in bytes.
+    pub const BUFFER_SIZE: usize = KernelError::BUFFER_WORDS * core::mem::size_of::<u32>();
+
+    /// Register the zeroed error buffer of a kernel that can raise errors.
+    pub fn register(&mut self, key: Key, name: impl Into<String>, buffer: Buffer) {
+        self.buffers.insert(
+            key,
+            ErrorBuffer {
+                name: name.into(),
+                buffer,
+            },
+        );
+    }
+
+    /// Whether the kernel has a registered error buffer.
+    pub fn contains(&self, key: &Key) -> bool {
+        self.buffers.contains_key(key)
+    }
+
+    /// The buffer to bind when launching the kernel, if it can raise errors.
+    pub fn launch(&mut self, key: &Key) -> Option<&Buffer> {
+        let errors = self.buffers.get(key)?;
+        if !self.launched.contains(key) {
+            self.launched.push(key.clone());
+        }
+
+        Some(&errors.buffer)
+    }
+
+    /// Take the buffers of the kernels launched since the last read, in launch order.
+    ///
+    /// The server must read and clear them, then pass the words read to
+    /// [collect](PendingErrors::collect) in the same order.
+    pub fn read(&mut self) -> (Vec<&Buffer>, PendingErrors) {
+        let launched = core::mem::take(&mut self.launched)
+            .into_iter()
+            .map(|key| &self.buffers[&key])
+            .collect::<Vec<_>>();
+        let names = launched.iter().map(|errors| errors.name.clone()).collect();
+        let buffers = launched.into_iter().map(|errors| &errors.buffer).collect();
+
+        (
+            buffers,
+            PendingErrors {
+                names,
+                raised: self.raised.clone(),
+            },
+        )
+    }
+
+    /// Take the errors collected so far.
+    pub fn take(&mut self) -> Vec<KernelError> {
+        core::mem::take(&mut *self.raised.lock())
+    }
+}
+
+impl PendingErrors {
+    /// Whether no buffer is being read.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Decode the words read from the buffers, in the order they were returned by
+    /// [read](KernelErrorBuffers::read).
+    pub fn collect<Words: AsRef<[u32]>>(self, words: impl IntoIterator<Item = Words>) {
+        let errors = self
+            .names
+            .into_iter()
+            .zip(words)
+            .filter_map(|(name, words)| KernelError::from_words(name, words.as_ref()));
+        KernelError::extend_pending(&mut self.raised.lock(), errors);
+    }
+}
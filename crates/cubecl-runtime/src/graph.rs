@@ -0,0 +1,163 @@
+use crate::channel::ComputeChannel;
+use crate::server::{Binding, ComputeServer, CubeCount, GraphId, StreamId};
+use crate::ExecutionMode;
+use alloc::vec::Vec;
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+/// The kernels [captured](crate::client::ComputeClient::capture) by a client, executed again
+/// with a single call to [replay](KernelGraph::replay).
+///
+/// Kernels are replayed over the bindings they were captured with, which the graph keeps alive,
+/// so their inputs can be changed between replays by [writing](KernelGraph::update) to those
+/// bindings. The scalar arguments of the kernels executed with their
+/// [location](ScalarBinding), like the ones launched by cubecl-core, can be
+/// [updated](KernelGraph::update_scalar) as well.
+///
+/// Servers without graphs execute the captured kernels again one by one.
+///
+/// The graph is freed when dropped.
+#[derive(Debug)]
+pub struct KernelGraph<Server: ComputeServer, Channel: ComputeChannel<Server>> {
+    channel: Channel,
+    /// The graph of the server, or `None` if it doesn't capture graphs.
+    graph: Option<GraphId>,
+    launches: Vec<CapturedLaunch<Server>>,
+    _server: PhantomData<fn() -> Server>,
+}
+
+impl<Server, Channel> KernelGraph<Server, Channel>
+where
+    Server: ComputeServer,
+    Channel: ComputeChannel<Server>,
+{
+    pub(crate) fn new(
+        channel: Channel,
+        graph: Option<GraphId>,
+        launches: Vec<CapturedLaunch<Server>>,
+    ) -> Self {
+        Self {
+            channel,
+            graph,
+            launches,
+            _server: PhantomData,
+        }
+    }
+
+    /// Execute the captured kernels again, in the order they were captured, on the default
+    /// stream.
+    pub fn replay(&self) {
+        let Some(graph) = self.graph else {
+            for launch in self.launches.iter() {
+                unsafe {
+                    self.channel.execute(
+                        StreamId::default(),
+                        launch.kernel.clone(),
+                        launch.count.clone(),
+                        launch.bindings.clone(),
+                        launch.mode,
+                    )
+                }
+            }
+            return;
+        };
+
+        self.channel.replay_graph(graph)
+    }
+
+    /// The number of captured kernels.
+    pub fn num_launches(&self) -> usize {
+        self.launches.len()
+    }
+
+    /// The bindings the kernel at the given index of the graph was captured with.
+    pub fn bindings(&self, launch: usize) -> &[Binding] {
+        &self.launches[launch].bindings
+    }
+
+    /// Write `data` to the start of a binding of a captured kernel, on the default stream, so the
+    /// next replays read it.
+    pub fn update(&self, launch: usize, binding: usize, data: &[u8]) {
+        let binding = self.launches[launch].bindings[binding].clone();
+        self.channel.write(StreamId::default(), binding, 0, data)
+    }
+
+    /// Set the scalar argument at the given index of a captured kernel, on the default stream, so
+    /// the next replays read it.
+    ///
+    /// Scalars are indexed in the order of the arguments of the kernel.
+    pub fn update_scalar<T: bytemuck::NoUninit>(&self, launch: usize, index: usize, value: T) {
+        let launch_scalars = &self.launches[launch].scalars;
+        let scalar = launch_scalars.get(index).unwrap_or_else(|| {
+            panic!(
+                "Kernel {launch} has {} scalars, not {index}",
+                launch_scalars.len()
+            )
+        });
+        assert!(
+            scalar.ty == TypeId::of::<T>(),
+            "Scalar {index} of kernel {launch} isn't a {}",
+            core::any::type_name::<T>()
+        );
+
+        let binding = self.launches[launch].bindings[scalar.binding].clone();
+        self.channel.write(
+            StreamId::default(),
+            binding,
+            scalar.offset,
+            bytemuck::bytes_of(&value),
+        )
+    }
+}
+
+/// A captured kernel, with the bindings it was captured with.
+pub(crate) struct CapturedLaunch<Server: ComputeServer> {
+    pub(crate) kernel: Server::Kernel,
+    pub(crate) count: CubeCount,
+    pub(crate) mode: ExecutionMode,
+    pub(crate) bindings: Vec<Binding>,
+    pub(crate) scalars: Vec<ScalarBinding>,
+}
+
+impl<Server: ComputeServer> core::fmt::Debug for CapturedLaunch<Server> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CapturedLaunch")
+            .field("count", &self.count)
+            .field("mode", &self.mode)
+            .field("bindings", &self.bindings)
+            .field("scalars", &self.scalars)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Where a scalar argument of a kernel is stored in its bindings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScalarBinding {
+    binding: usize,
+    offset: u64,
+    ty: TypeId,
+}
+
+impl ScalarBinding {
+    /// A scalar of type `T`, at the given index of an array of `T` stored in the binding at the
+    /// given index.
+    pub fn new<T: 'static>(binding: usize, index: usize) -> Self {
+        Self {
+            binding,
+            offset: (index * core::mem::size_of::<T>()) as u64,
+            ty: TypeId::of::<T>(),
+        }
+    }
+}
+
+impl<Server, Channel> Drop for KernelGraph<Server, Channel>
+where
+    Server: ComputeServer,
+    Channel: ComputeChannel<Server>,
+{
+    fn drop(&mut self) {
+        if let Some(graph) = self.graph {
+            self.channel.free_graph(graph)
+        }
+    }
+}
@@ -14,13 +14,19 @@ mod id;
 pub mod channel;
 /// Compute client module.
 pub mod client;
+/// Kernel error buffers module.
+pub mod errors;
 
 /// Autotune module
 #[cfg(feature = "channel-mpsc")]
 pub mod tune;
 
+/// Kernel graph module.
+pub mod graph;
 /// Memory management module.
 pub mod memory_management;
+/// Kernel profiling module.
+pub mod profile;
 /// Compute server module.
 pub mod server;
 /// Compute Storage module.
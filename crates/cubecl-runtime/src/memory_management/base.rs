@@ -1,9 +1,11 @@
 #[cfg(not(feature = "std"))]
 use alloc::{format, string::String};
+use serde::{Deserialize, Serialize};
 
 /// Amount of memory in use by this allocator
 /// and statistics on how much memory is reserved and
 /// wasted in total.
+#[derive(Serialize, Deserialize)]
 pub struct MemoryUsage {
     /// The number of allocations currently active.
     pub number_allocs: u64,
@@ -2,9 +2,11 @@ pub(crate) mod memory_pool;
 
 mod base;
 mod memory_lock;
+mod stream_memory;
 
 pub use base::*;
 pub use memory_lock::*;
+pub use stream_memory::*;
 
 /// Dynamic memory management strategy.
 mod memory_manage;
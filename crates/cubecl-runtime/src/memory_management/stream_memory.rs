@@ -0,0 +1,132 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::server::{Binding, StreamId};
+
+/// The memory used by the tasks enqueued to streams other than the default one.
+///
+/// Each binding is kept alive with the event recorded on its stream after its last use, so
+/// the memory pools can't reuse it, even if its handle is dropped, until that event is
+/// reached.
+#[derive(Debug)]
+pub struct StreamMemory<E> {
+    pending: VecDeque<PendingUse<E>>,
+}
+
+#[derive(Debug)]
+struct PendingUse<E> {
+    stream: StreamId,
+    event: E,
+    // Only held to keep the memory in use.
+    _bindings: Vec<Binding>,
+}
+
+impl<E> Default for StreamMemory<E> {
+    fn default() -> Self {
+        Self {
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+impl<E> StreamMemory<E> {
+    /// Keep the memory of the bindings alive until the event, recorded on the stream after the tasks using
+    /// them, is reached.
+    pub fn register(&mut self, stream: StreamId, event: E, bindings: Vec<Binding>) {
+        self.pending.push_back(PendingUse {
+            stream,
+            event,
+            _bindings: bindings,
+        });
+    }
+
+    /// Release the memory whose event is reached, returning those events.
+    ///
+    /// Events of the same stream are reached in the order they were registered, so only the
+    /// first pending event of each stream is checked.
+    pub fn release(&mut self, mut is_reached: impl FnMut(&E) -> bool) -> Vec<E> {
+        let mut blocked = Vec::new();
+        let mut released = Vec::new();
+        let mut pending = VecDeque::with_capacity(self.pending.len());
+
+        for item in self.pending.drain(..) {
+            if !blocked.contains(&item.stream) && is_reached(&item.event) {
+                released.push(item.event);
+            } else {
+                blocked.push(item.stream);
+                pending.push_back(item);
+            }
+        }
+        self.pending = pending;
+
+        released
+    }
+
+    /// Release all the memory, returning all the events.
+    ///
+    /// Only valid once all the streams are synchronized.
+    pub fn release_all(&mut self) -> Vec<E> {
+        self.pending.drain(..).map(|item| item.event).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory_management::{MemoryManagement, MemoryPoolOptions, PoolType};
+    use crate::storage::BytesStorage;
+
+    fn memory_management() -> MemoryManagement<BytesStorage> {
+        MemoryManagement::new(
+            BytesStorage::default(),
+            vec![MemoryPoolOptions {
+                page_size: 512,
+                chunk_num_prealloc: 0,
+                pool_type: PoolType::ExclusivePages,
+                dealloc_period: None,
+            }],
+            32,
+        )
+    }
+
+    #[test]
+    fn pending_slices_are_not_reused() {
+        let mut memory_management = memory_management();
+        let mut stream_memory = StreamMemory::default();
+        let stream = StreamId { value: 1 };
+
+        let handle = memory_management.reserve(512, None);
+        stream_memory.register(
+            stream,
+            0,
+            vec![Binding {
+                memory: handle.clone().binding(),
+                offset_start: None,
+                offset_end: None,
+            }],
+        );
+        drop(handle);
+
+        let _other = memory_management.reserve(512, None);
+        assert_eq!(memory_management.memory_usage().bytes_reserved, 1024);
+
+        assert_eq!(stream_memory.release(|event| *event == 0), vec![0]);
+        let _reused = memory_management.reserve(512, None);
+        assert_eq!(memory_management.memory_usage().bytes_reserved, 1024);
+    }
+
+    #[test]
+    fn release_follows_stream_order() {
+        let mut stream_memory = StreamMemory::default();
+        let first = StreamId { value: 1 };
+        let second = StreamId { value: 2 };
+
+        stream_memory.register(first, 0, Vec::new());
+        stream_memory.register(first, 1, Vec::new());
+        stream_memory.register(second, 2, Vec::new());
+
+        assert_eq!(stream_memory.release(|event| *event != 0), vec![2]);
+        assert_eq!(stream_memory.release(|_| true), vec![0, 1]);
+        assert!(stream_memory.release_all().is_empty());
+    }
+}
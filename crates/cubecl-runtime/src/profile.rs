@@ -0,0 +1,168 @@
+use crate::server::CubeCount;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::time::Duration;
+use cubecl_common::benchmark::TimestampsError;
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// The launch of a kernel, grouping the kernels of a [profile](KernelProfile).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct KernelLaunch {
+    /// The name of the kernel.
+    pub name: String,
+    /// The number of cubes of the launch, or `None` if it was read from a buffer.
+    pub cube_count: Option<(u32, u32, u32)>,
+    /// The number of units in each cube.
+    pub cube_dim: (u32, u32, u32),
+}
+
+/// A kernel executed while profiling, timed on the device.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfiledKernel {
+    /// How the kernel was launched.
+    pub launch: KernelLaunch,
+    /// When the kernel started, since the start of the first kernel of the profile.
+    pub start: Duration,
+    /// How long the kernel ran.
+    pub duration: Duration,
+}
+
+/// The kernels executed by a server while profiling, in the order they were executed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct KernelProfile {
+    /// The profiled kernels.
+    pub kernels: Vec<ProfiledKernel>,
+}
+
+/// Result when profiling kernels.
+pub type ProfileResult = Result<KernelProfile, TimestampsError>;
+
+/// The kernels of a [profile](KernelProfile) with the same launch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KernelSummary {
+    /// The launch shared by the kernels.
+    pub launch: KernelLaunch,
+    /// The number of kernels.
+    pub count: usize,
+    /// The total duration of the kernels.
+    pub total: Duration,
+}
+
+impl KernelLaunch {
+    /// Create the launch of the kernel with the given name.
+    pub fn new(name: impl Into<String>, count: &CubeCount, cube_dim: (u32, u32, u32)) -> Self {
+        let cube_count = match count {
+            CubeCount::Static(x, y, z) => Some((*x, *y, *z)),
+            CubeCount::Dynamic(_) => None,
+        };
+
+        Self {
+            name: name.into(),
+            cube_count,
+            cube_dim,
+        }
+    }
+}
+
+impl KernelProfile {
+    /// The total duration of the kernels of each launch, sorted from the longest.
+    pub fn summary(&self) -> Vec<KernelSummary> {
+        let mut summaries = HashMap::<&KernelLaunch, KernelSummary>::new();
+
+        for kernel in self.kernels.iter() {
+            let summary = summaries
+                .entry(&kernel.launch)
+                .or_insert_with(|| KernelSummary {
+                    launch: kernel.launch.clone(),
+                    count: 0,
+                    total: Duration::ZERO,
+                });
+            summary.count += 1;
+            summary.total += kernel.duration;
+        }
+
+        let mut summaries: Vec<_> = summaries.into_values().collect();
+        summaries.sort_by_key(|summary| core::cmp::Reverse(summary.total));
+        summaries
+    }
+
+    /// Export the profile in the Chrome trace event format, which can be opened in
+    /// `chrome://tracing` or [Perfetto](https://ui.perfetto.dev).
+    pub fn to_chrome_trace(&self) -> String {
+        let events: Vec<_> = self
+            .kernels
+            .iter()
+            .map(|kernel| {
+                let launch = &kernel.launch;
+                let cube_count = match launch.cube_count {
+                    Some(count) => alloc::format!("{count:?}"),
+                    None => "dynamic".into(),
+                };
+
+                serde_json::json!({
+                    "name": launch.name,
+                    "cat": "kernel",
+                    "ph": "X",
+                    "ts": kernel.start.as_nanos() as f64 / 1000.,
+                    "dur": kernel.duration.as_nanos() as f64 / 1000.,
+                    "pid": 0,
+                    "tid": 0,
+                    "args": {
+                        "cube_count": cube_count,
+                        "cube_dim": alloc::format!("{:?}", launch.cube_dim),
+                    },
+                })
+            })
+            .collect();
+
+        serde_json::json!({ "traceEvents": events }).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn kernel(name: &str, start: u64, duration: u64) -> ProfiledKernel {
+        ProfiledKernel {
+            launch: KernelLaunch::new(name, &CubeCount::Static(4, 1, 1), (32, 8, 1)),
+            start: Duration::from_micros(start),
+            duration: Duration::from_micros(duration),
+        }
+    }
+
+    #[test]
+    fn summary_groups_kernels_by_launch() {
+        let profile = KernelProfile {
+            kernels: vec![kernel("a", 0, 5), kernel("b", 5, 20), kernel("a", 25, 10)],
+        };
+
+        let summary = profile.summary();
+
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].launch.name, "b");
+        assert_eq!(summary[0].total, Duration::from_micros(20));
+        assert_eq!(summary[1].launch.name, "a");
+        assert_eq!(summary[1].count, 2);
+        assert_eq!(summary[1].total, Duration::from_micros(15));
+    }
+
+    #[test]
+    fn chrome_trace_has_an_event_per_kernel() {
+        let profile = KernelProfile {
+            kernels: vec![kernel("a", 0, 5), kernel("b", 5, 20)],
+        };
+
+        let trace: serde_json::Value = serde_json::from_str(&profile.to_chrome_trace()).unwrap();
+        let events = trace["traceEvents"].as_array().unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1]["name"], "b");
+        assert_eq!(events[1]["ph"], "X");
+        assert_eq!(events[1]["ts"], 5.);
+        assert_eq!(events[1]["dur"], 20.);
+        assert_eq!(events[1]["args"]["cube_count"], "(4, 1, 1)");
+    }
+}
@@ -1,3 +1,4 @@
+use crate::profile::ProfileResult;
 use crate::{
     memory_management::{
         memory_pool::{SliceBinding, SliceHandle},
@@ -6,9 +7,14 @@ use crate::{
     storage::{BindingResource, ComputeStorage},
     ExecutionMode,
 };
+use alloc::string::String;
 use alloc::vec::Vec;
-use core::{fmt::Debug, future::Future};
-use cubecl_common::benchmark::TimestampsResult;
+use core::{
+    fmt::{Debug, Display},
+    future::Future,
+};
+use cubecl_common::benchmark::{TimestampsError, TimestampsResult};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 /// The compute server is responsible for handling resources and computations over resources.
 ///
@@ -19,7 +25,9 @@ where
     Self: Sized,
 {
     /// The kernel type defines the computation algorithms.
-    type Kernel: Send;
+    ///
+    /// Kernels are cloned by the client while they are [captured](Self::start_capture).
+    type Kernel: Send + Clone;
     /// The [storage](ComputeStorage) type defines how data is stored and accessed.
     type Storage: ComputeStorage;
     /// The type of the features supported by the server.
@@ -40,6 +48,20 @@ where
     /// Reserves `size` bytes in the storage, and returns a handle over them.
     fn empty(&mut self, size: usize) -> Handle;
 
+    /// Copies the bytes of the `src` binding to the start of the `dst` binding, after the
+    /// tasks already submitted.
+    ///
+    /// The destination must be at least as large as the source.
+    fn copy(&mut self, src: Binding, dst: Binding);
+
+    /// Fills the binding with the `pattern` of bytes repeated, after the tasks already submitted.
+    ///
+    /// The size of the binding must be a multiple of the length of the pattern.
+    fn fill(&mut self, binding: Binding, pattern: &[u8]);
+
+    /// Writes `data` to the binding starting `offset` bytes in, after the tasks already submitted.
+    fn write(&mut self, binding: Binding, offset: u64, data: &[u8]);
+
     /// Executes the `kernel` over the given memory `handles`.
     ///
     /// Kernels have mutable access to every resource they are given
@@ -56,10 +78,95 @@ where
         kind: ExecutionMode,
     );
 
+    /// Create a stream, on which tasks can execute concurrently with the tasks of the other
+    /// streams.
+    ///
+    /// Servers with a single queue return the [default stream](StreamId::default) and execute
+    /// every task in the order it was submitted, which trivially satisfies every event.
+    fn create_stream(&mut self) -> StreamId {
+        StreamId::default()
+    }
+
+    /// Copies the bytes of the `src` binding to the start of the `dst` binding, after the
+    /// tasks already submitted to the stream.
+    #[allow(unused_variables)]
+    fn copy_on(&mut self, stream: StreamId, src: Binding, dst: Binding) {
+        self.copy(src, dst)
+    }
+
+    /// Fills the binding with the `pattern` of bytes repeated, after the tasks already submitted
+    /// to the stream.
+    #[allow(unused_variables)]
+    fn fill_on(&mut self, stream: StreamId, binding: Binding, pattern: &[u8]) {
+        self.fill(binding, pattern)
+    }
+
+    /// Writes `data` to the binding starting `offset` bytes in, after the tasks already submitted
+    /// to the stream.
+    #[allow(unused_variables)]
+    fn write_on(&mut self, stream: StreamId, binding: Binding, offset: u64, data: &[u8]) {
+        self.write(binding, offset, data)
+    }
+
+    /// Executes the `kernel` over the given memory `handles`, after the tasks already submitted
+    /// to the stream.
+    ///
+    /// # Safety
+    ///
+    /// When executing with mode [ExecutionMode::Unchecked], out-of-bound reads and writes can happen.
+    #[allow(unused_variables)]
+    unsafe fn execute_on(
+        &mut self,
+        stream: StreamId,
+        kernel: Self::Kernel,
+        count: CubeCount,
+        bindings: Vec<Binding>,
+        kind: ExecutionMode,
+    ) {
+        self.execute(kernel, count, bindings, kind)
+    }
+
+    /// Record an event on the stream, reached once the tasks already submitted to it complete.
+    #[allow(unused_variables)]
+    fn record_event(&mut self, stream: StreamId) -> EventId {
+        EventId::default()
+    }
+
+    /// Make the tasks submitted to the stream after this call wait until the event is reached.
+    ///
+    /// Events freed by a [sync](ComputeServer::sync) are already reached, so waiting on them
+    /// does nothing.
+    #[allow(unused_variables)]
+    fn wait_event(&mut self, stream: StreamId, event: EventId) {}
+
+    /// Start capturing the kernels executed by the server into a graph. Kernels are still
+    /// executed while they are captured.
+    ///
+    /// Servers without graphs don't capture anything, and the client replays the launches it
+    /// captured one by one instead.
+    fn start_capture(&mut self) {}
+
+    /// Stop capturing, returning the graph of the kernels executed since the capture started, or
+    /// `None` if the server doesn't capture graphs.
+    fn end_capture(&mut self) -> Option<GraphId> {
+        None
+    }
+
+    /// Execute the kernels of the graph again on the default stream, in the order they were
+    /// captured and over the bindings they were captured with.
+    ///
+    /// Only called with graphs returned by [end_capture](Self::end_capture).
+    #[allow(unused_variables)]
+    fn replay_graph(&mut self, graph: GraphId) {}
+
+    /// Free the graph, and the bindings it keeps alive.
+    #[allow(unused_variables)]
+    fn free_graph(&mut self, graph: GraphId) {}
+
     /// Flush all outstanding tasks in the server.
     fn flush(&mut self);
 
-    /// Wait for the completion of every task in the server.
+    /// Wait for the completion of every task in the server, on every stream.
     fn sync(&mut self) -> impl Future<Output = ()> + Send + 'static;
 
     /// Wait for the completion of every task in the server.
@@ -75,8 +182,244 @@ where
 
     /// Disable collecting timestamps.
     fn disable_timestamps(&mut self);
+
+    /// Start timing each kernel executed on the device, until [end_profile](Self::end_profile).
+    fn start_profile(&mut self) {}
+
+    /// Wait for the completion of every task in the server, and return the kernels executed since
+    /// [start_profile](Self::start_profile) with their device timings.
+    fn end_profile(&mut self) -> impl Future<Output = ProfileResult> + Send + 'static {
+        let fut = self.sync();
+
+        async move {
+            fut.await;
+            Err(TimestampsError::Unavailable)
+        }
+    }
+
+    /// Take the errors raised by kernels that completed before the last sync or read.
+    ///
+    /// Servers keep at most [MAX_PENDING_ERRORS](KernelError::MAX_PENDING_ERRORS) errors until
+    /// they are taken, dropping the following ones.
+    fn errors(&mut self) -> Vec<KernelError> {
+        Vec::new()
+    }
+}
+
+/// Identifier of a stream of a [compute server](ComputeServer), on which tasks execute in the
+/// order they were submitted.
+///
+/// Tasks of different streams can run concurrently, unless a stream
+/// [waits](ComputeServer::wait_event) on an event recorded on another one. Handles are created,
+/// read and freed on the default stream, so:
+///
+/// - A stream must wait on an event recorded on the default stream after the creation of the
+///   handles it uses.
+/// - The default stream must wait on an event recorded on the stream before reading the outputs
+///   of its tasks.
+///
+/// The memory used by the tasks of a stream isn't reused before those tasks are completed, even
+/// if their handles are dropped.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub struct StreamId {
+    /// The index of the stream in its server, where `0` is the default stream.
+    pub value: u32,
 }
 
+/// Identifier of an event [recorded](ComputeServer::record_event) on a stream.
+///
+/// Events live until the next [sync](ComputeServer::sync) of their server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EventId {
+    /// The index of the event in its server.
+    pub value: u64,
+}
+
+/// Identifier of a graph of kernels [captured](ComputeServer::start_capture) by a server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GraphId {
+    /// The index of the graph in its server.
+    pub value: u64,
+}
+
+/// A [compute server](ComputeServer) whose kernels can be recorded in a trace and replayed,
+/// possibly by another server with the same kind of records.
+pub trait RecordableServer: ComputeServer {
+    /// The serializable form of a kernel.
+    type KernelRecord: Serialize + DeserializeOwned + Send;
+    /// Identifies kernels with the same record, which is only recorded once.
+    type KernelKey: core::hash::Hash + Eq + Send;
+
+    /// The key of a kernel.
+    fn kernel_key(kernel: &Self::Kernel) -> Self::KernelKey;
+
+    /// Record a kernel, returning `None` if it can't be recorded.
+    fn record_kernel(kernel: &Self::Kernel) -> Option<Self::KernelRecord>;
+
+    /// Recreate a recorded kernel.
+    fn replay_kernel(record: Self::KernelRecord) -> Self::Kernel;
+}
+
+/// An error raised by a kernel at runtime, for example by a failed `cube_assert!`.
+///
+/// Only the first error raised during a launch is recorded, along with the number of errors.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KernelError {
+    /// The name of the kernel that raised the error, or why the device was
+    /// [lost](KernelErrorKind::DeviceLost).
+    pub kernel: String,
+    /// What went wrong.
+    pub kind: KernelErrorKind,
+    /// The position of the unit that raised the error in its cube.
+    pub unit_pos: u32,
+    /// The position of the cube that raised the error.
+    pub cube_pos: (u32, u32, u32),
+    /// The number of errors raised during the launch.
+    pub count: u32,
+}
+
+/// The kind of a [kernel error](KernelError).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KernelErrorKind {
+    /// An assertion failed, with the code given to `cube_assert!`.
+    Assertion {
+        /// The code of the assertion.
+        code: u32,
+    },
+    /// An index was out of bounds, in [strict](crate::ExecutionMode::Strict) mode.
+    OutOfBounds {
+        /// The position of the indexed binding among the inputs and outputs of the kernel, or
+        /// `None` if the indexed array isn't a binding, like a slice.
+        binding: Option<u32>,
+        /// The index that was accessed.
+        index: u32,
+        /// The length of the indexed array.
+        length: u32,
+    },
+    /// The device was lost, for example because the process of an
+    /// [IPC compute server](crate::channel::IpcComputeChannel) crashed. The operations sent since
+    /// then were dropped.
+    DeviceLost,
+    /// The error buffer held a tag that isn't known, for example because the kernel was compiled
+    /// by a newer compiler.
+    Unknown {
+        /// The tag written in the error buffer.
+        tag: u32,
+    },
+}
+
+impl KernelErrorKind {
+    /// The tag of assertions in the error buffer.
+    pub const ASSERTION: u32 = 0;
+    /// The tag of out-of-bounds accesses in the error buffer.
+    pub const OUT_OF_BOUNDS: u32 = 1;
+    /// The binding written in the error buffer when the indexed array isn't a binding.
+    pub const NO_BINDING: u32 = u32::MAX;
+}
+
+impl KernelError {
+    /// The number of `u32` words in the error buffer of a kernel.
+    ///
+    /// The buffer holds the number of errors raised, followed by the tag of the first error, the
+    /// unit position, the x, y and z cube position and up to three arguments depending on the
+    /// kind of the error. It must be zeroed before the launch.
+    pub const BUFFER_WORDS: usize = 9;
+
+    /// The maximum number of errors kept by a server until they are taken.
+    pub const MAX_PENDING_ERRORS: usize = 32;
+
+    /// Add errors to the pending errors of a server, dropping them once
+    /// [MAX_PENDING_ERRORS](Self::MAX_PENDING_ERRORS) errors are pending.
+    pub fn extend_pending(pending: &mut Vec<Self>, errors: impl IntoIterator<Item = Self>) {
+        let free = Self::MAX_PENDING_ERRORS.saturating_sub(pending.len());
+        pending.extend(errors.into_iter().take(free));
+    }
+
+    /// The error reported once the device was lost, for the given reason.
+    pub fn device_lost(reason: impl Into<String>) -> Self {
+        Self {
+            kernel: reason.into(),
+            kind: KernelErrorKind::DeviceLost,
+            unit_pos: 0,
+            cube_pos: (0, 0, 0),
+            count: 1,
+        }
+    }
+
+    /// Decode the error buffer of a kernel, returning `None` if no error was raised.
+    pub fn from_words(kernel: impl Into<String>, words: &[u32]) -> Option<Self> {
+        let [count, tag, unit_pos, x, y, z, arg0, arg1, arg2] =
+            words.get(..Self::BUFFER_WORDS)?.try_into().ok()?;
+        if count == 0 {
+            return None;
+        }
+
+        let kind = match tag {
+            KernelErrorKind::ASSERTION => KernelErrorKind::Assertion { code: arg0 },
+            KernelErrorKind::OUT_OF_BOUNDS => KernelErrorKind::OutOfBounds {
+                binding: (arg0 != KernelErrorKind::NO_BINDING).then_some(arg0),
+                index: arg1,
+                length: arg2,
+            },
+            tag => KernelErrorKind::Unknown { tag },
+        };
+
+        Some(Self {
+            kernel: kernel.into(),
+            kind,
+            unit_pos,
+            cube_pos: (x, y, z),
+            count,
+        })
+    }
+}
+
+impl Display for KernelErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            KernelErrorKind::Assertion { code } => write!(f, "Assertion failed with code {code}"),
+            KernelErrorKind::OutOfBounds {
+                binding: Some(binding),
+                index,
+                length,
+            } => write!(
+                f,
+                "Index {index} out of bounds for binding {binding} of length {length}"
+            ),
+            KernelErrorKind::OutOfBounds {
+                binding: None,
+                index,
+                length,
+            } => write!(f, "Index {index} out of bounds for length {length}"),
+            KernelErrorKind::DeviceLost => write!(f, "The device was lost"),
+            KernelErrorKind::Unknown { tag } => write!(f, "Unknown error with tag {tag}"),
+        }
+    }
+}
+
+impl Display for KernelError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if let KernelErrorKind::DeviceLost = self.kind {
+            return write!(f, "{}: {}", self.kind, self.kernel);
+        }
+        let (x, y, z) = self.cube_pos;
+        write!(
+            f,
+            "{} in kernel {} at unit {} of cube ({x}, {y}, {z})",
+            self.kind, self.kernel, self.unit_pos
+        )?;
+        if self.count > 1 {
+            write!(f, " ({} errors raised)", self.count)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for KernelError {}
+
 /// Server handle containing the [memory handle](MemoryManagement::Handle).
 #[derive(new, Debug)]
 pub struct Handle {
@@ -168,6 +511,17 @@ impl Clone for Binding {
     }
 }
 
+/// Repeats the `pattern` of a [fill](ComputeServer::fill) over `size` bytes.
+pub fn fill_pattern(pattern: &[u8], size: usize) -> Vec<u8> {
+    assert!(
+        !pattern.is_empty() && size % pattern.len() == 0,
+        "Can't fill {size} bytes with a pattern of {} bytes",
+        pattern.len()
+    );
+
+    pattern.iter().copied().cycle().take(size).collect()
+}
+
 /// Specifieds the number of cubes to be dispatched for a kernel.
 ///
 /// This translates to eg. a grid for CUDA, or to num_workgroups for wgsl.
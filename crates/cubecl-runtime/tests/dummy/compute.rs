@@ -1,4 +1,5 @@
 use super::DummyServer;
+use cubecl_runtime::channel::ComputeChannel;
 use cubecl_runtime::client::ComputeClient;
 use cubecl_runtime::memory_management::{
     MemoryConfiguration, MemoryDeviceProperties, MemoryManagement,
@@ -28,6 +29,12 @@ pub fn autotune_execute(
 }
 
 pub fn init_client() -> ComputeClient<DummyServer, MutexComputeChannel<DummyServer>> {
+    init_client_with(MutexComputeChannel::new)
+}
+
+pub fn init_client_with<Channel: ComputeChannel<DummyServer>>(
+    channel: impl FnOnce(DummyServer) -> Channel,
+) -> ComputeClient<DummyServer, Channel> {
     let storage = BytesStorage::default();
     let mem_properties = MemoryDeviceProperties {
         max_page_size: 1024 * 1024 * 512,
@@ -44,9 +51,8 @@ pub fn init_client() -> ComputeClient<DummyServer, MutexComputeChannel<DummyServ
         MemoryConfiguration::default(),
     );
     let server = DummyServer::new(memory_management);
-    let channel = MutexComputeChannel::new(server);
     ComputeClient::new(
-        channel,
+        channel(server),
         DeviceProperties::new(&[], mem_properties, topology),
     )
 }
@@ -1,8 +1,13 @@
-use cubecl_runtime::storage::BytesResource;
+use cubecl_runtime::{server::KernelErrorKind, storage::BytesResource};
 
 /// The DummyKernel trait should be implemented for every supported operation
 pub trait DummyKernel: Sync + Send + 'static + core::fmt::Debug {
     fn compute(&self, resources: &mut [&BytesResource]);
+
+    /// The error raised by the kernel, if any.
+    fn error(&self) -> Option<KernelErrorKind> {
+        None
+    }
 }
 
 /// Contains the algorithm for element-wise addition
@@ -24,3 +29,15 @@ impl DummyKernel for DummyElementwiseAddition {
         }
     }
 }
+
+/// Raises an assertion error without computing anything.
+#[derive(Debug)]
+pub struct DummyAssertion;
+
+impl DummyKernel for DummyAssertion {
+    fn compute(&self, _inputs: &mut [&BytesResource]) {}
+
+    fn error(&self) -> Option<KernelErrorKind> {
+        Some(KernelErrorKind::Assertion { code: 1 })
+    }
+}
@@ -3,13 +3,16 @@ use std::future::Future;
 use std::sync::Arc;
 use std::time::Instant;
 
-use super::DummyKernel;
+use super::{
+    DummyAssertion, DummyElementwiseAddition, DummyElementwiseMultiplication, DummyKernel,
+};
 use cubecl_runtime::memory_management::MemoryUsage;
-use cubecl_runtime::server::CubeCount;
+use cubecl_runtime::profile::{KernelLaunch, KernelProfile, ProfileResult, ProfiledKernel};
+use cubecl_runtime::server::{fill_pattern, CubeCount, EventId, StreamId};
 use cubecl_runtime::storage::{BindingResource, ComputeStorage};
 use cubecl_runtime::{
     memory_management::MemoryManagement,
-    server::{Binding, ComputeServer, Handle},
+    server::{Binding, ComputeServer, Handle, KernelError, RecordableServer},
     storage::BytesStorage,
     ExecutionMode,
 };
@@ -20,6 +23,10 @@ use cubecl_runtime::{
 pub struct DummyServer {
     memory_management: MemoryManagement<BytesStorage>,
     timestamps: KernelTimestamps,
+    profile: Option<(Instant, KernelProfile)>,
+    streams: u32,
+    events: Vec<StreamId>,
+    errors: Vec<KernelError>,
 }
 
 #[derive(Debug)]
@@ -86,10 +93,32 @@ impl ComputeServer for DummyServer {
         )
     }
 
+    fn copy(&mut self, src: Binding, dst: Binding) {
+        let src = self.get_resource(src);
+        let dst = self.get_resource(dst);
+        let data = src.resource().read();
+
+        dst.resource().write()[..data.len()].copy_from_slice(data);
+    }
+
+    fn fill(&mut self, binding: Binding, pattern: &[u8]) {
+        let resource = self.get_resource(binding);
+        let bytes = resource.resource().write();
+
+        bytes.copy_from_slice(&fill_pattern(pattern, bytes.len()));
+    }
+
+    fn write(&mut self, binding: Binding, offset: u64, data: &[u8]) {
+        let resource = self.get_resource(binding);
+        let offset = offset as usize;
+
+        resource.resource().write()[offset..offset + data.len()].copy_from_slice(data);
+    }
+
     unsafe fn execute(
         &mut self,
         kernel: Self::Kernel,
-        _count: CubeCount,
+        count: CubeCount,
         bindings: Vec<Binding>,
         _mode: ExecutionMode,
     ) {
@@ -100,7 +129,76 @@ impl ComputeServer for DummyServer {
 
         let mut resources: Vec<_> = bind_resources.iter().map(|x| x.resource()).collect();
 
+        let start = Instant::now();
         kernel.compute(&mut resources);
+        if let Some(kind) = kernel.error() {
+            let error = KernelError {
+                kernel: format!("{kernel:?}"),
+                kind,
+                unit_pos: 0,
+                cube_pos: (0, 0, 0),
+                count: 1,
+            };
+            KernelError::extend_pending(&mut self.errors, [error]);
+        }
+
+        if let Some((profile_start, profile)) = &mut self.profile {
+            profile.kernels.push(ProfiledKernel {
+                launch: KernelLaunch::new(format!("{kernel:?}"), &count, (1, 1, 1)),
+                start: start - *profile_start,
+                duration: start.elapsed(),
+            });
+        }
+    }
+
+    fn create_stream(&mut self) -> StreamId {
+        self.streams += 1;
+        StreamId {
+            value: self.streams,
+        }
+    }
+
+    fn copy_on(&mut self, stream: StreamId, src: Binding, dst: Binding) {
+        self.check_stream(stream);
+        self.copy(src, dst)
+    }
+
+    fn fill_on(&mut self, stream: StreamId, binding: Binding, pattern: &[u8]) {
+        self.check_stream(stream);
+        self.fill(binding, pattern)
+    }
+
+    fn write_on(&mut self, stream: StreamId, binding: Binding, offset: u64, data: &[u8]) {
+        self.check_stream(stream);
+        self.write(binding, offset, data)
+    }
+
+    unsafe fn execute_on(
+        &mut self,
+        stream: StreamId,
+        kernel: Self::Kernel,
+        count: CubeCount,
+        bindings: Vec<Binding>,
+        mode: ExecutionMode,
+    ) {
+        self.check_stream(stream);
+        self.execute(kernel, count, bindings, mode)
+    }
+
+    fn record_event(&mut self, stream: StreamId) -> EventId {
+        self.check_stream(stream);
+        self.events.push(stream);
+        EventId {
+            value: self.events.len() as u64 - 1,
+        }
+    }
+
+    fn wait_event(&mut self, stream: StreamId, event: EventId) {
+        self.check_stream(stream);
+        assert!(
+            (event.value as usize) < self.events.len(),
+            "Events should be recorded before being waited on"
+        );
     }
 
     fn flush(&mut self) {
@@ -137,6 +235,24 @@ impl ComputeServer for DummyServer {
     fn disable_timestamps(&mut self) {
         self.timestamps.disable();
     }
+
+    fn start_profile(&mut self) {
+        self.profile = Some((Instant::now(), KernelProfile::default()));
+    }
+
+    #[allow(clippy::manual_async_fn)]
+    fn end_profile(&mut self) -> impl Future<Output = ProfileResult> + 'static {
+        let profile = match self.profile.take() {
+            Some((_, profile)) => Ok(profile),
+            None => Err(TimestampsError::Disabled),
+        };
+
+        async move { profile }
+    }
+
+    fn errors(&mut self) -> Vec<KernelError> {
+        core::mem::take(&mut self.errors)
+    }
 }
 
 impl DummyServer {
@@ -144,6 +260,37 @@ impl DummyServer {
         Self {
             memory_management,
             timestamps: KernelTimestamps::Disabled,
+            profile: None,
+            streams: 0,
+            events: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    // Tasks execute as soon as they are submitted, so streams are only checked to exist.
+    fn check_stream(&self, stream: StreamId) {
+        assert!(stream.value <= self.streams, "Unknown stream {stream:?}");
+    }
+}
+
+impl RecordableServer for DummyServer {
+    type KernelRecord = String;
+    type KernelKey = String;
+
+    fn kernel_key(kernel: &Self::Kernel) -> String {
+        format!("{kernel:?}")
+    }
+
+    fn record_kernel(kernel: &Self::Kernel) -> Option<String> {
+        Some(format!("{kernel:?}"))
+    }
+
+    fn replay_kernel(record: String) -> Self::Kernel {
+        match record.as_str() {
+            "DummyElementwiseAddition" => Arc::new(DummyElementwiseAddition),
+            "DummyElementwiseMultiplication" => Arc::new(DummyElementwiseMultiplication),
+            "DummyAssertion" => Arc::new(DummyAssertion),
+            _ => panic!("Unknown dummy kernel {record}"),
         }
     }
 }
@@ -9,6 +9,7 @@ use crate::dummy::{client, DummyDevice, DummyElementwiseAddition};
 #[cfg(autotune_persistent_cache)]
 use crate::dummy::{TUNER_DEVICE_ID, TUNER_PREFIX};
 
+use cubecl_runtime::graph::ScalarBinding;
 use cubecl_runtime::server::CubeCount;
 use cubecl_runtime::ComputeRuntime;
 
@@ -56,6 +57,460 @@ fn execute_elementwise_addition() {
     assert_eq!(obtained_resource, Vec::from([4, 5, 6]))
 }
 
+#[test]
+fn copy_overwrites_the_start_of_the_destination() {
+    let client = client(&DummyDevice);
+    let src = client.create(&[1, 2, 3]);
+    let dst = client.create(&[0, 0, 0, 0, 9]);
+
+    client.copy(src.binding(), dst.clone().binding());
+
+    assert_eq!(client.read_one(dst.binding()), vec![1, 2, 3, 0, 9]);
+}
+
+#[test]
+fn fill_repeats_the_pattern() {
+    let client = client(&DummyDevice);
+    let handle = client.empty(6);
+
+    client.fill(handle.clone().binding(), &[1, 2]);
+
+    assert_eq!(client.read_one(handle.binding()), vec![1, 2, 1, 2, 1, 2]);
+}
+
+#[test]
+fn write_updates_a_range_of_the_handle() {
+    let client = client(&DummyDevice);
+    let handle = client.create(&[0, 1, 2, 3, 4]);
+
+    client.write(handle.clone().binding(), 1, &[7, 8]);
+
+    assert_eq!(client.read_one(handle.binding()), vec![0, 7, 8, 3, 4]);
+}
+
+#[test]
+fn kernels_wait_on_events_of_other_streams() {
+    let client = client(&DummyDevice);
+    let upload = client.clone().on_stream(client.create_stream());
+    let lhs = client.empty(3);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    upload.wait_event(client.record_event());
+    upload.write(lhs.clone().binding(), 0, &[0, 1, 2]);
+    let uploaded = upload.record_event();
+    client.wait_event(uploaded);
+    client.execute(
+        Arc::new(DummyElementwiseAddition),
+        CubeCount::Static(1, 1, 1),
+        vec![lhs.binding(), rhs.binding(), out.clone().binding()],
+    );
+
+    assert_ne!(upload.stream(), client.stream());
+    assert_eq!(client.read_one(out.binding()), vec![4, 5, 6]);
+}
+
+#[test]
+#[serial]
+fn captured_kernels_are_replayed_over_updated_bindings() {
+    let client = client(&DummyDevice);
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    let graph = client.capture(|| {
+        client.execute(
+            Arc::new(DummyElementwiseAddition),
+            CubeCount::Static(1, 1, 1),
+            vec![lhs.binding(), rhs.binding(), out.clone().binding()],
+        );
+    });
+    let captured = client.read_one(out.clone().binding());
+    graph.update(0, 0, &[1, 1, 1]);
+    graph.replay();
+
+    assert_eq!(graph.num_launches(), 1);
+    assert_eq!(captured, vec![4, 5, 6]);
+    assert_eq!(client.read_one(out.binding()), vec![5, 5, 5]);
+}
+
+#[test]
+#[serial]
+fn captured_scalars_are_updated_by_index() {
+    let client = client(&DummyDevice);
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    let graph = client.capture(|| {
+        client.execute_with_scalars(
+            Arc::new(DummyElementwiseAddition),
+            CubeCount::Static(1, 1, 1),
+            vec![lhs.binding(), rhs.binding(), out.clone().binding()],
+            vec![
+                ScalarBinding::new::<u8>(1, 0),
+                ScalarBinding::new::<u8>(1, 2),
+            ],
+        );
+    });
+    graph.update_scalar(0, 1, 7u8);
+    graph.replay();
+
+    assert_eq!(client.read_one(out.binding()), vec![4, 5, 9]);
+}
+
+#[test]
+#[serial]
+#[should_panic(expected = "isn't a u16")]
+fn captured_scalars_are_updated_with_their_type() {
+    let client = client(&DummyDevice);
+    let lhs = client.create(&[0, 1, 2]);
+    let out = client.empty(3);
+
+    let graph = client.capture(|| {
+        client.execute_with_scalars(
+            Arc::new(DummyElementwiseAddition),
+            CubeCount::Static(1, 1, 1),
+            vec![lhs.clone().binding(), lhs.binding(), out.binding()],
+            vec![ScalarBinding::new::<u8>(1, 0)],
+        );
+    });
+    graph.update_scalar(0, 0, 7u16);
+}
+
+#[test]
+#[serial]
+fn capture_ends_when_the_captured_function_panics() {
+    let client = client(&DummyDevice);
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        client.capture(|| panic!("Failed to launch"));
+    }));
+
+    assert!(result.is_err());
+    client.capture(|| {});
+}
+
+#[test]
+fn kernel_profile_times_each_kernel() {
+    let client = client(&DummyDevice);
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    client.start_kernel_profile();
+    for _ in 0..2 {
+        client.execute(
+            Arc::new(DummyElementwiseAddition),
+            CubeCount::Static(3, 1, 1),
+            vec![
+                lhs.clone().binding(),
+                rhs.clone().binding(),
+                out.clone().binding(),
+            ],
+        );
+    }
+    let profile = cubecl_common::reader::read_sync(client.end_kernel_profile()).unwrap();
+
+    assert_eq!(profile.kernels.len(), 2);
+    assert!(profile.kernels[0].start <= profile.kernels[1].start);
+
+    let summary = profile.summary();
+    assert_eq!(summary.len(), 1);
+    assert_eq!(summary[0].launch.name, "DummyElementwiseAddition");
+    assert_eq!(summary[0].launch.cube_count, Some((3, 1, 1)));
+    assert_eq!(summary[0].count, 2);
+}
+
+#[test]
+#[cfg(feature = "channel-record")]
+fn replayed_trace_reads_the_recorded_data() {
+    use cubecl_runtime::channel::{MutexComputeChannel, RecordChannel, ReplayedRead, Trace};
+
+    let path = std::env::temp_dir().join(format!("cubecl-trace-{}.jsonl", std::process::id()));
+    let recording = dummy::init_client_with(|server| {
+        RecordChannel::create(MutexComputeChannel::new(server), &path).unwrap()
+    });
+    let lhs = recording.create(&[0, 1, 2]);
+    let rhs = recording.create(&[4, 4, 4]);
+    let out = recording.empty(3);
+
+    recording.execute(
+        Arc::new(DummyElementwiseAddition),
+        CubeCount::Static(1, 1, 1),
+        vec![lhs.binding(), rhs.binding(), out.clone().binding()],
+    );
+    recording.read_one(out.binding());
+
+    let trace = Trace::<String>::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let reads = trace.replay(&client(&DummyDevice));
+
+    assert_eq!(
+        reads,
+        vec![ReplayedRead {
+            recorded: Some(vec![vec![4, 5, 6]]),
+            replayed: vec![vec![4, 5, 6]],
+        }]
+    );
+}
+
+#[test]
+#[cfg(feature = "channel-record")]
+fn replayed_trace_drops_buffers_after_their_last_use() {
+    use cubecl_runtime::channel::{MutexComputeChannel, RecordChannel, Trace};
+
+    let path =
+        std::env::temp_dir().join(format!("cubecl-trace-drops-{}.jsonl", std::process::id()));
+    let recording = dummy::init_client_with(|server| {
+        RecordChannel::create(MutexComputeChannel::new(server), &path).unwrap()
+    });
+    for i in 0..4 {
+        let handle = recording.create(&[i; 64]);
+        recording.read_one(handle.binding());
+    }
+
+    let trace = Trace::<String>::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let replay = dummy::init_client_with(MutexComputeChannel::new);
+    let reads = trace.replay(&replay);
+
+    assert_eq!(reads.len(), 4);
+    assert_eq!(replay.memory_usage().number_allocs, 0);
+}
+
+#[test]
+#[cfg(feature = "channel-record")]
+fn replayed_trace_creates_the_recorded_streams() {
+    use cubecl_runtime::channel::{MutexComputeChannel, RecordChannel, Trace};
+
+    let path =
+        std::env::temp_dir().join(format!("cubecl-trace-streams-{}.jsonl", std::process::id()));
+    let recording = dummy::init_client_with(|server| {
+        RecordChannel::create(MutexComputeChannel::new(server), &path).unwrap()
+    });
+    // Create a first stream, so the recorded stream isn't the first one of the replay server.
+    recording.create_stream();
+    let stream = recording.clone().on_stream(recording.create_stream());
+    let lhs = recording.create(&[0, 1, 2]);
+    let rhs = recording.create(&[4, 4, 4]);
+    let out = recording.empty(3);
+
+    stream.wait_event(recording.record_event());
+    stream.execute(
+        Arc::new(DummyElementwiseAddition),
+        CubeCount::Static(1, 1, 1),
+        vec![lhs.binding(), rhs.binding(), out.clone().binding()],
+    );
+    recording.wait_event(stream.record_event());
+    recording.read_one(out.binding());
+
+    let trace = Trace::<String>::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let replay = dummy::init_client_with(MutexComputeChannel::new);
+    let reads = trace.replay(&replay);
+
+    assert_eq!(reads[0].replayed, vec![vec![4, 5, 6]]);
+}
+
+#[test]
+#[cfg(feature = "channel-record")]
+fn replayed_trace_replays_the_recorded_graphs() {
+    use cubecl_runtime::channel::{MutexComputeChannel, RecordChannel, Trace};
+
+    let path =
+        std::env::temp_dir().join(format!("cubecl-trace-graphs-{}.jsonl", std::process::id()));
+    let recording = dummy::init_client_with(|server| {
+        RecordChannel::create(MutexComputeChannel::new(server), &path).unwrap()
+    });
+    let lhs = recording.create(&[0, 1, 2]);
+    let rhs = recording.create(&[4, 4, 4]);
+    let out = recording.empty(3);
+
+    let graph = recording.capture(|| {
+        recording.execute(
+            Arc::new(DummyElementwiseAddition),
+            CubeCount::Static(1, 1, 1),
+            vec![lhs.binding(), rhs.binding(), out.clone().binding()],
+        );
+    });
+    graph.update(0, 0, &[1, 1, 1]);
+    graph.replay();
+    drop(graph);
+    recording.read_one(out.binding());
+
+    let trace = Trace::<String>::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let replay = dummy::init_client_with(MutexComputeChannel::new);
+    let reads = trace.replay(&replay);
+
+    assert_eq!(reads[0].replayed, vec![vec![5, 5, 5]]);
+}
+
+#[test]
+#[cfg(feature = "channel-record")]
+fn recorded_kernels_are_written_once() {
+    use cubecl_runtime::channel::{MutexComputeChannel, RecordChannel, Trace, TraceEntry};
+
+    let path =
+        std::env::temp_dir().join(format!("cubecl-trace-kernels-{}.jsonl", std::process::id()));
+    let recording = dummy::init_client_with(|server| {
+        RecordChannel::create(MutexComputeChannel::new(server), &path).unwrap()
+    });
+    let lhs = recording.create(&[0, 1, 2]);
+    let rhs = recording.create(&[4, 4, 4]);
+    let out = recording.empty(3);
+
+    for _ in 0..2 {
+        recording.execute(
+            Arc::new(DummyElementwiseAddition),
+            CubeCount::Static(1, 1, 1),
+            vec![
+                lhs.clone().binding(),
+                rhs.clone().binding(),
+                out.clone().binding(),
+            ],
+        );
+    }
+    recording.read_one(out.binding());
+
+    let trace = Trace::<String>::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let kernels = trace
+        .entries
+        .iter()
+        .filter(|entry| matches!(entry, TraceEntry::Kernel { .. }))
+        .count();
+    let reads = trace.replay(&client(&DummyDevice));
+
+    assert_eq!(kernels, 1);
+    assert_eq!(reads[0].replayed, vec![vec![4, 5, 6]]);
+}
+
+#[test]
+#[cfg(feature = "channel-record")]
+fn finished_recording_returns_the_write_error() {
+    use cubecl_runtime::channel::{MutexComputeChannel, RecordChannel};
+
+    struct FailingWriter;
+
+    impl std::io::Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("disk full"))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut channel = None;
+    let recording = dummy::init_client_with(|server| {
+        let recording = RecordChannel::new(MutexComputeChannel::new(server), FailingWriter);
+        channel = Some(recording.clone());
+        recording
+    });
+    let handle = recording.create(&[0, 1, 2]);
+    let data = recording.read_one(handle.binding());
+
+    assert_eq!(data, vec![0, 1, 2]);
+    let err = channel.unwrap().finish().unwrap_err();
+    assert_eq!(err.to_string(), "disk full");
+}
+
+#[test]
+#[cfg(all(feature = "channel-ipc", unix))]
+fn ipc_server_executes_kernels_of_the_client() {
+    use cubecl_runtime::channel::IpcComputeChannel;
+    use std::os::unix::net::UnixListener;
+
+    let path = std::env::temp_dir().join(format!("cubecl-ipc-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let client = dummy::init_client_with(|server| {
+        let listener = UnixListener::bind(&path).unwrap();
+        std::thread::spawn(move || IpcComputeChannel::serve(server, listener));
+        IpcComputeChannel::connect(&path).unwrap()
+    });
+    std::fs::remove_file(&path).unwrap();
+
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    client.execute(
+        Arc::new(DummyElementwiseAddition),
+        CubeCount::Static(1, 1, 1),
+        vec![lhs.binding(), rhs.binding(), out.clone().binding()],
+    );
+
+    assert_eq!(client.memory_usage().number_allocs, 1);
+    assert_eq!(client.read_one(out.binding()), vec![4, 5, 6]);
+    assert_eq!(client.memory_usage().number_allocs, 0);
+}
+
+#[test]
+#[cfg(all(feature = "channel-ipc", unix))]
+fn ipc_server_reports_errors_to_the_connection_that_raised_them() {
+    use crate::dummy::DummyAssertion;
+    use cubecl_common::reader::read_sync;
+    use cubecl_runtime::channel::IpcComputeChannel;
+    use cubecl_runtime::server::KernelErrorKind;
+    use std::os::unix::net::UnixListener;
+
+    let path = std::env::temp_dir().join(format!("cubecl-ipc-errors-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let first = dummy::init_client_with(|server| {
+        let listener = UnixListener::bind(&path).unwrap();
+        std::thread::spawn(move || IpcComputeChannel::serve(server, listener));
+        IpcComputeChannel::connect(&path).unwrap()
+    });
+    let second = dummy::init_client_with(|_server| IpcComputeChannel::connect(&path).unwrap());
+    std::fs::remove_file(&path).unwrap();
+
+    first.execute(Arc::new(DummyAssertion), CubeCount::Static(1, 1, 1), vec![]);
+    read_sync(first.sync());
+
+    let lhs = second.create(&[0, 1, 2]);
+    let rhs = second.create(&[4, 4, 4]);
+    let out = second.empty(3);
+    second.execute(
+        Arc::new(DummyElementwiseAddition),
+        CubeCount::Static(1, 1, 1),
+        vec![lhs.binding(), rhs.binding(), out.binding()],
+    );
+    assert!(read_sync(second.try_sync()).is_ok());
+
+    let errors = read_sync(first.try_sync()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, KernelErrorKind::Assertion { code: 1 });
+    assert!(read_sync(first.try_sync()).is_ok());
+}
+
+#[test]
+#[cfg(all(feature = "channel-ipc", unix))]
+fn ipc_client_reports_a_lost_server_instead_of_panicking() {
+    use cubecl_runtime::channel::IpcComputeChannel;
+    use cubecl_runtime::server::KernelErrorKind;
+    use std::os::unix::net::UnixListener;
+
+    let path = std::env::temp_dir().join(format!("cubecl-ipc-lost-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let client = dummy::init_client_with(|_server| {
+        let listener = UnixListener::bind(&path).unwrap();
+        // A server that crashes as soon as the client connects.
+        std::thread::spawn(move || drop(listener.accept()));
+        IpcComputeChannel::connect(&path).unwrap()
+    });
+    std::fs::remove_file(&path).unwrap();
+
+    let out = client.create(&[0, 1, 2]);
+    let errors = client.try_read(vec![out.binding()]).unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, KernelErrorKind::DeviceLost);
+    assert!(cubecl_common::reader::read_sync(client.try_sync()).is_err());
+}
+
 #[test]
 #[serial]
 #[cfg(feature = "std")]
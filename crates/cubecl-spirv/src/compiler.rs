@@ -2,7 +2,6 @@ use cubecl_core::{ir as core, Metadata};
 use cubecl_opt::{BasicBlock, NodeIndex, Optimizer};
 use std::{
     collections::HashSet,
-    env,
     fmt::Debug,
     mem::take,
     ops::{Deref, DerefMut},
@@ -18,6 +17,7 @@ use rspirv::{
 };
 
 use crate::{
+    debug::error_binding,
     item::Item,
     lookups::LookupTables,
     target::{GLCompute, SpirvTarget},
@@ -81,7 +81,7 @@ impl<T: SpirvTarget> Default for SpirvCompiler<T> {
             setup_block: Default::default(),
             opt: Default::default(),
             current_block: Default::default(),
-            debug: env::var("CUBECL_DEBUG_LOG").is_ok(),
+            debug: cubecl_runtime::debug::debug_symbols_enabled(),
             visited: Default::default(),
             metadata: Default::default(),
             ext_meta_pos: Default::default(),
@@ -107,7 +107,29 @@ impl<T: SpirvTarget> Compiler for SpirvCompiler<T> {
     type Representation = SpirvKernel;
 
     fn compile(value: KernelDefinition, mode: ExecutionMode) -> Self::Representation {
-        let bindings = value
+        Self {
+            mode,
+            ..Default::default()
+        }
+        .compile_definition(value)
+    }
+
+    fn elem_size(elem: core::Elem) -> usize {
+        elem.size()
+    }
+
+    fn local_allocator() -> impl LocalAllocator {
+        HybridAllocator::default()
+    }
+
+    fn max_shared_memory_size() -> usize {
+        32768
+    }
+}
+
+impl<Target: SpirvTarget> SpirvCompiler<Target> {
+    fn compile_definition(mut self, value: KernelDefinition) -> SpirvKernel {
+        let mut bindings: Vec<_> = value
             .inputs
             .clone()
             .into_iter()
@@ -125,31 +147,20 @@ impl<T: SpirvTarget> Compiler for SpirvCompiler<T> {
             }
         }
 
-        let (module, optimizer) = Self {
-            mode,
-            metadata: Metadata::new(num_meta as u32, num_ext),
-            ext_meta_pos,
-            ..Default::default()
+        self.metadata = Metadata::new(num_meta as u32, num_ext);
+        self.ext_meta_pos = ext_meta_pos;
+        let (module, optimizer) = self.compile_kernel(value);
+        let errors = self.state.errors.is_some();
+        if errors {
+            bindings.push(error_binding());
         }
-        .compile_kernel(value);
         SpirvKernel {
             module,
             optimizer,
             bindings,
+            errors,
         }
     }
-
-    fn elem_size(elem: core::Elem) -> usize {
-        elem.size()
-    }
-
-    fn local_allocator() -> impl LocalAllocator {
-        HybridAllocator::default()
-    }
-
-    fn max_shared_memory_size() -> usize {
-        32768
-    }
 }
 
 impl<Target: SpirvTarget> Debug for SpirvCompiler<Target> {
@@ -241,6 +252,19 @@ impl<Target: SpirvTarget> SpirvCompiler<Target> {
         }
     }
 
+    /// Point the next instructions of the current block to their source location.
+    fn compile_source_loc(&mut self, loc: &core::SourceLoc) {
+        let file = match self.state.source_files.get(&*loc.file) {
+            Some(file) => *file,
+            None => {
+                let file = self.string(loc.file.to_string());
+                self.state.source_files.insert(loc.file.to_string(), file);
+                file
+            }
+        };
+        self.line(file, loc.line, loc.column);
+    }
+
     pub fn compile_block(&mut self, block: NodeIndex) {
         if self.visited.contains(&block) {
             return;
@@ -253,7 +277,15 @@ impl<Target: SpirvTarget> SpirvCompiler<Target> {
         let block_id = self.selected_block().unwrap();
 
         let operations = self.current_block().ops.borrow().clone();
+        let mut source_loc = None;
         for (_, operation) in operations {
+            match &operation.source_loc {
+                Some(loc) if self.debug && source_loc.as_ref() != Some(loc) => {
+                    self.compile_source_loc(loc);
+                    source_loc = Some(loc.clone());
+                }
+                _ => {}
+            }
             self.compile_operation(operation);
         }
 
@@ -317,3 +349,41 @@ impl<Target: SpirvTarget> SpirvCompiler<Target> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cubecl_core::runtime_tests::source_locs::kernel_with_source_locs;
+
+    fn source_lines(kernel: &SpirvKernel) -> Vec<u32> {
+        kernel
+            .module
+            .all_inst_iter()
+            .filter(|inst| inst.class.opcode == Op::Line)
+            .map(|inst| inst.operands[1].unwrap_literal_bit32())
+            .collect()
+    }
+
+    #[test]
+    fn source_locs_are_emitted_as_op_line_when_they_change() {
+        let kernel = SpirvCompiler::<GLCompute> {
+            debug: true,
+            mode: ExecutionMode::Unchecked,
+            ..Default::default()
+        }
+        .compile_definition(kernel_with_source_locs());
+
+        assert_eq!(source_lines(&kernel), vec![3, 4]);
+    }
+
+    #[test]
+    fn source_locs_are_skipped_without_debug_symbols() {
+        let kernel = SpirvCompiler::<GLCompute> {
+            mode: ExecutionMode::Unchecked,
+            ..Default::default()
+        }
+        .compile_definition(kernel_with_source_locs());
+
+        assert!(source_lines(&kernel).is_empty());
+    }
+}
@@ -0,0 +1,189 @@
+use cubecl_core::ir::{self as core, parse_format_string, FormatSegment};
+use cubecl_runtime::server::{KernelError, KernelErrorKind};
+use rspirv::{
+    dr::Operand,
+    spirv::{MemorySemantics, Scope, SelectionControl, StorageClass, Word},
+};
+
+use crate::{
+    item::{Elem, Item},
+    SpirvCompiler, SpirvTarget,
+};
+
+/// `NonSemantic.DebugPrintf` only has a single instruction.
+const DEBUG_PRINTF: u32 = 1;
+
+impl<T: SpirvTarget> SpirvCompiler<T> {
+    /// Print with `NonSemantic.DebugPrintf`. The output is only visible with the validation layers
+    /// enabled and `printf` messages turned on.
+    pub fn compile_print(&mut self, format_string: String, args: Vec<core::Variable>) {
+        let segments = parse_format_string(&format_string)
+            .unwrap_or_else(|err| panic!("Invalid print format string {format_string:?}: {err}"));
+        let mut format = String::new();
+        let mut operands = Vec::new();
+        let mut args = args.into_iter();
+
+        for segment in segments {
+            match segment {
+                FormatSegment::Text(text) => format.push_str(&text.replace('%', "%%")),
+                FormatSegment::Arg => {
+                    let arg = self.compile_variable(args.next().expect("Missing print argument"));
+                    let value = self.read(&arg);
+                    let (spec, value) = self.print_arg(&arg.item(), value);
+                    format.push_str(&spec);
+                    operands.push(Operand::IdRef(value));
+                }
+            }
+        }
+
+        let set = self.debug_printf();
+        let format = self.string(format);
+        let void = self.type_void();
+        operands.insert(0, Operand::IdRef(format));
+        self.ext_inst(void, None, set, DEBUG_PRINTF, operands)
+            .unwrap();
+    }
+
+    /// Get the format specifier of a value, converting it to a type supported by `printf`.
+    fn print_arg(&mut self, item: &Item, value: Word) -> (String, Word) {
+        let (spec, elem) = match item.elem() {
+            Elem::Float(64) => ("f", Elem::Float(64)),
+            Elem::Float(_) | Elem::Relaxed => ("f", Elem::Float(32)),
+            Elem::Int(64, true) => ("ld", Elem::Int(64, true)),
+            Elem::Int(64, false) => ("lu", Elem::Int(64, false)),
+            Elem::Int(_, true) => ("d", Elem::Int(32, true)),
+            Elem::Int(_, false) | Elem::Bool => ("u", Elem::Int(32, false)),
+            Elem::Void => unreachable!("Can't print void"),
+        };
+        let (spec, target) = match item {
+            Item::Vector(_, factor) => (format!("%v{factor}{spec}"), Item::Vector(elem, *factor)),
+            _ => (format!("%{spec}"), Item::Scalar(elem)),
+        };
+
+        if target == *item {
+            (spec, value)
+        } else {
+            (spec, item.cast_to(self, None, value, &target))
+        }
+    }
+
+    fn debug_printf(&mut self) -> Word {
+        match self.state.debug_printf {
+            Some(set) => set,
+            None => {
+                self.extension("SPV_KHR_non_semantic_info");
+                let set = self.ext_inst_import("NonSemantic.DebugPrintf");
+                self.state.debug_printf = Some(set);
+                set
+            }
+        }
+    }
+
+    /// Raise an assertion error in the error buffer if the condition is false.
+    pub fn compile_assert(&mut self, condition: core::Variable, code: u32) {
+        let condition = self.compile_variable(condition);
+        let condition = self.read(&condition);
+        let bool = self.type_bool();
+        let failed = self.logical_not(bool, None, condition).unwrap();
+        let current_block = self.current_block.unwrap();
+
+        let raise = self.id();
+        let next = self.id();
+
+        self.selection_merge(next, SelectionControl::DONT_FLATTEN)
+            .unwrap();
+        self.branch_conditional(failed, raise, next, vec![0, 1])
+            .unwrap();
+
+        self.begin_block(Some(raise)).unwrap();
+        let code = self.const_u32(code);
+        self.raise_error(KernelErrorKind::ASSERTION, [code]);
+        self.branch(next).unwrap();
+
+        self.begin_block(Some(next)).unwrap();
+        self.state.end_labels.insert(current_block, next);
+    }
+
+    /// Write an error to the error buffer, unless another unit already wrote one. Uses the same
+    /// layout as the other backends, see [`KernelError::BUFFER_WORDS`].
+    fn raise_error<const N: usize>(&mut self, tag: u32, args: [Word; N]) {
+        let errors = self.error_buffer();
+        let int = Item::Scalar(Elem::Int(32, false));
+        let int_ty = int.id(self);
+        let int_ptr = Item::Pointer(StorageClass::StorageBuffer, Box::new(int)).id(self);
+        let memory = self.const_u32(Scope::Device as u32);
+        let semantics = self.const_u32(MemorySemantics::UNIFORM_MEMORY.bits());
+        let zero = self.const_u32(0);
+        let one = self.const_u32(1);
+
+        let count = self
+            .access_chain(int_ptr, None, errors, vec![zero, zero])
+            .unwrap();
+        let previous = self
+            .atomic_i_add(int_ty, None, count, memory, semantics, one)
+            .unwrap();
+        let bool = self.type_bool();
+        let first = self.i_equal(bool, None, previous, zero).unwrap();
+
+        let write = self.id();
+        let next = self.id();
+        self.selection_merge(next, SelectionControl::DONT_FLATTEN)
+            .unwrap();
+        self.branch_conditional(first, write, next, vec![]).unwrap();
+
+        self.begin_block(Some(write)).unwrap();
+        let tag = self.const_u32(tag);
+        let builtins = [
+            core::Builtin::UnitPos,
+            core::Builtin::CubePosX,
+            core::Builtin::CubePosY,
+            core::Builtin::CubePosZ,
+        ]
+        .map(|builtin| {
+            let var = self.compile_variable(core::Variable::builtin(builtin));
+            self.read(&var)
+        });
+        let words = [tag].into_iter().chain(builtins).chain(args);
+        for (i, value) in words.enumerate() {
+            let index = self.const_u32(i as u32 + 1);
+            let ptr = self
+                .access_chain(int_ptr, None, errors, vec![zero, index])
+                .unwrap();
+            self.atomic_store(ptr, memory, semantics, value).unwrap();
+        }
+        self.branch(next).unwrap();
+
+        self.begin_block(Some(next)).unwrap();
+    }
+
+    /// The error buffer, bound after all other bindings and declared by the first error raised.
+    fn error_buffer(&mut self) -> Word {
+        match self.state.errors {
+            Some(errors) => errors,
+            None => {
+                let index =
+                    self.state.inputs.len() + self.state.outputs.len() + self.state.named.len();
+                let mut target = self.target.clone();
+                let errors = target.generate_binding(
+                    self,
+                    error_binding(),
+                    "errors".to_string(),
+                    index as u32,
+                );
+                self.state.errors = Some(errors);
+                errors
+            }
+        }
+    }
+}
+
+/// The binding of the error buffer.
+pub(crate) fn error_binding() -> core::Binding {
+    core::Binding {
+        location: core::Location::Storage,
+        visibility: core::Visibility::ReadWrite,
+        item: core::Item::new(core::Elem::AtomicUInt(core::UIntKind::U32)),
+        size: None,
+        has_extended_meta: false,
+    }
+}
@@ -32,6 +32,15 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
             Operation::Plane(plane) => self.compile_plane(plane, inst.out),
             Operation::Synchronization(sync) => self.compile_sync(sync),
             Operation::CoopMma(cmma) => self.compile_cmma(cmma, inst.out),
+            Operation::NonSemantic(core::NonSemantic::Print {
+                format_string,
+                args,
+            }) => self.compile_print(format_string, args),
+            Operation::NonSemantic(core::NonSemantic::Assert { condition, code }) => {
+                if let ExecutionMode::Checked | ExecutionMode::Strict = self.mode {
+                    self.compile_assert(condition, code);
+                }
+            }
         }
     }
 
@@ -45,7 +54,9 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
                 let out = self.compile_variable(out);
 
                 if is_atomic {
-                    let checked = matches!(self.mode, ExecutionMode::Checked) && value.has_len();
+                    let checked =
+                        matches!(self.mode, ExecutionMode::Checked | ExecutionMode::Strict)
+                            && value.has_len();
                     let ptr = match self.index(&value, &index, !checked) {
                         IndexedVariable::Pointer(ptr, _) => ptr,
                         _ => unreachable!("Atomic is always pointer"),
@@ -683,8 +694,9 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
 
                 let in_ptr = self.index_ptr(&input, &in_index);
                 let out_ptr = self.index_ptr(&out, &out_index);
-                let checked =
-                    matches!(self.mode, ExecutionMode::Checked) && input.has_len() && out.has_len();
+                let checked = matches!(self.mode, ExecutionMode::Checked | ExecutionMode::Strict)
+                    && input.has_len()
+                    && out.has_len();
                 if checked {
                     let in_index = self.read(&in_index);
                     let out_index = self.read(&out_index);
@@ -706,8 +718,9 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
                 let source = self.index_ptr(&input, &in_index);
                 let target = self.index_ptr(&out, &out_index);
                 let size = self.const_u32(op.len * out.item().size());
-                let checked =
-                    matches!(self.mode, ExecutionMode::Checked) && input.has_len() && out.has_len();
+                let checked = matches!(self.mode, ExecutionMode::Checked | ExecutionMode::Strict)
+                    && input.has_len()
+                    && out.has_len();
                 if checked {
                     let in_index = self.read(&in_index);
                     let out_index = self.read(&out_index);
@@ -11,6 +11,7 @@ mod atomic;
 mod branch;
 mod cmma;
 mod compiler;
+mod debug;
 mod extensions;
 mod globals;
 mod instruction;
@@ -30,6 +31,8 @@ pub struct SpirvKernel {
     pub module: Module,
     pub optimizer: Optimizer,
     pub bindings: Vec<Binding>,
+    /// Whether the kernel can raise errors, in which case the error buffer is the last binding.
+    pub errors: bool,
 }
 
 impl CompilerRepresentation for SpirvKernel {
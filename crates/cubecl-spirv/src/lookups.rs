@@ -43,6 +43,12 @@ pub struct LookupTables {
     pub loops: VecDeque<Loop>,
 
     pub debug_types: HashSet<Word>,
+    /// `OpString` of each source file referenced by `OpLine`.
+    pub source_files: HashMap<String, Word>,
+    /// Import of `NonSemantic.DebugPrintf`, added by the first print.
+    pub debug_printf: Option<Word>,
+    /// The error buffer, declared by the first error raised.
+    pub errors: Option<Word>,
 }
 
 #[derive(Clone, Debug)]
@@ -48,6 +48,7 @@ impl SpirvTarget for GLCompute {
             .chain(b.state.inputs.iter().copied())
             .chain(b.state.outputs.iter().copied())
             .chain(b.state.named.values().copied())
+            .chain(b.state.errors)
             .chain(b.state.const_arrays.iter().map(|it| it.id))
             .chain(b.state.shared_memories.values().map(|it| it.id))
             .collect();
@@ -577,7 +577,8 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
     }
 
     pub fn read_indexed(&mut self, out: &Variable, variable: &Variable, index: &Variable) -> Word {
-        let checked = matches!(self.mode, ExecutionMode::Checked) && variable.has_len();
+        let checked = matches!(self.mode, ExecutionMode::Checked | ExecutionMode::Strict)
+            && variable.has_len();
         let always_in_bounds = is_always_in_bounds(variable, index);
         let index_id = self.read(index);
         let indexed = self.index(variable, index, always_in_bounds);
@@ -692,7 +693,8 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
     }
 
     pub fn write_indexed(&mut self, out: &Variable, index: &Variable, value: Word) {
-        let checked = matches!(self.mode, ExecutionMode::Checked) && out.has_len();
+        let checked =
+            matches!(self.mode, ExecutionMode::Checked | ExecutionMode::Strict) && out.has_len();
         let always_in_bounds = is_always_in_bounds(out, index);
         let index_id = self.read(index);
         let variable = self.index(out, index, always_in_bounds);
@@ -0,0 +1,42 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+/// Apply `f` to each element of `input` and write the result to the same position in `output`.
+///
+/// Each unit processes one element at `ABSOLUTE_POS`. Units past the end of `output` do nothing.
+#[cube]
+pub fn elementwise<I: CubePrimitive, O: CubePrimitive>(
+    input: &Tensor<I>,
+    output: &mut Tensor<O>,
+    f: impl Fn(I) -> O,
+) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = f(input[ABSOLUTE_POS]);
+    }
+}
+
+/// Apply `f` to each element of `input` and write the result to the same position in `output`.
+///
+/// The units of the cube process the slice in strides of `CUBE_DIM` elements.
+#[cube]
+pub fn map<I: CubePrimitive, O: CubePrimitive>(
+    input: &Slice<I>,
+    output: &mut SliceMut<O>,
+    f: impl Fn(I) -> O,
+) {
+    for i in range_stepped(UNIT_POS, input.len(), CUBE_DIM) {
+        output[i] = f(input[i]);
+    }
+}
+
+/// Combine all elements of `input` with `op`, starting from `init`.
+///
+/// The reduction is done sequentially by the calling unit. `op` doesn't need to be commutative.
+#[cube]
+pub fn reduce_with<N: CubePrimitive>(input: &Slice<N>, init: N, op: impl Fn(N, N) -> N) -> N {
+    let mut acc = init;
+    for i in 0..input.len() {
+        acc = op(acc, input[i]);
+    }
+    acc
+}
@@ -0,0 +1,4 @@
+pub mod test;
+
+mod base;
+pub use base::*;
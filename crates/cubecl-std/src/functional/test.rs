@@ -0,0 +1,115 @@
+#![allow(missing_docs)]
+
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+use super::{elementwise, map, reduce_with};
+
+#[macro_export]
+macro_rules! testgen_functional {
+    () => {
+        mod functional {
+            use super::*;
+            use cubecl_std::functional::test::{test_elementwise, test_map, test_reduce_with};
+
+            #[test]
+            pub fn elementwise_affine() {
+                test_elementwise::<TestRuntime>(&Default::default());
+            }
+
+            #[test]
+            pub fn map_square() {
+                test_map::<TestRuntime>(&Default::default());
+            }
+
+            #[test]
+            pub fn reduce_with_max() {
+                test_reduce_with::<TestRuntime>(&Default::default());
+            }
+        }
+    };
+}
+
+#[cube(launch_unchecked)]
+pub fn kernel_elementwise_affine<F: Float>(input: &Tensor<F>, output: &mut Tensor<F>, scale: F) {
+    let offset = F::new(1.0);
+    elementwise::<F, F>(input, output, |x| x * scale + offset);
+}
+
+#[cube(launch_unchecked)]
+pub fn kernel_map_square<F: Float>(input: &Tensor<F>, output: &mut Tensor<F>) {
+    map::<F, F>(&input.to_slice(), &mut output.to_slice_mut(), |x| x * x);
+}
+
+#[cube(launch_unchecked)]
+pub fn kernel_reduce_with_max<F: Float>(input: &Tensor<F>, output: &mut Tensor<F>) {
+    if UNIT_POS == 0 {
+        output[0] = reduce_with::<F>(&input.to_slice(), input[0], |acc, value| F::max(acc, value));
+    }
+}
+
+pub fn test_elementwise<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let input = client.create(f32::as_bytes(&[0.0, 1.0, 2.0, 3.0, 4.0]));
+    let output = client.create(f32::as_bytes(&[0.0; 5]));
+
+    unsafe {
+        kernel_elementwise_affine::launch_unchecked::<f32, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(8, 1, 1),
+            TensorArg::from_raw_parts::<f32>(&input, &[1], &[5], 1),
+            TensorArg::from_raw_parts::<f32>(&output, &[1], &[5], 1),
+            ScalarArg::new(2.0),
+        );
+    }
+
+    let actual = client.read_one(output.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, &[1.0, 3.0, 5.0, 7.0, 9.0]);
+}
+
+pub fn test_map<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let values: Vec<f32> = (0..20).map(|i| i as f32).collect();
+    let input = client.create(f32::as_bytes(&values));
+    let output = client.create(f32::as_bytes(&[0.0; 20]));
+
+    unsafe {
+        kernel_map_square::launch_unchecked::<f32, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(8, 1, 1),
+            TensorArg::from_raw_parts::<f32>(&input, &[1], &[20], 1),
+            TensorArg::from_raw_parts::<f32>(&output, &[1], &[20], 1),
+        );
+    }
+
+    let actual = client.read_one(output.binding());
+    let actual = f32::from_bytes(&actual);
+    let expected: Vec<f32> = values.iter().map(|value| value * value).collect();
+
+    assert_eq!(actual, &expected);
+}
+
+pub fn test_reduce_with<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let input = client.create(f32::as_bytes(&[3.0, -1.0, 7.0, 2.0, 5.0]));
+    let output = client.create(f32::as_bytes(&[0.0]));
+
+    unsafe {
+        kernel_reduce_with_max::launch_unchecked::<f32, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(4, 1, 1),
+            TensorArg::from_raw_parts::<f32>(&input, &[1], &[5], 1),
+            TensorArg::from_raw_parts::<f32>(&output, &[1], &[1], 1),
+        );
+    }
+
+    let actual = client.read_one(output.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, &[7.0]);
+}
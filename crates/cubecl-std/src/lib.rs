@@ -1 +1,2 @@
+pub mod functional;
 pub mod reduce;
@@ -6,7 +6,7 @@ use cubecl_core::{
 use cubecl_runtime::DeviceProperties;
 use wgpu::{Adapter, ComputePipeline, Device, Queue};
 
-use crate::WgpuServer;
+use crate::{compiler::wgsl::PrintFormat, WgpuServer};
 
 pub trait WgpuCompiler: Compiler {
     fn compile(
@@ -21,6 +21,16 @@ pub trait WgpuCompiler: Compiler {
         mode: ExecutionMode,
     ) -> Arc<ComputePipeline>;
 
+    /// The prints of a kernel that writes them to a buffer, since it can't print directly.
+    fn print_formats(_kernel: &CompiledKernel<Self>) -> Vec<PrintFormat> {
+        Vec::new()
+    }
+
+    /// Whether a kernel can raise errors, in which case it needs an error buffer.
+    fn raises_errors(_kernel: &CompiledKernel<Self>) -> bool {
+        false
+    }
+
     #[allow(async_fn_in_trait)]
     async fn request_device(adapter: &Adapter) -> (Device, Queue);
     fn register_features(adapter: &Adapter, device: &Device, props: &mut DeviceProperties<Feature>);
@@ -103,14 +103,12 @@ impl WgpuCompiler for SpirvCompiler<GLCompute> {
             .unwrap_or_else(|| {
                 let source = &kernel.source;
                 let module = match mode {
-                    ExecutionMode::Checked => {
-                        server
-                            .device
-                            .create_shader_module(wgpu::ShaderModuleDescriptor {
-                                label: None,
-                                source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(source)),
-                            })
-                    }
+                    ExecutionMode::Checked | ExecutionMode::Strict => server
+                        .device
+                        .create_shader_module(wgpu::ShaderModuleDescriptor {
+                            label: None,
+                            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(source)),
+                        }),
                     ExecutionMode::Unchecked => unsafe {
                         server
                             .device
@@ -147,7 +145,7 @@ impl WgpuCompiler for SpirvCompiler<GLCompute> {
     ) -> CompiledKernel<Self> {
         // `wgpu` currently always enables `robustness2` on Vulkan if available, so default to
         // unchecked execution if robustness is enabled and let Vulkan handle it
-        let mode = if is_robust(&server.device) {
+        let mode = if mode == ExecutionMode::Checked && is_robust(&server.device) {
             ExecutionMode::Unchecked
         } else {
             mode
@@ -159,6 +157,10 @@ impl WgpuCompiler for SpirvCompiler<GLCompute> {
         compiled
     }
 
+    fn raises_errors(kernel: &CompiledKernel<Self>) -> bool {
+        kernel.repr.as_ref().is_some_and(|repr| repr.errors)
+    }
+
     async fn request_device(adapter: &wgpu::Adapter) -> (wgpu::Device, wgpu::Queue) {
         let limits = adapter.limits();
         let features = adapter.features();
@@ -1,6 +1,6 @@
 use std::{borrow::Cow, sync::Arc};
 
-use super::{shader::ComputeShader, ConstantArray, Item, SharedMemory};
+use super::{shader::ComputeShader, ConstantArray, Item, PrintFormat, SharedMemory};
 use super::{LocalArray, Subgroup};
 use crate::{
     compiler::{base::WgpuCompiler, wgsl},
@@ -12,7 +12,7 @@ use cubecl_core::{
     server::ComputeServer,
     Feature, Metadata,
 };
-use cubecl_runtime::{DeviceProperties, ExecutionMode};
+use cubecl_runtime::{server::KernelErrorKind, DeviceProperties, ExecutionMode};
 use wgpu::{
     BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType, BufferBindingType,
     ComputePipeline, DeviceDescriptor, PipelineLayoutDescriptor, ShaderModuleDescriptor,
@@ -39,6 +39,11 @@ pub struct WgslCompiler {
     shared_memories: Vec<SharedMemory>,
     const_arrays: Vec<ConstantArray>,
     local_arrays: Vec<LocalArray>,
+    debug_symbols: bool,
+    source_loc: Option<cube::SourceLoc>,
+    prints: Vec<PrintFormat>,
+    mode: ExecutionMode,
+    errors: bool,
 }
 
 impl core::fmt::Debug for WgslCompiler {
@@ -50,8 +55,12 @@ impl core::fmt::Debug for WgslCompiler {
 impl cubecl_core::Compiler for WgslCompiler {
     type Representation = ComputeShader;
 
-    fn compile(shader: cube::KernelDefinition, _mode: ExecutionMode) -> Self::Representation {
-        let mut compiler = Self::default();
+    fn compile(shader: cube::KernelDefinition, mode: ExecutionMode) -> Self::Representation {
+        let mut compiler = Self {
+            debug_symbols: cubecl_runtime::debug::debug_symbols_enabled(),
+            mode,
+            ..Self::default()
+        };
         compiler.compile_shader(shader)
     }
 
@@ -76,10 +85,12 @@ impl WgpuCompiler for WgslCompiler {
     ) -> Arc<ComputePipeline> {
         let source = &kernel.source;
         let module = match mode {
-            ExecutionMode::Checked => server.device.create_shader_module(ShaderModuleDescriptor {
-                label: None,
-                source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(source)),
-            }),
+            ExecutionMode::Checked | ExecutionMode::Strict => {
+                server.device.create_shader_module(ShaderModuleDescriptor {
+                    label: None,
+                    source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(source)),
+                })
+            }
             ExecutionMode::Unchecked => unsafe {
                 server
                     .device
@@ -157,6 +168,18 @@ impl WgpuCompiler for WgslCompiler {
         kernel.compile(mode)
     }
 
+    fn print_formats(kernel: &CompiledKernel<Self>) -> Vec<PrintFormat> {
+        kernel
+            .repr
+            .as_ref()
+            .map(|repr| repr.prints.clone())
+            .unwrap_or_default()
+    }
+
+    fn raises_errors(kernel: &CompiledKernel<Self>) -> bool {
+        kernel.repr.as_ref().is_some_and(|repr| repr.errors)
+    }
+
     async fn request_device(adapter: &wgpu::Adapter) -> (wgpu::Device, wgpu::Queue) {
         let limits = adapter.limits();
         adapter
@@ -235,6 +258,35 @@ impl WgslCompiler {
             id: self.id,
         };
 
+        let mut named = value
+            .named
+            .into_iter()
+            .map(|(name, binding)| (name, Self::compile_binding(binding)))
+            .collect::<Vec<_>>();
+        // Bound by the server after all other bindings.
+        if !self.prints.is_empty() {
+            named.push((
+                "debug_print".to_string(),
+                wgsl::Binding {
+                    location: wgsl::Location::Storage,
+                    visibility: wgsl::Visibility::ReadWrite,
+                    item: Item::Scalar(wgsl::Elem::AtomicU32),
+                    size: None,
+                },
+            ));
+        }
+        if self.errors {
+            named.push((
+                "errors".to_string(),
+                wgsl::Binding {
+                    location: wgsl::Location::Storage,
+                    visibility: wgsl::Visibility::ReadWrite,
+                    item: Item::Scalar(wgsl::Elem::AtomicU32),
+                    size: None,
+                },
+            ));
+        }
+
         wgsl::ComputeShader {
             inputs: value
                 .inputs
@@ -246,11 +298,7 @@ impl WgslCompiler {
                 .into_iter()
                 .map(Self::compile_binding)
                 .collect(),
-            named: value
-                .named
-                .into_iter()
-                .map(|(name, binding)| (name, Self::compile_binding(binding)))
-                .collect(),
+            named,
             shared_memories: self.shared_memories.clone(),
             constant_arrays: self.const_arrays.clone(),
             local_arrays: self.local_arrays.clone(),
@@ -269,6 +317,8 @@ impl WgslCompiler {
             num_workgroups_no_axis: self.num_workgroup_no_axis,
             workgroup_id_no_axis: self.workgroup_id_no_axis,
             workgroup_size_no_axis: self.workgroup_size_no_axis,
+            prints: self.prints.clone(),
+            errors: self.errors,
         }
     }
 
@@ -486,14 +536,31 @@ impl WgslCompiler {
             });
         }
 
-        processing
-            .operations
-            .into_iter()
-            .for_each(|op| self.compile_operation(&mut instructions, op.operation, op.out));
+        processing.operations.into_iter().for_each(|op| {
+            self.compile_source_loc(&mut instructions, op.source_loc);
+            self.compile_operation(&mut instructions, op.operation, op.out)
+        });
 
         instructions
     }
 
+    /// Comment the source location of the next instructions when it changes.
+    fn compile_source_loc(
+        &mut self,
+        instructions: &mut Vec<wgsl::Instruction>,
+        source_loc: Option<cube::SourceLoc>,
+    ) {
+        match source_loc {
+            Some(loc) if self.debug_symbols && self.source_loc.as_ref() != Some(&loc) => {
+                instructions.push(wgsl::Instruction::Comment {
+                    content: loc.to_string(),
+                });
+                self.source_loc = Some(loc);
+            }
+            _ => {}
+        }
+    }
+
     fn compile_operation(
         &mut self,
         instructions: &mut Vec<wgsl::Instruction>,
@@ -505,7 +572,12 @@ impl WgslCompiler {
                 input: self.compile_variable(variable),
                 out: self.compile_variable(out.unwrap()),
             }),
-            cube::Operation::Operator(op) => instructions.push(self.compile_instruction(op, out)),
+            cube::Operation::Operator(op) => {
+                if let ExecutionMode::Strict = self.mode {
+                    self.compile_bounds_check(instructions, &op, out);
+                }
+                instructions.push(self.compile_instruction(op, out))
+            }
             cube::Operation::Atomic(op) => instructions.push(self.compile_atomic(op, out)),
             cube::Operation::Metadata(op) => instructions.push(self.compile_metadata(op, out)),
             cube::Operation::Branch(val) => self.compile_branch(instructions, val),
@@ -516,9 +588,82 @@ impl WgslCompiler {
             cube::Operation::CoopMma(_) => {
                 panic!("Cooperative matrix-multiply and accumulate isn't supported on wgpu.")
             }
+            cube::Operation::NonSemantic(cube::NonSemantic::Print {
+                format_string,
+                args,
+            }) => {
+                let args = args
+                    .into_iter()
+                    .map(|arg| {
+                        assert!(!arg.item.elem.is_atomic(), "Can't print atomic values");
+                        self.compile_variable(arg)
+                    })
+                    .collect::<Vec<_>>();
+                self.prints.push(PrintFormat {
+                    format_string,
+                    args: args.iter().map(|arg| arg.item()).collect(),
+                });
+                instructions.push(wgsl::Instruction::Print {
+                    format_index: self.prints.len() - 1,
+                    args,
+                });
+            }
+            cube::Operation::NonSemantic(cube::NonSemantic::Assert { condition, code }) => {
+                if let ExecutionMode::Checked | ExecutionMode::Strict = self.mode {
+                    self.errors = true;
+                    let cube_pos = [
+                        cube::Builtin::CubePosX,
+                        cube::Builtin::CubePosY,
+                        cube::Builtin::CubePosZ,
+                    ]
+                    .map(|builtin| self.compile_variable(cube::Variable::builtin(builtin)));
+                    instructions.push(wgsl::Instruction::Assert {
+                        condition: self.compile_variable(condition),
+                        code,
+                        unit_pos: self
+                            .compile_variable(cube::Variable::builtin(cube::Builtin::UnitPos)),
+                        cube_pos,
+                    });
+                }
+            }
         }
     }
 
+    /// Record an out-of-bounds error if an index into a binding or a slice is too big.
+    fn compile_bounds_check(
+        &mut self,
+        instructions: &mut Vec<wgsl::Instruction>,
+        op: &cube::Operator,
+        out: Option<cube::Variable>,
+    ) {
+        let (array, index) = match op {
+            cube::Operator::Index(op) => (op.lhs, op.rhs),
+            cube::Operator::IndexAssign(op) => (out.unwrap(), op.lhs),
+            _ => return,
+        };
+        let binding = match array.kind {
+            cube::VariableKind::GlobalInputArray(id) => id as u32,
+            cube::VariableKind::GlobalOutputArray(id) => self.num_inputs as u32 + id as u32,
+            cube::VariableKind::Slice { .. } => KernelErrorKind::NO_BINDING,
+            _ => return,
+        };
+
+        self.errors = true;
+        let cube_pos = [
+            cube::Builtin::CubePosX,
+            cube::Builtin::CubePosY,
+            cube::Builtin::CubePosZ,
+        ]
+        .map(|builtin| self.compile_variable(cube::Variable::builtin(builtin)));
+        instructions.push(wgsl::Instruction::CheckBounds {
+            array: self.compile_variable(array),
+            index: self.compile_variable(index),
+            binding,
+            unit_pos: self.compile_variable(cube::Variable::builtin(cube::Builtin::UnitPos)),
+            cube_pos,
+        });
+    }
+
     fn compile_subgroup(
         &mut self,
         instructions: &mut Vec<wgsl::Instruction>,
@@ -1113,3 +1258,35 @@ fn register_extensions(instructions: &[wgsl::Instruction]) -> Vec<wgsl::Extensio
 
     extensions
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cubecl_core::runtime_tests::source_locs::kernel_with_source_locs;
+
+    #[test]
+    fn source_locs_are_commented_when_they_change() {
+        let mut compiler = WgslCompiler {
+            debug_symbols: true,
+            ..Default::default()
+        };
+        let shader = compiler
+            .compile_shader(kernel_with_source_locs())
+            .to_string();
+
+        assert_eq!(shader.matches("// kernel.rs:3:5\n").count(), 1);
+        assert_eq!(shader.matches("// kernel.rs:4:5\n").count(), 1);
+        assert!(
+            shader.find("// kernel.rs:3:5").unwrap() < shader.find("// kernel.rs:4:5").unwrap()
+        );
+    }
+
+    #[test]
+    fn source_locs_are_skipped_without_debug_symbols() {
+        let shader = WgslCompiler::default()
+            .compile_shader(kernel_with_source_locs())
+            .to_string();
+
+        assert!(!shader.contains("kernel.rs"));
+    }
+}
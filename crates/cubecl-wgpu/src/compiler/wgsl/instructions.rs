@@ -2,6 +2,7 @@ use super::{
     base::{Item, Variable},
     Elem, Subgroup,
 };
+use cubecl_runtime::server::KernelErrorKind;
 use std::fmt::Display;
 
 /// All instructions that can be used in a WGSL compute shader.
@@ -11,6 +12,26 @@ pub enum Instruction {
     DeclareVariable {
         var: Variable,
     },
+    Comment {
+        content: String,
+    },
+    Print {
+        format_index: usize,
+        args: Vec<Variable>,
+    },
+    Assert {
+        condition: Variable,
+        code: u32,
+        unit_pos: Variable,
+        cube_pos: [Variable; 3],
+    },
+    CheckBounds {
+        array: Variable,
+        index: Variable,
+        binding: u32,
+        unit_pos: Variable,
+        cube_pos: [Variable; 3],
+    },
     Max {
         lhs: Variable,
         rhs: Variable,
@@ -362,6 +383,75 @@ impl Display for Instruction {
                 let item = var.item();
                 writeln!(f, "var {var}: {item};")
             }
+            Instruction::Comment { content } => writeln!(f, "// {content}"),
+            Instruction::Print { format_index, args } => {
+                let values = args
+                    .iter()
+                    .flat_map(|arg| {
+                        let item = arg.item();
+                        (0..item.vectorization_factor()).map(move |i| match item.elem() {
+                            Elem::Bool => format!("u32({})", arg.index(i)),
+                            _ => format!("bitcast<u32>({})", arg.index(i)),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                let len = values.len() + 1;
+
+                // The first word is the number of words written.
+                writeln!(f, "{{")?;
+                writeln!(f, "let print_offset = atomicAdd(&debug_print[0], {len}u);")?;
+                writeln!(f, "if print_offset + {len}u < arrayLength(&debug_print) {{")?;
+                writeln!(
+                    f,
+                    "atomicStore(&debug_print[print_offset + 1u], {format_index}u);"
+                )?;
+                for (i, value) in values.iter().enumerate() {
+                    writeln!(
+                        f,
+                        "atomicStore(&debug_print[print_offset + {}u], {value});",
+                        i + 2
+                    )?;
+                }
+                writeln!(f, "}}")?;
+                writeln!(f, "}}")
+            }
+            Instruction::Assert {
+                condition,
+                code,
+                unit_pos,
+                cube_pos,
+            } => {
+                writeln!(f, "if !({condition}) {{")?;
+                format_error(
+                    f,
+                    KernelErrorKind::ASSERTION,
+                    unit_pos,
+                    cube_pos,
+                    [format!("{code}u")],
+                )?;
+                writeln!(f, "}}")
+            }
+            Instruction::CheckBounds {
+                array,
+                index,
+                binding,
+                unit_pos,
+                cube_pos,
+            } => {
+                let len = match array {
+                    Variable::Slice { .. } => format!("{array}_length"),
+                    _ => format!("arrayLength(&{array})"),
+                };
+                writeln!(f, "if !(u32({index}) < {len}) {{")?;
+                format_error(
+                    f,
+                    KernelErrorKind::OUT_OF_BOUNDS,
+                    unit_pos,
+                    cube_pos,
+                    [format!("{binding}u"), format!("u32({index})"), len],
+                )?;
+                writeln!(f, "}}")
+            }
             Instruction::Add { lhs, rhs, out } => {
                 if out.is_atomic() {
                     assert_eq!(lhs, out, "Can't use regular addition on atomic");
@@ -1143,3 +1233,24 @@ fn index_assign(
         }
     }
 }
+
+/// Record an error in the error buffer, if it's the first one raised by the launch.
+fn format_error<const N: usize>(
+    f: &mut std::fmt::Formatter<'_>,
+    tag: u32,
+    unit_pos: &Variable,
+    cube_pos: &[Variable; 3],
+    args: [String; N],
+) -> std::fmt::Result {
+    let [x, y, z] = cube_pos;
+    writeln!(f, "if atomicAdd(&errors[0], 1u) == 0u {{")?;
+    writeln!(f, "atomicStore(&errors[1], {tag}u);")?;
+    writeln!(f, "atomicStore(&errors[2], {unit_pos});")?;
+    writeln!(f, "atomicStore(&errors[3], {x});")?;
+    writeln!(f, "atomicStore(&errors[4], {y});")?;
+    writeln!(f, "atomicStore(&errors[5], {z});")?;
+    for (i, arg) in args.iter().enumerate() {
+        writeln!(f, "atomicStore(&errors[{}], {arg});", 6 + i)?;
+    }
+    writeln!(f, "}}")
+}
@@ -1,5 +1,8 @@
-use super::{Body, Extension, Item, Variable};
-use cubecl_core::{ir::CubeDim, CompilerRepresentation};
+use super::{Body, Elem, Extension, Item, Variable};
+use cubecl_core::{
+    ir::{parse_format_string, CubeDim, FormatSegment},
+    CompilerRepresentation,
+};
 use std::fmt::Display;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -88,6 +91,61 @@ pub struct ComputeShader {
     pub workgroup_size_no_axis: bool,
     pub body: Body,
     pub extensions: Vec<Extension>,
+    pub prints: Vec<PrintFormat>,
+    pub errors: bool,
+}
+
+/// A `debug_print!` of the kernel, written to the `debug_print` buffer as its index followed by
+/// each lane of each argument, bitcast to `u32`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PrintFormat {
+    pub format_string: String,
+    pub args: Vec<Item>,
+}
+
+impl PrintFormat {
+    /// The number of words of a print in the buffer.
+    pub fn num_words(&self) -> usize {
+        1 + self
+            .args
+            .iter()
+            .map(|item| item.vectorization_factor())
+            .sum::<usize>()
+    }
+
+    /// Format the values of a print, without the index.
+    pub fn format(&self, mut words: &[u32]) -> String {
+        let segments = parse_format_string(&self.format_string).unwrap();
+        let mut args = self.args.iter();
+        let mut output = String::new();
+
+        for segment in segments {
+            match segment {
+                FormatSegment::Text(text) => output.push_str(&text),
+                FormatSegment::Arg => {
+                    let item = args.next().unwrap();
+                    let (values, rest) = words.split_at(item.vectorization_factor());
+                    words = rest;
+
+                    let values = values
+                        .iter()
+                        .map(|value| match item.elem() {
+                            Elem::F32 => f32::from_bits(*value).to_string(),
+                            Elem::I32 | Elem::AtomicI32 => (*value as i32).to_string(),
+                            Elem::U32 | Elem::AtomicU32 => value.to_string(),
+                            Elem::Bool => (*value != 0).to_string(),
+                        })
+                        .collect::<Vec<_>>();
+                    match item {
+                        Item::Scalar(_) => output.push_str(&values[0]),
+                        _ => output.push_str(&format!("[{}]", values.join(", "))),
+                    }
+                }
+            }
+        }
+
+        output
+    }
 }
 
 impl Display for ComputeShader {
@@ -0,0 +1,88 @@
+use std::{future::Future, sync::Arc};
+
+use cubecl_core::KernelId;
+use cubecl_runtime::{errors::KernelErrorBuffers, server::KernelError};
+
+use super::{stream::WgpuStream, WgpuResource};
+
+type Buffers = KernelErrorBuffers<KernelId, Arc<wgpu::Buffer>>;
+
+/// Size of the error buffer of each kernel, in bytes.
+const ERROR_BUFFER_SIZE: u64 = Buffers::BUFFER_SIZE as u64;
+
+/// Errors raised by kernels, like failed `cube_assert!`.
+///
+/// The buffers of the kernels launched since the last read are read and cleared on sync and read,
+/// and the errors are collected once the reads complete.
+#[derive(Debug)]
+pub struct KernelErrors {
+    device: Arc<wgpu::Device>,
+    buffers: Buffers,
+}
+
+impl KernelErrors {
+    pub fn new(device: Arc<wgpu::Device>) -> Self {
+        Self {
+            device,
+            buffers: Buffers::default(),
+        }
+    }
+
+    /// Register a kernel that can raise errors, creating its buffer.
+    pub fn register(&mut self, kernel_id: KernelId, name: &str) {
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("errors"),
+            size: ERROR_BUFFER_SIZE,
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_SRC
+                | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        self.buffers.register(kernel_id, name, Arc::new(buffer));
+    }
+
+    /// The buffer to bind when launching the kernel, if it can raise errors.
+    pub fn binding(&mut self, kernel_id: &KernelId) -> Option<WgpuResource> {
+        let buffer = self.buffers.launch(kernel_id)?;
+        Some(WgpuResource::new(buffer.clone(), 0, ERROR_BUFFER_SIZE))
+    }
+
+    /// Read the buffers of the kernels launched since the last read, and collect their errors
+    /// once the returned future completes.
+    pub fn read(
+        &mut self,
+        stream: &mut WgpuStream,
+        queue: &wgpu::Queue,
+    ) -> Option<impl Future<Output = ()> + Send + 'static> {
+        let (buffers, pending) = self.buffers.read();
+        if pending.is_empty() {
+            return None;
+        }
+
+        let fut = stream.read_buffers(
+            buffers
+                .iter()
+                .map(|buffer| (Arc::clone(buffer), 0, ERROR_BUFFER_SIZE))
+                .collect(),
+        );
+
+        // The copies were submitted by the read, so clearing happens before the next launches.
+        for buffer in buffers {
+            queue.write_buffer(buffer, 0, &[0; ERROR_BUFFER_SIZE as usize]);
+        }
+
+        Some(async move {
+            let data = fut.await;
+            let words = data
+                .iter()
+                .map(|data| bytemuck::cast_slice::<u8, u32>(data));
+            pending.collect(words);
+        })
+    }
+
+    /// Take the errors collected so far.
+    pub fn take(&mut self) -> Vec<KernelError> {
+        self.buffers.take()
+    }
+}
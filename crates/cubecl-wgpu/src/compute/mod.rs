@@ -1,4 +1,6 @@
+pub(super) mod errors;
 pub(super) mod poll;
+pub(super) mod print;
 pub(super) mod stream;
 pub(super) mod timestamps;
 
@@ -0,0 +1,134 @@
+use std::{future::Future, sync::Arc};
+
+use cubecl_core::KernelId;
+use hashbrown::HashMap;
+
+use super::{stream::WgpuStream, WgpuResource};
+use crate::compiler::wgsl::PrintFormat;
+
+/// Size of the buffer of each printing kernel, in bytes.
+const PRINT_BUFFER_SIZE: u64 = 1 << 20;
+
+/// Output of `debug_print!` for kernels that can't print directly, like WGSL kernels.
+///
+/// Each printing kernel writes to its own buffer, bound after all other bindings. The first word
+/// is the number of words written, followed by the prints. The buffers are read, logged and
+/// cleared on sync.
+#[derive(Debug)]
+pub struct DebugPrints {
+    device: Arc<wgpu::Device>,
+    kernels: HashMap<KernelId, PrintBuffer>,
+    /// Kernels launched since the last read, in launch order.
+    launched: Vec<KernelId>,
+}
+
+#[derive(Debug)]
+struct PrintBuffer {
+    name: String,
+    formats: Arc<Vec<PrintFormat>>,
+    buffer: Arc<wgpu::Buffer>,
+}
+
+impl DebugPrints {
+    pub fn new(device: Arc<wgpu::Device>) -> Self {
+        Self {
+            device,
+            kernels: HashMap::new(),
+            launched: Vec::new(),
+        }
+    }
+
+    /// Register the prints of a kernel, creating its buffer.
+    pub fn register(&mut self, kernel_id: KernelId, name: &str, formats: Vec<PrintFormat>) {
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("debug_print"),
+            size: PRINT_BUFFER_SIZE,
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_SRC
+                | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        self.kernels.insert(
+            kernel_id,
+            PrintBuffer {
+                name: name.to_string(),
+                formats: Arc::new(formats),
+                buffer: Arc::new(buffer),
+            },
+        );
+    }
+
+    /// The buffer to bind when launching the kernel, if it prints.
+    pub fn binding(&mut self, kernel_id: &KernelId) -> Option<WgpuResource> {
+        let print = self.kernels.get(kernel_id)?;
+        if !self.launched.contains(kernel_id) {
+            self.launched.push(kernel_id.clone());
+        }
+
+        Some(WgpuResource::new(
+            print.buffer.clone(),
+            0,
+            PRINT_BUFFER_SIZE,
+        ))
+    }
+
+    /// Read the buffers of the kernels launched since the last read, and log their prints once
+    /// the returned future completes.
+    pub fn read(
+        &mut self,
+        stream: &mut WgpuStream,
+        queue: &wgpu::Queue,
+    ) -> Option<impl Future<Output = ()> + Send + 'static> {
+        if self.launched.is_empty() {
+            return None;
+        }
+
+        let launched = core::mem::take(&mut self.launched)
+            .into_iter()
+            .map(|kernel_id| &self.kernels[&kernel_id])
+            .collect::<Vec<_>>();
+        let fut = stream.read_buffers(
+            launched
+                .iter()
+                .map(|print| (print.buffer.clone(), 0, PRINT_BUFFER_SIZE))
+                .collect(),
+        );
+
+        // The copies were submitted by the read, so clearing happens before the next launches.
+        for print in launched.iter() {
+            queue.write_buffer(&print.buffer, 0, &[0; 4]);
+        }
+
+        let kernels = launched
+            .into_iter()
+            .map(|print| (print.name.clone(), print.formats.clone()))
+            .collect::<Vec<_>>();
+
+        Some(async move {
+            let data = fut.await;
+            for ((name, formats), data) in kernels.into_iter().zip(data) {
+                let words: &[u32] = bytemuck::cast_slice(&data);
+                log_prints(&name, &formats, words);
+            }
+        })
+    }
+}
+
+fn log_prints(name: &str, formats: &[PrintFormat], words: &[u32]) {
+    let written = words[0] as usize;
+    let mut words = &words[1..written.min(words.len() - 1) + 1];
+
+    while let Some(format) = words.first().and_then(|index| formats.get(*index as usize)) {
+        let num_words = format.num_words();
+        if words.len() < num_words {
+            break;
+        }
+        log::info!("[{name}] {}", format.format(&words[1..num_words]));
+        words = &words[num_words..];
+    }
+
+    if written >= PRINT_BUFFER_SIZE as usize / 4 {
+        log::warn!("[{name}] Print buffer is full, some prints were dropped");
+    }
+}
@@ -1,18 +1,27 @@
-use std::{future::Future, marker::PhantomData, num::NonZero, time::Duration};
+use std::{future::Future, marker::PhantomData, num::NonZero, ops::Range, time::Duration};
 
 use super::{
+    errors::KernelErrors,
+    print::DebugPrints,
     stream::{PipelineDispatch, WgpuStream},
     WgpuStorage,
 };
 use crate::compiler::base::WgpuCompiler;
-use crate::timestamps::KernelTimestamps;
+use crate::timestamps::{DispatchTimestamps, KernelTimestamps};
 use alloc::sync::Arc;
 use cubecl_common::future;
-use cubecl_core::{compute::DebugInformation, prelude::*, server::Handle, Feature, KernelId};
+use cubecl_core::{
+    compute::{DebugInformation, DefinitionKernel},
+    ir::KernelDefinition,
+    prelude::*,
+    server::Handle,
+    Feature, KernelId,
+};
 use cubecl_runtime::{
     debug::{DebugLogger, ProfileLevel},
     memory_management::{MemoryHandle, MemoryLock, MemoryManagement},
-    server::{self, ComputeServer},
+    profile::{KernelLaunch, ProfileResult},
+    server::{self, fill_pattern, ComputeServer, GraphId, KernelError, RecordableServer},
     storage::{BindingResource, ComputeStorage},
     ExecutionMode, TimestampsError, TimestampsResult,
 };
@@ -26,13 +35,34 @@ pub struct WgpuServer<C: WgpuCompiler> {
     pub(crate) device: Arc<wgpu::Device>,
     queue: Arc<wgpu::Queue>,
     pipelines: HashMap<KernelId, Arc<ComputePipeline>>,
+    cube_dims: HashMap<KernelId, CubeDim>,
     logger: DebugLogger,
     storage_locked: MemoryLock,
     duration_profiled: Option<Duration>,
     stream: WgpuStream,
+    prints: DebugPrints,
+    errors: KernelErrors,
+    /// The dispatches executed since the capture started, if kernels are being captured.
+    capture: Option<Vec<CapturedDispatch>>,
+    graphs: HashMap<GraphId, Vec<CapturedDispatch>>,
+    next_graph: u64,
     _compiler: PhantomData<C>,
 }
 
+/// A dispatch executed while capturing a graph.
+///
+/// Command buffers can't be submitted more than once, so graphs keep the pipeline and bind group
+/// of each dispatch instead, and only record the dispatch again when replayed.
+#[derive(Debug)]
+struct CapturedDispatch {
+    kernel_id: KernelId,
+    pipeline: Arc<ComputePipeline>,
+    bind_group: wgpu::BindGroup,
+    count: CubeCount,
+    bindings: Vec<server::Binding>,
+    launch: KernelLaunch,
+}
+
 impl<C: WgpuCompiler> WgpuServer<C> {
     /// Create a new server.
     pub fn new(
@@ -56,9 +86,15 @@ impl<C: WgpuCompiler> WgpuServer<C> {
             queue: queue.clone(),
             storage_locked: MemoryLock::default(),
             pipelines: HashMap::new(),
+            cube_dims: HashMap::new(),
             logger,
             duration_profiled: None,
             stream,
+            prints: DebugPrints::new(device.clone()),
+            errors: KernelErrors::new(device.clone()),
+            capture: None,
+            graphs: HashMap::new(),
+            next_graph: 0,
             _compiler: PhantomData,
         }
     }
@@ -66,16 +102,24 @@ impl<C: WgpuCompiler> WgpuServer<C> {
     fn pipeline(
         &mut self,
         kernel: <Self as ComputeServer>::Kernel,
+        kernel_id: KernelId,
         mode: ExecutionMode,
     ) -> Arc<ComputePipeline> {
-        let mut kernel_id = kernel.id();
-        kernel_id.mode(mode);
-
         if let Some(pipeline) = self.pipelines.get(&kernel_id) {
             return pipeline.clone();
         }
 
+        let name = kernel.name();
         let mut compile = <C as WgpuCompiler>::compile(self, kernel, mode);
+        self.cube_dims.insert(kernel_id.clone(), compile.cube_dim);
+
+        let prints = C::print_formats(&compile);
+        if !prints.is_empty() {
+            self.prints.register(kernel_id.clone(), name, prints);
+        }
+        if C::raises_errors(&compile) {
+            self.errors.register(kernel_id.clone(), name);
+        }
 
         if self.logger.is_activated() {
             compile.debug_info = Some(DebugInformation::new("wgsl", kernel_id.clone()));
@@ -89,6 +133,72 @@ impl<C: WgpuCompiler> WgpuServer<C> {
         pipeline
     }
 
+    /// Returns the buffer and the byte range of `size` bytes of the binding starting `offset`
+    /// bytes in, for copy commands.
+    ///
+    /// Copies have to be 4 byte aligned. The end is rounded up when the range ends with the
+    /// memory of the binding, as memory is 32 bytes aligned (see WgpuStorage).
+    fn copy_range(
+        &mut self,
+        binding: server::Binding,
+        offset: u64,
+        size: u64,
+    ) -> (Arc<wgpu::Buffer>, Range<u64>) {
+        let memory = self.memory_management.get(binding.memory.clone());
+        let resource = self.get_resource(binding).into_resource();
+        assert!(
+            offset + size <= resource.size(),
+            "Can't copy {size} bytes at offset {offset} of a binding of {} bytes",
+            resource.size()
+        );
+
+        let align = wgpu::COPY_BUFFER_ALIGNMENT;
+        let start = resource.offset() + offset;
+        let mut end = start + size;
+        if end == memory.offset() + memory.size() {
+            end = end.div_ceil(align) * align;
+        }
+
+        (resource.buffer, start..end)
+    }
+
+    /// Writes `data` to the start of the range of the buffer, zeroing the rest of the range.
+    ///
+    /// The words only partially covered by an unaligned range are read back first, so they can
+    /// be written along with the data.
+    fn write_range(&mut self, buffer: &Arc<wgpu::Buffer>, range: Range<u64>, data: &[u8]) {
+        let align = wgpu::COPY_BUFFER_ALIGNMENT;
+        let start = range.start - range.start % align;
+        let end = range.end.div_ceil(align) * align;
+        let mut bytes = vec![0; (end - start) as usize];
+
+        let mut words = Vec::new();
+        if start < range.start {
+            words.push(start);
+        }
+        if end > range.end {
+            words.push(end - align);
+        }
+        if !words.is_empty() {
+            let fut = self.stream.read_buffers(
+                words
+                    .iter()
+                    .map(|word| (buffer.clone(), *word, align))
+                    .collect(),
+            );
+            self.on_flushed();
+
+            for (word, content) in words.iter().zip(future::block_on(fut)) {
+                let position = (word - start) as usize;
+                bytes[position..position + align as usize].copy_from_slice(&content);
+            }
+        }
+
+        let position = (range.start - start) as usize;
+        bytes[position..position + data.len()].copy_from_slice(data);
+        self.stream.write_buffer(buffer, start, &bytes);
+    }
+
     fn on_flushed(&mut self) {
         self.storage_locked.clear_locked();
 
@@ -117,11 +227,21 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
             })
             .collect();
 
+        let prints = self.prints.read(&mut self.stream, &self.queue);
+        let errors = self.errors.read(&mut self.stream, &self.queue);
         // Clear compute pass.
         let fut = self.stream.read_buffers(resources);
         self.on_flushed();
 
-        fut
+        async move {
+            if let Some(prints) = prints {
+                prints.await;
+            }
+            if let Some(errors) = errors {
+                errors.await;
+            }
+            fut.await
+        }
     }
 
     fn get_resource(&mut self, binding: server::Binding) -> BindingResource<Self> {
@@ -190,6 +310,63 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
         )
     }
 
+    fn copy(&mut self, src: server::Binding, dst: server::Binding) {
+        let size = self.get_resource(src.clone()).resource().size();
+        let (dst, dst_range) = self.copy_range(dst, 0, size);
+        let (src, src_range) = self.copy_range(src, 0, size);
+
+        if dst_range.is_empty() {
+            return;
+        }
+
+        // Copying a few bytes past the source is fine, since its memory is aligned as well.
+        if is_aligned(&dst_range) && src_range.start % wgpu::COPY_BUFFER_ALIGNMENT == 0 {
+            self.stream.copy_buffer(
+                &src,
+                src_range.start,
+                &dst,
+                dst_range.start,
+                dst_range.end - dst_range.start,
+            );
+        } else {
+            // Unaligned copies go through the host.
+            let align = wgpu::COPY_BUFFER_ALIGNMENT;
+            let skip = src_range.start % align;
+            let fut = self
+                .stream
+                .read_buffers(vec![(src, src_range.start - skip, skip + size)]);
+            self.on_flushed();
+            let data = future::block_on(fut).remove(0);
+
+            self.write_range(&dst, dst_range, &data[skip as usize..]);
+        }
+    }
+
+    fn fill(&mut self, binding: server::Binding, pattern: &[u8]) {
+        let size = self.get_resource(binding.clone()).resource().size();
+        let data = fill_pattern(pattern, size as usize);
+        let (buffer, range) = self.copy_range(binding, 0, size);
+
+        if range.is_empty() {
+            return;
+        }
+
+        if is_aligned(&range) && data.iter().all(|byte| *byte == 0) {
+            self.stream
+                .clear_buffer(&buffer, range.start, range.end - range.start);
+        } else {
+            self.write_range(&buffer, range, &data);
+        }
+    }
+
+    fn write(&mut self, binding: server::Binding, offset: u64, data: &[u8]) {
+        let (buffer, range) = self.copy_range(binding, offset, data.len() as u64);
+
+        if !range.is_empty() {
+            self.write_range(&buffer, range, data);
+        }
+    }
+
     unsafe fn execute(
         &mut self,
         kernel: Self::Kernel,
@@ -217,14 +394,23 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
         }
 
         // Start execution.
-        let pipeline = self.pipeline(kernel, mode);
+        let mut kernel_id = kernel.id();
+        kernel_id.mode(mode);
+        let name = kernel.name();
+        let pipeline = self.pipeline(kernel, kernel_id.clone(), mode);
+        let launch = self.stream.profile.is_some().then(|| {
+            let cube_dim = self.cube_dims[&kernel_id];
+            KernelLaunch::new(name, &count, (cube_dim.x, cube_dim.y, cube_dim.z))
+        });
 
         // Store all the resources we'll be using. This could be eliminated if
         // there was a way to tie the lifetime of the resource to the memory handle.
-        let resources: Vec<_> = bindings
+        let mut resources: Vec<_> = bindings
             .iter()
             .map(|binding| self.get_resource(binding.clone()).into_resource())
             .collect();
+        resources.extend(self.prints.binding(&kernel_id));
+        resources.extend(self.errors.binding(&kernel_id));
 
         // First resolve the dispatch buffer if needed. The weird ordering is because the lifetime of this
         // needs to be longer than the compute pass, so we can't do this just before dispatching.
@@ -235,7 +421,27 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
             CubeCount::Static(x, y, z) => PipelineDispatch::Static(x, y, z),
         };
 
-        if self.stream.register(pipeline, resources, dispatch) {
+        let flushed = match &mut self.capture {
+            Some(capture) => {
+                let bind_group = self.stream.bind_group(&pipeline, &resources);
+                let flushed = self
+                    .stream
+                    .register_bound(&pipeline, &bind_group, dispatch, launch);
+                let cube_dim = self.cube_dims[&kernel_id];
+                capture.push(CapturedDispatch {
+                    kernel_id: kernel_id.clone(),
+                    pipeline,
+                    bind_group,
+                    count: count.clone(),
+                    bindings,
+                    launch: KernelLaunch::new(name, &count, (cube_dim.x, cube_dim.y, cube_dim.z)),
+                });
+                flushed
+            }
+            None => self.stream.register(pipeline, resources, dispatch, launch),
+        };
+
+        if flushed {
             self.on_flushed();
         }
 
@@ -268,6 +474,69 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
         }
     }
 
+    fn start_capture(&mut self) {
+        self.capture = Some(Vec::new());
+    }
+
+    fn end_capture(&mut self) -> Option<GraphId> {
+        let dispatches = self.capture.take().expect("Kernels should be captured");
+        let graph = GraphId {
+            value: self.next_graph,
+        };
+        self.next_graph += 1;
+        self.graphs.insert(graph, dispatches);
+
+        Some(graph)
+    }
+
+    /// Unlike CUDA graphs, nothing is pre-encoded: command buffers are consumed when submitted,
+    /// and wgpu has no bundles for compute passes. Each dispatch is recorded again in the current
+    /// compute pass, reusing its cached pipeline and bind group, so replays skip the compilation,
+    /// binding and validation work of a launch, but not the encoding.
+    fn replay_graph(&mut self, graph: GraphId) {
+        let dispatches = match self.graphs.remove(&graph) {
+            Some(dispatches) => dispatches,
+            None => panic!("Unknown graph {graph:?}"),
+        };
+
+        for captured in dispatches.iter() {
+            // Lock the buffers of the dispatch like any other, and mark its print and error
+            // buffers as used so they are read on sync.
+            for binding in captured.bindings.iter() {
+                self.get_resource(binding.clone());
+            }
+            self.prints.binding(&captured.kernel_id);
+            self.errors.binding(&captured.kernel_id);
+
+            let dispatch = match captured.count.clone() {
+                CubeCount::Dynamic(binding) => {
+                    PipelineDispatch::Dynamic(self.get_resource(binding).into_resource())
+                }
+                CubeCount::Static(x, y, z) => PipelineDispatch::Static(x, y, z),
+            };
+            let launch = self
+                .stream
+                .profile
+                .is_some()
+                .then(|| captured.launch.clone());
+
+            if self.stream.register_bound(
+                &captured.pipeline,
+                &captured.bind_group,
+                dispatch,
+                launch,
+            ) {
+                self.on_flushed();
+            }
+        }
+
+        self.graphs.insert(graph, dispatches);
+    }
+
+    fn free_graph(&mut self, graph: GraphId) {
+        self.graphs.remove(&graph);
+    }
+
     fn flush(&mut self) {
         // End the current compute pass.
         self.stream.flush();
@@ -277,10 +546,20 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
     /// Returns the total time of GPU work this sync completes.
     fn sync(&mut self) -> impl Future<Output = ()> + 'static {
         self.logger.profile_summary();
+        let prints = self.prints.read(&mut self.stream, &self.queue);
+        let errors = self.errors.read(&mut self.stream, &self.queue);
         let fut = self.stream.sync();
         self.on_flushed();
 
-        fut
+        async move {
+            if let Some(prints) = prints {
+                prints.await;
+            }
+            if let Some(errors) = errors {
+                errors.await;
+            }
+            fut.await;
+        }
     }
 
     /// Returns the total time of GPU work this sync completes.
@@ -325,4 +604,60 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
             self.stream.timestamps.disable();
         }
     }
+
+    fn start_profile(&mut self) {
+        if self
+            .device
+            .features()
+            .contains(wgpu::Features::TIMESTAMP_QUERY)
+        {
+            self.stream.profile = Some(DispatchTimestamps::new(&self.device));
+        }
+    }
+
+    fn end_profile(&mut self) -> impl Future<Output = ProfileResult> + Send + 'static {
+        let available = self
+            .device
+            .features()
+            .contains(wgpu::Features::TIMESTAMP_QUERY);
+        let fut = self.stream.end_profile();
+        self.on_flushed();
+
+        async move {
+            match fut.await {
+                Err(TimestampsError::Disabled) if !available => Err(TimestampsError::Unavailable),
+                result => result,
+            }
+        }
+    }
+
+    fn errors(&mut self) -> Vec<KernelError> {
+        let errors = self.errors.take();
+        errors
+            .into_iter()
+            .map(|error| self.logger.debug(error))
+            .collect()
+    }
+}
+
+impl<C: WgpuCompiler> RecordableServer for WgpuServer<C> {
+    type KernelRecord = KernelDefinition;
+    type KernelKey = KernelId;
+
+    fn kernel_key(kernel: &Self::Kernel) -> KernelId {
+        kernel.id()
+    }
+
+    fn record_kernel(kernel: &Self::Kernel) -> Option<KernelDefinition> {
+        kernel.definition()
+    }
+
+    fn replay_kernel(definition: KernelDefinition) -> Self::Kernel {
+        DefinitionKernel::replay(definition)
+    }
+}
+
+fn is_aligned(range: &Range<u64>) -> bool {
+    let align = wgpu::COPY_BUFFER_ALIGNMENT;
+    range.start % align == 0 && range.end % align == 0
 }
@@ -1,9 +1,16 @@
 use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
 use web_time::Instant;
 
-use super::{poll::WgpuPoll, timestamps::KernelTimestamps, WgpuResource};
-use cubecl_runtime::{TimestampsError, TimestampsResult};
-use wgpu::ComputePipeline;
+use super::{
+    poll::WgpuPoll,
+    timestamps::{dispatch_profile, DispatchTimestamps, KernelTimestamps},
+    WgpuResource,
+};
+use cubecl_runtime::{
+    profile::{KernelLaunch, ProfileResult},
+    TimestampsError, TimestampsResult,
+};
+use wgpu::{util::DeviceExt, ComputePipeline};
 
 #[derive(Debug)]
 pub struct WgpuStream {
@@ -17,6 +24,7 @@ pub struct WgpuStream {
     poll: WgpuPoll,
     sync_buffer: Option<Arc<wgpu::Buffer>>,
     submission_load: SubmissionLoad,
+    pub profile: Option<DispatchTimestamps>,
 }
 
 pub enum PipelineDispatch {
@@ -58,6 +66,7 @@ impl WgpuStream {
             poll,
             sync_buffer,
             submission_load: SubmissionLoad::default(),
+            profile: None,
         }
     }
 
@@ -66,7 +75,59 @@ impl WgpuStream {
         pipeline: Arc<ComputePipeline>,
         resources: Vec<WgpuResource>,
         dispatch: PipelineDispatch,
+        launch: Option<KernelLaunch>,
     ) -> bool {
+        let bind_group = self.bind_group(&pipeline, &resources);
+        self.register_bound(&pipeline, &bind_group, dispatch, launch)
+    }
+
+    /// Create the bind group of the resources of a dispatch of the pipeline.
+    pub fn bind_group(
+        &self,
+        pipeline: &ComputePipeline,
+        resources: &[WgpuResource],
+    ) -> wgpu::BindGroup {
+        let entries = &resources
+            .iter()
+            .enumerate()
+            .map(|(i, r)| wgpu::BindGroupEntry {
+                binding: i as u32,
+                resource: r.as_wgpu_bind_resource(),
+            })
+            .collect::<Vec<_>>();
+
+        let group_layout = pipeline.get_bind_group_layout(0);
+        self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &group_layout,
+            entries,
+        })
+    }
+
+    /// Register a dispatch of the pipeline over a bind group that was already created, which
+    /// can be registered any number of times.
+    pub fn register_bound(
+        &mut self,
+        pipeline: &ComputePipeline,
+        bind_group: &wgpu::BindGroup,
+        dispatch: PipelineDispatch,
+        launch: Option<KernelLaunch>,
+    ) -> bool {
+        // Each profiled dispatch is timed in a compute pass of its own.
+        if let (Some(profile), Some(launch)) = (&mut self.profile, launch) {
+            self.pass = None;
+            let timestamps = profile.register(launch, &self.device, &mut self.encoder);
+
+            self.pass = Some(
+                self.encoder
+                    .begin_compute_pass(&wgpu::ComputePassDescriptor {
+                        label: None,
+                        timestamp_writes: Some(timestamps),
+                    })
+                    .forget_lifetime(),
+            );
+        }
+
         // Start a new compute pass if needed. The forget_lifetime allows
         // to store this with a 'static lifetime, but the compute pass must
         // be dropped before the encoder. This isn't unsafe - it's still checked at runtime.
@@ -96,24 +157,8 @@ impl WgpuStream {
 
         self.tasks_count += 1;
 
-        let entries = &resources
-            .iter()
-            .enumerate()
-            .map(|(i, r)| wgpu::BindGroupEntry {
-                binding: i as u32,
-                resource: r.as_wgpu_bind_resource(),
-            })
-            .collect::<Vec<_>>();
-
-        let group_layout = pipeline.get_bind_group_layout(0);
-        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: None,
-            layout: &group_layout,
-            entries,
-        });
-
-        pass.set_pipeline(&pipeline);
-        pass.set_bind_group(0, &bind_group, &[]);
+        pass.set_pipeline(pipeline);
+        pass.set_bind_group(0, bind_group, &[]);
 
         match dispatch {
             PipelineDispatch::Static(x, y, z) => {
@@ -127,6 +172,10 @@ impl WgpuStream {
             }
         }
 
+        if self.profile.is_some() {
+            self.pass = None;
+        }
+
         if self.tasks_count >= self.tasks_max {
             self.flush();
             true
@@ -135,6 +184,42 @@ impl WgpuStream {
         }
     }
 
+    /// Copy `size` bytes between buffers after the registered tasks.
+    pub fn copy_buffer(
+        &mut self,
+        src: &wgpu::Buffer,
+        src_offset: u64,
+        dst: &wgpu::Buffer,
+        dst_offset: u64,
+        size: u64,
+    ) {
+        self.pass = None;
+        self.encoder
+            .copy_buffer_to_buffer(src, src_offset, dst, dst_offset, size);
+    }
+
+    /// Zero `size` bytes of the buffer after the registered tasks.
+    pub fn clear_buffer(&mut self, buffer: &wgpu::Buffer, offset: u64, size: u64) {
+        self.pass = None;
+        self.encoder.clear_buffer(buffer, offset, Some(size));
+    }
+
+    /// Write the data to the buffer after the registered tasks.
+    ///
+    /// Unlike writes to the queue, which happen at the start of the next submission, the data
+    /// is copied from a staging buffer so the tasks still see the previous content.
+    pub fn write_buffer(&mut self, buffer: &wgpu::Buffer, offset: u64, data: &[u8]) {
+        let staging_buffer = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: data,
+                usage: wgpu::BufferUsages::COPY_SRC,
+            });
+
+        self.copy_buffer(&staging_buffer, 0, buffer, offset, data.len() as u64);
+    }
+
     pub fn read_buffers(
         &mut self,
         buffers: Vec<(Arc<wgpu::Buffer>, u64, u64)>,
@@ -295,6 +380,41 @@ impl WgpuStream {
         }
     }
 
+    /// Wait for the registered tasks, and return the kernels dispatched since the profile started
+    /// with their timings.
+    pub fn end_profile(&mut self) -> Pin<Box<dyn Future<Output = ProfileResult> + Send + 'static>> {
+        let Some(mut profile) = self.profile.take() else {
+            let fut = self.sync();
+
+            return Box::pin(async move {
+                fut.await;
+                Err(TimestampsError::Disabled)
+            });
+        };
+
+        self.pass = None;
+        profile.resolve(&self.device, &mut self.encoder);
+
+        let (buffers, launches): (Vec<_>, Vec<_>) = profile
+            .take_resolved()
+            .into_iter()
+            .map(|(buffer, launches)| {
+                let size = buffer.size();
+                ((Arc::new(buffer), 0, size), launches)
+            })
+            .unzip();
+        let period = self.queue.get_timestamp_period() as f64 * 1e-9;
+        let fut = self.read_buffers(buffers);
+
+        Box::pin(async move {
+            let data = fut.await;
+            Ok(dispatch_profile(
+                data.into_iter().zip(launches).collect(),
+                period,
+            ))
+        })
+    }
+
     pub fn sync(&mut self) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>> {
         self.flush();
 
@@ -1,6 +1,11 @@
+use cubecl_runtime::profile::{KernelLaunch, KernelProfile, ProfiledKernel};
+use std::time::Duration;
 use web_time::Instant;
 use wgpu::{QuerySet, QuerySetDescriptor, QueryType};
 
+/// The number of dispatches timed with a query set before it's resolved.
+const PROFILE_DISPATCHES: u32 = 1024;
+
 #[derive(Debug)]
 pub enum KernelTimestamps {
     Native { query_set: QuerySet, init: bool },
@@ -36,3 +41,102 @@ impl KernelTimestamps {
         *self = Self::Disabled;
     }
 }
+
+/// Timestamps written at the start and end of each dispatch while profiling kernels,
+/// each dispatch having its own compute pass.
+#[derive(Debug)]
+pub struct DispatchTimestamps {
+    query_set: QuerySet,
+    launches: Vec<KernelLaunch>,
+    resolved: Vec<(wgpu::Buffer, Vec<KernelLaunch>)>,
+}
+
+impl DispatchTimestamps {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let query_set = device.create_query_set(&QuerySetDescriptor {
+            label: Some("CubeCL kernel profile queries"),
+            ty: QueryType::Timestamp,
+            count: 2 * PROFILE_DISPATCHES,
+        });
+
+        Self {
+            query_set,
+            launches: Vec::new(),
+            resolved: Vec::new(),
+        }
+    }
+
+    /// Returns where the timestamps of the compute pass of the launch are written.
+    ///
+    /// The query set is resolved first when it's full.
+    pub fn register(
+        &mut self,
+        launch: KernelLaunch,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+    ) -> wgpu::ComputePassTimestampWrites<'_> {
+        if self.launches.len() as u32 == PROFILE_DISPATCHES {
+            self.resolve(device, encoder);
+        }
+
+        let index = 2 * self.launches.len() as u32;
+        self.launches.push(launch);
+
+        wgpu::ComputePassTimestampWrites {
+            query_set: &self.query_set,
+            beginning_of_pass_write_index: Some(index),
+            end_of_pass_write_index: Some(index + 1),
+        }
+    }
+
+    /// Resolve the timestamps written so far to a buffer.
+    pub fn resolve(&mut self, device: &wgpu::Device, encoder: &mut wgpu::CommandEncoder) {
+        if self.launches.is_empty() {
+            return;
+        }
+
+        let count = 2 * self.launches.len() as u32;
+        let resolved = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: count as u64 * size_of::<u64>() as u64,
+            usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::QUERY_RESOLVE,
+            mapped_at_creation: false,
+        });
+
+        encoder.resolve_query_set(&self.query_set, 0..count, &resolved, 0);
+        self.resolved
+            .push((resolved, core::mem::take(&mut self.launches)));
+    }
+
+    /// Take the resolved buffers, with the launches whose timestamps they contain.
+    pub fn take_resolved(&mut self) -> Vec<(wgpu::Buffer, Vec<KernelLaunch>)> {
+        core::mem::take(&mut self.resolved)
+    }
+}
+
+/// Create the profile of the launches from their resolved timestamps, in ticks of `period`
+/// seconds.
+pub fn dispatch_profile(resolved: Vec<(Vec<u8>, Vec<KernelLaunch>)>, period: f64) -> KernelProfile {
+    let mut first = None;
+    let mut kernels = Vec::new();
+
+    for (data, launches) in resolved {
+        let timestamps: Vec<u64> = data
+            .chunks_exact(8)
+            .map(|x| u64::from_le_bytes(x.try_into().unwrap()))
+            .collect();
+
+        for (launch, timestamps) in launches.into_iter().zip(timestamps.chunks_exact(2)) {
+            let first = *first.get_or_insert(timestamps[0]);
+            let ticks = |delta: u64| Duration::from_secs_f64(delta as f64 * period);
+
+            kernels.push(ProfiledKernel {
+                launch,
+                start: ticks(timestamps[0].saturating_sub(first)),
+                duration: ticks(timestamps[1].saturating_sub(timestamps[0])),
+            });
+        }
+    }
+
+    KernelProfile { kernels }
+}
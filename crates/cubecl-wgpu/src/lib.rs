@@ -27,7 +27,11 @@ mod tests {
     cubecl_core::testgen_all!();
     cubecl_linalg::testgen_plane_mma!([flex32, f32], f32);
     cubecl_linalg::testgen_tiling2d!([flex32, f32]);
+    cubecl_linalg::testgen_conv2d!();
+    cubecl_linalg::testgen_permute!();
+    cubecl_linalg::testgen_attention!();
     cubecl_std::testgen_reduce!();
+    cubecl_std::testgen_functional!();
 }
 
 #[cfg(all(test, feature = "spirv"))]
@@ -2,7 +2,7 @@ use cubecl::prelude::*;
 use cubecl_linalg::matmul;
 use std::marker::PhantomData;
 
-use cubecl::benchmark::{Benchmark, TimestampsResult, TimingMethod};
+use cubecl::benchmark::{self, Benchmark, BenchmarkResult, TimestampsResult, TimingMethod};
 use cubecl::frontend::Float;
 use cubecl::future;
 use cubecl_linalg::tensor::TensorHandle;
@@ -30,6 +30,15 @@ impl<R: Runtime, E: Float> Benchmark for MatmulBench<R, E> {
         10
     }
 
+    fn num_bytes(&self) -> Option<u64> {
+        let elems = self.b * (self.m * self.k + self.k * self.n + self.m * self.n);
+        Some((elems * size_of::<E>()) as u64)
+    }
+
+    fn num_flops(&self) -> Option<u64> {
+        Some((2 * self.b * self.m * self.k * self.n) as u64)
+    }
+
     fn name(&self) -> String {
         format!("matmul-{}-{}-{:?}", R::name(), E::as_elem(), self.strategy).to_lowercase()
     }
@@ -56,7 +65,7 @@ struct MatmulBench<R: Runtime, E> {
 }
 
 #[allow(dead_code)]
-fn run<R: Runtime, E: Float>(device: R::Device, strategy: matmul::Strategy) {
+fn run<R: Runtime, E: Float>(device: R::Device, strategy: matmul::Strategy) -> BenchmarkResult {
     let client = R::client(&device);
     client.enable_timestamps();
 
@@ -70,30 +79,44 @@ fn run<R: Runtime, E: Float>(device: R::Device, strategy: matmul::Strategy) {
         strategy,
         _e: PhantomData,
     };
-    println!("{}", bench.name());
-    println!("{}", bench.run(TimingMethod::DeviceOnly));
+    let result = benchmark::run_benchmark_with(bench, TimingMethod::DeviceOnly);
+    println!("{result}");
+    result
 }
 
 fn main() {
+    #[allow(unused_mut)]
+    let mut results = Vec::new();
+
     #[cfg(feature = "wgpu")]
     {
-        run::<cubecl::wgpu::WgpuRuntime, f32>(
+        results.push(run::<cubecl::wgpu::WgpuRuntime, f32>(
             Default::default(),
             matmul::Strategy::Tiling2D(Default::default()),
-        );
-        run::<cubecl::wgpu::WgpuRuntime, f32>(Default::default(), matmul::Strategy::PlaneMma);
+        ));
+        results.push(run::<cubecl::wgpu::WgpuRuntime, f32>(
+            Default::default(),
+            matmul::Strategy::PlaneMma,
+        ));
+        results.push(run::<cubecl::wgpu::WgpuRuntime, f32>(
+            Default::default(),
+            matmul::Strategy::Auto,
+        ));
     }
 
     #[cfg(feature = "wgpu-spirv")]
     {
-        run::<cubecl::wgpu::WgpuRuntime<cubecl::wgpu::spirv::SpirvCompiler>, f32>(
+        results.push(run::<
+            cubecl::wgpu::WgpuRuntime<cubecl::wgpu::spirv::SpirvCompiler>,
+            f32,
+        >(
             Default::default(),
             matmul::Strategy::Tiling2D(Default::default()),
-        );
-        run::<cubecl::wgpu::WgpuRuntime<cubecl::wgpu::spirv::SpirvCompiler>, f32>(
-            Default::default(),
-            matmul::Strategy::PlaneMma,
-        );
+        ));
+        results.push(run::<
+            cubecl::wgpu::WgpuRuntime<cubecl::wgpu::spirv::SpirvCompiler>,
+            f32,
+        >(Default::default(), matmul::Strategy::PlaneMma));
     }
 
     #[cfg(all(feature = "hip", target_os = "linux"))]
@@ -101,57 +124,90 @@ fn main() {
         // TODO: unless annotated OOM, all the benches can randomly hang
         // Full-precision ----------------------------------------------------
         // Tiling2D
-        run::<cubecl::hip::HipRuntime, f32>(
+        results.push(run::<cubecl::hip::HipRuntime, f32>(
             Default::default(),
             matmul::Strategy::Tiling2D(Default::default()),
-        );
+        ));
         // PlaneMma
         // run::<cubecl::hip::HipRuntime, f32>(Default::default(), matmul::Strategy::PlaneMma);
         // CmmaOld
         // run::<cubecl::hip::HipRuntime,<cubecl::hip::HipDialect> f32>(Default::default(), matmul::Strategy::CmmaOld(Default::default()));
         // Accelerated
-        run::<cubecl::hip::HipRuntime, f32>(Default::default(), matmul::Strategy::Accelerated);
+        results.push(run::<cubecl::hip::HipRuntime, f32>(
+            Default::default(),
+            matmul::Strategy::Accelerated,
+        ));
         // Half-precision ----------------------------------------------------
         // Tiling2D
-        run::<cubecl::hip::HipRuntime, half::f16>(
+        results.push(run::<cubecl::hip::HipRuntime, half::f16>(
             Default::default(),
             matmul::Strategy::Tiling2D(Default::default()),
-        );
+        ));
         // PlaneMma: OOM
         // run::<cubecl::hip::HipRuntime, half::f16>(Default::default(), matmul::Strategy::PlaneMma);
         // CmmaOld: OOM
         // run::<cubecl::hip::HipRuntime, half::f16>(Default::default(), matmul::Strategy::CmmaOld(Default::default()));
         // Accelerated
-        run::<cubecl::hip::HipRuntime, half::f16>(
+        results.push(run::<cubecl::hip::HipRuntime, half::f16>(
             Default::default(),
             matmul::Strategy::Accelerated,
-        );
+        ));
     }
 
     #[cfg(feature = "cuda")]
     {
-        run::<cubecl::cuda::CudaRuntime, f32>(
+        results.push(run::<cubecl::cuda::CudaRuntime, f32>(
             Default::default(),
             matmul::Strategy::Tiling2D(Default::default()),
-        );
-        run::<cubecl::cuda::CudaRuntime, half::f16>(
+        ));
+        results.push(run::<cubecl::cuda::CudaRuntime, half::f16>(
             Default::default(),
             matmul::Strategy::Tiling2D(Default::default()),
-        );
-        run::<cubecl::cuda::CudaRuntime, f32>(
+        ));
+        results.push(run::<cubecl::cuda::CudaRuntime, f32>(
             Default::default(),
             matmul::Strategy::CmmaOld(Default::default()),
-        );
-        run::<cubecl::cuda::CudaRuntime, half::f16>(
+        ));
+        results.push(run::<cubecl::cuda::CudaRuntime, half::f16>(
             Default::default(),
             matmul::Strategy::CmmaOld(Default::default()),
-        );
-        run::<cubecl::cuda::CudaRuntime, f32>(Default::default(), matmul::Strategy::PlaneMma);
-        run::<cubecl::cuda::CudaRuntime, half::f16>(Default::default(), matmul::Strategy::PlaneMma);
-        run::<cubecl::cuda::CudaRuntime, f32>(Default::default(), matmul::Strategy::Accelerated);
-        run::<cubecl::cuda::CudaRuntime, half::f16>(
+        ));
+        results.push(run::<cubecl::cuda::CudaRuntime, f32>(
+            Default::default(),
+            matmul::Strategy::PlaneMma,
+        ));
+        results.push(run::<cubecl::cuda::CudaRuntime, half::f16>(
+            Default::default(),
+            matmul::Strategy::PlaneMma,
+        ));
+        results.push(run::<cubecl::cuda::CudaRuntime, f32>(
+            Default::default(),
+            matmul::Strategy::Accelerated,
+        ));
+        results.push(run::<cubecl::cuda::CudaRuntime, half::f16>(
             Default::default(),
             matmul::Strategy::Accelerated,
-        );
+        ));
+        results.push(run::<cubecl::cuda::CudaRuntime, half::f16>(
+            Default::default(),
+            matmul::Strategy::Auto,
+        ));
+    }
+
+    let comparisons = match benchmark::report_results(&results) {
+        Ok(comparisons) => comparisons,
+        Err(err) => {
+            eprintln!("Failed to report the benchmark results: {err}");
+            std::process::exit(1);
+        }
+    };
+    for comparison in comparisons.iter() {
+        println!("{comparison}");
+    }
+    if comparisons
+        .iter()
+        .any(|comparison| comparison.is_regression())
+    {
+        std::process::exit(1);
     }
 }
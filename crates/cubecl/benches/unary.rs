@@ -5,7 +5,7 @@ use std::marker::PhantomData;
 #[cfg(feature = "cuda")]
 use half::f16;
 
-use cubecl::benchmark::{Benchmark, TimingMethod};
+use cubecl::benchmark::{self, Benchmark, BenchmarkResult, TimingMethod};
 use cubecl::future;
 use cubecl_linalg::tensor::TensorHandle;
 
@@ -55,6 +55,11 @@ impl<R: Runtime, E: Float> Benchmark for UnaryBench<R, E> {
         100
     }
 
+    fn num_bytes(&self) -> Option<u64> {
+        let elems: usize = self.shape.iter().product();
+        Some((3 * elems * size_of::<E>()) as u64)
+    }
+
     fn name(&self) -> String {
         format!(
             "unary-{}-{}-{:?}",
@@ -84,7 +89,7 @@ struct UnaryBench<R: Runtime, E> {
 }
 
 #[allow(dead_code)]
-fn run<R: Runtime, E: frontend::Float>(device: R::Device, vectorization: u8) {
+fn run<R: Runtime, E: frontend::Float>(device: R::Device, vectorization: u8) -> BenchmarkResult {
     let client = R::client(&device);
     client.enable_timestamps();
 
@@ -95,17 +100,39 @@ fn run<R: Runtime, E: frontend::Float>(device: R::Device, vectorization: u8) {
         device,
         _e: PhantomData,
     };
-    println!("{}", bench.name());
-    println!("{}", bench.run(TimingMethod::DeviceOnly));
+    let result = benchmark::run_benchmark_with(bench, TimingMethod::DeviceOnly);
+    println!("{result}");
+    result
 }
 
+#[allow(clippy::vec_init_then_push)]
 fn main() {
+    #[allow(unused_mut)]
+    let mut results = Vec::new();
+
     #[cfg(feature = "cuda")]
-    run::<cubecl::cuda::CudaRuntime, f16>(Default::default(), 8);
+    results.push(run::<cubecl::cuda::CudaRuntime, f16>(Default::default(), 8));
     #[cfg(feature = "cuda")]
-    run::<cubecl::cuda::CudaRuntime, f32>(Default::default(), 4);
+    results.push(run::<cubecl::cuda::CudaRuntime, f32>(Default::default(), 4));
     #[cfg(feature = "wgpu")]
-    run::<cubecl::wgpu::WgpuRuntime, f32>(Default::default(), 1);
+    results.push(run::<cubecl::wgpu::WgpuRuntime, f32>(Default::default(), 1));
     #[cfg(feature = "wgpu")]
-    run::<cubecl::wgpu::WgpuRuntime, f32>(Default::default(), 4);
+    results.push(run::<cubecl::wgpu::WgpuRuntime, f32>(Default::default(), 4));
+
+    let comparisons = match benchmark::report_results(&results) {
+        Ok(comparisons) => comparisons,
+        Err(err) => {
+            eprintln!("Failed to report the benchmark results: {err}");
+            std::process::exit(1);
+        }
+    };
+    for comparison in comparisons.iter() {
+        println!("{comparison}");
+    }
+    if comparisons
+        .iter()
+        .any(|comparison| comparison.is_regression())
+    {
+        std::process::exit(1);
+    }
 }